@@ -1,19 +1,20 @@
 use crate::calculs::*;
 use crate::state::WakeRegistry;
-use crate::time::TimeManager;
+use crate::time::{FramePacer, TimeManager};
+use crate::window::{WindowConfig, WindowControl, WindowId, MAIN_WINDOW};
 use rore_render::State as RenderState;
 use rore_types::text::TextRenderer;
 use rore_types::RoreConfig;
 
 use glam::Vec2;
+use std::collections::HashMap;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use winit::{
-    dpi::PhysicalSize,
     event::*,
     event_loop::{ControlFlow, EventLoopBuilder},
-    window::WindowBuilder,
+    window::Window,
 };
 
 pub use crate::widgets::base::Widget;
@@ -21,6 +22,19 @@ pub use crate::widgets::base::Widget;
 #[derive(Debug)]
 pub enum RoreUserEvent {
     WakeUp,
+    // Logic Thread yangi oyna daraxtini qurdi, endi unga OS oynasi va Surface kerak
+    OpenWindow {
+        id: WindowId,
+        config: WindowConfig,
+        rx_render: Receiver<RenderPacket>,
+        tx_recycle: Sender<crate::widgets::base::RenderOutput>,
+    },
+    CloseWindow(WindowId),
+    Control(WindowId, WindowControl),
+    // Some((papka, fps)) - PNG ketma-ketligini yozishni boshlash, None - to'xtatish
+    Record(WindowId, Option<(std::path::PathBuf, u32)>),
+    // Debug: kuzatilayotgan .wgsl fayli saqlandi (shader_id, yangi manba)
+    ReloadShader(String, String),
 }
 
 #[derive(Debug, Clone)]
@@ -38,9 +52,375 @@ pub trait App: Send + 'static {
     fn update(&mut self, event: AppEvent);
 }
 
+// Asosiy oqimdagi (Main Thread) bitta oyna: OS oynasi + GPU Surface
+struct WindowSurface {
+    id: WindowId,
+    window: Arc<Window>,
+    render_state: RenderState<'static>,
+    rx_render: Receiver<RenderPacket>,
+    tx_recycle: Sender<crate::widgets::base::RenderOutput>,
+    latest_packet: Option<RenderPacket>,
+    last_cursor_icon: winit::window::CursorIcon,
+    clear_color: [f64; 4],
+    focused: bool,
+    occluded: bool,
+    refresh_hz: Option<u32>,
+    // Paketlar bilan kelgan capture so'rovlari: keyingi render'dan keyin o'qiladi
+    captures: Vec<(u64, Option<[u32; 4]>)>,
+    // GPU'dan o'qilayotgan kadrlar: chipta -> natija egasi
+    readbacks: HashMap<u64, crate::capture::CaptureTarget>,
+    recorder: Option<crate::capture::Recorder>,
+}
+
+impl WindowSurface {
+    // `gpu`: birinchi oyna uchun None, keyingilari undagi Device'ni bo'lishadi
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        id: WindowId,
+        window: Arc<Window>,
+        gpu: Option<rore_render::GpuContext>,
+        config: &WindowConfig,
+        rx_render: Receiver<RenderPacket>,
+        tx_recycle: Sender<crate::widgets::base::RenderOutput>,
+        app_config: &RoreConfig,
+        text_renderer_factory: &dyn Fn(
+            &wgpu::Device,
+            &wgpu::Queue,
+            &wgpu::SurfaceConfiguration,
+        ) -> Box<dyn TextRenderer>,
+    ) -> Self {
+        let mut render_state = pollster::block_on(RenderState::from_target(
+            window.inner_size(),
+            window.clone(),
+            gpu,
+            text_renderer_factory,
+        ));
+        render_state.resize(render_state.size, window.scale_factor());
+        render_state.set_sample_count(app_config.msaa.samples());
+        render_state.set_image_budget(app_config.image_cache_mb as u64 * 1024 * 1024);
+
+        // Shaffof oynada fon rangi ham shaffof bo'lishi kerak
+        let transparent = config.transparent && render_state.set_transparent(true);
+        let clear_color = if transparent {
+            [0.0, 0.0, 0.0, 0.0]
+        } else {
+            [0.11, 0.11, 0.18, 1.0]
+        };
+
+        let refresh_hz = monitor_refresh_hz(&window);
+
+        Self {
+            id,
+            window,
+            render_state,
+            rx_render,
+            tx_recycle,
+            latest_packet: None,
+            last_cursor_icon: winit::window::CursorIcon::Default,
+            clear_color,
+            focused: true,
+            occluded: false,
+            refresh_hz,
+            captures: Vec::new(),
+            readbacks: HashMap::new(),
+            recorder: None,
+        }
+    }
+
+    fn logic_resize(&self) -> LogicMessage {
+        let size = self.render_state.size;
+        LogicMessage::Resize(
+            size.width as f32,
+            size.height as f32,
+            self.window.scale_factor() as f32,
+        )
+    }
+
+    // Render thread kutmaydi: o'qish shu kadrda boshlanadi, natija keyingi kadrlarda tarqatiladi
+    fn process_captures(&mut self, global_time: f32) {
+        use crate::capture::{deliver, CaptureTarget};
+        use rore_render::readback::READBACK_RING;
+
+        for (ticket, image) in self.render_state.poll_captures() {
+            match self.readbacks.remove(&ticket) {
+                Some(CaptureTarget::Callback(id)) => deliver(id, image),
+                Some(CaptureTarget::Frames(sink, frames)) => {
+                    if let Some(image) = image {
+                        sink.save(frames, image);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        // Master texture endi tayyor kadrni saqlaydi. Halqa band bo'lsa so'rov keyingi kadrga qoladi
+        let mut waiting = Vec::new();
+        for (id, rect) in std::mem::take(&mut self.captures) {
+            if self.render_state.pending_captures() >= READBACK_RING {
+                waiting.push((id, rect));
+                continue;
+            }
+            match self.render_state.request_capture(rect) {
+                Some(ticket) => {
+                    self.readbacks.insert(ticket, CaptureTarget::Callback(id));
+                }
+                None => deliver(id, None),
+            }
+        }
+        self.captures = waiting;
+
+        if let Some(recorder) = &mut self.recorder {
+            if let Some(frames) = recorder.due(global_time) {
+                if self.render_state.pending_captures() < READBACK_RING {
+                    if let Some(ticket) = self.render_state.request_capture(None) {
+                        self.readbacks.insert(ticket, recorder.claim(&frames));
+                    }
+                }
+            }
+        }
+
+        // Natijalar keyingi kadrlarda yig'iladi
+        if !self.readbacks.is_empty() || !self.captures.is_empty() {
+            self.window.request_redraw();
+        }
+    }
+
+    // false qaytarsa, GPU xotirasi tugagan (ilovadan chiqish kerak)
+    fn redraw(&mut self, global_time: f32) -> bool {
+        let mut got_new_packet = false;
+        let mut combined_scissors = Vec::new();
+        let mut is_full_forced = false;
+
+        while let Ok(mut packet) = self.rx_render.try_recv() {
+            got_new_packet = true;
+            self.captures.append(&mut packet.captures);
+
+            if packet.is_full_redraw_forced || packet.scissor_rects.is_empty() {
+                is_full_forced = true;
+            } else if !is_full_forced {
+                combined_scissors.extend(packet.scissor_rects.iter().copied());
+            }
+
+            if packet.current_cursor_icon != self.last_cursor_icon {
+                self.window.set_cursor_icon(packet.current_cursor_icon);
+                self.last_cursor_icon = packet.current_cursor_icon;
+            }
+
+            // 1. O'chirilgan node'larni GPU dan tozalash!
+            self.render_state.free_gpu_indices(&packet.deleted_nodes);
+
+            // Rasmlar instance'lardan oldin: atlas ularni birinchi ishlatilganda yuklaydi
+            for (id, source) in &packet.output.texture_loads {
+                match source {
+                    crate::widgets::base::TextureSource::Path(path) => {
+                        self.render_state.load_texture_file(id, path)
+                    }
+                    crate::widgets::base::TextureSource::Bytes(bytes) => {
+                        self.render_state.load_texture(id, bytes)
+                    }
+                }
+            }
+
+            let mut compiler = DisplayListCompiler::new();
+            compiler.final_insts = packet.output.sparse_instances.clone();
+            compiler.final_texts = packet.output.sparse_texts.clone();
+
+            for (id, cmds) in &packet.output.node_commands {
+                compiler.compile(*id, cmds);
+            }
+
+            for cmd in &packet.commands {
+                match cmd {
+                    RenderCommand::RegisterShader(_id, _wgsl) => {}
+                    RenderCommand::UpdateNodeCommands(id, cmds) => compiler.compile(*id, cmds),
+                    RenderCommand::UpdateInstance(id, inst) => {
+                        compiler.final_insts.push((*id, **inst))
+                    }
+                    RenderCommand::UpdateText(_id, text) => compiler.final_texts.push(text.clone()),
+                    RenderCommand::Remove(del_id) => {
+                        compiler.final_texts.push((
+                            *del_id,
+                            "".to_string(),
+                            rore_types::Color::TRANSPARENT,
+                            16.0,
+                            Vec2::ZERO,
+                            None,
+                            0.0,
+                        ));
+                    }
+                }
+            }
+
+            self.render_state
+                .filters
+                .set_layers(std::mem::take(&mut packet.filter_layers));
+            self.render_state.update_instances_sparse(
+                &compiler.final_insts,
+                &packet.draw_order,
+                packet.total_nodes,
+            );
+            self.render_state
+                .text_system
+                .update_sparse(&compiler.final_texts);
+            for (id, mesh) in compiler.final_paths {
+                self.render_state.paths.set_mesh(id, mesh);
+            }
+
+            let custom_draws = std::mem::take(&mut packet.custom_draws);
+            let mut mapped_customs = Vec::new();
+            for c_draw in custom_draws {
+                if let Some(wgsl) = c_draw.wgsl_code {
+                    if let Err(e) = self.render_state.custom_shaders.compile(
+                        &self.render_state.device,
+                        &self.render_state.config,
+                        &self.render_state.camera.bind_group_layout,
+                        &c_draw.shader_id,
+                        &wgsl,
+                    ) {
+                        crate::shader::report(&c_draw.shader_id, e);
+                    }
+                }
+                mapped_customs.push(rore_render::custom_shader::CustomDraw {
+                    shader_id: c_draw.shader_id,
+                    rect: c_draw.rect,
+                    clip: c_draw.clip,
+                    uniforms: c_draw.uniforms,
+                    transform: c_draw.transform,
+                    texture: c_draw.texture,
+                });
+            }
+            if !mapped_customs.is_empty() {
+                self.render_state.update_custom_draws(mapped_customs);
+            }
+
+            if let Some(old_packet) = self.latest_packet.replace(packet) {
+                let _ = self.tx_recycle.send(old_packet.output);
+            }
+        }
+
+        self.render_state.global_time = global_time;
+
+        if got_new_packet {
+            if let Some(_packet) = &self.latest_packet {
+                let final_scissors = if is_full_forced {
+                    &[]
+                } else {
+                    &combined_scissors[..]
+                };
+
+                match self
+                    .render_state
+                    .render(self.clear_color, final_scissors, is_full_forced)
+                {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => self
+                        .render_state
+                        .resize(self.render_state.size, self.window.scale_factor()),
+                    Err(wgpu::SurfaceError::OutOfMemory) => return false,
+                    Err(e) => eprintln!("{:?}", e),
+                }
+            }
+        } else {
+            // Tizim bo'sh yotganda ham eski holatni saqlab qolish
+            match self.render_state.render(self.clear_color, &[], true) {
+                Ok(_) => {}
+                Err(wgpu::SurfaceError::Lost) => self
+                    .render_state
+                    .resize(self.render_state.size, self.window.scale_factor()),
+                Err(wgpu::SurfaceError::OutOfMemory) => return false,
+                Err(e) => eprintln!("{:?}", e),
+            }
+        }
+
+        self.process_captures(global_time);
+
+        true
+    }
+}
+
+fn monitor_refresh_hz(window: &Window) -> Option<u32> {
+    window
+        .current_monitor()
+        .and_then(|m| m.refresh_rate_millihertz())
+        .map(|mhz| mhz.div_ceil(1000))
+}
+
+// Barcha oynalarning umumiy holati: bittasi ko'rinib/fokusda tursa, o'sha tezlikda chizamiz
+fn sync_pacer(pacer: &mut FramePacer, surfaces: &HashMap<winit::window::WindowId, WindowSurface>) {
+    pacer.focused = surfaces.values().any(|s| s.focused);
+    pacer.occluded = surfaces.values().all(|s| s.occluded);
+    pacer.monitor_hz = surfaces
+        .values()
+        .filter(|s| !s.occluded)
+        .filter_map(|s| s.refresh_hz)
+        .max();
+    crate::time::record_target_fps(pacer.target_fps());
+}
+
+fn window_event_to_logic(window: &Window, event: &WindowEvent) -> Option<LogicMessage> {
+    match event {
+        WindowEvent::CursorMoved { position, .. } => {
+            let scale_factor = window.scale_factor();
+            let logical_x = (position.x / scale_factor) as f32;
+            let logical_y = (position.y / scale_factor) as f32;
+            Some(LogicMessage::CursorMoved(logical_x, logical_y))
+        }
+        WindowEvent::MouseInput { state, button, .. } => {
+            Some(LogicMessage::MouseInput(*state, *button))
+        }
+        WindowEvent::KeyboardInput {
+            event: key_event, ..
+        } => Some(LogicMessage::KeyboardInput(key_event.clone())),
+        WindowEvent::ModifiersChanged(modifiers) => {
+            Some(LogicMessage::ModifiersChanged(modifiers.state()))
+        }
+        WindowEvent::Touch(touch) => {
+            let scale_factor = window.scale_factor();
+            Some(LogicMessage::Touch {
+                id: touch.id,
+                phase: touch.phase,
+                x: (touch.location.x / scale_factor) as f32,
+                y: (touch.location.y / scale_factor) as f32,
+                // Qalam bosimi; oddiy sensorda kuch ma'lum bo'lmasa 1.0
+                pressure: touch.force.map(|f| f.normalized() as f32).unwrap_or(1.0),
+            })
+        }
+        WindowEvent::TouchpadMagnify { delta, .. } => {
+            Some(LogicMessage::TouchpadMagnify(*delta as f32))
+        }
+        // winit gradusda, soat strelkasiga teskari; bizda radian, ekran koordinatalarida
+        WindowEvent::TouchpadRotate { delta, .. } => {
+            Some(LogicMessage::TouchpadRotate(-delta.to_radians()))
+        }
+        WindowEvent::HoveredFile(path) => Some(LogicMessage::FileHovered(path.clone())),
+        WindowEvent::HoveredFileCancelled => Some(LogicMessage::FileHoverCancelled),
+        WindowEvent::DroppedFile(path) => Some(LogicMessage::FileDropped(path.clone())),
+        WindowEvent::MouseWheel { delta, .. } => {
+            let (x_delta, y_delta) = match delta {
+                MouseScrollDelta::LineDelta(x, y) => (x * 40.0, y * 40.0),
+                MouseScrollDelta::PixelDelta(pos) => (pos.x as f32, pos.y as f32),
+            };
+            Some(LogicMessage::MouseWheel(x_delta, y_delta))
+        }
+        _ => None,
+    }
+}
+
 pub fn run<F>(app: impl App + 'static, config: RoreConfig, text_renderer_factory: F)
 where
-    F: FnOnce(&wgpu::Device, &wgpu::Queue, &wgpu::SurfaceConfiguration) -> Box<dyn TextRenderer>,
+    F: Fn(&wgpu::Device, &wgpu::Queue, &wgpu::SurfaceConfiguration) -> Box<dyn TextRenderer>,
+{
+    run_with_window(app, config, WindowConfig::default(), text_renderer_factory);
+}
+
+/// `run` bilan bir xil, lekin asosiy oyna `WindowConfig` orqali sozlanadi.
+pub fn run_with_window<F>(
+    app: impl App + 'static,
+    config: RoreConfig,
+    window_config: WindowConfig,
+    text_renderer_factory: F,
+) where
+    F: Fn(&wgpu::Device, &wgpu::Queue, &wgpu::SurfaceConfiguration) -> Box<dyn TextRenderer>,
 {
     env_logger::init();
 
@@ -49,21 +429,12 @@ where
         .build()
         .unwrap();
     let proxy = event_loop.create_proxy();
+    let _ = crate::window::WINDOW_PROXY.set(Mutex::new(proxy.clone()));
 
-    let window = WindowBuilder::new()
-        .with_title("Rore UI App")
-        .with_inner_size(PhysicalSize::new(1024, 768))
-        .with_resizable(true)
-        .with_maximized(true)
-        .with_visible(true)
-        .build(&event_loop)
-        .unwrap();
+    let window = Arc::new(window_config.to_builder().build(&event_loop).unwrap());
 
-    let window = Arc::new(window);
-
-    let mut render_state = pollster::block_on(RenderState::new(&window, text_renderer_factory));
     let mut time_manager = TimeManager::new();
-    let mut last_cursor_icon = winit::window::CursorIcon::Default;
+    let mut pacer = FramePacer::new(config.max_fps, config.power_saving);
 
     let wake_registry = Arc::new(Mutex::new(WakeRegistry::new()));
     {
@@ -73,26 +444,39 @@ where
         });
     }
 
-    let window_loop = window.clone();
-    let (tx_logic, rx_logic): (Sender<LogicMessage>, Receiver<LogicMessage>) = mpsc::channel();
+    {
+        let proxy_clone = proxy.clone();
+        crate::reactive::command::CommandQueue::set_waker(move || {
+            let _ = proxy_clone.send_event(RoreUserEvent::WakeUp);
+        });
+    }
+
+    let (tx_logic, rx_logic): (Sender<WindowMessage>, Receiver<WindowMessage>) = mpsc::channel();
     let (tx_render, rx_render): (Sender<RenderPacket>, Receiver<RenderPacket>) = mpsc::channel();
     let (tx_recycle, rx_recycle): (
         Sender<crate::widgets::base::RenderOutput>,
         Receiver<crate::widgets::base::RenderOutput>,
     ) = mpsc::channel();
 
-    let initial_size = render_state.size;
-    let initial_scale = window.scale_factor();
-    let config_clone = config.clone();
+    let main_surface = WindowSurface::new(
+        MAIN_WINDOW,
+        window,
+        None,
+        &window_config,
+        rx_render,
+        tx_recycle,
+        &config,
+        &text_renderer_factory,
+    );
+
+    let initial_size = main_surface.render_state.size;
+    let initial_scale = main_surface.window.scale_factor();
+    let app_config = config;
+    let config_clone = config;
     let wake_registry_logic = wake_registry.clone();
 
-    render_state.resize(initial_size, initial_scale);
-    let _ = tx_logic.send(LogicMessage::Resize(
-        initial_size.width as f32,
-        initial_size.height as f32,
-        initial_scale as f32,
-    ));
-    let _ = tx_logic.send(LogicMessage::RequestRedraw);
+    let _ = tx_logic.send((MAIN_WINDOW, main_surface.logic_resize()));
+    let _ = tx_logic.send((MAIN_WINDOW, LogicMessage::RequestRedraw));
 
     // Katta mantiqiy tsikl calculs.rs ga ko'chib o'tdi!
     thread::spawn(move || {
@@ -109,7 +493,9 @@ where
         );
     });
 
-    let mut latest_packet: Option<RenderPacket> = None;
+    let main_winit_id = main_surface.window.id();
+    let mut surfaces: HashMap<winit::window::WindowId, WindowSurface> = HashMap::new();
+    surfaces.insert(main_winit_id, main_surface);
 
     event_loop
         .run(move |event, elwt| {
@@ -117,182 +503,166 @@ where
 
             match event {
                 Event::UserEvent(RoreUserEvent::WakeUp) => {
-                    window_loop.request_redraw();
-                }
-                Event::WindowEvent {
-                    event: window_event,
-                    window_id,
-                } if window_id == window_loop.id() => match window_event {
-                    WindowEvent::CloseRequested => elwt.exit(),
-                    WindowEvent::Resized(physical_size) => {
-                        if physical_size.width > 0 && physical_size.height > 0 {
-                            let scale_factor = window_loop.scale_factor();
-                            render_state.resize(physical_size, scale_factor);
-                            let _ = tx_logic.send(LogicMessage::Resize(
-                                physical_size.width as f32,
-                                physical_size.height as f32,
-                                scale_factor as f32,
-                            ));
-                            window_loop.request_redraw();
-                        }
-                    }
-                    WindowEvent::CursorMoved { position, .. } => {
-                        let scale_factor = window_loop.scale_factor();
-                        let logical_x = (position.x / scale_factor) as f32;
-                        let logical_y = (position.y / scale_factor) as f32;
-                        let _ = tx_logic.send(LogicMessage::CursorMoved(logical_x, logical_y));
+                    for surface in surfaces.values() {
+                        surface.window.request_redraw();
                     }
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        let _ = tx_logic.send(LogicMessage::MouseInput(state, button));
+                }
+                Event::UserEvent(RoreUserEvent::OpenWindow {
+                    id,
+                    config: window_config,
+                    rx_render,
+                    tx_recycle,
+                }) => {
+                    let Ok(window) = window_config.to_builder().build(elwt) else {
+                        return;
+                    };
+                    let gpu = surfaces.values().next().map(|s| s.render_state.gpu());
+                    let surface = WindowSurface::new(
+                        id,
+                        Arc::new(window),
+                        gpu,
+                        &window_config,
+                        rx_render,
+                        tx_recycle,
+                        &app_config,
+                        &text_renderer_factory,
+                    );
+
+                    let _ = tx_logic.send((id, surface.logic_resize()));
+                    let _ = tx_logic.send((id, LogicMessage::RequestRedraw));
+                    surface.window.request_redraw();
+                    surfaces.insert(surface.window.id(), surface);
+                }
+                Event::UserEvent(RoreUserEvent::Control(id, control)) => {
+                    if let Some(surface) = surfaces.values().find(|s| s.id == id) {
+                        control.apply(&surface.window);
                     }
-                    WindowEvent::KeyboardInput {
-                        event: key_event, ..
-                    } => {
-                        let _ = tx_logic.send(LogicMessage::KeyboardInput(key_event));
+                }
+                Event::UserEvent(RoreUserEvent::Record(id, target)) => {
+                    if let Some(surface) = surfaces.values_mut().find(|s| s.id == id) {
+                        let was_recording = surface.recorder.is_some();
+                        surface.recorder =
+                            target.map(|(dir, fps)| crate::capture::Recorder::new(dir, fps));
+                        // Yozuv davomida kadrlar to'xtovsiz chizilishi kerak
+                        match (was_recording, surface.recorder.is_some()) {
+                            (false, true) => wake_registry.lock().unwrap().acquire("recording"),
+                            (true, false) => wake_registry.lock().unwrap().release("recording"),
+                            _ => {}
+                        }
+                        surface.window.request_redraw();
                     }
-                    // winit WindowEvent loopi ichida (Masalan, WindowEvent::KeyboardInput ning ustiga) yozing:
-                    WindowEvent::ModifiersChanged(modifiers) => {
-                        let _ = tx_logic.send(LogicMessage::ModifiersChanged(modifiers.state()));
+                }
+                Event::UserEvent(RoreUserEvent::ReloadShader(shader_id, wgsl)) => {
+                    // Xato faqat bir marta xabar qilinadi (har oynada bir xil bo'ladi)
+                    let mut error = None;
+                    for surface in surfaces.values_mut() {
+                        let state = &mut surface.render_state;
+                        if let Err(e) = state.custom_shaders.reload(
+                            &state.device,
+                            &state.config,
+                            &state.camera.bind_group_layout,
+                            &shader_id,
+                            &wgsl,
+                        ) {
+                            error = Some(e);
+                        }
+                        surface.window.request_redraw();
                     }
-                    WindowEvent::MouseWheel { delta, .. } => {
-                        let (x_delta, y_delta) = match delta {
-                            MouseScrollDelta::LineDelta(x, y) => (x * 40.0, y * 40.0),
-                            MouseScrollDelta::PixelDelta(pos) => (pos.x as f32, pos.y as f32),
-                        };
-                        let _ = tx_logic.send(LogicMessage::MouseWheel(x_delta, y_delta));
+                    if let Some(e) = error {
+                        crate::shader::report(&shader_id, e);
                     }
-                    WindowEvent::RedrawRequested => {
-                        let mut got_new_packet = false;
-                        let mut combined_scissors = Vec::new();
-                        let mut is_full_forced = false;
-
-                        while let Ok(mut packet) = rx_render.try_recv() {
-                            got_new_packet = true;
-
-                            if packet.is_full_redraw_forced || packet.scissor_rects.is_empty() {
-                                is_full_forced = true;
-                            } else if !is_full_forced {
-                                combined_scissors.extend(packet.scissor_rects.iter().copied());
-                            }
-
-                            if packet.current_cursor_icon != last_cursor_icon {
-                                window_loop.set_cursor_icon(packet.current_cursor_icon);
-                                last_cursor_icon = packet.current_cursor_icon;
-                            }
-
-                            // 1. O'chirilgan node'larni GPU dan tozalash!
-                            render_state.free_gpu_indices(&packet.deleted_nodes);
-
-                            let mut compiler = DisplayListCompiler::new();
-                            compiler.final_insts = packet.output.sparse_instances.clone();
-                            compiler.final_texts = packet.output.sparse_texts.clone();
-
-                            for (id, cmds) in &packet.output.node_commands {
-                                compiler.compile(*id, cmds);
+                }
+                Event::UserEvent(RoreUserEvent::CloseWindow(id)) => {
+                    if id == MAIN_WINDOW {
+                        crate::reactive::persist::flush_persisted();
+                        elwt.exit();
+                    } else {
+                        surfaces.retain(|_, s| {
+                            if s.id == id && s.recorder.is_some() {
+                                wake_registry.lock().unwrap().release("recording");
                             }
-
-                            for cmd in &packet.commands {
-                                match cmd {
-                                    RenderCommand::RegisterShader(_id, _wgsl) => {}
-                                    RenderCommand::UpdateNodeCommands(id, cmds) => {
-                                        compiler.compile(*id, cmds)
-                                    }
-                                    RenderCommand::UpdateInstance(id, inst) => {
-                                        compiler.final_insts.push((*id, inst.clone()))
-                                    }
-                                    RenderCommand::UpdateText(_id, text) => {
-                                        compiler.final_texts.push(text.clone())
-                                    }
-                                    RenderCommand::Remove(del_id) => {
-                                        compiler.final_texts.push((
-                                            *del_id,
-                                            "".to_string(),
-                                            rore_types::Color::TRANSPARENT,
-                                            16.0,
-                                            Vec2::ZERO,
-                                            None,
-                                            0.0,
-                                        ));
+                            s.id != id
+                        });
+                    }
+                }
+                Event::WindowEvent {
+                    event: window_event,
+                    window_id,
+                } => {
+                    let Some(surface) = surfaces.get_mut(&window_id) else {
+                        return;
+                    };
+                    let rore_id = surface.id;
+
+                    match window_event {
+                        WindowEvent::CloseRequested => {
+                            if window_id == main_winit_id {
+                                // Debounce kutayotgan holatni yo'qotmaslik uchun
+                                crate::reactive::persist::flush_persisted();
+                                elwt.exit();
+                            } else {
+                                if let Some(closed) = surfaces.remove(&window_id) {
+                                    if closed.recorder.is_some() {
+                                        wake_registry.lock().unwrap().release("recording");
                                     }
                                 }
+                                let _ = tx_logic.send((rore_id, LogicMessage::WindowClosed));
                             }
-
-                            render_state.update_instances_sparse(
-                                &compiler.final_insts,
-                                &packet.draw_order,
-                                packet.total_nodes,
-                            );
-                            render_state
-                                .text_system
-                                .update_sparse(&compiler.final_texts);
-
-                            let custom_draws = std::mem::take(&mut packet.custom_draws);
-                            let mut mapped_customs = Vec::new();
-                            for c_draw in custom_draws {
-                                if let Some(wgsl) = c_draw.wgsl_code {
-                                    render_state.custom_shaders.compile(
-                                        &render_state.device,
-                                        &render_state.config,
-                                        &render_state.camera.bind_group_layout,
-                                        &c_draw.shader_id,
-                                        &wgsl,
-                                    );
-                                }
-                                mapped_customs.push((
-                                    c_draw.shader_id,
-                                    c_draw.rect,
-                                    c_draw.clip,
-                                    c_draw.uniforms,
+                        }
+                        WindowEvent::Resized(physical_size) => {
+                            if physical_size.width > 0 && physical_size.height > 0 {
+                                let scale_factor = surface.window.scale_factor();
+                                surface.render_state.resize(physical_size, scale_factor);
+                                let _ = tx_logic.send((
+                                    rore_id,
+                                    LogicMessage::Resize(
+                                        physical_size.width as f32,
+                                        physical_size.height as f32,
+                                        scale_factor as f32,
+                                    ),
                                 ));
+                                surface.window.request_redraw();
                             }
-                            if !mapped_customs.is_empty() {
-                                render_state.update_custom_draws(mapped_customs);
+                        }
+                        WindowEvent::RedrawRequested => {
+                            let started = std::time::Instant::now();
+                            if !surface.redraw(time_manager.elapsed) {
+                                elwt.exit();
                             }
-
-                            if let Some(old_packet) = latest_packet.replace(packet) {
-                                let _ = tx_recycle.send(old_packet.output);
+                            // Statistika asosiy oyna kadrlari bo'yicha yuritiladi
+                            if rore_id == MAIN_WINDOW {
+                                crate::time::record_frame(
+                                    std::time::Instant::now(),
+                                    started.elapsed(),
+                                );
                             }
                         }
-
-                        render_state.global_time = time_manager.elapsed;
-
-                        if got_new_packet {
-                            if let Some(_packet) = &latest_packet {
-                                let final_scissors = if is_full_forced {
-                                    &[]
-                                } else {
-                                    &combined_scissors[..]
-                                };
-
-                                match render_state.render(
-                                    [0.11, 0.11, 0.18, 1.0],
-                                    final_scissors,
-                                    is_full_forced,
-                                ) {
-                                    Ok(_) => {}
-                                    Err(wgpu::SurfaceError::Lost) => render_state
-                                        .resize(render_state.size, window_loop.scale_factor()),
-                                    Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
-                                    Err(e) => eprintln!("{:?}", e),
-                                }
+                        WindowEvent::Focused(focused) => {
+                            surface.focused = focused;
+                        }
+                        WindowEvent::Occluded(occluded) => {
+                            surface.occluded = occluded;
+                            if !occluded {
+                                surface.window.request_redraw();
                             }
-                        } else {
-                            // Tizim bo'sh yotganda ham eski holatni saqlab qolish
-                            match render_state.render([0.11, 0.11, 0.18, 1.0], &[], true) {
-                                Ok(_) => {}
-                                Err(wgpu::SurfaceError::Lost) => render_state
-                                    .resize(render_state.size, window_loop.scale_factor()),
-                                Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
-                                Err(e) => eprintln!("{:?}", e),
+                        }
+                        WindowEvent::Moved(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                            // Oyna boshqa monitorga o'tgan bo'lishi mumkin
+                            surface.refresh_hz = monitor_refresh_hz(&surface.window);
+                        }
+                        other => {
+                            if let Some(msg) = window_event_to_logic(&surface.window, &other) {
+                                let _ = tx_logic.send((rore_id, msg));
                             }
                         }
                     }
-                    _ => {}
-                },
+                }
                 Event::AboutToWait => {
                     time_manager.update();
-                    let _ =
-                        tx_logic.send(LogicMessage::Tick(time_manager.dt, time_manager.elapsed));
+                    let _ = tx_logic.send((
+                        MAIN_WINDOW,
+                        LogicMessage::Tick(time_manager.dt, time_manager.elapsed),
+                    ));
 
                     let is_ticking = crate::reactive::signals::ACTIVE_TICKERS
                         .load(std::sync::atomic::Ordering::SeqCst)
@@ -303,10 +673,14 @@ where
                     let is_animating = is_ticking || has_locks;
 
                     if is_animating {
-                        elwt.set_control_flow(ControlFlow::WaitUntil(
-                            std::time::Instant::now() + std::time::Duration::from_millis(16),
-                        ));
-                        window_loop.request_redraw();
+                        sync_pacer(&mut pacer, &surfaces);
+                        let now = std::time::Instant::now();
+                        if pacer.should_render(now) {
+                            for surface in surfaces.values().filter(|s| !s.occluded) {
+                                surface.window.request_redraw();
+                            }
+                        }
+                        elwt.set_control_flow(ControlFlow::WaitUntil(pacer.next_wakeup(now)));
                     } else {
                         elwt.set_control_flow(ControlFlow::Wait);
                     }
@@ -1,15 +1,18 @@
-use crate::app::{App, AppEvent};
-use crate::state::FrameworkState;
+use crate::app::{App, AppEvent, RoreUserEvent};
+use crate::gesture::{Gesture, GestureRecognizer};
+use crate::reactive::signals::{create_scope, dispose_scope, provide_context, ScopeId};
+use crate::state::{FrameworkState, NodeId};
 use crate::time::TimeManager;
-use crate::widgets::base::{BuildContext, EventResult, RenderOutput, WidgetEvent};
-use glam::Vec2;
-use rore_layout::LayoutEngine;
+use crate::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use crate::window::{set_current_window, wake_event_loop, WindowId, WindowOp, MAIN_WINDOW};
+use glam::{Affine2, Vec2};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_types::RoreConfig;
-use std::collections::HashMap;
-use std::sync::mpsc::{Receiver, Sender};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use winit::event::KeyEvent;
-use winit::event::{ElementState, MouseButton};
+use winit::event::{ElementState, MouseButton, TouchPhase};
 
 #[derive(Clone)]
 pub struct CustomShaderDraw {
@@ -18,11 +21,14 @@ pub struct CustomShaderDraw {
     pub rect: [f32; 4],
     pub clip: [f32; 4],
     pub uniforms: Vec<u8>,
+    // rect va clip layout fazosida; shader vs_main da builtins.transform ni qo'llaydi
+    pub transform: Affine2,
+    pub texture: Option<rore_render::ShaderTexture>,
 }
 
 pub enum RenderCommand {
     UpdateNodeCommands(u32, Vec<crate::widgets::base::DisplayCommand>),
-    UpdateInstance(u32, rore_render::Instance),
+    UpdateInstance(u32, Box<rore_render::Instance>),
     UpdateText(u32, rore_types::text::SparseTextItem),
     RegisterShader(String, String),
     Remove(u32),
@@ -39,8 +45,14 @@ pub struct RenderPacket {
     pub deleted_nodes: Vec<u32>,
     pub draw_order: Vec<u32>,
     pub custom_draws: Vec<CustomShaderDraw>,
+    pub filter_layers: Vec<rore_render::FilterLayer>,
+    // Kadr chizilgandan keyin olinadigan rasmlar: id va fizik rect (None = butun oyna)
+    pub captures: Vec<(u64, Option<[u32; 4]>)>,
 }
 
+// Logic Thread'ga keladigan xabar va u tegishli oyna
+pub type WindowMessage = (WindowId, LogicMessage);
+
 pub enum LogicMessage {
     Resize(f32, f32, f32),
     CursorMoved(f32, f32),
@@ -51,28 +63,46 @@ pub enum LogicMessage {
     RequestRedraw,
     RegisterShader(String, String),
     ModifiersChanged(winit::keyboard::ModifiersState), // INQILOB: Ctrl, Shift kabi tugmalar holati
+    WindowClosed, // Foydalanuvchi ikkinchi darajali oynani yopdi
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        x: f32,
+        y: f32,
+        pressure: f32,
+    },
+    TouchpadMagnify(f32), // Trackpad pinch: 0.1 = 10% kattalashtirish
+    TouchpadRotate(f32),  // Radianlarda, soat strelkasi bo'yicha musbat
+    FileHovered(std::path::PathBuf),
+    FileHoverCancelled,
+    FileDropped(std::path::PathBuf),
 }
 
 pub struct DisplayListCompiler {
     pub clip_stack: Vec<[f32; 4]>,
-    pub transform_stack: Vec<Vec2>,
+    pub transform_stack: Vec<Affine2>,
     pub final_insts: Vec<(u32, rore_render::Instance)>,
     pub final_texts: Vec<rore_types::text::SparseTextItem>,
     pub final_custom: Vec<CustomShaderDraw>,
+    // Node qayta kompilyatsiya qilinsa, eski yo'llari shu mesh bilan almashadi (bo'sh = o'chirish)
+    pub final_paths: HashMap<u32, rore_render::PathMesh>,
 }
 
 impl DisplayListCompiler {
     pub fn new() -> Self {
         Self {
             clip_stack: vec![[-10000.0, -10000.0, 20000.0, 20000.0]],
-            transform_stack: vec![Vec2::ZERO],
+            transform_stack: vec![Affine2::IDENTITY],
             final_insts: Vec::new(),
             final_texts: Vec::new(),
             final_custom: Vec::new(),
+            final_paths: HashMap::new(),
         }
     }
 
     pub fn compile(&mut self, id: u32, cmds: &[crate::widgets::base::DisplayCommand]) {
+        self.final_paths
+            .insert(id, rore_render::PathMesh::default());
         for cmd in cmds {
             match cmd {
                 crate::widgets::base::DisplayCommand::PushClip { rect } => {
@@ -93,9 +123,9 @@ impl DisplayListCompiler {
                         self.clip_stack.pop();
                     }
                 }
-                crate::widgets::base::DisplayCommand::PushTransform { offset } => {
+                crate::widgets::base::DisplayCommand::PushTransform { transform } => {
                     let current = self.transform_stack.last().unwrap();
-                    self.transform_stack.push(*current + *offset);
+                    self.transform_stack.push(*current * *transform);
                 }
                 crate::widgets::base::DisplayCommand::PopTransform => {
                     if self.transform_stack.len() > 1 {
@@ -107,9 +137,13 @@ impl DisplayListCompiler {
                     color_start,
                     color_end,
                     gradient_angle,
+                    gradient,
+                    image,
                     border_radius,
                     border_width,
                     border_color,
+                    border_style,
+                    border_dash,
                     anim_start_time,
                     anim_duration,
                 } => {
@@ -118,20 +152,21 @@ impl DisplayListCompiler {
                     self.final_insts.push((
                         id,
                         rore_render::Instance {
-                            position: Vec2::new(
-                                rect[0] + current_transform.x,
-                                rect[1] + current_transform.y,
-                            ),
+                            position: Vec2::new(rect[0], rect[1]),
                             size: Vec2::new(rect[2], rect[3]),
                             color_start: *color_start,
                             color_end: *color_end,
                             target_color_start: *color_start,
                             target_color_end: *color_end,
                             gradient_angle: *gradient_angle,
+                            gradient: gradient.as_deref().copied(),
+                            image: *image,
                             border_radius: *border_radius,
                             border_width: *border_width,
                             border_color: *border_color,
                             target_border_color: *border_color,
+                            border_style: *border_style,
+                            border_dash: *border_dash,
                             shadow_color: [0.0; 4],
                             shadow_offset: Vec2::ZERO,
                             shadow_blur: 0.0,
@@ -139,6 +174,7 @@ impl DisplayListCompiler {
                             clip_rect: current_clip,
                             anim_start_time: *anim_start_time,
                             anim_duration: *anim_duration,
+                            transform: current_transform,
                         },
                     ));
                 }
@@ -152,14 +188,17 @@ impl DisplayListCompiler {
                 } => {
                     let current_clip = clip.unwrap_or(*self.clip_stack.last().unwrap());
                     let current_transform = *self.transform_stack.last().unwrap();
-                    self.final_texts.push((
-                        id,
-                        text.clone(),
-                        rore_types::Color::new(color[0], color[1], color[2], color[3]),
-                        *font_size,
-                        *pos + current_transform,
-                        Some(current_clip),
-                        *width_limit,
+                    self.final_texts.push(transform_text(
+                        &current_transform,
+                        (
+                            id,
+                            text.clone(),
+                            rore_types::Color::new(color[0], color[1], color[2], color[3]),
+                            *font_size,
+                            *pos,
+                            Some(current_clip),
+                            *width_limit,
+                        ),
                     ));
                 }
                 crate::widgets::base::DisplayCommand::DrawCustomShader {
@@ -167,246 +206,396 @@ impl DisplayListCompiler {
                     wgsl_code,
                     rect,
                     uniforms,
+                    texture,
                 } => {
                     let current_clip = *self.clip_stack.last().unwrap();
                     let current_transform = *self.transform_stack.last().unwrap();
+                    // Faqat siljish bo'lsa rect'ga qo'shiladi: transform'ni o'qimaydigan eski shader'lar uchun
+                    let (rect, clip, transform) =
+                        if current_transform.matrix2 == glam::Mat2::IDENTITY {
+                            let t = current_transform.translation;
+                            (
+                                [rect[0] + t.x, rect[1] + t.y, rect[2], rect[3]],
+                                [
+                                    current_clip[0] + t.x,
+                                    current_clip[1] + t.y,
+                                    current_clip[2],
+                                    current_clip[3],
+                                ],
+                                Affine2::IDENTITY,
+                            )
+                        } else {
+                            (*rect, current_clip, current_transform)
+                        };
                     self.final_custom.push(CustomShaderDraw {
                         shader_id: shader_id.clone(),
                         wgsl_code: wgsl_code.clone(),
-                        rect: [
-                            rect[0] + current_transform.x,
-                            rect[1] + current_transform.y,
-                            rect[2],
-                            rect[3],
-                        ],
-                        clip: current_clip,
+                        rect,
+                        clip,
                         uniforms: uniforms.clone(),
+                        transform,
+                        texture: texture.clone(),
                     });
                 }
+                crate::widgets::base::DisplayCommand::DrawPath { mesh } => {
+                    let current_clip = *self.clip_stack.last().unwrap();
+                    let current_transform = *self.transform_stack.last().unwrap();
+                    if let Some(target) = self.final_paths.get_mut(&id) {
+                        target.append_transformed(mesh, current_transform, Some(current_clip));
+                    }
+                }
             }
         }
     }
 }
 
-pub fn run_logic_thread<A: App + 'static>(
-    mut app: A,
-    rx_logic: Receiver<LogicMessage>,
-    tx_render: Sender<RenderPacket>,
-    rx_recycle: Receiver<RenderOutput>,
-    config_clone: RoreConfig,
-    wake_registry_logic: Arc<Mutex<crate::state::WakeRegistry>>,
-    initial_width: f32,
-    initial_height: f32,
-    initial_scale: f32,
-) {
-    let mut fw_state = FrameworkState::new(config_clone.clone(), wake_registry_logic.clone());
-    let mut layout_engine = LayoutEngine::new();
-    let build_ctx = BuildContext {};
-    let mut logic_time_manager = TimeManager::new();
-
-    let mut previous_active_nodes: std::collections::HashSet<u32> =
-        std::collections::HashSet::new();
-    let mut previous_visual_bounds: HashMap<u32, [u32; 4]> = HashMap::new();
-
-    app.update(AppEvent::Init);
-    layout_engine.clear();
-    fw_state.arena.clear();
-
-    let (_, root_node_id) = crate::reactive::signals::create_scope(|| {
-        let root_widget = app.view();
-        root_widget.build(&mut fw_state.arena, &mut layout_engine, &build_ctx)
-    });
+// Qatlam rect'i, clip'i va a'zolari joriy layout bo'yicha to'ldiriladi.
+// FilterBox va `.opacity()` / `.cache_as_layer()` bir tugunda bo'lsa, bitta qatlamga qo'shiladi
+fn collect_filter_layers(
+    fw_state: &FrameworkState,
+    engine: &LayoutEngine,
+) -> Vec<rore_render::FilterLayer> {
+    let arena = &fw_state.arena;
+    let ids: HashSet<NodeId> = fw_state
+        .filter_layers
+        .keys()
+        .chain(arena.group_layers.keys())
+        .copied()
+        .collect();
+    let mut layers = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let Some(&root) = arena.taffy_map.get(id) else {
+            continue;
+        };
+        let Some(&own) = fw_state.node_bounds.get(&root) else {
+            continue;
+        };
+        let own = fw_state.screen_rect(id.0, own);
+
+        let mut layer = fw_state
+            .filter_layers
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| rore_render::FilterLayer::new(id.0, Vec::new(), false));
+        layer.opacity = arena.opacities[id.0 as usize];
+        layer.cached = arena.group_layers.get(id) == Some(&true);
+        layer.stale = fw_state.stale_layers.contains(id);
+        // To'liq shaffof emas va keshlanmaydigan bo'sh qatlam oddiy chiziladi
+        if layer.filters.is_empty() && !layer.backdrop && !layer.cached && layer.opacity >= 1.0 {
+            continue;
+        }
+        layer.clip = fw_state
+            .get_clip_rect(root)
+            .unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]);
+        layer.members.clear();
+        // Content qatlam toshib chiqqan bolalarni ham qamrab olishi kerak
+        let mut rect = own;
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if let Some(member) = fw_state.arena.node_map.get(&node) {
+                layer.members.push(member.0);
+            }
+            if !layer.backdrop && node != root {
+                let bounds = fw_state.node_bounds.get(&node).map(|&b| {
+                    let member = fw_state.arena.node_map.get(&node).map_or(u32::MAX, |m| m.0);
+                    fw_state.screen_rect(member, b)
+                });
+                if let Some(b) = bounds {
+                    let x = rect[0].min(b[0]);
+                    let y = rect[1].min(b[1]);
+                    let r = (rect[0] + rect[2]).max(b[0] + b[2]);
+                    let bottom = (rect[1] + rect[3]).max(b[1] + b[3]);
+                    rect = [x, y, r - x, bottom - y];
+                }
+            }
+            if let Ok(children) = engine.taffy.children(node) {
+                stack.extend(children);
+            }
+        }
+        layer.rect = rect;
+        layers.push(layer);
+    }
+    layers.sort_by_key(|l| l.id);
+    layers
+}
 
-    let root_taffy_node = *fw_state.arena.taffy_map.get(&root_node_id).unwrap();
-    layout_engine.root = Some(root_taffy_node);
+// glyphon matnni aylantira olmaydi: boshlanish nuqtasi ko'chadi, o'lcham o'rtacha masshtab bilan
+fn transform_text(
+    m: &Affine2,
+    text: rore_types::text::SparseTextItem,
+) -> rore_types::text::SparseTextItem {
+    if *m == Affine2::IDENTITY {
+        return text;
+    }
+    let (id, content, color, size, pos, clip, width) = text;
+    let scale = m.matrix2.determinant().abs().sqrt();
+    (
+        id,
+        content,
+        color,
+        size * scale,
+        m.transform_point2(pos),
+        clip.map(|c| rore_types::transform_rect(m, c)),
+        width * scale,
+    )
+}
 
-    let mut current_width = initial_width;
-    let mut current_height = initial_height;
-    let mut current_scale = initial_scale;
+// Widget'lar layout fazosida chizadi: ajdodlardan yig'ilgan transform shu yerda qo'shiladi
+fn apply_world_transforms(fw_state: &FrameworkState, output: &mut RenderOutput) {
+    if fw_state.world_transforms.is_empty() {
+        return;
+    }
+    for (id, inst) in &mut output.sparse_instances {
+        if let Some(m) = fw_state.world_transforms.get(id) {
+            inst.transform = *m * inst.transform;
+        }
+    }
+    for text in &mut output.sparse_texts {
+        if let Some(m) = fw_state.world_transforms.get(&text.0) {
+            *text = transform_text(m, text.clone());
+        }
+    }
+    for (id, cmds) in &mut output.node_commands {
+        if let Some(m) = fw_state.world_transforms.get(id) {
+            cmds.insert(
+                0,
+                crate::widgets::base::DisplayCommand::PushTransform { transform: *m },
+            );
+            cmds.push(crate::widgets::base::DisplayCommand::PopTransform);
+        }
+    }
+}
 
-    layout_engine.compute(
-        current_width / current_scale,
-        current_height / current_scale,
-    );
-    fw_state.update_aabbs(&layout_engine, root_taffy_node, true);
+fn pixel_rect(rect: [f32; 4], sf: f32) -> [u32; 4] {
+    let x1 = (rect[0] * sf).floor().max(0.0);
+    let y1 = (rect[1] * sf).floor().max(0.0);
+    let x2 = ((rect[0] + rect[2]) * sf).ceil() + 2.0;
+    let y2 = ((rect[1] + rect[3]) * sf).ceil() + 2.0;
+    [
+        x1 as u32,
+        y1 as u32,
+        (x2 - x1).max(0.0) as u32,
+        (y2 - y1).max(0.0) as u32,
+    ]
+}
 
+// Filtr natijasi butun qatlam bo'yicha o'zgaradi: unga tekkan damage butun qatlamga kengayadi.
+// Tegmagan qatlam qayta chizilmaydi va GPU'da blur qayta hisoblanmaydi
+fn expand_filter_damage(dirty: &mut Vec<[u32; 4]>, layers: &[[u32; 4]]) {
+    let touches = |a: [u32; 4], b: [u32; 4]| {
+        a[0] < b[0] + b[2] && b[0] < a[0] + a[2] && a[1] < b[1] + b[3] && b[1] < a[1] + a[3]
+    };
+    let mut added = vec![false; layers.len()];
+    // Qo'shilgan qatlam boshqasiga tegishi mumkin
     loop {
-        let Ok(first_msg) = rx_logic.recv() else {
+        let mut changed = false;
+        for (i, &layer) in layers.iter().enumerate() {
+            if !added[i] && dirty.iter().any(|&d| touches(d, layer)) {
+                dirty.push(layer);
+                added[i] = true;
+                changed = true;
+            }
+        }
+        if !changed {
             break;
-        };
-
-        let mut needs_compute = false;
-        let mut msgs = vec![first_msg];
-        while let Ok(m) = rx_logic.try_recv() {
-            msgs.push(m);
         }
+    }
+}
+
+/// Bitta oynaning mantiqiy holati: o'z daraxti, layout dvigateli va render kanali.
+/// Barcha oynalar bitta Logic Thread va bitta ReactiveRuntime'ni bo'lishadi.
+pub struct LogicWindow {
+    pub id: WindowId,
+    pub fw_state: FrameworkState,
+    pub layout_engine: LayoutEngine,
+    tx_render: Sender<RenderPacket>,
+    rx_recycle: Receiver<RenderOutput>,
+    root_scope: ScopeId,
+    root_node_id: NodeId,
+    root_taffy_node: TaffyNode,
+    previous_active_nodes: HashSet<u32>,
+    previous_visual_bounds: HashMap<u32, [u32; 4]>,
+    current_width: f32,
+    current_height: f32,
+    current_scale: f32,
+    needs_compute: bool,
+    commands: Vec<RenderCommand>,
+
+    gestures: GestureRecognizer,
+    // Birinchi barmoq bosgan tugun: ishoralar shundan boshlab bubbling qilinadi
+    touch_target: Option<TaffyNode>,
+    // Sichqonchani emulyatsiya qilayotgan barmoq (tugmalar va ScrollView odatdagidek ishlashi uchun)
+    primary_touch: Option<u64>,
+    long_press_lock: bool,
+    tooltip_lock: bool,
+}
 
-        let mut batched_msgs = Vec::with_capacity(msgs.len());
-        let mut last_cursor_idx = None;
-        let mut last_resize_idx = None;
+impl LogicWindow {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<V: FnOnce() -> Box<dyn Widget>>(
+        id: WindowId,
+        view: V,
+        config: RoreConfig,
+        wake_registry: Arc<Mutex<crate::state::WakeRegistry>>,
+        tx_render: Sender<RenderPacket>,
+        rx_recycle: Receiver<RenderOutput>,
+        width: f32,
+        height: f32,
+        scale: f32,
+    ) -> Self {
+        set_current_window(Some(id));
+        let mut fw_state = FrameworkState::new(config, wake_registry);
+        fw_state.window_id = id;
+        let mut layout_engine = LayoutEngine::new();
+        let build_ctx = BuildContext {};
+
+        // Oyna ID si kontekst sifatida: shu daraxtdagi effectlar buyruqlarni o'z oynasiga yuboradi
+        let (root_scope, root_node_id) = create_scope(|| {
+            provide_context(id);
+            let root_widget = view();
+            root_widget.build(&mut fw_state.arena, &mut layout_engine, &build_ctx)
+        });
+
+        let root_taffy_node = *fw_state.arena.taffy_map.get(&root_node_id).unwrap();
+        layout_engine.root = Some(root_taffy_node);
+        layout_engine.compute(width / scale, height / scale);
+        fw_state.update_aabbs(&layout_engine, root_taffy_node, true);
 
-        for msg in msgs {
-            match msg {
-                LogicMessage::CursorMoved(_, _) => {
-                    if let Some(idx) = last_cursor_idx {
-                        batched_msgs[idx] = msg;
-                    } else {
-                        last_cursor_idx = Some(batched_msgs.len());
-                        batched_msgs.push(msg);
-                    }
-                }
-                LogicMessage::Resize(_, _, _) => {
-                    if let Some(idx) = last_resize_idx {
-                        batched_msgs[idx] = msg;
-                    } else {
-                        last_resize_idx = Some(batched_msgs.len());
-                        batched_msgs.push(msg);
-                    }
-                }
-                _ => batched_msgs.push(msg),
-            }
+        Self {
+            id,
+            fw_state,
+            layout_engine,
+            tx_render,
+            rx_recycle,
+            root_scope,
+            root_node_id,
+            root_taffy_node,
+            previous_active_nodes: HashSet::new(),
+            previous_visual_bounds: HashMap::new(),
+            current_width: width,
+            current_height: height,
+            current_scale: scale,
+            needs_compute: false,
+            commands: Vec::new(),
+            gestures: GestureRecognizer::new(),
+            touch_target: None,
+            primary_touch: None,
+            long_press_lock: false,
+            tooltip_lock: false,
         }
+    }
 
-        let mut commands = Vec::new();
+    pub fn handle_message<A: App>(&mut self, msg: LogicMessage, app: &mut A) {
+        let fw_state = &mut self.fw_state;
+        match msg {
+            LogicMessage::ModifiersChanged(state) => {
+                // INQILOB: Ctrl, Shift, Alt holatlari Yadroga yozildi!
+                fw_state.modifiers = state;
+            }
+            LogicMessage::RegisterShader(id, wgsl) => {
+                self.commands.push(RenderCommand::RegisterShader(id, wgsl));
+            }
+            LogicMessage::Resize(w, h, scale) => {
+                self.current_width = w;
+                self.current_height = h;
+                self.current_scale = scale;
+                self.needs_compute = true;
+                fw_state.full_redraw = true;
+                app.update(AppEvent::Resize(w / scale, h / scale));
+            }
+            LogicMessage::CursorMoved(x, y) => {
+                if fw_state.config.mouse_support || fw_state.config.touch_support {
+                    fw_state.update_cursor(x, y);
+                    if fw_state.is_dragging() {
+                        fw_state.update_drag(x, y);
+                    }
 
-        for msg in batched_msgs {
-            match msg {
-                LogicMessage::ModifiersChanged(state) => {
-                    // INQILOB: Ctrl, Shift, Alt holatlari Yadroga yozildi!
-                    fw_state.modifiers = state;
-                }
-                LogicMessage::RegisterShader(id, wgsl) => {
-                    commands.push(RenderCommand::RegisterShader(id, wgsl));
-                }
-                LogicMessage::Resize(w, h, scale) => {
-                    current_width = w;
-                    current_height = h;
-                    current_scale = scale;
-                    needs_compute = true;
-                    fw_state.full_redraw = true;
-                    app.update(AppEvent::Resize(w / scale, h / scale));
-                }
-                LogicMessage::CursorMoved(x, y) => {
-                    if fw_state.config.mouse_support {
-                        fw_state.update_cursor(x, y);
-
-                        // rore-core/src/calculs.rs faylida 327-qator atrofida:
-
-                        if let Some(active) = fw_state.active_node {
-                            let mut dx = 0.0;
-                            let mut dy = 0.0;
-                            if let Some(last) = fw_state.last_cursor_pos {
-                                dx = x - last.x;
-                                dy = y - last.y;
-                            }
+                    // rore-core/src/calculs.rs faylida 327-qator atrofida:
 
-                            if dx != 0.0 || dy != 0.0 {
-                                // INQILOB: Event Bubbling va Gesture Stealing (Ishoralarni o'g'irlash)
-                                let bubble_chain = fw_state.get_event_bubble_chain(active);
-                                let mut consumed_by = None;
+                    // Drag & Drop paytida MouseDrag yuborilmaydi: sessiya o'zi boshqaradi
+                    if let Some(active) = fw_state.active_node.filter(|_| !fw_state.is_dragging()) {
+                        let mut dx = 0.0;
+                        let mut dy = 0.0;
+                        if let Some(last) = fw_state.last_cursor_pos {
+                            dx = x - last.x;
+                            dy = y - last.y;
+                        }
 
-                                for node in bubble_chain {
-                                    if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
-                                        if let Some(mut widget) =
-                                            fw_state.arena.widgets[node_id.0 as usize].take()
-                                        {
-                                            // Tortishish hodisasini zanjir bo'ylab uzatamiz
-                                            let res = widget.handle_event(
-                                                &mut fw_state,
-                                                &WidgetEvent::MouseDrag { dx, dy },
-                                            );
-                                            fw_state.arena.widgets[node_id.0 as usize] =
-                                                Some(widget);
+                        if dx != 0.0 || dy != 0.0 {
+                            // INQILOB: Event Bubbling va Gesture Stealing (Ishoralarni o'g'irlash)
+                            let bubble_chain = fw_state.get_event_bubble_chain(active);
+                            let mut consumed_by = None;
 
-                                            if res == EventResult::Consumed {
-                                                consumed_by = Some(node);
-                                                break; // Skroll o'ziga oldi, yuqoriga chiqmaymiz
-                                            }
-                                        }
-                                    }
-                                }
+                            for node in bubble_chain {
+                                if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                                    if let Some(mut widget) =
+                                        fw_state.arena.widgets[node_id.0 as usize].take()
+                                    {
+                                        // Tortishish hodisasini zanjir bo'ylab uzatamiz
+                                        let res = widget.handle_event(
+                                            fw_state,
+                                            &WidgetEvent::MouseDrag { dx, dy },
+                                        );
+                                        fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
 
-                                // Agar hodisani boshqa vidjet (masalan ScrollView) o'zlashtirgan bo'lsa:
-                                if let Some(consumer) = consumed_by {
-                                    if consumer != active {
-                                        // 1. Eski tugmaga (active) "Bekor qilinding" deb xabar beramiz
-                                        if let Some(&active_id) =
-                                            fw_state.arena.node_map.get(&active)
-                                        {
-                                            if let Some(mut widget) =
-                                                fw_state.arena.widgets[active_id.0 as usize].take()
-                                            {
-                                                widget.handle_event(
-                                                    &mut fw_state,
-                                                    &WidgetEvent::MouseCancel,
-                                                );
-                                                fw_state.arena.widgets[active_id.0 as usize] =
-                                                    Some(widget);
-                                            }
+                                        if res == EventResult::Consumed {
+                                            consumed_by = Some(node);
+                                            break; // Skroll o'ziga oldi, yuqoriga chiqmaymiz
                                         }
-                                        // 2. Yangi xo'jayin (active_node) endi iste'molchi bo'ladi!
-                                        fw_state.active_node = Some(consumer);
                                     }
                                 }
                             }
-                        }
-
-                        let new_hover = fw_state.hit_test(x, y);
 
-                        if new_hover != fw_state.hovered_node {
-                            if let Some(old_node) = fw_state.hovered_node {
-                                let bubble_chain = fw_state.get_event_bubble_chain(old_node);
-                                for node in bubble_chain {
-                                    if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                            // Agar hodisani boshqa vidjet (masalan ScrollView) o'zlashtirgan bo'lsa:
+                            if let Some(consumer) = consumed_by {
+                                if consumer != active {
+                                    // 1. Eski tugmaga (active) "Bekor qilinding" deb xabar beramiz
+                                    if let Some(&active_id) = fw_state.arena.node_map.get(&active) {
                                         if let Some(mut widget) =
-                                            fw_state.arena.widgets[node_id.0 as usize].take()
+                                            fw_state.arena.widgets[active_id.0 as usize].take()
                                         {
-                                            let res = widget.handle_event(
-                                                &mut fw_state,
-                                                &WidgetEvent::HoverLeave,
-                                            );
-                                            fw_state.arena.widgets[node_id.0 as usize] =
+                                            widget
+                                                .handle_event(fw_state, &WidgetEvent::MouseCancel);
+                                            fw_state.arena.widgets[active_id.0 as usize] =
                                                 Some(widget);
-                                            if res == EventResult::Consumed {
-                                                break;
-                                            }
                                         }
                                     }
+                                    // 2. Yangi xo'jayin (active_node) endi iste'molchi bo'ladi!
+                                    fw_state.active_node = Some(consumer);
                                 }
                             }
-                            if let Some(new_node) = new_hover {
-                                let bubble_chain = fw_state.get_event_bubble_chain(new_node);
-                                for node in bubble_chain {
-                                    if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
-                                        if let Some(mut widget) =
-                                            fw_state.arena.widgets[node_id.0 as usize].take()
-                                        {
-                                            let res = widget.handle_event(
-                                                &mut fw_state,
-                                                &WidgetEvent::HoverEnter,
-                                            );
-                                            fw_state.arena.widgets[node_id.0 as usize] =
-                                                Some(widget);
-                                            if res == EventResult::Consumed {
-                                                break;
-                                            }
+                        }
+                    }
+
+                    let new_hover = fw_state.hit_test(x, y);
+
+                    if new_hover != fw_state.hovered_node {
+                        if let Some(old_node) = fw_state.hovered_node {
+                            let bubble_chain = fw_state.get_event_bubble_chain(old_node);
+                            for node in bubble_chain {
+                                if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                                    if let Some(mut widget) =
+                                        fw_state.arena.widgets[node_id.0 as usize].take()
+                                    {
+                                        let res =
+                                            widget.handle_event(fw_state, &WidgetEvent::HoverLeave);
+                                        fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
+                                        if res == EventResult::Consumed {
+                                            break;
                                         }
                                     }
                                 }
                             }
-                            fw_state.hovered_node = new_hover;
                         }
-                        if let Some(hover) = fw_state.hovered_node {
-                            let bubble_chain = fw_state.get_event_bubble_chain(hover);
+                        if let Some(new_node) = new_hover {
+                            let bubble_chain = fw_state.get_event_bubble_chain(new_node);
                             for node in bubble_chain {
                                 if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
                                     if let Some(mut widget) =
                                         fw_state.arena.widgets[node_id.0 as usize].take()
                                     {
-                                        let res = widget.handle_event(
-                                            &mut fw_state,
-                                            &WidgetEvent::MouseMove { x, y },
-                                        );
+                                        let res =
+                                            widget.handle_event(fw_state, &WidgetEvent::HoverEnter);
                                         fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
                                         if res == EventResult::Consumed {
                                             break;
@@ -415,17 +604,90 @@ pub fn run_logic_thread<A: App + 'static>(
                                 }
                             }
                         }
+                        fw_state.hovered_node = new_hover;
+                        fw_state.update_tooltip_owner();
+                    }
+                    if let Some(hover) = fw_state.hovered_node {
+                        let bubble_chain = fw_state.get_event_bubble_chain(hover);
+                        for node in bubble_chain {
+                            if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                                if let Some(mut widget) =
+                                    fw_state.arena.widgets[node_id.0 as usize].take()
+                                {
+                                    let res = widget
+                                        .handle_event(fw_state, &WidgetEvent::MouseMove { x, y });
+                                    fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
+                                    if res == EventResult::Consumed {
+                                        break;
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
-                LogicMessage::MouseInput(state, button) => {
-                    if fw_state.config.mouse_support || fw_state.config.touch_support {
-                        match state {
-                            ElementState::Pressed => {
-                                if button == MouseButton::Left {
+            }
+            LogicMessage::MouseInput(state, button) => {
+                if fw_state.config.mouse_support || fw_state.config.touch_support {
+                    match state {
+                        ElementState::Pressed => {
+                            fw_state.dismiss_tooltip();
+                            if button == MouseButton::Left {
+                                fw_state.press_serial += 1;
+                                if let Some(hit_node) = fw_state.hovered_node {
+                                    let bubble_chain = fw_state.get_event_bubble_chain(hit_node);
+                                    let mut consumed_node = None;
+                                    for node in bubble_chain {
+                                        let mut consumed = false;
+                                        if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                                            if let Some(mut widget) =
+                                                fw_state.arena.widgets[node_id.0 as usize].take()
+                                            {
+                                                let result = widget.handle_event(
+                                                    fw_state,
+                                                    &WidgetEvent::MouseDown {
+                                                        button,
+                                                        modifiers: fw_state.modifiers,
+                                                    },
+                                                );
+                                                fw_state.arena.widgets[node_id.0 as usize] =
+                                                    Some(widget);
+                                                if matches!(result, EventResult::Consumed) {
+                                                    consumed = true;
+                                                }
+                                            }
+                                        }
+                                        if consumed {
+                                            consumed_node = Some(node);
+                                            break;
+                                        }
+                                    }
+                                    fw_state.active_node = consumed_node.or(fw_state.hovered_node);
+                                    fw_state.focused_node = consumed_node.or(fw_state.hovered_node);
+                                } else {
+                                    fw_state.active_node = None;
+                                    fw_state.focused_node = None;
+                                }
+                            } else if let Some(hit_node) = fw_state.hovered_node {
+                                // O'ng/o'rta tugma: active_node (drag, Click) ga tegmaymiz
+                                let event = WidgetEvent::MouseDown {
+                                    button,
+                                    modifiers: fw_state.modifiers,
+                                };
+                                fw_state.dispatch_bubbling(hit_node, &event);
+                            }
+                        }
+                        ElementState::Released => {
+                            if button == MouseButton::Left {
+                                if fw_state.is_dragging() {
+                                    // Sudrash tugadi: Click o'rniga Drop
+                                    fw_state.finish_drag();
+                                    fw_state.active_node = None;
+                                    return;
+                                }
+                                if fw_state.active_node.is_some() {
                                     if let Some(hit_node) = fw_state.hovered_node {
                                         let bubble_chain =
                                             fw_state.get_event_bubble_chain(hit_node);
-                                        let mut consumed_node = None;
                                         for node in bubble_chain {
                                             let mut consumed = false;
                                             if let Some(&node_id) =
@@ -436,8 +698,11 @@ pub fn run_logic_thread<A: App + 'static>(
                                                     .take()
                                                 {
                                                     let result = widget.handle_event(
-                                                        &mut fw_state,
-                                                        &WidgetEvent::MouseDown,
+                                                        fw_state,
+                                                        &WidgetEvent::Click {
+                                                            button,
+                                                            modifiers: fw_state.modifiers,
+                                                        },
                                                     );
                                                     fw_state.arena.widgets[node_id.0 as usize] =
                                                         Some(widget);
@@ -447,136 +712,96 @@ pub fn run_logic_thread<A: App + 'static>(
                                                 }
                                             }
                                             if consumed {
-                                                consumed_node = Some(node);
-                                                break;
-                                            }
-                                        }
-                                        fw_state.active_node =
-                                            consumed_node.or(fw_state.hovered_node);
-                                        fw_state.focused_node =
-                                            consumed_node.or(fw_state.hovered_node);
-                                    } else {
-                                        fw_state.active_node = None;
-                                        fw_state.focused_node = None;
-                                    }
-                                }
-                            }
-                            ElementState::Released => {
-                                if button == MouseButton::Left {
-                                    if fw_state.active_node.is_some() {
-                                        if let Some(hit_node) = fw_state.hovered_node {
-                                            let bubble_chain =
-                                                fw_state.get_event_bubble_chain(hit_node);
-                                            for node in bubble_chain {
-                                                let mut consumed = false;
                                                 if let Some(&node_id) =
                                                     fw_state.arena.node_map.get(&node)
                                                 {
-                                                    if let Some(mut widget) = fw_state.arena.widgets
-                                                        [node_id.0 as usize]
-                                                        .take()
+                                                    if let Some(id_str) =
+                                                        fw_state.arena.node_to_id_str.get(&node_id)
                                                     {
-                                                        let result = widget.handle_event(
-                                                            &mut fw_state,
-                                                            &WidgetEvent::Click,
-                                                        );
-                                                        fw_state.arena.widgets
-                                                            [node_id.0 as usize] = Some(widget);
-                                                        if matches!(result, EventResult::Consumed) {
-                                                            consumed = true;
-                                                        }
+                                                        app.update(AppEvent::Click(id_str.clone()));
                                                     }
                                                 }
-                                                if consumed {
-                                                    if let Some(&node_id) =
-                                                        fw_state.arena.node_map.get(&node)
-                                                    {
-                                                        if let Some(id_str) = fw_state
-                                                            .arena
-                                                            .node_to_id_str
-                                                            .get(&node_id)
-                                                        {
-                                                            app.update(AppEvent::Click(
-                                                                id_str.clone(),
-                                                            ));
-                                                        }
-                                                    }
-                                                    break;
-                                                }
+                                                break;
                                             }
                                         }
                                     }
-                                    fw_state.active_node = None;
                                 }
+                                fw_state.active_node = None;
+                            } else if let Some(hit_node) = fw_state.hovered_node {
+                                let event = WidgetEvent::Click {
+                                    button,
+                                    modifiers: fw_state.modifiers,
+                                };
+                                fw_state.dispatch_bubbling(hit_node, &event);
                             }
                         }
                     }
                 }
-                LogicMessage::KeyboardInput(key_event) => {
-                    if key_event.state == ElementState::Pressed {
-                        // INQILOB: Tab tugmasi bosilsa, yadro uni o'g'irlaydi va Fokusni aylantiradi
-                        if key_event.logical_key
-                            == winit::keyboard::Key::Named(winit::keyboard::NamedKey::Tab)
-                        {
-                            let forward = !fw_state.modifiers.shift_key();
-                            fw_state.cycle_focus(forward);
-                            continue; // Vidjetga jo'natmaymiz, chunki yadro bajardi
-                        }
+            }
+            LogicMessage::KeyboardInput(key_event) => {
+                if key_event.state == ElementState::Pressed {
+                    // INQILOB: Tab tugmasi bosilsa, yadro uni o'g'irlaydi va Fokusni aylantiradi
+                    if fw_state.is_dragging()
+                        && key_event.logical_key
+                            == winit::keyboard::Key::Named(winit::keyboard::NamedKey::Escape)
+                    {
+                        fw_state.cancel_drag();
+                        fw_state.active_node = None;
+                        return;
+                    }
 
-                        if let Some(focused_node) = fw_state.focused_node {
-                            let bubble_chain = fw_state.get_event_bubble_chain(focused_node);
-                            for node in bubble_chain {
-                                let mut consumed = false;
-                                if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
-                                    if let Some(mut widget) =
-                                        fw_state.arena.widgets[node_id.0 as usize].take()
-                                    {
-                                        if let Some(text) = &key_event.text {
-                                            if !text.as_str().chars().any(|c: char| c.is_control())
-                                            {
-                                                let res = widget.handle_event(
-                                                    &mut fw_state,
-                                                    &WidgetEvent::TextInput(text.to_string()),
-                                                );
-                                                if res == EventResult::Consumed {
-                                                    consumed = true;
-                                                }
-                                            }
-                                        }
-                                        let res = widget.handle_event(
-                                            &mut fw_state,
-                                            &WidgetEvent::KeyPress(key_event.logical_key.clone()),
-                                        );
-                                        if res == EventResult::Consumed {
-                                            consumed = true;
-                                        }
-                                        fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
-                                    }
-                                }
-                                if consumed {
-                                    break;
-                                }
-                            }
+                    // Modal ochiq bo'lsa, klaviatura faqat unga boradi
+                    fw_state.contain_focus();
+
+                    // Ro'yxatdagi tugmalar vidjetlardan oldin ishlaydi
+                    if fw_state.dispatch_shortcut(&key_event.logical_key) {
+                        return;
+                    }
+
+                    if let Some(cmd) = crate::clipboard::ClipboardCommand::from_shortcut(
+                        &key_event.logical_key,
+                        fw_state.modifiers,
+                    ) {
+                        if fw_state.route_clipboard(cmd) {
+                            return;
                         }
                     }
-                }
-                LogicMessage::MouseWheel(delta_x, delta_y) => {
-                    if let Some(hit_node) = fw_state.hovered_node {
-                        let bubble_chain = fw_state.get_event_bubble_chain(hit_node);
+
+                    if key_event.logical_key
+                        == winit::keyboard::Key::Named(winit::keyboard::NamedKey::Tab)
+                    {
+                        let forward = !fw_state.modifiers.shift_key();
+                        fw_state.cycle_focus(forward);
+                        return; // Vidjetga jo'natmaymiz, chunki yadro bajardi
+                    }
+
+                    if let Some(focused_node) = fw_state.focused_node {
+                        let bubble_chain = fw_state.get_event_bubble_chain(focused_node);
                         for node in bubble_chain {
                             let mut consumed = false;
                             if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
                                 if let Some(mut widget) =
                                     fw_state.arena.widgets[node_id.0 as usize].take()
                                 {
-                                    let result = widget.handle_event(
-                                        &mut fw_state,
-                                        &WidgetEvent::MouseScroll { delta_x, delta_y },
+                                    if let Some(text) = &key_event.text {
+                                        if !text.as_str().chars().any(|c: char| c.is_control()) {
+                                            let res = widget.handle_event(
+                                                fw_state,
+                                                &WidgetEvent::TextInput(text.to_string()),
+                                            );
+                                            if res == EventResult::Consumed {
+                                                consumed = true;
+                                            }
+                                        }
+                                    }
+                                    let res = widget.handle_event(
+                                        fw_state,
+                                        &WidgetEvent::KeyPress(key_event.logical_key.clone()),
                                     );
-                                    fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
-                                    if matches!(result, EventResult::Consumed) {
+                                    if res == EventResult::Consumed {
                                         consumed = true;
                                     }
+                                    fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
                                 }
                             }
                             if consumed {
@@ -585,64 +810,322 @@ pub fn run_logic_thread<A: App + 'static>(
                         }
                     }
                 }
-                LogicMessage::Tick(dt, gpu_time) => {
-                    logic_time_manager.add_accum(dt);
-                    fw_state.global_time = gpu_time;
-
-                    crate::reactive::signals::tick_all(dt);
-                    let is_animating = crate::reactive::context::tick_tweens(dt);
-                    let is_loading = crate::reactive::resource::ACTIVE_RESOURCES
-                        .load(std::sync::atomic::Ordering::SeqCst)
-                        > 0;
-
-                    if is_animating || is_loading {
-                        fw_state.request_redraw();
-                    }
-
-                    if let Some(focused) = fw_state.focused_node {
-                        if let Some(&node_id) = fw_state.arena.node_map.get(&focused) {
-                            if let Some(w) = fw_state.arena.widgets[node_id.0 as usize].as_ref() {
-                                if w.type_name() == "TextInput" {
-                                    if !fw_state.sparse_update_queue.contains(&node_id) {
-                                        fw_state.sparse_update_queue.push(node_id);
-                                    }
-                                    fw_state.request_redraw();
+            }
+            LogicMessage::MouseWheel(delta_x, delta_y) => {
+                fw_state.dismiss_tooltip();
+                if let Some(hit_node) = fw_state.hovered_node {
+                    let bubble_chain = fw_state.get_event_bubble_chain(hit_node);
+                    for node in bubble_chain {
+                        let mut consumed = false;
+                        if let Some(&node_id) = fw_state.arena.node_map.get(&node) {
+                            if let Some(mut widget) =
+                                fw_state.arena.widgets[node_id.0 as usize].take()
+                            {
+                                let result = widget.handle_event(
+                                    fw_state,
+                                    &WidgetEvent::MouseScroll { delta_x, delta_y },
+                                );
+                                fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
+                                if matches!(result, EventResult::Consumed) {
+                                    consumed = true;
                                 }
                             }
                         }
+                        if consumed {
+                            break;
+                        }
                     }
-                    while logic_time_manager.consume_fixed_step() {
-                        app.update(AppEvent::Tick(logic_time_manager.fixed_dt));
-                    }
                 }
-                LogicMessage::RequestRedraw => {
-                    fw_state.request_redraw();
+            }
+            LogicMessage::Touch {
+                id,
+                phase,
+                x,
+                y,
+                pressure,
+            } => {
+                if fw_state.config.touch_support {
+                    self.handle_touch(id, phase, x, y, pressure, app);
+                }
+            }
+            LogicMessage::TouchpadMagnify(delta) => {
+                if let Some(hover) = fw_state.hovered_node {
+                    let (cx, cy) = (fw_state.cursor_pos.x, fw_state.cursor_pos.y);
+                    fw_state.dispatch_bubbling(
+                        hover,
+                        &WidgetEvent::Gesture(Gesture::Pinch {
+                            scale: 1.0 + delta,
+                            center_x: cx,
+                            center_y: cy,
+                        }),
+                    );
+                }
+            }
+            LogicMessage::TouchpadRotate(radians) => {
+                if let Some(hover) = fw_state.hovered_node {
+                    let (cx, cy) = (fw_state.cursor_pos.x, fw_state.cursor_pos.y);
+                    fw_state.dispatch_bubbling(
+                        hover,
+                        &WidgetEvent::Gesture(Gesture::Rotate {
+                            radians,
+                            center_x: cx,
+                            center_y: cy,
+                        }),
+                    );
                 }
             }
+            LogicMessage::FileHovered(path) => fw_state.file_hovered(path),
+            LogicMessage::FileHoverCancelled => fw_state.file_hover_cancelled(),
+            LogicMessage::FileDropped(path) => fw_state.file_dropped(path),
+            LogicMessage::Tick(_, _) | LogicMessage::WindowClosed => {}
+            LogicMessage::RequestRedraw => {
+                fw_state.request_redraw();
+            }
         }
+    }
+
+    fn handle_touch<A: App>(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        x: f32,
+        y: f32,
+        pressure: f32,
+        app: &mut A,
+    ) {
+        let now = std::time::Instant::now();
+        match phase {
+            TouchPhase::Started => {
+                if !self.gestures.is_active() {
+                    self.touch_target = self.fw_state.hit_test(x, y);
+                    self.primary_touch = Some(id);
+                    self.handle_message(LogicMessage::CursorMoved(x, y), app);
+                    self.handle_message(
+                        LogicMessage::MouseInput(ElementState::Pressed, MouseButton::Left),
+                        app,
+                    );
+                } else if self.primary_touch.take().is_some() {
+                    // Ikkinchi barmoq tushdi: bu endi ko'p barmoqli ishora, bosilgan tugma bekor
+                    self.cancel_active_pointer();
+                }
+                self.gestures.pointer_down(id, x, y, now);
+
+                if let Some(hit) = self.fw_state.hit_test(x, y) {
+                    self.fw_state
+                        .dispatch_bubbling(hit, &WidgetEvent::PointerDown { id, x, y, pressure });
+                }
+            }
+            TouchPhase::Moved => {
+                if self.primary_touch == Some(id) {
+                    self.handle_message(LogicMessage::CursorMoved(x, y), app);
+                }
+                if let Some(target) = self.touch_target {
+                    self.fw_state.dispatch_bubbling(
+                        target,
+                        &WidgetEvent::PointerMove { id, x, y, pressure },
+                    );
+                }
+                for gesture in self.gestures.pointer_move(id, x, y) {
+                    self.dispatch_gesture(gesture);
+                }
+            }
+            TouchPhase::Ended => {
+                if let Some(target) = self.touch_target {
+                    self.fw_state
+                        .dispatch_bubbling(target, &WidgetEvent::PointerUp { id, x, y });
+                }
+                let gestures = self.gestures.pointer_up(id, x, y, now);
+                if self.primary_touch == Some(id) {
+                    self.primary_touch = None;
+                    self.handle_message(
+                        LogicMessage::MouseInput(ElementState::Released, MouseButton::Left),
+                        app,
+                    );
+                }
+                for gesture in gestures {
+                    self.dispatch_gesture(gesture);
+                }
+                if !self.gestures.is_active() {
+                    self.end_touch_sequence(app);
+                }
+            }
+            TouchPhase::Cancelled => {
+                self.gestures.pointer_cancel(id);
+                if self.primary_touch == Some(id) {
+                    self.primary_touch = None;
+                    self.cancel_active_pointer();
+                }
+                if !self.gestures.is_active() {
+                    self.end_touch_sequence(app);
+                }
+            }
+        }
+        self.sync_long_press_lock();
+    }
+
+    // Ishora zanjiri: MouseDrag dagi kabi, ota vidjet ishorani o'ziga olsa (masalan ScrollView),
+    // avvalgi nishonga MouseCancel yuboriladi va u yangi nishonga aylanadi
+    fn dispatch_gesture(&mut self, gesture: Gesture) {
+        let Some(target) = self.touch_target else {
+            return;
+        };
+        let consumer = self
+            .fw_state
+            .dispatch_bubbling(target, &WidgetEvent::Gesture(gesture));
+        if let Some(consumer) = consumer {
+            if consumer != target {
+                self.fw_state.dispatch_to(target, &WidgetEvent::MouseCancel);
+                self.touch_target = Some(consumer);
+            }
+        }
+    }
+
+    fn cancel_active_pointer(&mut self) {
+        if let Some(active) = self.fw_state.active_node.take() {
+            self.fw_state.dispatch_to(active, &WidgetEvent::MouseCancel);
+        }
+    }
+
+    fn end_touch_sequence<A: App>(&mut self, app: &mut A) {
+        self.touch_target = None;
+        // Sensorda "hover" yo'q: barmoq ko'tarilgach kursorni ekrandan tashqariga chiqaramiz
+        self.handle_message(LogicMessage::CursorMoved(-1.0, -1.0), app);
+    }
+
+    fn sync_long_press_lock(&mut self) {
+        let waiting = self.gestures.is_waiting();
+        if waiting != self.long_press_lock {
+            self.long_press_lock = waiting;
+            let mut registry = self.fw_state.wake_registry.lock().unwrap();
+            if waiting {
+                registry.acquire("gesture_long_press");
+            } else {
+                registry.release("gesture_long_press");
+            }
+        }
+    }
+
+    fn sync_tooltip_lock(&mut self) {
+        let waiting = self.fw_state.tooltip_waiting();
+        if waiting != self.tooltip_lock {
+            self.tooltip_lock = waiting;
+            let mut registry = self.fw_state.wake_registry.lock().unwrap();
+            if waiting {
+                registry.acquire("tooltip_delay");
+            } else {
+                registry.release("tooltip_delay");
+            }
+        }
+    }
+
+    pub fn on_tick(&mut self, gpu_time: f32, needs_redraw: bool) {
+        if let Some(gesture) = self.gestures.poll(std::time::Instant::now()) {
+            // Uzoq bosish: tugmaning oddiy "Click" i endi ishlamasligi kerak
+            self.cancel_active_pointer();
+            self.dispatch_gesture(gesture);
+        }
+        self.sync_long_press_lock();
+
+        self.fw_state.global_time = gpu_time;
+        self.fw_state.poll_tooltip();
+        self.sync_tooltip_lock();
+
+        let fw_state = &mut self.fw_state;
+
+        if needs_redraw {
+            fw_state.request_redraw();
+        }
+
+        if let Some(focused) = fw_state.focused_node {
+            if let Some(&node_id) = fw_state.arena.node_map.get(&focused) {
+                if let Some(w) = fw_state.arena.widgets[node_id.0 as usize].as_ref() {
+                    if w.type_name() == "TextInput" {
+                        if !fw_state.sparse_update_queue.contains(&node_id) {
+                            fw_state.sparse_update_queue.push(node_id);
+                        }
+                        fw_state.request_redraw();
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn flush(&mut self) {
+        let fw_state = &mut self.fw_state;
+        let layout_engine = &mut self.layout_engine;
+        let mut needs_compute = std::mem::take(&mut self.needs_compute);
 
         crate::reactive::signals::process_pending_effects();
-        fw_state.process_commands(&mut layout_engine);
+        fw_state.process_commands(layout_engine);
+        fw_state.process_drag(layout_engine);
+        fw_state.process_tooltip(layout_engine);
 
         let rebuilds = std::mem::take(&mut fw_state.pending_rebuilds);
-        let mut tree_changed = false;
+        // Hodisalar paytida olib tashlangan tugunlar (masalan, tugagan drag preview)
+        let mut tree_changed = !fw_state.drop_queue.borrow().is_empty();
+        let mut rebuild_failed = false;
         for (node_id, action) in rebuilds {
             if let Some(mut widget) = fw_state.arena.widgets[node_id.0 as usize].take() {
-                widget.rebuild(&mut fw_state, &mut layout_engine, action);
+                // Rebuild paytidagi panik butun Logic Thread'ni o'ldirmasligi kerak
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    widget.rebuild(fw_state, layout_engine, action);
+                }));
                 fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
                 tree_changed = true;
+
+                if let Err(payload) = result {
+                    let scope = fw_state.arena.node_scopes.get(&node_id).copied();
+                    if !crate::reactive::signals::report_error(scope, &*payload) {
+                        eprintln!(
+                            "Rore: rebuild xatosi: {}",
+                            crate::reactive::signals::panic_message(&*payload)
+                        );
+                    }
+                    rebuild_failed = true;
+                }
+            }
+        }
+        if rebuild_failed {
+            // ErrorBoundary'lar shu kadrning o'zida fallback'ni chizishi uchun
+            crate::reactive::signals::process_pending_effects();
+            fw_state.process_commands(layout_engine);
+            let rebuilds = std::mem::take(&mut fw_state.pending_rebuilds);
+            tree_changed |= !rebuilds.is_empty();
+            for (node_id, action) in rebuilds {
+                if let Some(mut widget) = fw_state.arena.widgets[node_id.0 as usize].take() {
+                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        widget.rebuild(fw_state, layout_engine, action);
+                    }));
+                    fw_state.arena.widgets[node_id.0 as usize] = Some(widget);
+
+                    if let Err(payload) = result {
+                        // Fallback ham yiqildi: tashqi boundary'ga uzatamiz, bo'lmasa log
+                        let scope = fw_state.arena.node_scopes.get(&node_id).copied();
+                        if !crate::reactive::signals::report_error(scope, &*payload) {
+                            eprintln!(
+                                "Rore: fallback rebuild xatosi: {}",
+                                crate::reactive::signals::panic_message(&*payload)
+                            );
+                        }
+                        // Yarim qurilgan tugun keyingi kadrda qayta o'lchanib chiziladi
+                        fw_state.mark_dirty(node_id);
+                        fw_state.request_redraw();
+                    }
+                }
             }
         }
         if tree_changed {
-            fw_state.process_drop_queue(&layout_engine);
+            fw_state.process_drop_queue(layout_engine);
             needs_compute = true;
         }
 
         let do_full_redraw = fw_state.full_redraw;
         let do_partial_redraw =
             !fw_state.sparse_update_queue.is_empty() || fw_state.dirty_rect.is_some();
+        // Hech narsa o'zgarmagan bo'lsa ham capture so'rovi paket bilan yetib borishi kerak
+        let has_captures = !fw_state.pending_captures.is_empty();
 
-        if do_full_redraw || do_partial_redraw {
+        if do_full_redraw || do_partial_redraw || has_captures {
             for &node_id in &fw_state.pending_dirty_nodes {
                 let idx = node_id.0 as usize;
                 let flags = fw_state.arena.dirty_flags[idx];
@@ -659,18 +1142,23 @@ pub fn run_logic_thread<A: App + 'static>(
 
             if needs_compute {
                 layout_engine.compute(
-                    current_width / current_scale,
-                    current_height / current_scale,
+                    self.current_width / self.current_scale,
+                    self.current_height / self.current_scale,
+                );
+                fw_state.update_aabbs(
+                    layout_engine,
+                    self.root_taffy_node,
+                    fw_state.needs_aabb_update,
                 );
-                fw_state.update_aabbs(&layout_engine, root_taffy_node, fw_state.needs_aabb_update);
                 fw_state.needs_aabb_update = false;
             } else if fw_state.needs_aabb_update {
-                fw_state.update_aabbs(&layout_engine, root_taffy_node, true);
+                fw_state.update_aabbs(layout_engine, self.root_taffy_node, true);
                 fw_state.needs_aabb_update = false;
             }
 
             fw_state.current_cursor_icon = winit::window::CursorIcon::Default;
-            let mut render_output = rx_recycle
+            let mut render_output = self
+                .rx_recycle
                 .try_recv()
                 .unwrap_or_else(|_| RenderOutput::new());
 
@@ -687,7 +1175,7 @@ pub fn run_logic_thread<A: App + 'static>(
 
             if do_full_redraw {
                 fw_state.full_redraw = false;
-                previous_visual_bounds.clear();
+                self.previous_visual_bounds.clear();
                 for (id, node) in &fw_state.arena.taffy_map {
                     let bounds = fw_state.node_bounds.get(node).copied().unwrap_or([0.0; 4]);
                     let mut overflow = [0.0, 0.0, 0.0, 0.0];
@@ -695,11 +1183,12 @@ pub fn run_logic_thread<A: App + 'static>(
                         overflow = widget.visual_overflow();
                     }
 
-                    let sf = current_scale;
+                    let sf = self.current_scale;
                     let v_x = bounds[0] - overflow[3];
                     let v_y = bounds[1] - overflow[0];
                     let v_w = bounds[2] + overflow[3] + overflow[1];
                     let v_h = bounds[3] + overflow[0] + overflow[2];
+                    let [v_x, v_y, v_w, v_h] = fw_state.screen_rect(id.0, [v_x, v_y, v_w, v_h]);
 
                     let raw_x = v_x * sf;
                     let raw_y = v_y * sf;
@@ -716,23 +1205,24 @@ pub fn run_logic_thread<A: App + 'static>(
                     let safe_w = (x2 - x1.max(0)).max(0) as u32;
                     let safe_h = (y2 - y1.max(0)).max(0) as u32;
 
-                    previous_visual_bounds.insert(id.0, [safe_x, safe_y, safe_w, safe_h]);
+                    self.previous_visual_bounds
+                        .insert(id.0, [safe_x, safe_y, safe_w, safe_h]);
                 }
 
                 fw_state.is_overlay_pass = false;
                 if let Some(root_widget_ref) =
-                    fw_state.arena.widgets[root_node_id.0 as usize].take()
+                    fw_state.arena.widgets[self.root_node_id.0 as usize].take()
                 {
                     let new_output = root_widget_ref.render(
-                        &layout_engine,
-                        &mut fw_state,
-                        root_taffy_node,
+                        layout_engine,
+                        fw_state,
+                        self.root_taffy_node,
                         Vec2::ZERO,
                         None,
                         "root".to_string(),
                     );
                     render_output.extend(new_output);
-                    fw_state.arena.widgets[root_node_id.0 as usize] = Some(root_widget_ref);
+                    fw_state.arena.widgets[self.root_node_id.0 as usize] = Some(root_widget_ref);
                 }
 
                 fw_state.is_overlay_pass = true;
@@ -753,8 +1243,8 @@ pub fn run_logic_thread<A: App + 'static>(
                             fw_state.arena.widgets[overlay_id.0 as usize].take()
                         {
                             let new_output = overlay_widget_ref.render(
-                                &layout_engine,
-                                &mut fw_state,
+                                layout_engine,
+                                fw_state,
                                 overlay_node,
                                 start_pos,
                                 None,
@@ -768,6 +1258,54 @@ pub fn run_logic_thread<A: App + 'static>(
                 }
                 fw_state.is_overlay_pass = false;
 
+                // Tooltip overlay'lar ustida, egasining AABB siga nisbatan
+                if let Some((tip_id, anchor)) = fw_state.tooltip_view() {
+                    if let Some(&tip_node) = fw_state.arena.taffy_map.get(&tip_id) {
+                        layout_engine.compute_detached(tip_node);
+                        let size = layout_engine
+                            .taffy
+                            .layout(tip_node)
+                            .map(|l| Vec2::new(l.size.width, l.size.height))
+                            .unwrap_or(Vec2::ZERO);
+                        let pos = crate::tooltip::place_tooltip(anchor, size, fw_state.screen_size);
+                        if let Some(tip_ref) = fw_state.arena.widgets[tip_id.0 as usize].take() {
+                            let new_output = tip_ref.render(
+                                layout_engine,
+                                fw_state,
+                                tip_node,
+                                pos,
+                                None,
+                                "tooltip".to_string(),
+                            );
+                            render_output.extend(new_output);
+                            fw_state.arena.widgets[tip_id.0 as usize] = Some(tip_ref);
+                        }
+                    }
+                }
+
+                // Drag preview hamma narsaning ustida, kursor yonida
+                if let Some((preview_id, pos)) = fw_state.drag_preview() {
+                    if let Some(&preview_node) = fw_state.arena.taffy_map.get(&preview_id) {
+                        layout_engine.compute_detached(preview_node);
+                        if let Some(preview_ref) =
+                            fw_state.arena.widgets[preview_id.0 as usize].take()
+                        {
+                            let new_output = preview_ref.render(
+                                layout_engine,
+                                fw_state,
+                                preview_node,
+                                pos,
+                                None,
+                                "drag_preview".to_string(),
+                            );
+                            render_output.extend(new_output);
+                            fw_state.arena.widgets[preview_id.0 as usize] = Some(preview_ref);
+                        }
+                    }
+                    fw_state.current_cursor_icon = winit::window::CursorIcon::Grabbing;
+                }
+                apply_world_transforms(fw_state, &mut render_output);
+
                 fw_state.current_draw_order = render_output
                     .sparse_instances
                     .iter()
@@ -779,7 +1317,7 @@ pub fn run_logic_thread<A: App + 'static>(
             } else if do_partial_redraw {
                 let mut shifted_nodes = Vec::new();
                 for (id, taffy_node) in &fw_state.arena.taffy_map {
-                    let old_rect_opt = previous_visual_bounds.get(&id.0).copied();
+                    let old_rect_opt = self.previous_visual_bounds.get(&id.0).copied();
                     let bounds = fw_state
                         .node_bounds
                         .get(taffy_node)
@@ -790,11 +1328,12 @@ pub fn run_logic_thread<A: App + 'static>(
                         overflow = widget.visual_overflow();
                     }
 
-                    let sf = current_scale;
+                    let sf = self.current_scale;
                     let v_x = bounds[0] - overflow[3];
                     let v_y = bounds[1] - overflow[0];
                     let v_w = bounds[2] + overflow[3] + overflow[1];
                     let v_h = bounds[3] + overflow[0] + overflow[2];
+                    let [v_x, v_y, v_w, v_h] = fw_state.screen_rect(id.0, [v_x, v_y, v_w, v_h]);
 
                     let raw_x = v_x * sf;
                     let raw_y = v_y * sf;
@@ -825,7 +1364,7 @@ pub fn run_logic_thread<A: App + 'static>(
                 nodes_to_update.extend(shifted_nodes);
 
                 for node_id in &nodes_to_update {
-                    let old_rect = previous_visual_bounds.get(&node_id.0).copied();
+                    let old_rect = self.previous_visual_bounds.get(&node_id.0).copied();
                     let mut new_rect = None;
 
                     if let Some(taffy_node) = fw_state.arena.taffy_map.get(node_id).copied() {
@@ -839,11 +1378,13 @@ pub fn run_logic_thread<A: App + 'static>(
                             overflow = widget.visual_overflow();
                         }
 
-                        let sf = current_scale;
+                        let sf = self.current_scale;
                         let v_x = bounds[0] - overflow[3];
                         let v_y = bounds[1] - overflow[0];
                         let v_w = bounds[2] + overflow[3] + overflow[1];
                         let v_h = bounds[3] + overflow[0] + overflow[2];
+                        let [v_x, v_y, v_w, v_h] =
+                            fw_state.screen_rect(node_id.0, [v_x, v_y, v_w, v_h]);
 
                         let raw_x = v_x * sf;
                         let raw_y = v_y * sf;
@@ -862,9 +1403,9 @@ pub fn run_logic_thread<A: App + 'static>(
 
                         let rect = [safe_x, safe_y, safe_w, safe_h];
                         new_rect = Some(rect);
-                        previous_visual_bounds.insert(node_id.0, rect);
+                        self.previous_visual_bounds.insert(node_id.0, rect);
                     } else {
-                        previous_visual_bounds.remove(&node_id.0);
+                        self.previous_visual_bounds.remove(&node_id.0);
                     }
 
                     if let Some(old) = old_rect {
@@ -904,20 +1445,22 @@ pub fn run_logic_thread<A: App + 'static>(
 
                 for top_id in topmost {
                     if let Some(t_node) = fw_state.arena.taffy_map.get(&top_id).copied() {
-                        let p_pos = fw_state.get_parent_pos(&layout_engine, t_node);
+                        let p_pos = fw_state.get_parent_pos(layout_engine, t_node);
                         let clip_rect = fw_state.get_clip_rect(t_node);
                         if let Some(widget_ref) = fw_state.arena.widgets[top_id.0 as usize].take() {
-                            let subtree_output = widget_ref.render(
-                                &layout_engine,
-                                &mut fw_state,
+                            let mut subtree_output = widget_ref.render(
+                                layout_engine,
+                                fw_state,
                                 t_node,
                                 p_pos,
                                 clip_rect,
                                 "partial".to_string(),
                             );
+                            apply_world_transforms(fw_state, &mut subtree_output);
 
                             for (id, inst) in subtree_output.sparse_instances {
-                                commands.push(RenderCommand::UpdateInstance(id, inst));
+                                self.commands
+                                    .push(RenderCommand::UpdateInstance(id, Box::new(inst)));
 
                                 if fw_state.draw_order_set.insert(id) {
                                     fw_state.current_draw_order.push(id);
@@ -925,11 +1468,12 @@ pub fn run_logic_thread<A: App + 'static>(
                             }
 
                             for text in subtree_output.sparse_texts {
-                                commands.push(RenderCommand::UpdateText(text.0, text));
+                                self.commands.push(RenderCommand::UpdateText(text.0, text));
                             }
 
                             for (id, cmds) in subtree_output.node_commands {
-                                commands.push(RenderCommand::UpdateNodeCommands(id, cmds));
+                                self.commands
+                                    .push(RenderCommand::UpdateNodeCommands(id, cmds));
                                 if fw_state.draw_order_set.insert(id) {
                                     fw_state.current_draw_order.push(id);
                                 }
@@ -948,12 +1492,13 @@ pub fn run_logic_thread<A: App + 'static>(
                 }
             }
 
-            let deleted_nodes: Vec<u32> = previous_active_nodes
+            let deleted_nodes: Vec<u32> = self
+                .previous_active_nodes
                 .difference(&current_active_nodes)
                 .copied()
                 .collect();
             for &del_id in &deleted_nodes {
-                commands.push(RenderCommand::Remove(del_id));
+                self.commands.push(RenderCommand::Remove(del_id));
             }
 
             fw_state
@@ -966,15 +1511,29 @@ pub fn run_logic_thread<A: App + 'static>(
 
             if !do_full_redraw {
                 for &del_id in &deleted_nodes {
-                    if let Some(old_rect) = previous_visual_bounds.remove(&del_id) {
+                    if let Some(old_rect) = self.previous_visual_bounds.remove(&del_id) {
                         dirty_rects.push(old_rect);
                     }
                 }
             }
 
-            previous_active_nodes = current_active_nodes;
+            self.previous_active_nodes = current_active_nodes;
             fw_state.clear_dirty_flags();
 
+            fw_state
+                .filter_layers
+                .retain(|id, _| !deleted_nodes.contains(&id.0));
+            let filter_layers = collect_filter_layers(fw_state, layout_engine);
+            fw_state.stale_layers.clear();
+            if !do_full_redraw && !dirty_rects.is_empty() {
+                let sf = self.current_scale;
+                let layer_rects: Vec<[u32; 4]> = filter_layers
+                    .iter()
+                    .map(|l| pixel_rect(l.bounds(), sf))
+                    .collect();
+                expand_filter_damage(&mut dirty_rects, &layer_rects);
+            }
+
             let mut final_full_redraw = do_full_redraw;
 
             if !final_full_redraw && !dirty_rects.is_empty() {
@@ -1048,7 +1607,8 @@ pub fn run_logic_thread<A: App + 'static>(
                 for r in &dirty_rects {
                     total_area += r[2] * r[3];
                 }
-                let screen_area = current_width.max(1.0) as u32 * current_height.max(1.0) as u32;
+                let screen_area =
+                    self.current_width.max(1.0) as u32 * self.current_height.max(1.0) as u32;
                 if total_area > (screen_area as f32 * 0.70) as u32 {
                     final_full_redraw = true;
                     dirty_rects.clear();
@@ -1061,9 +1621,9 @@ pub fn run_logic_thread<A: App + 'static>(
                 compiler.compile(*id, cmds);
             }
 
-            let _ = tx_render.send(RenderPacket {
+            let _ = self.tx_render.send(RenderPacket {
                 output: render_output,
-                commands,
+                commands: std::mem::take(&mut self.commands),
                 current_cursor_icon: fw_state.current_cursor_icon,
                 is_animating: false,
                 total_nodes,
@@ -1072,9 +1632,437 @@ pub fn run_logic_thread<A: App + 'static>(
                 deleted_nodes: deleted_nodes.clone(),
                 draw_order: fw_state.current_draw_order.clone(),
                 custom_draws: compiler.final_custom,
+                filter_layers,
+                captures: fw_state
+                    .pending_captures
+                    .drain(..)
+                    .map(|(id, rect)| {
+                        (
+                            id,
+                            rect.map(|r| crate::capture::pixel_region(r, self.current_scale)),
+                        )
+                    })
+                    .collect(),
+            });
+
+            fw_state.wake_registry.lock().unwrap().wake();
+        }
+    }
+
+    // Oyna yopilganda butun daraxt va uning Scope'lari tozalanadi
+    pub fn dispose(mut self) {
+        let nodes: Vec<NodeId> = (0..self.fw_state.arena.active.len())
+            .filter(|&i| self.fw_state.arena.active[i])
+            .map(|i| NodeId(i as u32, self.fw_state.arena.generations[i]))
+            .collect();
+        for id in nodes {
+            self.fw_state.arena.remove_node(id);
+        }
+        dispose_scope(self.root_scope);
+        crate::reactive::command::CommandQueue::discard(self.id);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_logic_thread<A: App + 'static>(
+    mut app: A,
+    rx_logic: Receiver<WindowMessage>,
+    tx_render: Sender<RenderPacket>,
+    rx_recycle: Receiver<RenderOutput>,
+    config_clone: RoreConfig,
+    wake_registry_logic: Arc<Mutex<crate::state::WakeRegistry>>,
+    initial_width: f32,
+    initial_height: f32,
+    initial_scale: f32,
+) {
+    let mut logic_time_manager = TimeManager::new();
+
+    // Menyu/toolbar dagi Copy, Cut, Paste. Ilova Init da o'z handler'i bilan almashtirishi mumkin
+    crate::reactive::command::CommandQueue::register::<crate::clipboard::ClipboardCommand, _>(
+        |cmd, state, _| {
+            state.route_clipboard(cmd);
+        },
+    );
+    crate::reactive::command::CommandQueue::register::<crate::capture::CaptureCommand, _>(
+        |cmd, state, _| {
+            crate::capture::handle_command(cmd, state);
+        },
+    );
+    crate::reactive::command::CommandQueue::register::<crate::shortcuts::RunCommand, _>(
+        |cmd, state, _| {
+            if !crate::shortcuts::Shortcuts::run(&cmd.0, state) {
+                eprintln!("Rore: {:?} buyrug'i ro'yxatdan o'tmagan", cmd.0);
+            }
+        },
+    );
+
+    app.update(AppEvent::Init);
+
+    let main_window = LogicWindow::new(
+        MAIN_WINDOW,
+        || app.view(),
+        config_clone,
+        wake_registry_logic.clone(),
+        tx_render,
+        rx_recycle,
+        initial_width,
+        initial_height,
+        initial_scale,
+    );
+
+    let mut windows: Vec<LogicWindow> = vec![main_window];
+    let mut tween_lock = false;
+
+    loop {
+        apply_window_ops(&mut windows, &config_clone, &wake_registry_logic);
+
+        let Ok(first_msg) = rx_logic.recv() else {
+            break;
+        };
+        // Boshqa oqimdan kelgan so'rov uyg'otgan bo'lishi mumkin
+        apply_window_ops(&mut windows, &config_clone, &wake_registry_logic);
+
+        let mut msgs = vec![first_msg];
+        while let Ok(m) = rx_logic.try_recv() {
+            msgs.push(m);
+        }
+
+        let mut batched_msgs: Vec<WindowMessage> = Vec::with_capacity(msgs.len());
+        let mut last_cursor_idx: HashMap<WindowId, usize> = HashMap::new();
+        let mut last_resize_idx: HashMap<WindowId, usize> = HashMap::new();
+
+        for (window_id, msg) in msgs {
+            match msg {
+                LogicMessage::CursorMoved(_, _) => {
+                    if let Some(&idx) = last_cursor_idx.get(&window_id) {
+                        batched_msgs[idx] = (window_id, msg);
+                    } else {
+                        last_cursor_idx.insert(window_id, batched_msgs.len());
+                        batched_msgs.push((window_id, msg));
+                    }
+                }
+                LogicMessage::Resize(_, _, _) => {
+                    if let Some(&idx) = last_resize_idx.get(&window_id) {
+                        batched_msgs[idx] = (window_id, msg);
+                    } else {
+                        last_resize_idx.insert(window_id, batched_msgs.len());
+                        batched_msgs.push((window_id, msg));
+                    }
+                }
+                _ => batched_msgs.push((window_id, msg)),
+            }
+        }
+
+        for (window_id, msg) in batched_msgs {
+            match msg {
+                LogicMessage::Tick(dt, gpu_time) => {
+                    // Reaktiv vaqt umumiy: tickerlar va tweenlar bir marta yuradi
+                    set_current_window(Some(MAIN_WINDOW));
+                    logic_time_manager.add_accum(dt);
+
+                    crate::reactive::signals::tick_all(dt);
+                    let is_animating = crate::reactive::context::tick_tweens(dt);
+                    let is_loading = crate::reactive::resource::ACTIVE_RESOURCES
+                        .load(std::sync::atomic::Ordering::SeqCst)
+                        > 0;
+
+                    for window in windows.iter_mut() {
+                        set_current_window(Some(window.id));
+                        window.on_tick(gpu_time, is_animating || is_loading);
+                    }
+
+                    set_current_window(Some(MAIN_WINDOW));
+                    while logic_time_manager.consume_fixed_step() {
+                        app.update(AppEvent::Tick(logic_time_manager.fixed_dt));
+                    }
+                }
+                LogicMessage::WindowClosed => {
+                    if let Some(pos) = windows.iter().position(|w| w.id == window_id) {
+                        windows.remove(pos).dispose();
+                    }
+                }
+                msg => {
+                    if let Some(window) = windows.iter_mut().find(|w| w.id == window_id) {
+                        set_current_window(Some(window.id));
+                        window.handle_message(msg, &mut app);
+                    }
+                }
+            }
+        }
+
+        crate::reactive::signals::process_pending_effects();
+
+        for window in windows.iter_mut() {
+            set_current_window(Some(window.id));
+            window.flush();
+        }
+        set_current_window(Some(MAIN_WINDOW));
+
+        // Tween flush paytida ham boshlanishi mumkin: tugaguncha event loop uxlamasin
+        let tweening = crate::reactive::context::has_active_tweens();
+        if tweening != tween_lock {
+            tween_lock = tweening;
+            let mut registry = wake_registry_logic.lock().unwrap();
+            if tweening {
+                registry.acquire("tweens");
+            } else {
+                registry.release("tweens");
+            }
+        }
+    }
+}
+
+// App kodi so'ragan oynalarni ochish/yopish (Logic Thread tsiklining xavfsiz nuqtasida)
+fn apply_window_ops(
+    windows: &mut Vec<LogicWindow>,
+    config: &RoreConfig,
+    wake_registry: &Arc<Mutex<crate::state::WakeRegistry>>,
+) {
+    for op in crate::window::take_window_ops() {
+        match op {
+            WindowOp::Open {
+                id,
+                config: window_config,
+                view,
+            } => {
+                let (tx_render, rx_render) = mpsc::channel();
+                let (tx_recycle, rx_recycle) = mpsc::channel();
+                let mut window = LogicWindow::new(
+                    id,
+                    view,
+                    *config,
+                    wake_registry.clone(),
+                    tx_render,
+                    rx_recycle,
+                    window_config.size.0 as f32,
+                    window_config.size.1 as f32,
+                    1.0,
+                );
+                window.flush();
+                windows.push(window);
+                wake_event_loop(RoreUserEvent::OpenWindow {
+                    id,
+                    config: window_config,
+                    rx_render,
+                    tx_recycle,
+                });
+            }
+            WindowOp::Close(id) => {
+                if let Some(pos) = windows.iter().position(|w| w.id == id) {
+                    windows.remove(pos).dispose();
+                }
+                wake_event_loop(RoreUserEvent::CloseWindow(id));
+            }
+        }
+    }
+    set_current_window(Some(MAIN_WINDOW));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::command::{CommandQueue, UICommand};
+    use crate::reactive::signals::{create_effect, Signal};
+    use crate::window::{close_window, open_window_with, WindowConfig};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    // Build paytida signalga obuna bo'ladigan bitta tugunli daraxt
+    struct Counter {
+        source: Signal<u32>,
+        runs: Arc<AtomicU32>,
+    }
+
+    impl Widget for Counter {
+        fn type_name(&self) -> &'static str {
+            "Counter"
+        }
+
+        fn build(
+            self: Box<Self>,
+            arena: &mut crate::state::UiArena,
+            engine: &mut LayoutEngine,
+            _ctx: &BuildContext,
+        ) -> NodeId {
+            let Counter { source, runs } = *self;
+            create_effect(move || {
+                source.get();
+                runs.fetch_add(1, Ordering::SeqCst);
             });
+            let id = arena.allocate_node();
+            let node = engine.new_leaf(rore_types::Style::default());
+            arena.taffy_map.insert(id, node);
+            arena.node_map.insert(node, id);
+            id
+        }
 
-            wake_registry_logic.lock().unwrap().wake();
+        fn render(
+            &self,
+            _engine: &LayoutEngine,
+            _state: &mut FrameworkState,
+            _taffy_node: TaffyNode,
+            _parent_pos: glam::Vec2,
+            _clip_rect: Option<[f32; 4]>,
+            _path: String,
+        ) -> crate::widgets::base::RenderOutput {
+            crate::widgets::base::RenderOutput::new()
         }
     }
+
+    #[test]
+    fn closing_window_disposes_its_tree_and_inbox() {
+        let config = RoreConfig::desktop();
+        let registry = Arc::new(Mutex::new(crate::state::WakeRegistry::new()));
+        let source = Signal::new(0u32);
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let view_runs = runs.clone();
+        let id = open_window_with(WindowConfig::new("B").size(200, 100), move || {
+            Box::new(Counter {
+                source,
+                runs: view_runs,
+            }) as Box<dyn Widget>
+        });
+        let mut windows = Vec::new();
+        apply_window_ops(&mut windows, &config, &registry);
+        assert_eq!(windows.iter().map(|w| w.id).collect::<Vec<_>>(), [id]);
+
+        // Runtime umumiy: tashqaridagi signal oyna daraxtidagi effektni yuritadi
+        source.set(1);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        CommandQueue::send_to(id, UICommand::MarkDirty(NodeId(0, 0), 1));
+        close_window(id);
+        apply_window_ops(&mut windows, &config, &registry);
+        assert!(windows.is_empty());
+        assert!(CommandQueue::drain_for(id).is_empty());
+
+        source.set(2);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn filter_damage_spreads_to_touched_layers_only() {
+        let layers = [[100, 100, 50, 50], [140, 140, 50, 50], [400, 400, 10, 10]];
+        let mut dirty = vec![[90, 90, 20, 20]];
+        expand_filter_damage(&mut dirty, &layers);
+        // Birinchisi damage'ga, ikkinchisi birinchi qatlamga tegadi
+        assert_eq!(
+            dirty,
+            vec![[90, 90, 20, 20], [100, 100, 50, 50], [140, 140, 50, 50]]
+        );
+
+        let mut untouched = vec![[0, 0, 10, 10]];
+        expand_filter_damage(&mut untouched, &layers);
+        assert_eq!(untouched, vec![[0, 0, 10, 10]]);
+    }
+
+    #[test]
+    fn quad_border_style_reaches_instance() {
+        use crate::widgets::base::DisplayCommand;
+        use rore_types::BorderStyle;
+
+        let mut compiler = DisplayListCompiler::new();
+        compiler.compile(
+            7,
+            &[DisplayCommand::DrawQuad {
+                rect: [0.0, 0.0, 120.0, 40.0],
+                color_start: [0.0; 4],
+                color_end: [0.0; 4],
+                gradient_angle: 0.0,
+                gradient: None,
+                image: None,
+                border_radius: [6.0; 4],
+                border_width: [2.0; 4],
+                border_color: [1.0; 4],
+                border_style: [
+                    BorderStyle::Dashed,
+                    BorderStyle::Solid,
+                    BorderStyle::Dotted,
+                    BorderStyle::None,
+                ],
+                border_dash: [8.0, 3.5],
+                anim_start_time: 0.0,
+                anim_duration: 0.0,
+            }],
+        );
+        let (id, inst) = &compiler.final_insts[0];
+        assert_eq!(*id, 7);
+        assert_eq!(inst.border_style[0], BorderStyle::Dashed);
+        assert_eq!(inst.border_style[3], BorderStyle::None);
+        assert_eq!(inst.border_dash, [8.0, 3.5]);
+    }
+
+    #[test]
+    fn quads_with_same_image_share_atlas_key() {
+        use crate::widgets::base::DisplayCommand;
+        use rore_render::ImageFill;
+        use rore_types::{BorderStyle, ImageFit};
+
+        let quad = |id: &str| DisplayCommand::DrawQuad {
+            rect: [0.0, 0.0, 24.0, 24.0],
+            color_start: [0.0; 4],
+            color_end: [0.0; 4],
+            gradient_angle: 0.0,
+            gradient: None,
+            image: Some(ImageFill::new(id, ImageFit::Contain)),
+            border_radius: [12.0; 4],
+            border_width: [0.0; 4],
+            border_color: [0.0; 4],
+            border_style: [BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
+            anim_start_time: 0.0,
+            anim_duration: 0.0,
+        };
+        let mut compiler = DisplayListCompiler::new();
+        compiler.compile(1, &[quad("btc.png")]);
+        compiler.compile(2, &[quad("btc.png")]);
+        compiler.compile(3, &[quad("eth.png")]);
+
+        let keys: Vec<u64> = compiler
+            .final_insts
+            .iter()
+            .map(|(_, inst)| inst.image.unwrap().key)
+            .collect();
+        assert_eq!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+    }
+
+    #[test]
+    fn dirty_descendant_invalidates_cached_layer() {
+        let mut state = FrameworkState::new(
+            RoreConfig::desktop(),
+            Arc::new(Mutex::new(crate::state::WakeRegistry::new())),
+        );
+        let mut engine = LayoutEngine::new();
+        let child_node = engine.new_node(rore_types::Style::default(), &[]);
+        let panel_node = engine.new_node(rore_types::Style::default(), &[child_node]);
+        let mut add = |taffy_node| {
+            let id = state.arena.allocate_node();
+            state.arena.taffy_map.insert(id, taffy_node);
+            state.arena.node_map.insert(taffy_node, id);
+            state
+                .node_bounds
+                .insert(taffy_node, [10.0, 10.0, 100.0, 50.0]);
+            id
+        };
+        let panel = add(panel_node);
+        let child = add(child_node);
+        state.parent_map.insert(child_node, panel_node);
+
+        // Shaffof bo'lmagan, keshlanmagan qatlam oddiy chiziladi
+        state.arena.group_layers.insert(panel, false);
+        assert!(collect_filter_layers(&state, &engine).is_empty());
+
+        state.arena.group_layers.insert(panel, true);
+        state.arena.opacities[panel.0 as usize] = 0.5;
+        let layers = collect_filter_layers(&state, &engine);
+        assert_eq!(layers.len(), 1);
+        assert!(layers[0].cached && !layers[0].stale);
+        assert_eq!(layers[0].opacity, 0.5);
+        assert_eq!(layers[0].members.len(), 2);
+
+        state.mark_dirty_with_flag(child, crate::state::DIRTY_COLOR);
+        assert!(state.stale_layers.contains(&panel));
+        assert!(collect_filter_layers(&state, &engine)[0].stale);
+    }
 }
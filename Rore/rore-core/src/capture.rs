@@ -0,0 +1,212 @@
+use crate::app::RoreUserEvent;
+use crate::reactive::command::CommandQueue;
+use crate::state::FrameworkState;
+use crate::window::{current_window, wake_event_loop, WindowId};
+use rore_render::RgbaImage;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+
+/// Kadrning qaysi qismi olinadi. Koordinatalar mantiqiy (logical) piksellarda.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptureRegion {
+    Window,
+    // `.id("chart")` bilan ro'yxatdan o'tgan tugunning ekrandagi to'rtburchagi
+    Node(String),
+    Rect([f32; 4]),
+}
+
+type CaptureCallback = Box<dyn FnOnce(Option<RgbaImage>) + Send>;
+
+static NEXT_CAPTURE_ID: AtomicU64 = AtomicU64::new(1);
+static CALLBACKS: OnceLock<Mutex<HashMap<u64, CaptureCallback>>> = OnceLock::new();
+
+fn callbacks() -> &'static Mutex<HashMap<u64, CaptureCallback>> {
+    CALLBACKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) struct CaptureCommand {
+    id: u64,
+    region: CaptureRegion,
+}
+
+/// Joriy oynaning keyingi kadrini rasmga oladi. `on_done` Render Thread'da, kadr chizilib
+/// GPU'dan o'qilgandan keyin chaqiriladi; tugun topilmasa yoki o'qib bo'lmasa `None` keladi.
+///
+/// ```ignore
+/// capture_frame(CaptureRegion::Node("chart".into()), |img| {
+///     if let Some(img) = img {
+///         let _ = img.save("chart.png");
+///     }
+/// });
+/// ```
+pub fn capture_frame<F>(region: CaptureRegion, on_done: F)
+where
+    F: FnOnce(Option<RgbaImage>) + Send + 'static,
+{
+    let id = NEXT_CAPTURE_ID.fetch_add(1, Ordering::SeqCst);
+    callbacks().lock().unwrap().insert(id, Box::new(on_done));
+    CommandQueue::dispatch(CaptureCommand { id, region });
+}
+
+// Logic Thread: hudud layout bo'yicha aniqlanadi, so'rov keyingi RenderPacket bilan ketadi
+pub(crate) fn handle_command(cmd: CaptureCommand, state: &mut FrameworkState) {
+    let rect = match &cmd.region {
+        CaptureRegion::Window => None,
+        CaptureRegion::Rect(rect) => Some(*rect),
+        CaptureRegion::Node(name) => {
+            let bounds = state.arena.dynamic_nodes.get(name).and_then(|id| {
+                let node = state.arena.taffy_map.get(id)?;
+                let rect = state.node_bounds.get(node)?;
+                Some(state.screen_rect(id.0, *rect))
+            });
+            match bounds {
+                Some(rect) => Some(rect),
+                None => {
+                    deliver(cmd.id, None);
+                    return;
+                }
+            }
+        }
+    };
+    state.pending_captures.push((cmd.id, rect));
+}
+
+// Mantiqiy rect -> fizik piksellar (Render Thread o'zi ekran chegarasiga qirqadi)
+pub(crate) fn pixel_region(rect: [f32; 4], scale: f32) -> [u32; 4] {
+    let x1 = (rect[0] * scale).floor().max(0.0);
+    let y1 = (rect[1] * scale).floor().max(0.0);
+    let x2 = ((rect[0] + rect[2]) * scale).ceil();
+    let y2 = ((rect[1] + rect[3]) * scale).ceil();
+    [
+        x1 as u32,
+        y1 as u32,
+        (x2 - x1).max(0.0) as u32,
+        (y2 - y1).max(0.0) as u32,
+    ]
+}
+
+// Render Thread: natijani ilova kodiga qaytaradi
+pub(crate) fn deliver(id: u64, image: Option<RgbaImage>) {
+    let callback = callbacks().lock().unwrap().remove(&id);
+    if let Some(callback) = callback {
+        callback(image);
+    }
+}
+
+/// Joriy oynani `dir` papkasiga `frame_000000.png`, `frame_000001.png` ... ketma-ketligi
+/// sifatida yozib boradi. Kadrlar `fps` bo'yicha vaqtga bog'langan: UI sekinlashsa,
+/// tushib qolgan kadrlar oxirgi rasm bilan to'ldiriladi, video uzunligi o'zgarmaydi.
+pub fn start_recording(dir: impl Into<PathBuf>, fps: u32) {
+    wake_event_loop(RoreUserEvent::Record(
+        current_window(),
+        Some((dir.into(), fps.max(1))),
+    ));
+}
+
+pub fn stop_recording() {
+    stop_recording_window(current_window());
+}
+
+pub fn stop_recording_window(window: WindowId) {
+    wake_event_loop(RoreUserEvent::Record(window, None));
+}
+
+// PNG kodlash alohida oqimda: Render Thread kadrni faqat GPU'dan o'qishni boshlaydi
+#[derive(Clone)]
+pub(crate) struct FrameSink {
+    dir: PathBuf,
+    tx_save: Sender<(PathBuf, RgbaImage)>,
+}
+
+impl FrameSink {
+    // Bitta rasm bir necha kadr raqamiga yoziladi (tushib qolgan kadrlar)
+    pub(crate) fn save(&self, frames: Range<u64>, image: RgbaImage) {
+        for n in frames {
+            let path = self.dir.join(format!("frame_{:06}.png", n));
+            let _ = self.tx_save.send((path, image.clone()));
+        }
+    }
+}
+
+// GPU o'qishi tugaganda natija kimga boradi
+pub(crate) enum CaptureTarget {
+    Callback(u64),
+    Frames(FrameSink, Range<u64>),
+}
+
+pub(crate) struct Recorder {
+    sink: FrameSink,
+    interval: f32,
+    started_at: Option<f32>,
+    next_frame: u64,
+}
+
+impl Recorder {
+    pub(crate) fn new(dir: PathBuf, fps: u32) -> Self {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            eprintln!("Rore: {} papkasi yaratilmadi: {}", dir.display(), e);
+        }
+        let (tx_save, rx_save) = mpsc::channel::<(PathBuf, RgbaImage)>();
+        std::thread::spawn(move || {
+            for (path, image) in rx_save {
+                if let Err(e) = image.save(&path) {
+                    eprintln!("Rore: {} yozilmadi: {}", path.display(), e);
+                }
+            }
+        });
+        Self {
+            sink: FrameSink { dir, tx_save },
+            interval: 1.0 / fps as f32,
+            started_at: None,
+            next_frame: 0,
+        }
+    }
+
+    // Vaqti kelgan, hali olinmagan kadr raqamlari (oxirgisi shu kadr)
+    pub(crate) fn due(&mut self, now: f32) -> Option<Range<u64>> {
+        let started_at = *self.started_at.get_or_insert(now);
+        let due = ((now - started_at) / self.interval) as u64;
+        (due >= self.next_frame).then(|| self.next_frame..due + 1)
+    }
+
+    // O'qish boshlandi: bu raqamlar shu rasm bilan to'ldiriladi. Boshlanmasa (halqa band),
+    // raqamlar keyingi kadrga qo'shilib ketadi
+    pub(crate) fn claim(&mut self, frames: &Range<u64>) -> CaptureTarget {
+        self.next_frame = frames.end;
+        CaptureTarget::Frames(self.sink.clone(), frames.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_region_covers_fractional_edges() {
+        assert_eq!(pixel_region([10.5, 4.0, 20.0, 8.25], 2.0), [21, 8, 40, 17]);
+        assert_eq!(pixel_region([-5.0, 0.0, 10.0, 10.0], 1.0), [0, 0, 5, 10]);
+    }
+
+    #[test]
+    fn recorder_fills_dropped_frames_and_skips_early_ones() {
+        let dir = std::env::temp_dir().join(format!("rore_rec_{}", std::process::id()));
+        let mut recorder = Recorder::new(dir.clone(), 10);
+
+        let first = recorder.due(1.0).unwrap();
+        assert_eq!(first, 0..1);
+        recorder.claim(&first);
+        assert_eq!(recorder.due(1.05), None);
+
+        // Halqa band bo'lib olinmagan kadrlar keyingisiga qo'shiladi
+        assert_eq!(recorder.due(1.15), Some(1..2));
+        let late = recorder.due(1.35).unwrap();
+        assert_eq!(late, 1..4);
+        recorder.claim(&late);
+        assert_eq!(recorder.due(1.36), None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
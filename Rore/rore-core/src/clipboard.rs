@@ -0,0 +1,292 @@
+use crate::state::FrameworkState;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex, OnceLock};
+use winit::keyboard::{Key, ModifiersState};
+
+/// RGBA8 rasm (qatorlar ketma-ket, har pikselga 4 bayt).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    // alt: HTML ni tushunmaydigan ilovalar uchun oddiy matn
+    Html { html: String, alt: String },
+    Image(ClipboardImage),
+}
+
+/// Ilova darajasidagi buyruqlar: `CommandQueue::dispatch(ClipboardCommand::Copy)`.
+/// Fokusdagi vidjetdan boshlab birinchi `Copyable` ga yetkaziladi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardCommand {
+    Copy,
+    Cut,
+    Paste,
+}
+
+impl ClipboardCommand {
+    /// Ctrl+C/X/V (macOS da Cmd).
+    pub fn from_shortcut(key: &Key, modifiers: ModifiersState) -> Option<Self> {
+        if !(modifiers.control_key() || modifiers.super_key()) || modifiers.alt_key() {
+            return None;
+        }
+        let Key::Character(c) = key else {
+            return None;
+        };
+        match c.to_lowercase().as_str() {
+            "c" => Some(ClipboardCommand::Copy),
+            "x" => Some(ClipboardCommand::Cut),
+            "v" => Some(ClipboardCommand::Paste),
+            _ => None,
+        }
+    }
+}
+
+/// Nusxa olish/qo'yishni qo'llab-quvvatlaydigan vidjet (`Widget::as_copyable` orqali topiladi).
+pub trait Copyable {
+    /// None: nusxa oladigan narsa yo'q (masalan, tanlov bo'sh)
+    fn copy(&mut self, state: &mut FrameworkState) -> Option<ClipboardContent>;
+
+    fn cut(&mut self, state: &mut FrameworkState) -> Option<ClipboardContent> {
+        self.copy(state)
+    }
+
+    /// Kerakli formatni o'zi tanlaydi (matn, HTML, rasm). true: qabul qilindi
+    fn paste(&mut self, _state: &mut FrameworkState, _clipboard: &ClipboardService) -> bool {
+        false
+    }
+}
+
+/// OS clipboard. Barcha oynalar va vidjetlar bitta nusxani ishlatadi: `clipboard()`.
+pub struct ClipboardService {
+    // Headless muhitda (CI, display yo'q) None: hamma amallar jimgina bekor bo'ladi
+    inner: Option<Mutex<arboard::Clipboard>>,
+}
+
+pub fn clipboard() -> Arc<ClipboardService> {
+    static SERVICE: OnceLock<Arc<ClipboardService>> = OnceLock::new();
+    SERVICE
+        .get_or_init(|| {
+            Arc::new(ClipboardService {
+                inner: arboard::Clipboard::new().ok().map(Mutex::new),
+            })
+        })
+        .clone()
+}
+
+impl ClipboardService {
+    pub fn is_available(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut arboard::Clipboard) -> Option<T>) -> Option<T> {
+        let mut cb = self.inner.as_ref()?.lock().ok()?;
+        f(&mut cb)
+    }
+
+    pub fn text(&self) -> Option<String> {
+        self.with(|cb| cb.get_text().ok())
+    }
+
+    pub fn set_text(&self, text: impl Into<String>) -> bool {
+        let text = text.into();
+        self.with(|cb| cb.set_text(text).ok()).is_some()
+    }
+
+    pub fn html(&self) -> Option<String> {
+        self.with(|cb| cb.get().html().ok())
+    }
+
+    pub fn set_html(&self, html: impl Into<String>, alt: impl Into<String>) -> bool {
+        let (html, alt) = (html.into(), alt.into());
+        self.with(|cb| cb.set_html(html, Some(alt)).ok()).is_some()
+    }
+
+    pub fn image(&self) -> Option<ClipboardImage> {
+        self.with(|cb| {
+            let img = cb.get_image().ok()?;
+            Some(ClipboardImage {
+                width: img.width,
+                height: img.height,
+                rgba: img.bytes.into_owned(),
+            })
+        })
+    }
+
+    pub fn set_image(&self, image: &ClipboardImage) -> bool {
+        if image.rgba.len() != image.width * image.height * 4 {
+            return false;
+        }
+        self.with(|cb| {
+            cb.set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: Cow::Borrowed(&image.rgba),
+            })
+            .ok()
+        })
+        .is_some()
+    }
+
+    pub fn write(&self, content: &ClipboardContent) -> bool {
+        match content {
+            ClipboardContent::Text(text) => self.set_text(text.as_str()),
+            ClipboardContent::Html { html, alt } => self.set_html(html.as_str(), alt.as_str()),
+            ClipboardContent::Image(image) => self.set_image(image),
+        }
+    }
+
+    /// Linux "primary selection" (belgilangan matn, o'rta tugma bilan qo'yiladi).
+    /// Boshqa platformalarda doim None.
+    pub fn primary_text(&self) -> Option<String> {
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+        {
+            use arboard::{GetExtLinux, LinuxClipboardKind};
+            self.with(|cb| cb.get().clipboard(LinuxClipboardKind::Primary).text().ok())
+        }
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android")))))]
+        {
+            None
+        }
+    }
+
+    pub fn set_primary_text(&self, text: impl Into<String>) -> bool {
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+        {
+            use arboard::{LinuxClipboardKind, SetExtLinux};
+            let text = text.into();
+            self.with(|cb| {
+                cb.set()
+                    .clipboard(LinuxClipboardKind::Primary)
+                    .text(text)
+                    .ok()
+            })
+            .is_some()
+        }
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android")))))]
+        {
+            let _ = text;
+            false
+        }
+    }
+}
+
+/// Jadval kataklarini elektron jadvalga qo'yiladigan TSV ga aylantiradi.
+/// Tab, yangi qator yoki qo'shtirnoqli katak qo'shtirnoqqa olinadi (Excel/Sheets shunday o'qiydi).
+pub fn to_tsv<R, C>(rows: R) -> String
+where
+    R: IntoIterator,
+    R::Item: IntoIterator<Item = C>,
+    C: AsRef<str>,
+{
+    let mut out = String::new();
+    for (r, row) in rows.into_iter().enumerate() {
+        if r > 0 {
+            out.push('\n');
+        }
+        for (c, cell) in row.into_iter().enumerate() {
+            if c > 0 {
+                out.push('\t');
+            }
+            let cell = cell.as_ref();
+            if cell.contains(['\t', '\n', '\r', '"']) {
+                out.push('"');
+                out.push_str(&cell.replace('"', "\"\""));
+                out.push('"');
+            } else {
+                out.push_str(cell);
+            }
+        }
+    }
+    out
+}
+
+impl FrameworkState {
+    /// Buyruqni fokusdagi vidjetdan ota-bobolar bo'ylab birinchi `Copyable` ga beradi.
+    /// true: kimdir qabul qildi (klaviatura yorlig'i vidjetga KeyPress bo'lib bormaydi).
+    pub fn route_clipboard(&mut self, cmd: ClipboardCommand) -> bool {
+        let Some(focused) = self.focused_node else {
+            return false;
+        };
+        for node in self.get_event_bubble_chain(focused) {
+            let Some(&node_id) = self.arena.node_map.get(&node) else {
+                continue;
+            };
+            let Some(mut widget) = self.arena.widgets[node_id.0 as usize].take() else {
+                continue;
+            };
+            let handled = match widget.as_copyable() {
+                Some(target) => {
+                    let service = self.clipboard.clone();
+                    match cmd {
+                        ClipboardCommand::Copy => {
+                            if let Some(content) = target.copy(self) {
+                                service.write(&content);
+                            }
+                        }
+                        ClipboardCommand::Cut => {
+                            if let Some(content) = target.cut(self) {
+                                service.write(&content);
+                            }
+                        }
+                        ClipboardCommand::Paste => {
+                            target.paste(self, &service);
+                        }
+                    }
+                    true
+                }
+                None => false,
+            };
+            self.arena.widgets[node_id.0 as usize] = Some(widget);
+            if handled {
+                if !self.sparse_update_queue.contains(&node_id) {
+                    self.sparse_update_queue.push(node_id);
+                }
+                self.request_redraw();
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tsv_quotes_cells_that_would_break_the_grid() {
+        let rows = vec![
+            vec!["Price", "Amount"],
+            vec!["64,210.5", "0.25"],
+            vec!["say \"hi\"", "a\tb"],
+        ];
+        assert_eq!(
+            to_tsv(rows),
+            "Price\tAmount\n64,210.5\t0.25\n\"say \"\"hi\"\"\"\t\"a\tb\""
+        );
+    }
+
+    #[test]
+    fn shortcuts_map_to_commands() {
+        let ctrl = ModifiersState::CONTROL;
+        let key = |s: &str| Key::Character(s.into());
+        assert_eq!(
+            ClipboardCommand::from_shortcut(&key("C"), ctrl),
+            Some(ClipboardCommand::Copy)
+        );
+        assert_eq!(
+            ClipboardCommand::from_shortcut(&key("v"), ModifiersState::SUPER),
+            Some(ClipboardCommand::Paste)
+        );
+        assert_eq!(
+            ClipboardCommand::from_shortcut(&key("x"), ModifiersState::empty()),
+            None
+        );
+        assert_eq!(ClipboardCommand::from_shortcut(&key("a"), ctrl), None);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::reactive::signals::{create_scope, dispose_scope, ScopeId};
+use crate::state::{FrameworkState, NodeId};
+use crate::widgets::base::{BuildContext, Widget, WidgetEvent};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use std::any::Any;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Sudralayotgan ma'lumot. Istalgan turdagi qiymatni olib yuradi,
+/// qabul qiluvchi `get::<T>()` orqali o'ziga keraklisini tekshiradi.
+#[derive(Clone)]
+pub struct DragData {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl DragData {
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self {
+            payload: Arc::new(value),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DragData<{}>", self.type_name)
+    }
+}
+
+/// OS dan (Explorer, Finder) tashlangan fayllar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedFiles(pub Vec<PathBuf>);
+
+pub struct DragSession {
+    pub data: DragData,
+    pub source: Option<TaffyNode>,
+    pub target: Option<TaffyNode>,
+    // Preview kursordan qancha siljib chiziladi
+    pub preview_offset: Vec2,
+    // true: sessiya OS fayl sudrashidan kelgan
+    pub external: bool,
+    pending_preview: Option<Box<dyn Widget>>,
+    preview: Option<(NodeId, ScopeId)>,
+    hovered_files: Vec<PathBuf>,
+    dropped_files: Vec<PathBuf>,
+}
+
+impl DragSession {
+    fn new(data: DragData, source: Option<TaffyNode>, external: bool) -> Self {
+        Self {
+            data,
+            source,
+            target: None,
+            preview_offset: Vec2::new(12.0, 12.0),
+            external,
+            pending_preview: None,
+            preview: None,
+            hovered_files: Vec::new(),
+            dropped_files: Vec::new(),
+        }
+    }
+}
+
+impl FrameworkState {
+    /// Vidjet ichidan (odatda MouseDrag paytida) chaqiriladi. Preview keyingi kadrda quriladi.
+    pub fn begin_drag(&mut self, data: DragData, preview: Option<Box<dyn Widget>>) {
+        if self.drag.is_some() {
+            return;
+        }
+        let mut session = DragSession::new(data, self.active_node, false);
+        session.pending_preview = preview;
+        self.drag = Some(session);
+        self.request_redraw();
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Kursor ostidagi nishonni yangilaydi. `DragOver` ni Consumed qilgan tugun nishon bo'ladi,
+    /// nishon almashsa eski tugunga `DragLeave`, yangisiga `DragEnter` yuboriladi.
+    pub(crate) fn update_drag(&mut self, x: f32, y: f32) {
+        let Some(session) = &self.drag else {
+            return;
+        };
+        let data = session.data.clone();
+        let old_target = session.target;
+
+        let new_target = self.hit_test(x, y).and_then(|hit| {
+            self.dispatch_bubbling(
+                hit,
+                &WidgetEvent::DragOver {
+                    data: data.clone(),
+                    x,
+                    y,
+                },
+            )
+        });
+
+        if new_target != old_target {
+            if let Some(old) = old_target {
+                self.dispatch_to(old, &WidgetEvent::DragLeave);
+            }
+            if let Some(new) = new_target {
+                self.dispatch_to(new, &WidgetEvent::DragEnter(data));
+            }
+            if let Some(session) = self.drag.as_mut() {
+                session.target = new_target;
+            }
+        }
+
+        if self.drag.as_ref().is_some_and(|s| s.preview.is_some()) {
+            self.request_redraw();
+        }
+    }
+
+    /// Sichqoncha qo'yib yuborildi: nishon bo'lsa `Drop`, manbaga `DragEnd`.
+    pub(crate) fn finish_drag(&mut self) {
+        let Some(session) = self.drag.take() else {
+            return;
+        };
+        let (x, y) = (self.cursor_pos.x, self.cursor_pos.y);
+        let accepted = match session.target {
+            Some(target) => {
+                self.dispatch_to(
+                    target,
+                    &WidgetEvent::Drop {
+                        data: session.data.clone(),
+                        x,
+                        y,
+                    },
+                );
+                true
+            }
+            None => false,
+        };
+        self.end_drag_session(session, accepted);
+    }
+
+    pub(crate) fn cancel_drag(&mut self) {
+        let Some(session) = self.drag.take() else {
+            return;
+        };
+        if let Some(target) = session.target {
+            self.dispatch_to(target, &WidgetEvent::DragLeave);
+        }
+        self.end_drag_session(session, false);
+    }
+
+    fn end_drag_session(&mut self, session: DragSession, accepted: bool) {
+        if let Some(source) = session.source {
+            self.dispatch_to(source, &WidgetEvent::DragEnd { accepted });
+        }
+        if let Some((preview_id, scope)) = session.preview {
+            self.drop_queue.borrow_mut().push(preview_id);
+            dispose_scope(scope);
+        }
+        self.request_redraw();
+    }
+
+    // ==================== OS FAYLLARI ====================
+
+    pub(crate) fn file_hovered(&mut self, path: PathBuf) {
+        let session = match &mut self.drag {
+            Some(session) if session.external => session,
+            Some(_) => return,
+            None => self.drag.insert(DragSession::new(
+                DragData::new(DroppedFiles(Vec::new())),
+                None,
+                true,
+            )),
+        };
+        session.hovered_files.push(path);
+        session.data = DragData::new(DroppedFiles(session.hovered_files.clone()));
+
+        let (x, y) = (self.cursor_pos.x, self.cursor_pos.y);
+        self.update_drag(x, y);
+    }
+
+    pub(crate) fn file_hover_cancelled(&mut self) {
+        if self.drag.as_ref().is_some_and(|s| s.external) {
+            self.cancel_drag();
+        }
+    }
+
+    // winit har bir fayl uchun alohida DroppedFile yuboradi: ularni yig'ib, kadr oxirida bitta Drop qilamiz
+    pub(crate) fn file_dropped(&mut self, path: PathBuf) {
+        if self.drag.as_ref().is_some_and(|s| !s.external) {
+            return;
+        }
+        if self.drag.is_none() {
+            self.drag = Some(DragSession::new(
+                DragData::new(DroppedFiles(Vec::new())),
+                None,
+                true,
+            ));
+        }
+        if let Some(session) = self.drag.as_mut() {
+            session.dropped_files.push(path);
+        }
+    }
+
+    /// Logic Thread har kadrda chaqiradi: preview qurish va yig'ilgan fayllarni tashlash.
+    pub(crate) fn process_drag(&mut self, engine: &mut LayoutEngine) {
+        let Some(session) = self.drag.as_mut() else {
+            return;
+        };
+
+        if !session.dropped_files.is_empty() {
+            session.data = DragData::new(DroppedFiles(std::mem::take(&mut session.dropped_files)));
+            let (x, y) = (self.cursor_pos.x, self.cursor_pos.y);
+            // HoveredFile kelmagan platformalarda nishon hali topilmagan bo'lishi mumkin
+            self.update_drag(x, y);
+            self.finish_drag();
+            return;
+        }
+
+        if let Some(preview) = session.pending_preview.take() {
+            let ctx = BuildContext {};
+            let (scope, preview_id) = create_scope(|| preview.build(&mut self.arena, engine, &ctx));
+            if let Some(session) = self.drag.as_mut() {
+                session.preview = Some((preview_id, scope));
+            }
+        }
+    }
+
+    /// Preview ning (NodeId, chizish pozitsiyasi). Overlay'lardan keyin, eng ustida chiziladi.
+    pub(crate) fn drag_preview(&self) -> Option<(NodeId, Vec2)> {
+        let session = self.drag.as_ref()?;
+        let (id, _) = session.preview?;
+        Some((id, self.cursor_pos + session.preview_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{UiArena, WakeRegistry};
+    use crate::widgets::base::RenderOutput;
+    use crate::widgets::drag_drop::DropTarget;
+    use rore_types::{RoreConfig, Style, Val};
+    use std::sync::Mutex;
+
+    struct Leaf;
+
+    impl Widget for Leaf {
+        fn type_name(&self) -> &'static str {
+            "Leaf"
+        }
+
+        fn build(
+            self: Box<Self>,
+            arena: &mut UiArena,
+            engine: &mut LayoutEngine,
+            _ctx: &BuildContext,
+        ) -> NodeId {
+            let style = Style {
+                width: Val::Px(200.0),
+                height: Val::Px(100.0),
+                ..Default::default()
+            };
+            let taffy_node = engine.new_leaf(style);
+            let id = arena.allocate_node();
+            arena.taffy_map.insert(id, taffy_node);
+            arena.node_map.insert(taffy_node, id);
+            arena.widgets[id.0 as usize] = Some(self);
+            id
+        }
+
+        fn render(
+            &self,
+            _engine: &LayoutEngine,
+            _state: &mut FrameworkState,
+            _taffy_node: TaffyNode,
+            _parent_pos: Vec2,
+            _clip_rect: Option<[f32; 4]>,
+            _path: String,
+        ) -> RenderOutput {
+            RenderOutput::new()
+        }
+    }
+
+    fn setup(dropped: Arc<Mutex<Vec<String>>>) -> (FrameworkState, LayoutEngine) {
+        let mut state = FrameworkState::new(
+            RoreConfig::desktop(),
+            Arc::new(Mutex::new(WakeRegistry::new())),
+        );
+        let mut engine = LayoutEngine::new();
+
+        let target = DropTarget::<String>::new(Leaf, move |symbol, _, _| {
+            dropped.lock().unwrap().push(symbol);
+        });
+        let root_id = Box::new(target).build(&mut state.arena, &mut engine, &BuildContext {});
+        let root = state.arena.taffy_map[&root_id];
+        engine.root = Some(root);
+        engine.compute(800.0, 600.0);
+        state.update_aabbs(&engine, root, true);
+        (state, engine)
+    }
+
+    #[test]
+    fn typed_payload_is_dropped_on_matching_target() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let (mut state, _engine) = setup(dropped.clone());
+
+        state.update_cursor(50.0, 50.0);
+        state.begin_drag(DragData::new("AAPL".to_string()), None);
+        state.update_drag(50.0, 50.0);
+        assert!(state.drag.as_ref().unwrap().target.is_some());
+
+        state.finish_drag();
+        assert!(!state.is_dragging());
+        assert_eq!(*dropped.lock().unwrap(), vec!["AAPL".to_string()]);
+    }
+
+    #[test]
+    fn target_ignores_payload_of_other_type() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let (mut state, _engine) = setup(dropped.clone());
+
+        state.update_cursor(50.0, 50.0);
+        state.begin_drag(DragData::new(42u32), None);
+        state.update_drag(50.0, 50.0);
+        assert!(state.drag.as_ref().unwrap().target.is_none());
+
+        state.finish_drag();
+        assert!(dropped.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+use glam::Vec2;
+use std::time::{Duration, Instant};
+
+// Barmoq shu masofadan ko'p siljisa, bu endi "Tap" emas, "Pan"
+const TAP_SLOP: f32 = 10.0;
+const TAP_MAX_DURATION: Duration = Duration::from_millis(300);
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// Xom pointer hodisalaridan tanib olingan yuqori darajadagi ishoralar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap {
+        x: f32,
+        y: f32,
+    },
+    LongPress {
+        x: f32,
+        y: f32,
+    },
+    // pointers: nechta barmoq bilan surilyapti (1 = oddiy drag, 2 = ikki barmoqli pan)
+    Pan {
+        dx: f32,
+        dy: f32,
+        pointers: usize,
+    },
+    // scale: oldingi holatga nisbatan ko'paytma (1.0 = o'zgarmadi)
+    Pinch {
+        scale: f32,
+        center_x: f32,
+        center_y: f32,
+    },
+    Rotate {
+        radians: f32,
+        center_x: f32,
+        center_y: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Pointer {
+    id: u64,
+    start: Vec2,
+    pos: Vec2,
+    started_at: Instant,
+}
+
+/// Multi-touch pointerlarni kuzatib, ulardan Tap/LongPress/Pan/Pinch/Rotate yasaydi.
+/// Vaqtga bog'liq narsalar (`LongPress`) uchun `poll()` har tickda chaqiriladi.
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    pointers: Vec<Pointer>,
+    // Joriy ishora davomida bir nechta barmoq ishtirok etdimi (unda Tap bo'lmaydi)
+    was_multi: bool,
+    moved: bool,
+    long_press_fired: bool,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pointer_count(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.pointers.is_empty()
+    }
+
+    /// LongPress hali kutilyaptimi (event loop uxlab qolmasligi uchun)
+    pub fn is_waiting(&self) -> bool {
+        self.pointers.len() == 1 && !self.moved && !self.long_press_fired && !self.was_multi
+    }
+
+    pub fn pointer_down(&mut self, id: u64, x: f32, y: f32, now: Instant) {
+        if self.pointers.is_empty() {
+            self.was_multi = false;
+            self.moved = false;
+            self.long_press_fired = false;
+        }
+        self.pointers.retain(|p| p.id != id);
+        let pos = Vec2::new(x, y);
+        self.pointers.push(Pointer {
+            id,
+            start: pos,
+            pos,
+            started_at: now,
+        });
+        if self.pointers.len() > 1 {
+            self.was_multi = true;
+        }
+    }
+
+    pub fn pointer_move(&mut self, id: u64, x: f32, y: f32) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        let Some(idx) = self.pointers.iter().position(|p| p.id == id) else {
+            return out;
+        };
+
+        let pos = Vec2::new(x, y);
+        let before: Vec<Vec2> = self.pointers.iter().map(|p| p.pos).collect();
+        let pointer = &mut self.pointers[idx];
+        pointer.pos = pos;
+        if pointer.start.distance(pos) > TAP_SLOP {
+            self.moved = true;
+        }
+        if !self.moved {
+            return out;
+        }
+
+        let after: Vec<Vec2> = self.pointers.iter().map(|p| p.pos).collect();
+        let delta = centroid(&after) - centroid(&before);
+        if delta != Vec2::ZERO {
+            out.push(Gesture::Pan {
+                dx: delta.x,
+                dy: delta.y,
+                pointers: after.len(),
+            });
+        }
+
+        // Ikki barmoq: masofa -> Pinch, burchak -> Rotate
+        if after.len() >= 2 {
+            let center = centroid(&after);
+            let (a0, a1) = (before[0], before[1]);
+            let (b0, b1) = (after[0], after[1]);
+
+            let old_dist = a0.distance(a1);
+            let new_dist = b0.distance(b1);
+            if old_dist > f32::EPSILON && (new_dist - old_dist).abs() > f32::EPSILON {
+                out.push(Gesture::Pinch {
+                    scale: new_dist / old_dist,
+                    center_x: center.x,
+                    center_y: center.y,
+                });
+            }
+
+            let old_angle = (a1.y - a0.y).atan2(a1.x - a0.x);
+            let new_angle = (b1.y - b0.y).atan2(b1.x - b0.x);
+            let mut radians = new_angle - old_angle;
+            if radians > std::f32::consts::PI {
+                radians -= std::f32::consts::TAU;
+            } else if radians < -std::f32::consts::PI {
+                radians += std::f32::consts::TAU;
+            }
+            if radians.abs() > f32::EPSILON {
+                out.push(Gesture::Rotate {
+                    radians,
+                    center_x: center.x,
+                    center_y: center.y,
+                });
+            }
+        }
+        out
+    }
+
+    pub fn pointer_up(&mut self, id: u64, x: f32, y: f32, now: Instant) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        let Some(idx) = self.pointers.iter().position(|p| p.id == id) else {
+            return out;
+        };
+        let pointer = self.pointers.remove(idx);
+
+        let is_tap = self.pointers.is_empty()
+            && !self.was_multi
+            && !self.moved
+            && !self.long_press_fired
+            && now.duration_since(pointer.started_at) <= TAP_MAX_DURATION;
+        if is_tap {
+            out.push(Gesture::Tap { x, y });
+        }
+        out
+    }
+
+    pub fn pointer_cancel(&mut self, id: u64) {
+        self.pointers.retain(|p| p.id != id);
+        // Bekor qilingan ishoradan Tap chiqmasligi kerak
+        self.moved = true;
+    }
+
+    pub fn poll(&mut self, now: Instant) -> Option<Gesture> {
+        if !self.is_waiting() {
+            return None;
+        }
+        let pointer = self.pointers[0];
+        if now.duration_since(pointer.started_at) >= LONG_PRESS_DURATION {
+            self.long_press_fired = true;
+            return Some(Gesture::LongPress {
+                x: pointer.pos.x,
+                y: pointer.pos.y,
+            });
+        }
+        None
+    }
+}
+
+fn centroid(points: &[Vec2]) -> Vec2 {
+    if points.is_empty() {
+        return Vec2::ZERO;
+    }
+    points.iter().copied().sum::<Vec2>() / points.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_release_without_movement_is_a_tap() {
+        let mut g = GestureRecognizer::new();
+        let t = Instant::now();
+        g.pointer_down(1, 10.0, 10.0, t);
+        assert!(g.pointer_move(1, 12.0, 11.0).is_empty());
+        let out = g.pointer_up(1, 12.0, 11.0, t + Duration::from_millis(100));
+        assert_eq!(out, vec![Gesture::Tap { x: 12.0, y: 11.0 }]);
+    }
+
+    #[test]
+    fn held_pointer_becomes_long_press_and_not_tap() {
+        let mut g = GestureRecognizer::new();
+        let t = Instant::now();
+        g.pointer_down(1, 5.0, 5.0, t);
+        assert!(g.is_waiting());
+        assert_eq!(g.poll(t + Duration::from_millis(200)), None);
+        assert_eq!(
+            g.poll(t + LONG_PRESS_DURATION),
+            Some(Gesture::LongPress { x: 5.0, y: 5.0 })
+        );
+        assert!(!g.is_waiting());
+        assert!(g
+            .pointer_up(1, 5.0, 5.0, t + Duration::from_secs(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn two_pointers_spreading_apart_pinch_out() {
+        let mut g = GestureRecognizer::new();
+        let t = Instant::now();
+        g.pointer_down(1, 100.0, 100.0, t);
+        g.pointer_down(2, 200.0, 100.0, t);
+
+        let out = g.pointer_move(2, 300.0, 100.0);
+        let pinch = out.iter().find_map(|e| match e {
+            Gesture::Pinch { scale, .. } => Some(*scale),
+            _ => None,
+        });
+        assert_eq!(pinch, Some(2.0));
+        assert!(out
+            .iter()
+            .any(|e| matches!(e, Gesture::Pan { pointers: 2, .. })));
+
+        assert!(g.pointer_up(2, 300.0, 100.0, t).is_empty());
+        assert!(g.pointer_up(1, 100.0, 100.0, t).is_empty());
+    }
+
+    #[test]
+    fn rotating_second_pointer_reports_angle() {
+        let mut g = GestureRecognizer::new();
+        let t = Instant::now();
+        g.pointer_down(1, 0.0, 0.0, t);
+        g.pointer_down(2, 100.0, 0.0, t);
+        let out = g.pointer_move(2, 0.0, 100.0);
+        let radians = out.iter().find_map(|e| match e {
+            Gesture::Rotate { radians, .. } => Some(*radians),
+            _ => None,
+        });
+        assert!((radians.unwrap() - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+    }
+}
@@ -1,9 +1,18 @@
 pub mod app;
+pub mod capture;
+pub mod clipboard;
+pub mod dnd;
+pub mod gesture;
+pub mod path;
 pub mod reactive;
+pub mod shader;
+pub mod shortcuts;
 pub mod state;
 pub mod time;
+pub mod tooltip;
 pub mod widgets;
+pub mod window;
 // Barcha kerakli narsalarni freymvorkdan tashqariga eksport qilamiz
-pub use crate::app::{run, App, AppEvent};
+pub use crate::app::{run, run_with_window, App, AppEvent};
 pub use crate::widgets::base::Widget;
 pub mod calculs;
@@ -0,0 +1,507 @@
+use glam::Vec2;
+use lyon::math::{point, vector, Angle, Point};
+use lyon::path::iterator::PathIterator;
+use lyon::path::PathEvent;
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator,
+    StrokeVertex, VertexBuffers,
+};
+use rore_render::{PathMesh, PathVertex};
+use rore_types::Color;
+
+// Egri chiziqlarni bo'laklashdagi xatolik (logik pikselda)
+const TOLERANCE: f32 = 0.1;
+// Chetdagi yarim shaffof hoshiya kengligi
+const AA_FRINGE: f32 = 1.0;
+// Klip yo'q: kompilyator DisplayList'dagi haqiqiy klipni qo'yadi
+const NO_CLIP: [f32; 4] = [-10000.0, -10000.0, 20000.0, 20000.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    // [chiziq, bo'shliq, chiziq, ...]. Bo'sh: uzluksiz
+    pub dash: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn dash(mut self, pattern: &[f32], offset: f32) -> Self {
+        self.dash = pattern.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+/// Vektor yo'l (SVG/Canvas kabi). `stroke` va `fill` uni uchburchaklarga aylantiradi,
+/// natija `DisplayCommand::DrawPath` orqali chiziladi.
+///
+/// ```ignore
+/// let line = Path::polyline(&points).stroke(&StrokeStyle::new(1.5), Color::hex("#0ECB81"));
+/// commands.push(DisplayCommand::DrawPath { mesh: Arc::new(line) });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+fn to_point(v: Vec2) -> Point {
+    point(v.x, v.y)
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn polyline(points: &[Vec2]) -> Self {
+        let mut path = Self::new();
+        for (i, &p) in points.iter().enumerate() {
+            path = if i == 0 {
+                path.move_to(p)
+            } else {
+                path.line_to(p)
+            };
+        }
+        path
+    }
+
+    pub fn polygon(points: &[Vec2]) -> Self {
+        Self::polyline(points).close()
+    }
+
+    pub fn rect(rect: [f32; 4]) -> Self {
+        let [x, y, w, h] = rect;
+        Self::polygon(&[
+            Vec2::new(x, y),
+            Vec2::new(x + w, y),
+            Vec2::new(x + w, y + h),
+            Vec2::new(x, y + h),
+        ])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn move_to(mut self, p: Vec2) -> Self {
+        self.segments.push(Segment::MoveTo(p));
+        self
+    }
+
+    pub fn line_to(mut self, p: Vec2) -> Self {
+        self.segments.push(Segment::LineTo(p));
+        self
+    }
+
+    pub fn quad_to(mut self, ctrl: Vec2, to: Vec2) -> Self {
+        self.segments.push(Segment::QuadTo(ctrl, to));
+        self
+    }
+
+    pub fn cubic_to(mut self, ctrl1: Vec2, ctrl2: Vec2, to: Vec2) -> Self {
+        self.segments.push(Segment::CubicTo(ctrl1, ctrl2, to));
+        self
+    }
+
+    /// Aylana yoyi (radianlarda, soat strelkasi bo'yicha musbat, chunki Y pastga).
+    /// Joriy nuqtadan yoy boshigacha to'g'ri chiziq tortiladi.
+    pub fn arc(mut self, center: Vec2, radius: f32, start_angle: f32, sweep_angle: f32) -> Self {
+        let arc = lyon::geom::Arc {
+            center: to_point(center),
+            radii: vector(radius, radius),
+            start_angle: Angle::radians(start_angle),
+            sweep_angle: Angle::radians(sweep_angle),
+            x_rotation: Angle::radians(0.0),
+        };
+        let start = arc.from();
+        let start = Vec2::new(start.x, start.y);
+        self = if self.current_point().is_some() {
+            self.line_to(start)
+        } else {
+            self.move_to(start)
+        };
+        arc.for_each_cubic_bezier(&mut |s| {
+            self.segments.push(Segment::CubicTo(
+                Vec2::new(s.ctrl1.x, s.ctrl1.y),
+                Vec2::new(s.ctrl2.x, s.ctrl2.y),
+                Vec2::new(s.to.x, s.to.y),
+            ));
+        });
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.segments.push(Segment::Close);
+        self
+    }
+
+    // Ochiq subpath'ning oxirgi nuqtasi
+    fn current_point(&self) -> Option<Vec2> {
+        match self.segments.last()? {
+            Segment::MoveTo(p) | Segment::LineTo(p) => Some(*p),
+            Segment::QuadTo(_, p) | Segment::CubicTo(_, _, p) => Some(*p),
+            Segment::Close => None,
+        }
+    }
+
+    fn to_lyon(&self) -> lyon::path::Path {
+        let mut builder = lyon::path::Path::builder();
+        let mut open = false;
+        for seg in &self.segments {
+            match *seg {
+                Segment::MoveTo(p) => {
+                    if open {
+                        builder.end(false);
+                    }
+                    builder.begin(to_point(p));
+                    open = true;
+                }
+                // move_to siz boshlangan chiziq shu nuqtadan boshlanadi
+                Segment::LineTo(p) if !open => {
+                    builder.begin(to_point(p));
+                    open = true;
+                }
+                Segment::LineTo(p) => {
+                    builder.line_to(to_point(p));
+                }
+                Segment::QuadTo(c, p) => {
+                    if !open {
+                        builder.begin(to_point(c));
+                        open = true;
+                    }
+                    builder.quadratic_bezier_to(to_point(c), to_point(p));
+                }
+                Segment::CubicTo(c1, c2, p) => {
+                    if !open {
+                        builder.begin(to_point(c1));
+                        open = true;
+                    }
+                    builder.cubic_bezier_to(to_point(c1), to_point(c2), to_point(p));
+                }
+                Segment::Close => {
+                    if open {
+                        builder.end(true);
+                        open = false;
+                    }
+                }
+            }
+        }
+        if open {
+            builder.end(false);
+        }
+        builder.build()
+    }
+
+    pub fn stroke(&self, style: &StrokeStyle, color: Color) -> PathMesh {
+        let mut mesh = PathMesh::default();
+        if self.is_empty() || style.width <= 0.0 {
+            return mesh;
+        }
+        let path = self.to_lyon();
+        let path = if style.dash.iter().any(|d| *d > 0.0) {
+            dash_path(&path, &style.dash, style.dash_offset)
+        } else {
+            path
+        };
+        stroke_into(&mut mesh, &path, style, color);
+        mesh
+    }
+
+    /// Chetlar opaque ranglarda 1px hoshiya bilan silliqlanadi
+    /// (yarim shaffof rangda hoshiya ichki qismni ikki marta bo'yab qo'yardi).
+    pub fn fill(&self, rule: FillRule, color: Color) -> PathMesh {
+        let mut mesh = PathMesh::default();
+        if self.is_empty() {
+            return mesh;
+        }
+        let path = self.to_lyon();
+        let rgba = [color.r, color.g, color.b, color.a];
+        let options = FillOptions::tolerance(TOLERANCE).with_fill_rule(match rule {
+            FillRule::NonZero => lyon::tessellation::FillRule::NonZero,
+            FillRule::EvenOdd => lyon::tessellation::FillRule::EvenOdd,
+        });
+
+        let mut buffers: VertexBuffers<PathVertex, u32> = VertexBuffers::new();
+        let result = FillTessellator::new().tessellate_path(
+            &path,
+            &options,
+            &mut BuffersBuilder::new(&mut buffers, |v: FillVertex| {
+                let p = v.position();
+                PathVertex {
+                    position: [p.x, p.y],
+                    center: [p.x, p.y],
+                    color: rgba,
+                    clip_rect: NO_CLIP,
+                    // Ichki qism: doim to'liq bo'yaladi
+                    half_width: f32::MAX,
+                }
+            }),
+        );
+        if result.is_err() {
+            return mesh;
+        }
+        mesh.vertices = buffers.vertices;
+        mesh.indices = buffers.indices;
+
+        if color.a >= 1.0 {
+            let fringe = StrokeStyle::new(0.0).join(LineJoin::Round);
+            stroke_into(&mut mesh, &path, &fringe, color);
+        }
+        mesh
+    }
+}
+
+// Chiziq AA_FRINGE ga kengroq chiziladi; qopqoqlik shader'da markazdan masofa bo'yicha hisoblanadi
+fn stroke_into(mesh: &mut PathMesh, path: &lyon::path::Path, style: &StrokeStyle, color: Color) {
+    let rgba = [color.r, color.g, color.b, color.a];
+    let half_width = (style.width + AA_FRINGE) * 0.5;
+    let options = StrokeOptions::tolerance(TOLERANCE)
+        .with_line_width(style.width + AA_FRINGE)
+        .with_line_cap(match style.cap {
+            LineCap::Butt => lyon::tessellation::LineCap::Butt,
+            LineCap::Round => lyon::tessellation::LineCap::Round,
+            LineCap::Square => lyon::tessellation::LineCap::Square,
+        })
+        .with_line_join(match style.join {
+            LineJoin::Miter => lyon::tessellation::LineJoin::Miter,
+            LineJoin::Round => lyon::tessellation::LineJoin::Round,
+            LineJoin::Bevel => lyon::tessellation::LineJoin::Bevel,
+        });
+
+    let mut buffers: VertexBuffers<PathVertex, u32> = VertexBuffers::new();
+    let result = StrokeTessellator::new().tessellate_path(
+        path,
+        &options,
+        &mut BuffersBuilder::new(&mut buffers, |v: StrokeVertex| {
+            let p = v.position();
+            let c = v.position_on_path();
+            PathVertex {
+                position: [p.x, p.y],
+                center: [c.x, c.y],
+                color: rgba,
+                clip_rect: NO_CLIP,
+                half_width,
+            }
+        }),
+    );
+    if result.is_ok() {
+        mesh.append(&PathMesh {
+            vertices: buffers.vertices,
+            indices: buffers.indices,
+        });
+    }
+}
+
+struct Dasher<'a> {
+    pattern: &'a [f32],
+    offset: f32,
+    total: f32,
+    idx: usize,
+    remaining: f32,
+    drawing: bool,
+}
+
+impl Dasher<'_> {
+    // Naqsh har bir subpath boshida offset dan qayta boshlanadi
+    fn restart(&mut self) {
+        let mut idx = 0;
+        let mut left = self.offset.rem_euclid(self.total);
+        while left >= self.pattern[idx].max(0.0) {
+            left -= self.pattern[idx].max(0.0);
+            idx = (idx + 1) % self.pattern.len();
+        }
+        self.idx = idx;
+        self.remaining = self.pattern[idx].max(0.0) - left;
+    }
+
+    fn segment(&mut self, builder: &mut lyon::path::path::Builder, from: Point, to: Point) {
+        let mut from = from;
+        let mut length = (to - from).length();
+        while length > 0.0 {
+            let on = self.idx.is_multiple_of(2);
+            let step = self.remaining.min(length);
+            let next = from + (to - from) * (step / length);
+            if on {
+                if !self.drawing {
+                    builder.begin(from);
+                    self.drawing = true;
+                }
+                builder.line_to(next);
+            }
+            from = next;
+            length -= step;
+            self.remaining -= step;
+            if self.remaining <= 0.0 {
+                self.finish(builder);
+                self.idx = (self.idx + 1) % self.pattern.len();
+                self.remaining = self.pattern[self.idx].max(0.0);
+            }
+        }
+    }
+
+    fn finish(&mut self, builder: &mut lyon::path::path::Builder) {
+        if self.drawing {
+            builder.end(false);
+            self.drawing = false;
+        }
+    }
+}
+
+// Egri chiziqlar to'g'ri bo'laklarga aylantirilib, naqsh bo'yicha kesiladi
+fn dash_path(path: &lyon::path::Path, pattern: &[f32], offset: f32) -> lyon::path::Path {
+    let mut builder = lyon::path::Path::builder();
+    let total: f32 = pattern.iter().map(|d| d.max(0.0)).sum();
+    if total <= 0.0 {
+        return builder.build();
+    }
+
+    let mut dasher = Dasher {
+        pattern,
+        offset,
+        total,
+        idx: 0,
+        remaining: 0.0,
+        drawing: false,
+    };
+    for event in path.iter().flattened(TOLERANCE) {
+        match event {
+            PathEvent::Begin { .. } => dasher.restart(),
+            PathEvent::Line { from, to } => dasher.segment(&mut builder, from, to),
+            PathEvent::End { last, first, close } => {
+                if close {
+                    dasher.segment(&mut builder, last, first);
+                }
+                dasher.finish(&mut builder);
+            }
+            // flattened() faqat Line qaytaradi
+            _ => {}
+        }
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(mesh: &PathMesh) -> [f32; 4] {
+        let mut b = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+        for v in &mesh.vertices {
+            b[0] = b[0].min(v.position[0]);
+            b[1] = b[1].min(v.position[1]);
+            b[2] = b[2].max(v.position[0]);
+            b[3] = b[3].max(v.position[1]);
+        }
+        b
+    }
+
+    #[test]
+    fn stroke_is_widened_by_the_aa_fringe() {
+        let line = Path::polyline(&[Vec2::new(0.0, 10.0), Vec2::new(100.0, 10.0)]);
+        let mesh = line.stroke(&StrokeStyle::new(2.0), Color::WHITE);
+        assert!(!mesh.is_empty());
+        assert_eq!(mesh.indices.len() % 3, 0);
+
+        let b = bounds(&mesh);
+        assert!((b[1] - 8.5).abs() < 1e-3 && (b[3] - 11.5).abs() < 1e-3);
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|v| v.center[1] == 10.0 && v.half_width == 1.5));
+    }
+
+    #[test]
+    fn dashes_split_the_line_into_pieces() {
+        let line = Path::polyline(&[Vec2::ZERO, Vec2::new(100.0, 0.0)]);
+        let dashed = dash_path(&line.to_lyon(), &[10.0, 10.0], 0.0);
+        let pieces = dashed
+            .iter()
+            .filter(|e| matches!(e, PathEvent::Begin { .. }))
+            .count();
+        assert_eq!(pieces, 5);
+
+        // Offset naqshni suradi: birinchi bo'lak 5px
+        let shifted = dash_path(&line.to_lyon(), &[10.0, 10.0], 5.0);
+        let first = shifted.iter().find_map(|e| match e {
+            PathEvent::Line { from, to } => Some((to - from).length()),
+            _ => None,
+        });
+        assert!((first.unwrap() - 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn fill_rules_differ_on_self_overlap() {
+        fn area(mesh: &PathMesh) -> f32 {
+            mesh.indices
+                .chunks(3)
+                .map(|t| {
+                    let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[t[i] as usize].position);
+                    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() * 0.5
+                })
+                .sum()
+        }
+
+        // Ichma-ich ikki kvadrat bir xil yo'nalishda: NonZero teshik qoldirmaydi
+        let mut both = Path::rect([0.0, 0.0, 30.0, 30.0]);
+        both.segments
+            .extend(Path::rect([10.0, 10.0, 10.0, 10.0]).segments);
+
+        // Yarim shaffof: hoshiya qo'shilmaydi, faqat ichki uchburchaklar qoladi
+        let transparent = Color::new(1.0, 1.0, 1.0, 0.5);
+        let non_zero = both.fill(FillRule::NonZero, transparent);
+        let even_odd = both.fill(FillRule::EvenOdd, transparent);
+        assert!((area(&non_zero) - 900.0).abs() < 0.5);
+        assert!((area(&even_odd) - 800.0).abs() < 0.5);
+    }
+}
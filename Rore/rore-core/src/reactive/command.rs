@@ -1,7 +1,11 @@
-use crate::state::NodeId;
+use crate::state::{FrameworkState, NodeId};
+use crate::window::{current_window, WindowId};
+use rore_layout::LayoutEngine;
 use rore_types::Style;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Mutex, Once, OnceLock};
 
 pub enum UICommand {
     SetColor(String, [f32; 4]),
@@ -11,23 +15,181 @@ pub enum UICommand {
     UpdateStyle(NodeId, Style),
     UpdateResource(u64, Box<dyn std::any::Any + Send>),
     UpdateTransform(NodeId, f32, f32),
+    // Aylantirish, masshtab, qiyshaytirish (`TransformBox::rotate` va boshqalar)
+    SetTransform(NodeId, rore_types::Transform),
+    // Guruh shaffofligi (`.opacity(signal)`)
+    SetOpacity(NodeId, f32),
+    // Tashqi (plagin) buyruqlar: tip bo'yicha ro'yxatdan o'tgan handlerga boradi
+    Custom(TypeId, Box<dyn Any + Send>),
 }
 
-pub static COMMAND_SENDER: OnceLock<Sender<UICommand>> = OnceLock::new();
-pub static COMMAND_RECEIVER: OnceLock<Mutex<Receiver<UICommand>>> = OnceLock::new();
+pub type CommandHandler =
+    Box<dyn FnMut(Box<dyn Any + Send>, &mut FrameworkState, &mut LayoutEngine) + Send>;
+
+pub static COMMAND_HANDLERS: OnceLock<Mutex<HashMap<TypeId, CommandHandler>>> = OnceLock::new();
+pub static COMMAND_WAKER: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+fn handlers() -> &'static Mutex<HashMap<TypeId, CommandHandler>> {
+    COMMAND_HANDLERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub static COMMAND_SENDER: OnceLock<Sender<(WindowId, UICommand)>> = OnceLock::new();
+pub static COMMAND_RECEIVER: OnceLock<Mutex<Receiver<(WindowId, UICommand)>>> = OnceLock::new();
+// Kanaldan o'qilgan, lekin boshqa oynaga tegishli buyruqlar shu yerda kutib turadi
+static COMMAND_INBOX: OnceLock<Mutex<HashMap<WindowId, Vec<UICommand>>>> = OnceLock::new();
+static COMMAND_INIT: Once = Once::new();
 
 pub struct CommandQueue;
 
 impl CommandQueue {
+    // Har bir FrameworkState chaqiradi: sender va receiver bitta kanaldan bo'lishi shart
     pub fn init() {
-        let (tx, rx) = mpsc::channel();
-        COMMAND_SENDER.set(tx).ok();
-        COMMAND_RECEIVER.set(Mutex::new(rx)).ok();
+        COMMAND_INIT.call_once(|| {
+            let (tx, rx) = mpsc::channel();
+            COMMAND_SENDER.set(tx).ok();
+            COMMAND_RECEIVER.set(Mutex::new(rx)).ok();
+        });
     }
 
     pub fn send(cmd: UICommand) {
+        Self::send_to(current_window(), cmd);
+    }
+
+    pub fn send_to(window: WindowId, cmd: UICommand) {
         if let Some(sender) = COMMAND_SENDER.get() {
-            let _ = sender.send(cmd);
+            let _ = sender.send((window, cmd));
+        }
+    }
+
+    /// Kanalni bo'shatib, faqat shu oynaning buyruqlarini qaytaradi
+    pub fn drain_for(window: WindowId) -> Vec<UICommand> {
+        let inbox = COMMAND_INBOX.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut inbox = inbox.lock().unwrap();
+        if let Some(rx_mutex) = COMMAND_RECEIVER.get() {
+            if let Ok(rx) = rx_mutex.try_lock() {
+                while let Ok((target, cmd)) = rx.try_recv() {
+                    inbox.entry(target).or_default().push(cmd);
+                }
+            }
+        }
+        inbox.remove(&window).unwrap_or_default()
+    }
+
+    /// Yopilgan oynaga kelgan buyruqlarni tashlab yuboradi
+    pub fn discard(window: WindowId) {
+        let _ = Self::drain_for(window);
+    }
+
+    /// Asosiy oynani uyg'otuvchi funksiya (worker thread'dan kelgan buyruqlar uchun)
+    pub fn set_waker<F: Fn() + Send + Sync + 'static>(waker: F) {
+        let _ = COMMAND_WAKER.set(Box::new(waker));
+    }
+
+    /// `C` tipidagi buyruqlar uchun handler. Handler Logic Thread'da ishlaydi.
+    /// Shu tip uchun oldingi handler bo'lsa, almashtiriladi.
+    pub fn register<C, F>(mut handler: F)
+    where
+        C: Send + 'static,
+        F: FnMut(C, &mut FrameworkState, &mut LayoutEngine) + Send + 'static,
+    {
+        let boxed: CommandHandler = Box::new(move |payload, state, engine| {
+            if let Ok(cmd) = payload.downcast::<C>() {
+                handler(*cmd, state, engine);
+            }
+        });
+        handlers().lock().unwrap().insert(TypeId::of::<C>(), boxed);
+    }
+
+    /// Istalgan oqimdan (thread) tipli buyruq yuborish
+    pub fn dispatch<C: Send + 'static>(cmd: C) {
+        Self::dispatch_to(current_window(), cmd);
+    }
+
+    /// Buyruqni aniq oynaga yuborish (masalan, ajratib olingan grafik oynasiga)
+    pub fn dispatch_to<C: Send + 'static>(window: WindowId, cmd: C) {
+        Self::send_to(window, UICommand::Custom(TypeId::of::<C>(), Box::new(cmd)));
+        if let Some(waker) = COMMAND_WAKER.get() {
+            waker();
+        }
+    }
+
+    pub(crate) fn run_custom(
+        type_id: TypeId,
+        payload: Box<dyn Any + Send>,
+        state: &mut FrameworkState,
+        engine: &mut LayoutEngine,
+    ) {
+        // Handler ichida yangi handler ro'yxatdan o'tishi mumkin, shuning uchun qulfni ushlab turmaymiz
+        let handler = handlers().lock().unwrap().remove(&type_id);
+        match handler {
+            Some(mut handler) => {
+                handler(payload, state, engine);
+                handlers().lock().unwrap().entry(type_id).or_insert(handler);
+            }
+            None => eprintln!("Rore: buyruq uchun handler topilmadi ({:?})", type_id),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::WakeRegistry;
+    use rore_types::RoreConfig;
+    use std::sync::Arc;
+
+    struct ResizePanel {
+        width: f32,
+    }
+
+    #[test]
+    fn custom_command_from_worker_thread_reaches_handler() {
+        let mut state = FrameworkState::new(
+            RoreConfig::desktop(),
+            Arc::new(Mutex::new(WakeRegistry::new())),
+        );
+        let mut engine = LayoutEngine::new();
+        let node = engine.new_leaf(Style::default());
+        engine.root = Some(node);
+
+        CommandQueue::register::<ResizePanel, _>(move |cmd, state, engine| {
+            let style = Style {
+                width: rore_types::Val::Px(cmd.width),
+                ..Default::default()
+            };
+            engine.update_style(node, style);
+            state.request_redraw();
+        });
+
+        std::thread::spawn(|| CommandQueue::dispatch(ResizePanel { width: 240.0 }))
+            .join()
+            .unwrap();
+        state.process_commands(&mut engine);
+
+        engine.compute(800.0, 600.0);
+        assert_eq!(engine.taffy.layout(node).unwrap().size.width, 240.0);
+    }
+
+    #[test]
+    fn commands_stay_with_their_window() {
+        CommandQueue::init();
+        let (a, b) = (WindowId(9_001), WindowId(9_002));
+        CommandQueue::send_to(b, UICommand::MarkDirty(NodeId(1, 0), 1));
+        CommandQueue::send_to(a, UICommand::MarkDirty(NodeId(2, 0), 2));
+        CommandQueue::send_to(b, UICommand::MarkDirty(NodeId(3, 0), 3));
+
+        let for_a = CommandQueue::drain_for(a);
+        assert!(matches!(for_a[..], [UICommand::MarkDirty(NodeId(2, 0), 2)]));
+        assert!(CommandQueue::drain_for(a).is_empty());
+
+        // B ning buyruqlari A o'qiganda yo'qolmaydi va tartibi saqlanadi
+        let for_b = CommandQueue::drain_for(b);
+        assert!(matches!(
+            for_b[..],
+            [
+                UICommand::MarkDirty(NodeId(1, 0), 1),
+                UICommand::MarkDirty(NodeId(3, 0), 3)
+            ]
+        ));
+    }
+}
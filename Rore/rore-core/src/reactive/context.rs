@@ -1,27 +1,8 @@
-use crate::reactive::signals::{create_effect, Signal, RUNTIME};
+use crate::reactive::signals::{create_effect, Signal};
+pub use crate::reactive::signals::{provide_context, use_context};
 use rore_types::Color;
-use std::any::TypeId;
 use std::cell::RefCell;
 
-pub fn provide_context<T: 'static + Clone + Send>(value: T) {
-    RUNTIME.with(|rt| {
-        let mut rt_mut = rt.borrow_mut();
-        rt_mut
-            .global_contexts
-            .insert(TypeId::of::<T>(), Box::new(value));
-    });
-}
-
-pub fn use_context<T: 'static + Clone>() -> Option<T> {
-    RUNTIME.with(|rt| {
-        let rt_ref = rt.borrow();
-        rt_ref
-            .global_contexts
-            .get(&TypeId::of::<T>())
-            .and_then(|any| any.downcast_ref::<T>().cloned())
-    })
-}
-
 /// Animatsiya uslublari (Matematik Easing)
 #[derive(Debug, Clone, Copy)]
 pub enum Easing {
@@ -150,6 +131,10 @@ pub fn create_tween<T: Tweenable + Clone + PartialEq + 'static>(
     tweened
 }
 
+pub fn has_active_tweens() -> bool {
+    ACTIVE_TWEENS.with(|tweens| !tweens.borrow().is_empty())
+}
+
 pub fn tick_tweens(dt: f32) -> bool {
     ACTIVE_TWEENS.with(|tweens| {
         let mut list = tweens.borrow_mut();
@@ -1,4 +1,4 @@
-use crate::reactive::signals::{create_effect, Signal};
+use crate::reactive::signals::{create_computed, Signal};
 use std::marker::PhantomData;
 
 /// Memo - bu qimmatli hisob-kitoblarni keshlovchi Reaktiv Quti.
@@ -19,18 +19,9 @@ impl<T> Clone for Memo<T> {
 impl<T> Copy for Memo<T> {}
 
 impl<T: Clone + PartialEq + 'static> Memo<T> {
-    pub fn new<F: FnMut() -> T + 'static>(mut f: F) -> Self {
-        // Dastlabki qiymatni darhol hisoblab, sof signal yaratamiz
-        let initial_val = f();
-        let sig = Signal::new(initial_val);
-
-        // Qolgan o'zgarishlarni Effect kuzatadi
-        create_effect(move || {
-            let new_val = f();
-            if sig.get_untracked() != new_val {
-                sig.set(new_val);
-            }
-        });
+    pub fn new<F: FnMut() -> T + 'static>(f: F) -> Self {
+        // Lazy: qiymat faqat kimdir o'qiganda hisoblanadi, manbalar balandlik bo'yicha yangilanadi
+        let sig = create_computed(f);
 
         Self {
             signal: sig,
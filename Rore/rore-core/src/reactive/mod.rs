@@ -1,5 +1,6 @@
 pub mod command;
 pub mod context; // YANGI
 pub mod memo;
+pub mod persist;
 pub mod resource;
 pub mod signals;
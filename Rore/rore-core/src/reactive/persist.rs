@@ -0,0 +1,319 @@
+use crate::reactive::signals::{create_effect, Signal};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// Tez-tez o'zgaradigan signallar (zoom, pan) diskni qiynamasligi uchun
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(400);
+
+type Migration = Box<dyn Fn(&mut Map<String, Value>) + Send>;
+
+struct PersistStore {
+    path: Option<PathBuf>,
+    version: u32,
+    migrations: Vec<(u32, Migration)>,
+    values: Map<String, Value>,
+    loaded: bool,
+    dirty: bool,
+}
+
+static STORE: OnceLock<Mutex<PersistStore>> = OnceLock::new();
+static SAVE_SENDER: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+fn store() -> &'static Mutex<PersistStore> {
+    STORE.get_or_init(|| {
+        Mutex::new(PersistStore {
+            path: None,
+            version: 0,
+            migrations: Vec::new(),
+            values: Map::new(),
+            loaded: false,
+            dirty: false,
+        })
+    })
+}
+
+fn default_path(app_name: &str) -> PathBuf {
+    let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join(app_name).join("state.json")
+}
+
+fn default_app_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "rore-app".to_string())
+}
+
+/// Ilova nomi va holat sxemasi versiyasini belgilaydi.
+/// Birinchi `create_persisted_signal` dan OLDIN chaqirilishi kerak.
+pub fn configure_persistence(app_name: &str, version: u32) {
+    let mut s = store().lock().unwrap();
+    s.path = Some(default_path(app_name));
+    s.version = version;
+}
+
+/// Holat faylini aniq yo'lga yo'naltiradi (testlar yoki portable rejim uchun)
+pub fn set_persistence_path(path: impl Into<PathBuf>) {
+    let mut s = store().lock().unwrap();
+    s.path = Some(path.into());
+    s.loaded = false;
+    s.values.clear();
+}
+
+/// `to_version` ga o'tish uchun migratsiya. Fayldagi versiyadan yuqori bo'lganlari
+/// ro'yxatdan o'tish tartibidan qat'i nazar, versiya bo'yicha o'sish tartibida ishlaydi.
+pub fn register_migration<F>(to_version: u32, f: F)
+where
+    F: Fn(&mut Map<String, Value>) + Send + 'static,
+{
+    let mut s = store().lock().unwrap();
+    s.migrations.push((to_version, Box::new(f)));
+}
+
+impl PersistStore {
+    fn path(&mut self) -> PathBuf {
+        self.path
+            .get_or_insert_with(|| default_path(&default_app_name()))
+            .clone()
+    }
+
+    fn ensure_loaded(&mut self) {
+        if self.loaded {
+            return;
+        }
+        self.loaded = true;
+
+        let path = self.path();
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return; // Birinchi ishga tushish
+        };
+
+        let parsed = serde_json::from_str::<Value>(&text).ok().and_then(|v| {
+            let version = v.get("version")?.as_u64()? as u32;
+            let values = v.get("values")?.as_object()?.clone();
+            Some((version, values))
+        });
+
+        match parsed {
+            Some((file_version, _)) if file_version > self.version => {
+                // Yangiroq build yozgan holatni eski versiya tamg'asi bilan bosib yozmaymiz
+                let backup = path.with_extension(format!("json.v{}", file_version));
+                let _ = std::fs::rename(&path, &backup);
+                eprintln!(
+                    "Rore: holat fayli yangiroq versiyadan ({}), standart qiymatlar ishlatiladi ({:?})",
+                    file_version, backup
+                );
+            }
+            Some((file_version, mut values)) => {
+                if file_version < self.version {
+                    // Stabil saralash: bir xil versiyadagilar ro'yxatdan o'tish tartibida
+                    self.migrations.sort_by_key(|(v, _)| *v);
+                    for (v, migrate) in &self.migrations {
+                        if *v > file_version && *v <= self.version {
+                            migrate(&mut values);
+                        }
+                    }
+                    self.dirty = true;
+                }
+                self.values = values;
+            }
+            None => {
+                // Buzilgan fayl: ilovani yiqitmaymiz, nusxasini saqlab, toza holatdan boshlaymiz
+                let backup = path.with_extension("json.corrupt");
+                let _ = std::fs::rename(&path, &backup);
+                eprintln!(
+                    "Rore: holat fayli buzilgan, standart qiymatlar ishlatiladi ({:?})",
+                    backup
+                );
+            }
+        }
+    }
+
+    fn snapshot(&mut self) -> Option<(PathBuf, String)> {
+        if !self.dirty {
+            return None;
+        }
+        self.dirty = false;
+        let mut root = Map::new();
+        root.insert("version".to_string(), Value::from(self.version));
+        root.insert("values".to_string(), Value::Object(self.values.clone()));
+        let text = serde_json::to_string_pretty(&Value::Object(root)).ok()?;
+        Some((self.path(), text))
+    }
+}
+
+fn write_snapshot() {
+    let _guard = WRITE_LOCK.lock().unwrap();
+    let snapshot = store().lock().unwrap().snapshot();
+    if let Some((path, text)) = snapshot {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        // Atomik yozish: yarim yozilgan fayl hech qachon asl faylni buzmaydi
+        let tmp = path.with_extension("json.tmp");
+        let result = std::fs::write(&tmp, text).and_then(|_| std::fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            eprintln!("Rore: holatni saqlab bo'lmadi: {:?}", e);
+        }
+    }
+}
+
+// Yozuvchi oqim: o'zgarishlar tinchigach bitta yozish qiladi
+fn schedule_save() {
+    let sender = SAVE_SENDER.get_or_init(|| {
+        let (tx, rx) = channel::<()>();
+        std::thread::spawn(move || {
+            while rx.recv().is_ok() {
+                loop {
+                    match rx.recv_timeout(SAVE_DEBOUNCE) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                write_snapshot();
+            }
+        });
+        Mutex::new(tx)
+    });
+    let _ = sender.lock().unwrap().send(());
+}
+
+/// Kutib turgan o'zgarishlarni darhol diskka yozadi (masalan, oyna yopilayotganda)
+pub fn flush_persisted() {
+    if STORE.get().is_some() {
+        write_snapshot();
+    }
+}
+
+/// Ilova qayta ishga tushganda ham qiymatini saqlab qoladigan signal.
+/// Fayldagi qiymat o'qib bo'lmasa (tip o'zgargan bo'lsa) `default` ishlatiladi.
+pub fn create_persisted_signal<T>(key: &str, default: T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    let initial = {
+        let mut s = store().lock().unwrap();
+        s.ensure_loaded();
+        let needs_save = s.dirty;
+        let value = s
+            .values
+            .get(key)
+            .and_then(|v| serde_json::from_value::<T>(v.clone()).ok())
+            .unwrap_or(default);
+        if needs_save {
+            drop(s);
+            schedule_save();
+        }
+        value
+    };
+
+    let sig = Signal::new(initial);
+    let key = key.to_string();
+    let mut is_first_run = true;
+
+    create_effect(move || {
+        let value = sig.get();
+        if is_first_run {
+            is_first_run = false;
+            return;
+        }
+        // Serializatsiya shu yerda (arzon), disk bilan ishlash esa boshqa oqimda
+        if let Ok(json) = serde_json::to_value(&value) {
+            let mut s = store().lock().unwrap();
+            if s.values.get(&key) != Some(&json) {
+                s.values.insert(key.clone(), json);
+                s.dirty = true;
+                drop(s);
+                schedule_save();
+            }
+        }
+    });
+
+    sig
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_and_recovers_from_corrupt_file() {
+        let dir = std::env::temp_dir().join(format!("rore-persist-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+
+        // Buzilgan fayl -> standart qiymat va .corrupt nusxa
+        std::fs::write(&path, "{ not json").unwrap();
+        set_persistence_path(&path);
+        let zoom = create_persisted_signal("chart.zoom", 1.0f32);
+        assert_eq!(zoom.get_untracked(), 1.0);
+        assert!(dir.join("state.json.corrupt").exists());
+
+        zoom.set(2.5);
+        flush_persisted();
+
+        // Eski versiya -> migratsiya kalitni qayta nomlaydi
+        std::fs::write(
+            &path,
+            r#"{ "version": 1, "values": { "zoom": 3.0, "symbols": ["BTC"] } }"#,
+        )
+        .unwrap();
+        {
+            let mut s = store().lock().unwrap();
+            s.version = 2;
+        }
+        register_migration(2, |values| {
+            if let Some(v) = values.remove("zoom") {
+                values.insert("chart.zoom".to_string(), v);
+            }
+        });
+        set_persistence_path(&path);
+        let zoom = create_persisted_signal("chart.zoom", 1.0f32);
+        let symbols = create_persisted_signal("symbols", Vec::<String>::new());
+        assert_eq!(zoom.get_untracked(), 3.0);
+        assert_eq!(symbols.get_untracked(), vec!["BTC".to_string()]);
+
+        flush_persisted();
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], 2);
+        assert_eq!(saved["values"]["chart.zoom"], 3.0);
+
+        // Teskari tartibda ro'yxatdan o'tgan migratsiyalar versiya bo'yicha ishlaydi
+        register_migration(4, |values| {
+            if let Some(v) = values.remove("b") {
+                values.insert("c".to_string(), v);
+            }
+        });
+        register_migration(3, |values| {
+            let a = values.get("a").and_then(Value::as_u64).unwrap_or(0);
+            values.insert("b".to_string(), Value::from(a * 10));
+        });
+        store().lock().unwrap().version = 4;
+        std::fs::write(&path, r#"{ "version": 2, "values": { "a": 1 } }"#).unwrap();
+        set_persistence_path(&path);
+        assert_eq!(create_persisted_signal("c", 0u64).get_untracked(), 10);
+        flush_persisted();
+
+        // Yangiroq build yozgan fayl: nusxasi saqlanadi, eski versiya bilan bosilmaydi
+        let newer = r#"{ "version": 9, "values": { "c": 99 } }"#;
+        std::fs::write(&path, newer).unwrap();
+        set_persistence_path(&path);
+        assert_eq!(create_persisted_signal("c", 0u64).get_untracked(), 0);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("state.json.v9")).unwrap(),
+            newer
+        );
+        flush_persisted();
+        assert!(!path.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
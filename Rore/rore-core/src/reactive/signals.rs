@@ -2,7 +2,7 @@ use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
-use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub static ACTIVE_TICKERS: AtomicUsize = AtomicUsize::new(0);
@@ -16,6 +16,16 @@ pub struct EffectId(pub u64);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ScopeId(pub u64);
 
+// Kuzatuvchi (Effect yoki Memo) holati: Push-Pull grafi uchun
+// Check - manbalardan biri "balki o'zgargan", avval memolarni tekshirish kerak
+// Dirty - manba aniq o'zgargan, qayta hisoblash shart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeState {
+    Clean,
+    Check,
+    Dirty,
+}
+
 pub struct ReactiveRuntime {
     next_id: u64,
     signals: HashMap<SignalId, Box<dyn Any>>,
@@ -24,11 +34,22 @@ pub struct ReactiveRuntime {
     active_effect: Option<EffectId>,
     pub pending_effects: HashSet<EffectId>,
 
+    // INQILOB: Glitch-free graf (balandlik bo'yicha tartiblangan)
+    effect_sources: HashMap<EffectId, HashSet<SignalId>>,
+    node_heights: HashMap<EffectId, u32>,
+    node_states: HashMap<EffectId, NodeState>,
+    memo_fns: HashMap<EffectId, Box<dyn FnMut() -> bool>>,
+    memo_signals: HashMap<EffectId, SignalId>,
+    memo_owners: HashMap<SignalId, EffectId>,
+    memo_scopes: HashMap<EffectId, ScopeId>,
+
     tickers: HashMap<u64, (Option<ScopeId>, Box<dyn FnMut(f32)>)>,
 
     scope_signals: HashMap<ScopeId, HashSet<SignalId>>,
     scope_effects: HashMap<ScopeId, HashSet<EffectId>>,
     scope_tickers: HashMap<ScopeId, HashSet<u64>>, // Scope ga ulangan tickerlar
+    scope_parents: HashMap<ScopeId, ScopeId>,
+    scope_contexts: HashMap<ScopeId, HashMap<TypeId, Box<dyn Any>>>,
 
     pub cleanups: HashMap<ScopeId, Vec<Box<dyn FnOnce()>>>,
     pub error_handlers: HashMap<ScopeId, Vec<Box<dyn FnMut(&(dyn Any + Send + 'static))>>>,
@@ -47,10 +68,19 @@ impl ReactiveRuntime {
             signal_subscribers: HashMap::new(),
             active_effect: None,
             pending_effects: HashSet::new(),
+            effect_sources: HashMap::new(),
+            node_heights: HashMap::new(),
+            node_states: HashMap::new(),
+            memo_fns: HashMap::new(),
+            memo_signals: HashMap::new(),
+            memo_owners: HashMap::new(),
+            memo_scopes: HashMap::new(),
             tickers: HashMap::new(),
             scope_signals: HashMap::new(),
             scope_effects: HashMap::new(),
             scope_tickers: HashMap::new(),
+            scope_parents: HashMap::new(),
+            scope_contexts: HashMap::new(),
             cleanups: HashMap::new(),
             error_handlers: HashMap::new(),
             active_scope: None,
@@ -64,24 +94,149 @@ impl ReactiveRuntime {
         self.next_id += 1;
         id
     }
+
+    pub fn node_state(&self, id: EffectId) -> NodeState {
+        self.node_states
+            .get(&id)
+            .copied()
+            .unwrap_or(NodeState::Clean)
+    }
+
+    fn height_of(&self, id: EffectId) -> u32 {
+        self.node_heights.get(&id).copied().unwrap_or(0)
+    }
+
+    // Aktiv kuzatuvchini signalga obuna qilamiz va uning balandligini yangilaymiz
+    fn track(&mut self, id: SignalId) {
+        if let Some(effect_id) = self.active_effect {
+            self.signal_subscribers
+                .entry(id)
+                .or_default()
+                .insert(effect_id);
+            self.effect_sources.entry(effect_id).or_default().insert(id);
+
+            let source_height = match self.memo_owners.get(&id) {
+                Some(&memo_id) => self.height_of(memo_id) + 1,
+                None => 0,
+            };
+            let height = self.node_heights.entry(effect_id).or_insert(0);
+            if *height < source_height + 1 {
+                *height = source_height + 1;
+            }
+        }
+    }
+
+    // Qayta ishga tushishdan oldin eski bog'lanishlarni uzamiz (dinamik bog'liqliklar uchun)
+    fn clear_sources(&mut self, effect_id: EffectId) {
+        if let Some(sources) = self.effect_sources.remove(&effect_id) {
+            for signal_id in sources {
+                if let Some(subs) = self.signal_subscribers.get_mut(&signal_id) {
+                    subs.remove(&effect_id);
+                }
+            }
+        }
+        self.node_heights.insert(effect_id, 0);
+    }
+
+    fn mark_node(&mut self, id: EffectId, state: NodeState) {
+        let prev = self.node_state(id);
+        if prev >= state {
+            return;
+        }
+        self.node_states.insert(id, state);
+        if prev != NodeState::Clean {
+            // Pastki tugunlar allaqachon belgilangan
+            return;
+        }
+
+        if let Some(&out) = self.memo_signals.get(&id) {
+            // Memo hali hisoblanmaydi (Lazy), faqat kuzatuvchilariga "tekshir" deymiz
+            self.mark_subscribers(out, NodeState::Check);
+        } else {
+            self.pending_effects.insert(id);
+        }
+    }
+
+    fn mark_subscribers(&mut self, id: SignalId, state: NodeState) {
+        if let Some(subs) = self.signal_subscribers.get(&id).cloned() {
+            for effect_id in subs {
+                self.mark_node(effect_id, state);
+            }
+        }
+    }
+
+    // Check holatidagi tugunning manba memolarini balandlik bo'yicha qaytaradi
+    fn upstream_memos(&self, id: EffectId) -> Vec<EffectId> {
+        let mut memos: Vec<EffectId> = self
+            .effect_sources
+            .get(&id)
+            .map(|sources| {
+                sources
+                    .iter()
+                    .filter_map(|s| self.memo_owners.get(s).copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+        memos.sort_by_key(|m| (self.height_of(*m), m.0));
+        memos
+    }
+
+    fn remove_node(&mut self, effect_id: EffectId) {
+        self.clear_sources(effect_id);
+        self.effects.remove(&effect_id);
+        self.pending_effects.remove(&effect_id);
+        self.node_heights.remove(&effect_id);
+        self.node_states.remove(&effect_id);
+        self.memo_fns.remove(&effect_id);
+        self.memo_scopes.remove(&effect_id);
+        if let Some(out) = self.memo_signals.remove(&effect_id) {
+            self.memo_owners.remove(&out);
+        }
+    }
 }
 
 thread_local! {
     pub static RUNTIME: RefCell<ReactiveRuntime> = RefCell::new(ReactiveRuntime::new());
 }
 
-pub fn provide_context<T: 'static + Clone + Send + Sync>(value: T) {
+/// Kontekstni joriy Scope ga joylaydi. Scope dan tashqarida chaqirilsa global bo'ladi.
+pub fn provide_context<T: 'static + Clone>(value: T) {
     RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
         let type_id = TypeId::of::<T>();
-        rt_mut.global_contexts.insert(type_id, Box::new(value));
+        match rt_mut.active_scope {
+            Some(scope_id) => {
+                rt_mut
+                    .scope_contexts
+                    .entry(scope_id)
+                    .or_default()
+                    .insert(type_id, Box::new(value));
+            }
+            None => {
+                rt_mut.global_contexts.insert(type_id, Box::new(value));
+            }
+        }
     });
 }
 
+/// Kontekstni joriy Scope dan boshlab ota-Scope lar bo'ylab qidiradi (eng yaqini yutadi).
 pub fn use_context<T: 'static + Clone>() -> Option<T> {
     RUNTIME.with(|rt| {
         let rt_ref = rt.borrow();
         let type_id = TypeId::of::<T>();
+
+        let mut current = rt_ref.active_scope;
+        while let Some(scope_id) = current {
+            if let Some(val) = rt_ref
+                .scope_contexts
+                .get(&scope_id)
+                .and_then(|ctx| ctx.get(&type_id))
+            {
+                return val.downcast_ref::<T>().cloned();
+            }
+            current = rt_ref.scope_parents.get(&scope_id).copied();
+        }
+
         rt_ref
             .global_contexts
             .get(&type_id)
@@ -143,7 +298,12 @@ where
 {
     let scope_id = RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
-        ScopeId(rt_mut.generate_id())
+        let id = ScopeId(rt_mut.generate_id());
+        // Ota Scope ni eslab qolamiz (Kontekst meros bo'lib o'tishi uchun)
+        if let Some(parent) = rt_mut.active_scope {
+            rt_mut.scope_parents.insert(id, parent);
+        }
+        id
     });
 
     let prev_scope = RUNTIME.with(|rt| {
@@ -163,6 +323,26 @@ where
     (scope_id, result)
 }
 
+/// Kodni berilgan Scope ichida bajaradi. Qayta qurishda (rebuild) bolalar
+/// o'z egasining kontekstini ko'rishi uchun kerak.
+pub fn run_in_scope<F, R>(scope: Option<ScopeId>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let prev_scope = RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        std::mem::replace(&mut rt_mut.active_scope, scope)
+    });
+
+    let result = f();
+
+    RUNTIME.with(|rt| {
+        rt.borrow_mut().active_scope = prev_scope;
+    });
+
+    result
+}
+
 pub fn on_cleanup<F: FnOnce() + 'static>(f: F) {
     RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
@@ -189,6 +369,56 @@ pub fn catch_error<F: FnMut(&(dyn Any + Send + 'static)) + 'static>(f: F) {
     });
 }
 
+/// Panikni eng yaqin `catch_error` egasiga (o'zi yoki ota-Scope lar) yetkazadi.
+/// Hech kim ushlamasa `false` qaytaradi.
+pub fn report_error(scope: Option<ScopeId>, payload: &(dyn Any + Send + 'static)) -> bool {
+    let mut current = scope;
+    while let Some(scope_id) = current {
+        // Handler ichida signal yozish mumkin bo'lishi uchun RUNTIME ni qarzdan bo'shatamiz
+        let handlers = RUNTIME.with(|rt| rt.borrow_mut().error_handlers.remove(&scope_id));
+        if let Some(mut handlers) = handlers {
+            for handler in &mut handlers {
+                handler(payload);
+            }
+            RUNTIME.with(|rt| {
+                let mut rt_mut = rt.borrow_mut();
+                let added = rt_mut.error_handlers.remove(&scope_id).unwrap_or_default();
+                handlers.extend(added);
+                rt_mut.error_handlers.insert(scope_id, handlers);
+            });
+            return true;
+        }
+        current = RUNTIME.with(|rt| rt.borrow().scope_parents.get(&scope_id).copied());
+    }
+    false
+}
+
+/// `scope` berilgan `ancestor` ning o'zi yoki uning avlodi ekanini tekshiradi
+pub fn scope_is_within(scope: ScopeId, ancestor: ScopeId) -> bool {
+    RUNTIME.with(|rt| {
+        let rt_ref = rt.borrow();
+        let mut current = Some(scope);
+        while let Some(scope_id) = current {
+            if scope_id == ancestor {
+                return true;
+            }
+            current = rt_ref.scope_parents.get(&scope_id).copied();
+        }
+        false
+    })
+}
+
+/// Panik matnini (payload) o'qiladigan ko'rinishga keltiradi
+pub fn panic_message(payload: &(dyn Any + Send + 'static)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Noma'lum xato (panic)".to_string()
+    }
+}
+
 pub fn dispose_scope(scope_id: ScopeId) {
     let cleanups = RUNTIME.with(|rt| rt.borrow_mut().cleanups.remove(&scope_id));
     if let Some(cleanups) = cleanups {
@@ -201,14 +431,12 @@ pub fn dispose_scope(scope_id: ScopeId) {
         let mut rt_mut = rt.borrow_mut();
 
         rt_mut.error_handlers.remove(&scope_id);
+        rt_mut.scope_contexts.remove(&scope_id);
+        rt_mut.scope_parents.remove(&scope_id);
 
         if let Some(effects) = rt_mut.scope_effects.remove(&scope_id) {
             for effect_id in effects {
-                rt_mut.effects.remove(&effect_id);
-                rt_mut.pending_effects.remove(&effect_id);
-                for (_, subs) in rt_mut.signal_subscribers.iter_mut() {
-                    subs.remove(&effect_id);
-                }
+                rt_mut.remove_node(effect_id);
             }
         }
 
@@ -264,15 +492,10 @@ impl<T: 'static + Clone> Signal<T> {
     }
 
     pub fn get(&self) -> T {
+        refresh_signal(self.id);
         RUNTIME.with(|rt| {
             let mut rt_mut = rt.borrow_mut();
-            if let Some(effect_id) = rt_mut.active_effect {
-                rt_mut
-                    .signal_subscribers
-                    .entry(self.id)
-                    .or_default()
-                    .insert(effect_id);
-            }
+            rt_mut.track(self.id);
             let any_val = rt_mut
                 .signals
                 .get(&self.id)
@@ -285,6 +508,7 @@ impl<T: 'static + Clone> Signal<T> {
     }
 
     pub fn get_untracked(&self) -> T {
+        refresh_signal(self.id);
         RUNTIME.with(|rt| {
             let rt_ref = rt.borrow();
             let any_val = rt_ref.signals.get(&self.id).expect("Signal topilmadi!");
@@ -300,11 +524,7 @@ impl<T: 'static + Clone> Signal<T> {
             let mut rt_mut = rt.borrow_mut();
             rt_mut.signals.insert(self.id, Box::new(value));
 
-            if let Some(subs) = rt_mut.signal_subscribers.get(&self.id).cloned() {
-                for effect_id in subs {
-                    rt_mut.pending_effects.insert(effect_id);
-                }
-            }
+            rt_mut.mark_subscribers(self.id, NodeState::Dirty);
             rt_mut.batch_depth == 0
         });
 
@@ -341,11 +561,7 @@ pub fn set_signal_untyped<T: 'static>(id: SignalId, value: T) {
     let should_process = RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
         rt_mut.signals.insert(id, Box::new(value));
-        if let Some(subs) = rt_mut.signal_subscribers.get(&id).cloned() {
-            for effect_id in subs {
-                rt_mut.pending_effects.insert(effect_id);
-            }
-        }
+        rt_mut.mark_subscribers(id, NodeState::Dirty);
         rt_mut.batch_depth == 0
     });
 
@@ -355,15 +571,10 @@ pub fn set_signal_untyped<T: 'static>(id: SignalId, value: T) {
 }
 
 pub fn get_signal_untyped<T: 'static + Clone>(id: SignalId) -> Option<T> {
+    refresh_signal(id);
     RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
-        if let Some(effect_id) = rt_mut.active_effect {
-            rt_mut
-                .signal_subscribers
-                .entry(id)
-                .or_default()
-                .insert(effect_id);
-        }
+        rt_mut.track(id);
         rt_mut
             .signals
             .get(&id)
@@ -377,17 +588,171 @@ where
     U: 'static + Clone + PartialEq,
     F: FnMut(&T) -> U + 'static,
 {
-    let initial = getter(&signal.get_untracked());
-    let derived = Signal::new(initial);
+    create_computed(move || getter(&signal.get()))
+}
 
-    create_effect(move || {
-        let new_val = getter(&signal.get());
-        if new_val != derived.get_untracked() {
-            derived.set(new_val);
+/// Lazy hisoblanuvchi signal (Memo poydevori). Qiymat faqat o'qilganda hisoblanadi,
+/// manbalar o'zgarsa ham hech kim o'qimaguncha qayta hisoblanmaydi.
+pub fn create_computed<T, F>(mut f: F) -> Signal<T>
+where
+    T: 'static + Clone + PartialEq,
+    F: FnMut() -> T + 'static,
+{
+    let (memo_id, signal_id) = RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        let memo_id = EffectId(rt_mut.generate_id());
+        let signal_id = SignalId(rt_mut.generate_id());
+
+        rt_mut.node_states.insert(memo_id, NodeState::Dirty);
+        rt_mut.memo_signals.insert(memo_id, signal_id);
+        rt_mut.memo_owners.insert(signal_id, memo_id);
+
+        if let Some(scope_id) = rt_mut.active_scope {
+            rt_mut.memo_scopes.insert(memo_id, scope_id);
+            rt_mut
+                .scope_effects
+                .entry(scope_id)
+                .or_default()
+                .insert(memo_id);
+            rt_mut
+                .scope_signals
+                .entry(scope_id)
+                .or_default()
+                .insert(signal_id);
         }
+        (memo_id, signal_id)
     });
 
-    derived
+    // Yangi qiymatni yozadi va haqiqatan o'zgardimi yoki yo'qligini qaytaradi
+    let compute = move || -> bool {
+        let new_val = f();
+        RUNTIME.with(|rt| {
+            let mut rt_mut = rt.borrow_mut();
+            let changed = match rt_mut
+                .signals
+                .get(&signal_id)
+                .and_then(|v| v.downcast_ref::<T>())
+            {
+                Some(old) => *old != new_val,
+                None => true,
+            };
+            if changed {
+                rt_mut.signals.insert(signal_id, Box::new(new_val));
+            }
+            changed
+        })
+    };
+
+    RUNTIME.with(|rt| {
+        rt.borrow_mut().memo_fns.insert(memo_id, Box::new(compute));
+    });
+
+    Signal {
+        id: signal_id,
+        _marker: PhantomData,
+    }
+}
+
+// Agar signal Memo natijasi bo'lsa, o'qishdan oldin uni yangilaymiz (Pull)
+fn refresh_signal(id: SignalId) {
+    let memo_id = RUNTIME.with(|rt| rt.borrow().memo_owners.get(&id).copied());
+    if let Some(memo_id) = memo_id {
+        refresh_memo(memo_id);
+    }
+}
+
+fn refresh_memo(memo_id: EffectId) {
+    let state = RUNTIME.with(|rt| rt.borrow().node_state(memo_id));
+    match state {
+        NodeState::Clean => {}
+        NodeState::Check => {
+            resolve_check(memo_id);
+            let state = RUNTIME.with(|rt| rt.borrow().node_state(memo_id));
+            if state == NodeState::Dirty {
+                run_memo(memo_id);
+            }
+        }
+        NodeState::Dirty => run_memo(memo_id),
+    }
+}
+
+// Manba memolarni yangilaymiz: birortasi o'zgarsa tugun Dirty bo'ladi, aks holda Clean
+fn resolve_check(id: EffectId) {
+    let upstream = RUNTIME.with(|rt| rt.borrow().upstream_memos(id));
+    for memo_id in upstream {
+        refresh_memo(memo_id);
+        if RUNTIME.with(|rt| rt.borrow().node_state(id)) == NodeState::Dirty {
+            return;
+        }
+    }
+    RUNTIME.with(|rt| {
+        rt.borrow_mut().node_states.insert(id, NodeState::Clean);
+    });
+}
+
+fn run_memo(memo_id: EffectId) {
+    let (compute, prev_effect, prev_scope) = RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        let compute = rt_mut.memo_fns.remove(&memo_id);
+        if compute.is_some() {
+            rt_mut.clear_sources(memo_id);
+            rt_mut.node_states.insert(memo_id, NodeState::Clean);
+        }
+        let prev = rt_mut.active_effect;
+        rt_mut.active_effect = Some(memo_id);
+        let owner = rt_mut.memo_scopes.get(&memo_id).copied();
+        let prev_scope = std::mem::replace(&mut rt_mut.active_scope, owner);
+        (compute, prev, prev_scope)
+    });
+
+    let Some(mut compute) = compute else {
+        RUNTIME.with(|rt| {
+            let mut rt_mut = rt.borrow_mut();
+            rt_mut.active_effect = prev_effect;
+            rt_mut.active_scope = prev_scope;
+        });
+        return;
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(&mut compute));
+
+    RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        rt_mut.active_effect = prev_effect;
+        rt_mut.active_scope = prev_scope;
+        // Memo hisoblash paytida o'chirilgan bo'lishi mumkin
+        if rt_mut.memo_signals.contains_key(&memo_id) {
+            rt_mut.memo_fns.insert(memo_id, compute);
+        }
+    });
+
+    match result {
+        Ok(true) => RUNTIME.with(|rt| {
+            let mut rt_mut = rt.borrow_mut();
+            let out = rt_mut.memo_signals.get(&memo_id).copied();
+            if let Some(out) = out {
+                // Kuzatuvchilar allaqachon Check holatida, endi ular aniq Dirty
+                if let Some(subs) = rt_mut.signal_subscribers.get(&out).cloned() {
+                    for sub in subs {
+                        if rt_mut.node_state(sub) != NodeState::Clean {
+                            rt_mut.node_states.insert(sub, NodeState::Dirty);
+                        }
+                    }
+                }
+            }
+        }),
+        Ok(false) => {}
+        Err(payload) => {
+            // Eski qiymat yangi emas: keyingi o'qishda qayta hisoblanadi
+            RUNTIME.with(|rt| {
+                let mut rt_mut = rt.borrow_mut();
+                if rt_mut.memo_signals.contains_key(&memo_id) {
+                    rt_mut.node_states.insert(memo_id, NodeState::Dirty);
+                }
+            });
+            resume_unwind(payload)
+        }
+    }
 }
 
 pub fn create_effect<F: FnMut() + 'static>(mut f: F) -> EffectId {
@@ -400,7 +765,7 @@ pub fn create_effect<F: FnMut() + 'static>(mut f: F) -> EffectId {
         (new_id, prev, scope)
     });
 
-    let _ = catch_unwind(AssertUnwindSafe(|| {
+    let result = catch_unwind(AssertUnwindSafe(|| {
         f();
     }));
 
@@ -412,8 +777,12 @@ pub fn create_effect<F: FnMut() + 'static>(mut f: F) -> EffectId {
         if let Some(scope_id) = active_scope {
             rt_mut.scope_effects.entry(scope_id).or_default().insert(id);
         }
-        id
-    })
+    });
+
+    if let Err(payload) = result {
+        report_error(active_scope, &*payload);
+    }
+    id
 }
 
 pub fn create_ticker<F: FnMut(f32) + 'static>(f: F) -> u64 {
@@ -445,10 +814,14 @@ pub fn tick_all(dt: f32) {
             std::mem::take(&mut rt_mut.tickers)
         };
 
-        for (_, (_, ref mut f)) in tickers.iter_mut() {
-            let _ = catch_unwind(AssertUnwindSafe(|| {
-                f(dt);
+        for (_, (scope, ref mut f)) in tickers.iter_mut() {
+            let owner = *scope;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                run_in_scope(owner, || f(dt));
             }));
+            if let Err(payload) = result {
+                report_error(owner, &*payload);
+            }
         }
 
         let mut rt_mut = rt.borrow_mut();
@@ -469,69 +842,86 @@ pub fn process_pending_effects() {
         rt.borrow_mut().batch_depth += 1;
     });
 
-    RUNTIME.with(|rt| {
-        let mut pending = {
+    loop {
+        // INQILOB: Effectlar balandlik bo'yicha (manbadan uzoqlashib) ishga tushadi.
+        // Shunda diamond bog'lanishda effect oraliq qiymatlarni ko'rmaydi.
+        let pending = RUNTIME.with(|rt| {
             let mut rt_mut = rt.borrow_mut();
-            std::mem::take(&mut rt_mut.pending_effects)
-        };
+            let mut pending: Vec<EffectId> = std::mem::take(&mut rt_mut.pending_effects)
+                .into_iter()
+                .collect();
+            pending.sort_by_key(|id| (rt_mut.height_of(*id), id.0));
+            pending
+        });
 
-        while !pending.is_empty() {
-            for effect_id in pending.drain() {
-                let effect_data = {
-                    let mut rt_inner = rt.borrow_mut();
-                    rt_inner.effects.remove(&effect_id)
-                };
-
-                if let Some((owner_scope, mut f)) = effect_data {
-                    let prev = {
-                        let mut rt_inner = rt.borrow_mut();
-                        let p = rt_inner.active_effect;
-                        rt_inner.active_effect = Some(effect_id);
-                        p
-                    };
-
-                    let result = catch_unwind(AssertUnwindSafe(|| {
-                        f();
-                    }));
-
-                    let mut rt_inner = rt.borrow_mut();
-                    rt_inner.active_effect = prev;
-                    rt_inner.effects.insert(effect_id, (owner_scope, f));
-
-                    if let Err(payload) = result {
-                        if let Some(scope_id) = owner_scope {
-                            if let Some(mut handlers) = rt_inner.error_handlers.remove(&scope_id) {
-                                for handler in &mut handlers {
-                                    handler(&*payload);
-                                }
-                                rt_inner.error_handlers.insert(scope_id, handlers);
-                            }
-                        }
-                    }
-                }
-            }
+        if pending.is_empty() {
+            break;
+        }
 
-            pending = {
-                let mut rt_inner = rt.borrow_mut();
-                std::mem::take(&mut rt_inner.pending_effects)
-            };
+        for effect_id in pending {
+            run_effect(effect_id);
         }
-    });
+    }
 
     RUNTIME.with(|rt| {
         rt.borrow_mut().batch_depth -= 1;
     });
 }
 
+fn run_effect(effect_id: EffectId) {
+    if RUNTIME.with(|rt| rt.borrow().node_state(effect_id)) == NodeState::Check {
+        resolve_check(effect_id);
+    }
+
+    let effect_data = RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        if rt_mut.node_state(effect_id) != NodeState::Dirty {
+            rt_mut.node_states.remove(&effect_id);
+            return None;
+        }
+        // Ishga tushishdan OLDIN tozalaymiz: effect o'zi yozgan signal uni qayta belgilay oladi
+        rt_mut.node_states.remove(&effect_id);
+        let data = rt_mut.effects.remove(&effect_id);
+        if data.is_some() {
+            rt_mut.clear_sources(effect_id);
+        }
+        data
+    });
+
+    let Some((owner_scope, mut f)) = effect_data else {
+        return;
+    };
+
+    // Effect o'zi yaratilgan Scope ichida ishlaydi (use_context to'g'ri ishlashi uchun)
+    let (prev, prev_scope) = RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        let p = rt_mut.active_effect;
+        rt_mut.active_effect = Some(effect_id);
+        let prev_scope = std::mem::replace(&mut rt_mut.active_scope, owner_scope);
+        (p, prev_scope)
+    });
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        f();
+    }));
+
+    RUNTIME.with(|rt| {
+        let mut rt_mut = rt.borrow_mut();
+        rt_mut.active_effect = prev;
+        rt_mut.active_scope = prev_scope;
+        rt_mut.effects.insert(effect_id, (owner_scope, f));
+    });
+
+    if let Err(payload) = result {
+        report_error(owner_scope, &*payload);
+    }
+}
+
 pub fn set_signal_any(id: SignalId, value: Box<dyn Any + Send>) {
     let should_process = RUNTIME.with(|rt| {
         let mut rt_mut = rt.borrow_mut();
         rt_mut.signals.insert(id, value);
-        if let Some(subs) = rt_mut.signal_subscribers.get(&id).cloned() {
-            for effect_id in subs {
-                rt_mut.pending_effects.insert(effect_id);
-            }
-        }
+        rt_mut.mark_subscribers(id, NodeState::Dirty);
         rt_mut.batch_depth == 0
     });
 
@@ -539,3 +929,231 @@ pub fn set_signal_any(id: SignalId, value: Box<dyn Any + Send>) {
         process_pending_effects();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::rc::Rc;
+
+    fn counter() -> Rc<RefCell<u32>> {
+        Rc::new(RefCell::new(0))
+    }
+
+    #[test]
+    fn diamond_runs_effect_once_without_glitch() {
+        let (scope, _) = create_scope(|| {
+            let a = Signal::new(1);
+            let b = create_computed(move || a.get() * 2);
+            let c = create_computed(move || a.get() + 10);
+
+            let runs = counter();
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            let (r, s) = (runs.clone(), seen.clone());
+            create_effect(move || {
+                *r.borrow_mut() += 1;
+                s.borrow_mut().push((b.get(), c.get()));
+            });
+
+            a.set(5);
+            assert_eq!(*runs.borrow(), 2);
+            assert_eq!(*seen.borrow(), vec![(2, 11), (10, 15)]);
+        });
+        dispose_scope(scope);
+    }
+
+    #[test]
+    fn deep_chain_propagates_in_one_pass() {
+        let (scope, _) = create_scope(|| {
+            let source = Signal::new(0i64);
+            let mut last = create_computed(move || source.get());
+            for _ in 0..200 {
+                let prev = last;
+                last = create_computed(move || prev.get() + 1);
+            }
+
+            let runs = counter();
+            let r = runs.clone();
+            let out = Rc::new(RefCell::new(0));
+            let o = out.clone();
+            create_effect(move || {
+                *r.borrow_mut() += 1;
+                *o.borrow_mut() = last.get();
+            });
+
+            source.set(7);
+            assert_eq!(*runs.borrow(), 2);
+            assert_eq!(*out.borrow(), 207);
+        });
+        dispose_scope(scope);
+    }
+
+    #[test]
+    fn unread_memo_stays_lazy() {
+        let (scope, _) = create_scope(|| {
+            let source = Signal::new(1);
+            let calls = counter();
+            let c = calls.clone();
+            let memo = create_computed(move || {
+                *c.borrow_mut() += 1;
+                source.get() * 3
+            });
+
+            for i in 0..10 {
+                source.set(i);
+            }
+            assert_eq!(*calls.borrow(), 0);
+            assert_eq!(memo.get(), 27);
+            assert_eq!(memo.get(), 27);
+            assert_eq!(*calls.borrow(), 1);
+        });
+        dispose_scope(scope);
+    }
+
+    #[test]
+    fn memo_recovers_after_panic() {
+        let (scope, _) = create_scope(|| {
+            let source = Signal::new(0);
+            let memo = create_computed(move || {
+                let v = source.get();
+                assert_ne!(v, 1, "bir bo'lmasin");
+                v * 10
+            });
+            assert_eq!(memo.get(), 0);
+
+            source.set(1);
+            assert!(catch_unwind(AssertUnwindSafe(|| memo.get())).is_err());
+            // Eski 0 yangi qiymatdek qaytmaydi
+            assert!(catch_unwind(AssertUnwindSafe(|| memo.get())).is_err());
+            source.set(2);
+            assert_eq!(memo.get(), 20);
+
+            // Hech narsa o'qimay yiqilgan memo ham keyingi o'qishda qayta hisoblanadi
+            let broken = Rc::new(RefCell::new(true));
+            let b = broken.clone();
+            let early = create_computed(move || {
+                assert!(!*b.borrow(), "hali tayyor emas");
+                source.get()
+            });
+            assert!(catch_unwind(AssertUnwindSafe(|| early.get())).is_err());
+            *broken.borrow_mut() = false;
+            assert_eq!(early.get(), 2);
+        });
+        dispose_scope(scope);
+    }
+
+    #[test]
+    fn context_is_scoped_to_subtree() {
+        provide_context(0u32);
+        let (outer, _) = create_scope(|| {
+            provide_context(1u32);
+            let (inner, _) = create_scope(|| {
+                assert_eq!(use_context::<u32>(), Some(1));
+                provide_context(2u32);
+                assert_eq!(use_context::<u32>(), Some(2));
+
+                // Effect qayta ishlaganda ham o'z Scope kontekstini ko'radi
+                let trigger = Signal::new(0);
+                let seen = Rc::new(RefCell::new(None));
+                let s = seen.clone();
+                create_effect(move || {
+                    trigger.get();
+                    *s.borrow_mut() = use_context::<u32>();
+                });
+                run_in_scope(None, || trigger.set(1));
+                assert_eq!(*seen.borrow(), Some(2));
+            });
+            let (sibling, _) = create_scope(|| {
+                assert_eq!(use_context::<u32>(), Some(1));
+            });
+            dispose_scope(inner);
+            dispose_scope(sibling);
+            assert_eq!(use_context::<u32>(), Some(1));
+        });
+        dispose_scope(outer);
+        assert_eq!(use_context::<u32>(), Some(0));
+        run_in_scope(Some(outer), || assert_eq!(use_context::<u32>(), Some(0)));
+    }
+
+    #[test]
+    fn effect_panic_reaches_ancestor_handler() {
+        let (outer, _) = create_scope(|| {
+            let caught = Rc::new(RefCell::new(None));
+            let c = caught.clone();
+            catch_error(move |payload| {
+                *c.borrow_mut() = Some(panic_message(payload));
+            });
+
+            let trigger = Signal::new(0);
+            let (inner, _) = create_scope(|| {
+                create_effect(move || {
+                    if trigger.get() > 0 {
+                        panic!("chart plugin");
+                    }
+                });
+            });
+
+            trigger.set(1);
+            assert_eq!(caught.borrow().as_deref(), Some("chart plugin"));
+            dispose_scope(inner);
+        });
+        dispose_scope(outer);
+    }
+
+    // Tasodifiy DAG: har bir memo manbalar va oldingi memolar yig'indisi
+    fn expected(sources: &[i64], nodes: &[Vec<usize>]) -> Vec<i64> {
+        let mut values: Vec<i64> = sources.to_vec();
+        for deps in nodes {
+            let v = deps.iter().map(|d| values[*d]).sum();
+            values.push(v);
+        }
+        values[sources.len()..].to_vec()
+    }
+
+    proptest! {
+        #[test]
+        fn random_graph_is_glitch_free(
+            source_count in 1usize..4,
+            raw_nodes in prop::collection::vec(prop::collection::vec(any::<prop::sample::Index>(), 1..4), 1..12),
+            writes in prop::collection::vec((any::<prop::sample::Index>(), -50i64..50), 1..20),
+        ) {
+            let nodes: Vec<Vec<usize>> = raw_nodes
+                .iter()
+                .enumerate()
+                .map(|(i, deps)| deps.iter().map(|d| d.index(source_count + i)).collect())
+                .collect();
+
+            let (scope, scope_result) = create_scope(|| {
+                let sources: Vec<Signal<i64>> = (0..source_count).map(|_| Signal::new(0)).collect();
+                let mut all: Vec<Signal<i64>> = sources.clone();
+                for deps in &nodes {
+                    let inputs: Vec<Signal<i64>> = deps.iter().map(|d| all[*d]).collect();
+                    all.push(create_computed(move || inputs.iter().map(|s| s.get()).sum()));
+                }
+                let memos: Vec<Signal<i64>> = all[source_count..].to_vec();
+
+                let runs = counter();
+                let seen = Rc::new(RefCell::new(Vec::new()));
+                let (r, s) = (runs.clone(), seen.clone());
+                create_effect(move || {
+                    *r.borrow_mut() += 1;
+                    *s.borrow_mut() = memos.iter().map(|m| m.get()).collect::<Vec<_>>();
+                });
+
+                let mut values = vec![0i64; source_count];
+                for (idx, value) in &writes {
+                    let i = idx.index(source_count);
+                    values[i] = *value;
+
+                    let before = *runs.borrow();
+                    sources[i].set(*value);
+                    prop_assert!(*runs.borrow() - before <= 1);
+                    prop_assert_eq!(&*seen.borrow(), &expected(&values, &nodes));
+                }
+                Ok(())
+            });
+            dispose_scope(scope);
+            scope_result?;
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::app::RoreUserEvent;
+use crate::window::wake_event_loop;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// Custom shader yig'ilmadi (WGSL sintaksis/validatsiya xatosi).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderError {
+    pub shader_id: String,
+    // naga izohi: qator, ustun va xato sababi
+    pub message: String,
+}
+
+type ErrorHook = Box<dyn Fn(&ShaderError) + Send>;
+
+static ERROR_HOOK: OnceLock<Mutex<Option<ErrorHook>>> = OnceLock::new();
+static WATCHED: OnceLock<Mutex<HashMap<String, Watched>>> = OnceLock::new();
+static WATCHER: Once = Once::new();
+
+struct Watched {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+fn error_hook() -> &'static Mutex<Option<ErrorHook>> {
+    ERROR_HOOK.get_or_init(|| Mutex::new(None))
+}
+
+fn watched() -> &'static Mutex<HashMap<String, Watched>> {
+    WATCHED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Shader xatolarini ilova kodiga yo'naltiradi (masalan, ekranda ko'rsatish uchun).
+/// Hook Render Thread'da chaqiriladi. O'rnatilmagan bo'lsa xato stderr'ga yoziladi.
+/// Xato bo'lgan shader chizilmaydi; hot reload'da esa oldingi ishlaydigan versiya qoladi.
+pub fn on_shader_error<F>(hook: F)
+where
+    F: Fn(&ShaderError) + Send + 'static,
+{
+    *error_hook().lock().unwrap() = Some(Box::new(hook));
+}
+
+pub(crate) fn report(shader_id: &str, message: String) {
+    let error = ShaderError {
+        shader_id: shader_id.to_string(),
+        message,
+    };
+    match &*error_hook().lock().unwrap() {
+        Some(hook) => hook(&error),
+        None => eprintln!(
+            "Rore: '{}' shader xatosi:\n{}",
+            error.shader_id, error.message
+        ),
+    }
+}
+
+/// `.wgsl` faylini kuzatadi: saqlanganda shader barcha oynalarda qayta yig'iladi.
+/// Faqat debug build'da ishlaydi (`ShaderBox::from_file` o'zi chaqiradi).
+pub fn watch(shader_id: &str, path: impl Into<PathBuf>) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let path = path.into();
+    let modified = modified(&path);
+    watched()
+        .lock()
+        .unwrap()
+        .insert(shader_id.to_string(), Watched { path, modified });
+
+    WATCHER.call_once(|| {
+        std::thread::spawn(|| loop {
+            std::thread::sleep(Duration::from_millis(250));
+            for (id, wgsl) in poll_changes() {
+                wake_event_loop(RoreUserEvent::ReloadShader(id, wgsl));
+            }
+        });
+    });
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// O'zgargan fayllarning yangi matni (o'qib bo'lmasa keyingi safar qayta uriniladi)
+fn poll_changes() -> Vec<(String, String)> {
+    let mut changed = Vec::new();
+    for (id, entry) in watched().lock().unwrap().iter_mut() {
+        let modified = modified(&entry.path);
+        if modified == entry.modified {
+            continue;
+        }
+        if let Ok(wgsl) = std::fs::read_to_string(&entry.path) {
+            entry.modified = modified;
+            changed.push((id.clone(), wgsl));
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_changes_picks_up_saved_file_once() {
+        let path = std::env::temp_dir().join(format!("rore_watch_{}.wgsl", std::process::id()));
+        std::fs::write(&path, "// v1").unwrap();
+        watched().lock().unwrap().insert(
+            "watch_test".into(),
+            Watched {
+                path: path.clone(),
+                modified: modified(&path),
+            },
+        );
+        assert!(poll_changes().iter().all(|(id, _)| id != "watch_test"));
+
+        std::fs::write(&path, "// v2").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        let changes = poll_changes();
+        assert!(changes.contains(&("watch_test".to_string(), "// v2".to_string())));
+        assert!(poll_changes().iter().all(|(id, _)| id != "watch_test"));
+
+        watched().lock().unwrap().remove("watch_test");
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,577 @@
+use crate::state::FrameworkState;
+use crate::window::WindowId;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// Bitta tugma bosilishi: modifikatorlar + asosiy tugma ("Ctrl+Shift+K").
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub modifiers: ModifiersState,
+    pub key: ChordKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChordKey {
+    // Doim kichik harfda
+    Char(String),
+    Named(NamedKey),
+}
+
+/// Ketma-ket bosiladigan chord'lar: "Ctrl+K Ctrl+S".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutError {
+    Parse(String),
+    // existing: shu tugmalarni allaqachon egallagan buyruq
+    Conflict {
+        keys: String,
+        command: String,
+        existing: String,
+    },
+    Config(String),
+}
+
+impl fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutError::Parse(s) => write!(f, "noto'g'ri tugma birikmasi: {:?}", s),
+            ShortcutError::Conflict {
+                keys,
+                command,
+                existing,
+            } => write!(
+                f,
+                "{} uchun {:?} band: {:?} ga biriktirilgan",
+                command, keys, existing
+            ),
+            ShortcutError::Config(s) => write!(f, "tugmalar fayli: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutError {}
+
+/// Qaysi holatda binding ishlaydi. Aniqrog'i ustun: Focused > Window > Global.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShortcutScope {
+    Global,
+    Window(WindowId),
+    // Fokus shu id li vidjet ichida bo'lganda (masalan, "editor")
+    Focused(String),
+}
+
+// "Mod": macOS da Cmd, boshqalarda Ctrl
+fn platform_mod() -> ModifiersState {
+    if cfg!(target_os = "macos") {
+        ModifiersState::SUPER
+    } else {
+        ModifiersState::CONTROL
+    }
+}
+
+fn named_key(name: &str) -> Option<NamedKey> {
+    let key = match name.to_lowercase().as_str() {
+        "enter" | "return" => NamedKey::Enter,
+        "escape" | "esc" => NamedKey::Escape,
+        "tab" => NamedKey::Tab,
+        "space" => NamedKey::Space,
+        "backspace" => NamedKey::Backspace,
+        "delete" | "del" => NamedKey::Delete,
+        "insert" | "ins" => NamedKey::Insert,
+        "home" => NamedKey::Home,
+        "end" => NamedKey::End,
+        "pageup" => NamedKey::PageUp,
+        "pagedown" => NamedKey::PageDown,
+        "up" | "arrowup" => NamedKey::ArrowUp,
+        "down" | "arrowdown" => NamedKey::ArrowDown,
+        "left" | "arrowleft" => NamedKey::ArrowLeft,
+        "right" | "arrowright" => NamedKey::ArrowRight,
+        "f1" => NamedKey::F1,
+        "f2" => NamedKey::F2,
+        "f3" => NamedKey::F3,
+        "f4" => NamedKey::F4,
+        "f5" => NamedKey::F5,
+        "f6" => NamedKey::F6,
+        "f7" => NamedKey::F7,
+        "f8" => NamedKey::F8,
+        "f9" => NamedKey::F9,
+        "f10" => NamedKey::F10,
+        "f11" => NamedKey::F11,
+        "f12" => NamedKey::F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+impl KeyChord {
+    pub fn parse(s: &str) -> Result<Self, ShortcutError> {
+        let err = || ShortcutError::Parse(s.to_string());
+        let mut parts: Vec<&str> = s.trim().split('+').collect();
+        // "Ctrl++" -> ["Ctrl", "", ""]: asosiy tugma '+'
+        let key_part = if parts.len() >= 2 && parts[parts.len() - 1].is_empty() {
+            parts.truncate(parts.len() - 2);
+            "+"
+        } else {
+            parts.pop().ok_or_else(err)?
+        };
+
+        let mut modifiers = ModifiersState::empty();
+        for part in parts {
+            modifiers |= match part.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" | "option" => ModifiersState::ALT,
+                "cmd" | "super" | "meta" | "win" => ModifiersState::SUPER,
+                "mod" | "cmdorctrl" => platform_mod(),
+                _ => return Err(err()),
+            };
+        }
+
+        let key_part = key_part.trim();
+        let key = if let Some(named) = named_key(key_part) {
+            ChordKey::Named(named)
+        } else if key_part.chars().count() == 1 {
+            ChordKey::Char(key_part.to_lowercase())
+        } else {
+            return Err(err());
+        };
+        Ok(Self { modifiers, key })
+    }
+
+    /// Modifikator tugmalarining o'zi (Ctrl, Shift...) chord bo'lmaydi.
+    pub fn from_key(key: &Key, modifiers: ModifiersState) -> Option<Self> {
+        let key = match key {
+            Key::Character(c) => ChordKey::Char(c.to_lowercase()),
+            Key::Named(
+                NamedKey::Control
+                | NamedKey::Shift
+                | NamedKey::Alt
+                | NamedKey::Super
+                | NamedKey::Meta
+                | NamedKey::AltGraph,
+            ) => return None,
+            Key::Named(named) => ChordKey::Named(*named),
+            _ => return None,
+        };
+        Some(Self { modifiers, key })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let m = self.modifiers;
+        if m.control_key() {
+            f.write_str("Ctrl+")?;
+        }
+        if m.alt_key() {
+            f.write_str(if cfg!(target_os = "macos") {
+                "Option+"
+            } else {
+                "Alt+"
+            })?;
+        }
+        if m.shift_key() {
+            f.write_str("Shift+")?;
+        }
+        if m.super_key() {
+            f.write_str(if cfg!(target_os = "macos") {
+                "Cmd+"
+            } else {
+                "Super+"
+            })?;
+        }
+        match &self.key {
+            ChordKey::Char(c) => f.write_str(&c.to_uppercase()),
+            ChordKey::Named(NamedKey::Escape) => f.write_str("Esc"),
+            ChordKey::Named(named) => write!(f, "{:?}", named),
+        }
+    }
+}
+
+impl KeySequence {
+    pub fn parse(s: &str) -> Result<Self, ShortcutError> {
+        let chords = s
+            .split_whitespace()
+            .map(KeyChord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(ShortcutError::Parse(s.to_string()));
+        }
+        Ok(Self(chords))
+    }
+
+    fn starts_with(&self, prefix: &[KeyChord]) -> bool {
+        self.0.starts_with(prefix)
+    }
+
+    // Biri ikkinchisining boshlanishi bo'lsa, qisqasi hech qachon uzunini kutmaydi
+    fn overlaps(&self, other: &KeySequence) -> bool {
+        self.0.starts_with(&other.0) || other.0.starts_with(&self.0)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+type ShortcutHandler = Box<dyn FnMut(&mut FrameworkState) + Send>;
+
+struct Binding {
+    keys: KeySequence,
+    command: String,
+    scope: ShortcutScope,
+    // Foydalanuvchi faylidan kelgan: standart binding bilan to'qnashsa, u yutadi
+    user: bool,
+}
+
+#[derive(Default)]
+struct Registry {
+    // None: handler hozir ishlayapti (qulfni ushlamaslik uchun vaqtincha olingan)
+    commands: HashMap<String, Option<ShortcutHandler>>,
+    bindings: Vec<Binding>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Registry::default()))
+}
+
+impl Registry {
+    fn bind(
+        &mut self,
+        keys: KeySequence,
+        command: &str,
+        scope: ShortcutScope,
+        user: bool,
+    ) -> Result<(), ShortcutError> {
+        let mut replaced = Vec::new();
+        for (i, b) in self.bindings.iter().enumerate() {
+            if b.scope != scope || !b.keys.overlaps(&keys) {
+                continue;
+            }
+            if b.command == command && b.keys == keys {
+                return Ok(());
+            }
+            if user && !b.user {
+                replaced.push(i);
+                continue;
+            }
+            return Err(ShortcutError::Conflict {
+                keys: keys.to_string(),
+                command: command.to_string(),
+                existing: b.command.clone(),
+            });
+        }
+        for i in replaced.into_iter().rev() {
+            self.bindings.remove(i);
+        }
+        self.bindings.push(Binding {
+            keys,
+            command: command.to_string(),
+            scope,
+            user,
+        });
+        Ok(())
+    }
+}
+
+/// Nomli buyruqlar va ularga biriktirilgan tugmalar. Hamma oynalar uchun bitta.
+///
+/// ```ignore
+/// Shortcuts::register_command("file.save_all", |state| { ... });
+/// Shortcuts::bind("Ctrl+K Ctrl+S", "file.save_all", ShortcutScope::Global)?;
+/// ```
+pub struct Shortcuts;
+
+impl Shortcuts {
+    /// Handler Logic Thread'da ishlaydi. Shu nomli oldingi handler almashtiriladi.
+    pub fn register_command<F>(name: &str, handler: F)
+    where
+        F: FnMut(&mut FrameworkState) + Send + 'static,
+    {
+        registry()
+            .lock()
+            .unwrap()
+            .commands
+            .insert(name.to_string(), Some(Box::new(handler)));
+    }
+
+    /// Shu scope'da tugmalar (yoki ularning boshlanishi) band bo'lsa, `Conflict` qaytadi.
+    pub fn bind(keys: &str, command: &str, scope: ShortcutScope) -> Result<(), ShortcutError> {
+        let keys = KeySequence::parse(keys)?;
+        registry().lock().unwrap().bind(keys, command, scope, false)
+    }
+
+    pub fn unbind(command: &str) {
+        registry()
+            .lock()
+            .unwrap()
+            .bindings
+            .retain(|b| b.command != command);
+    }
+
+    pub fn bindings_for(command: &str) -> Vec<KeySequence> {
+        registry()
+            .lock()
+            .unwrap()
+            .bindings
+            .iter()
+            .filter(|b| b.command == command)
+            .map(|b| b.keys.clone())
+            .collect()
+    }
+
+    /// Menyuda ko'rsatish uchun: "Ctrl+K Ctrl+S"
+    pub fn label(command: &str) -> Option<String> {
+        Self::bindings_for(command).first().map(|k| k.to_string())
+    }
+
+    /// Buyruqni nomi bo'yicha bajaradi (menyu, buyruqlar palitrasi). false: bunday buyruq yo'q.
+    pub fn run(command: &str, state: &mut FrameworkState) -> bool {
+        // Handler ichida yangi buyruq yoki binding qo'shilishi mumkin, shuning uchun qulfni ushlab turmaymiz
+        let handler = registry()
+            .lock()
+            .unwrap()
+            .commands
+            .get_mut(command)
+            .and_then(Option::take);
+        let Some(mut handler) = handler else {
+            return false;
+        };
+        handler(state);
+        if let Some(slot) = registry().lock().unwrap().commands.get_mut(command) {
+            slot.get_or_insert(handler);
+        }
+        true
+    }
+
+    /// Foydalanuvchi sozlamalari: `{"file.save": "Ctrl+S", "view.split": ["Ctrl+\\", "F6"], "app.quit": null}`.
+    /// Buyruqning eski tugmalari o'chadi, scope'i saqlanadi. Yaroqsiz yozuvlar o'tkazib yuboriladi
+    /// va xato sifatida qaytariladi.
+    pub fn apply_user_bindings(json: &str) -> Vec<ShortcutError> {
+        let map = match serde_json::from_str::<Value>(json) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => return vec![ShortcutError::Config("obyekt kutilgan".into())],
+            Err(e) => return vec![ShortcutError::Config(e.to_string())],
+        };
+
+        let mut errors = Vec::new();
+        let mut reg = registry().lock().unwrap();
+        for (command, value) in map {
+            let keys: Vec<&str> = match &value {
+                Value::Null => Vec::new(),
+                Value::String(s) => vec![s.as_str()],
+                Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+                _ => {
+                    errors.push(ShortcutError::Config(format!(
+                        "{}: noto'g'ri qiymat",
+                        command
+                    )));
+                    continue;
+                }
+            };
+
+            let scope = reg
+                .bindings
+                .iter()
+                .find(|b| b.command == command)
+                .map(|b| b.scope.clone())
+                .unwrap_or(ShortcutScope::Global);
+            reg.bindings.retain(|b| b.command != command);
+
+            for keys in keys {
+                let result = KeySequence::parse(keys)
+                    .and_then(|seq| reg.bind(seq, &command, scope.clone(), true));
+                if let Err(e) = result {
+                    errors.push(e);
+                }
+            }
+        }
+        errors
+    }
+
+    /// Fayl yo'q bo'lsa, standart tugmalar qoladi (xato emas).
+    pub fn load_user_bindings(path: impl AsRef<Path>) -> Vec<ShortcutError> {
+        match std::fs::read_to_string(path.as_ref()) {
+            Ok(text) => Self::apply_user_bindings(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => vec![ShortcutError::Config(e.to_string())],
+        }
+    }
+
+    /// Odatiy joy: `<config_dir>/<app_name>/keybindings.json`
+    pub fn user_bindings_path(app_name: &str) -> PathBuf {
+        let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        base.join(app_name).join("keybindings.json")
+    }
+}
+
+/// Buyruqni `CommandQueue` orqali nomi bilan chaqirish (istalgan oqimdan).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunCommand(pub String);
+
+impl FrameworkState {
+    // Scope hozir faolmi va qanchalik aniq (kichik = aniqroq)
+    fn scope_rank(
+        &self,
+        scope: &ShortcutScope,
+        focus_chain: &[rore_layout::Node],
+    ) -> Option<usize> {
+        match scope {
+            ShortcutScope::Focused(id) => {
+                let node_id = self.arena.dynamic_nodes.get(id)?;
+                let taffy = self.arena.taffy_map.get(node_id)?;
+                focus_chain.iter().position(|n| n == taffy)
+            }
+            ShortcutScope::Window(window) => (*window == self.window_id).then_some(usize::MAX - 1),
+            ShortcutScope::Global => Some(usize::MAX),
+        }
+    }
+
+    /// Vidjetlarga yetib borishidan oldin chaqiriladi. true: tugma iste'mol qilindi
+    /// (buyruq bajarildi yoki chord davom etmoqda).
+    pub fn dispatch_shortcut(&mut self, key: &Key) -> bool {
+        let Some(chord) = KeyChord::from_key(key, self.modifiers) else {
+            return false;
+        };
+        let was_pending = !self.pending_chord.is_empty();
+        let mut sequence = std::mem::take(&mut self.pending_chord);
+        sequence.push(chord);
+
+        let focus_chain = self
+            .focused_node
+            .map(|n| self.get_event_bubble_chain(n))
+            .unwrap_or_default();
+
+        // Eng aniq scope'dagi mos binding hal qiladi
+        let best = {
+            let reg = registry().lock().unwrap();
+            reg.bindings
+                .iter()
+                .filter(|b| b.keys.starts_with(&sequence))
+                .filter_map(|b| Some((self.scope_rank(&b.scope, &focus_chain)?, b)))
+                .min_by_key(|(rank, b)| (*rank, b.keys.0.len()))
+                .map(|(_, b)| (b.keys.0.len() == sequence.len(), b.command.clone()))
+        };
+
+        match best {
+            Some((true, command)) => {
+                if !Shortcuts::run(&command, self) {
+                    eprintln!("Rore: {:?} buyrug'i ro'yxatdan o'tmagan", command);
+                }
+                true
+            }
+            Some((false, _)) => {
+                self.pending_chord = sequence;
+                true
+            }
+            // Tugallanmagan chord'dan keyin noto'g'ri tugma: yutib yuboramiz (matnga tushmasin)
+            None => was_pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::WakeRegistry;
+    use rore_types::RoreConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn state() -> FrameworkState {
+        FrameworkState::new(
+            RoreConfig::default(),
+            Arc::new(Mutex::new(WakeRegistry::new())),
+        )
+    }
+
+    #[test]
+    fn chords_parse_and_print() {
+        let seq = KeySequence::parse("Ctrl+K  ctrl+shift+s").unwrap();
+        assert_eq!(seq.0.len(), 2);
+        assert_eq!(
+            seq.0[1].modifiers,
+            ModifiersState::CONTROL | ModifiersState::SHIFT
+        );
+        assert_eq!(seq.0[1].key, ChordKey::Char("s".into()));
+        assert_eq!(seq.to_string(), "Ctrl+K Ctrl+Shift+S");
+
+        let plus = KeyChord::parse("Ctrl++").unwrap();
+        assert_eq!(plus.key, ChordKey::Char("+".into()));
+        assert_eq!(
+            KeyChord::parse("Alt+F4").unwrap().key,
+            ChordKey::Named(NamedKey::F4)
+        );
+        assert!(KeyChord::parse("Hyper+K").is_err());
+        assert!(KeySequence::parse("   ").is_err());
+    }
+
+    #[test]
+    fn overlapping_bindings_in_same_scope_conflict() {
+        let scope = ShortcutScope::Focused("conflict_test_panel".into());
+        Shortcuts::bind("Alt+F7 Alt+F8", "test.conflict_a", scope.clone()).unwrap();
+        let err = Shortcuts::bind("Alt+F7", "test.conflict_b", scope.clone()).unwrap_err();
+        assert!(
+            matches!(err, ShortcutError::Conflict { existing, .. } if existing == "test.conflict_a")
+        );
+
+        // Boshqa scope'da soya qiladi, to'qnashmaydi
+        Shortcuts::bind("Alt+F7", "test.conflict_b", ShortcutScope::Global).unwrap();
+
+        // Foydalanuvchi fayli standart binding'ni siqib chiqaradi
+        let errors = Shortcuts::apply_user_bindings(r#"{"test.conflict_c": "Alt+F7 Alt+F8"}"#);
+        assert!(errors.is_empty());
+        assert_eq!(
+            Shortcuts::bindings_for("test.conflict_c"),
+            vec![KeySequence::parse("Alt+F7 Alt+F8").unwrap()]
+        );
+        assert!(Shortcuts::bindings_for("test.conflict_b").is_empty());
+        assert_eq!(Shortcuts::bindings_for("test.conflict_a").len(), 1);
+    }
+
+    #[test]
+    fn chord_sequence_runs_command_and_swallows_keys() {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+        Shortcuts::register_command("test.chord", |_| {
+            RUNS.fetch_add(1, Ordering::SeqCst);
+        });
+        Shortcuts::bind("Ctrl+F9 Ctrl+F10", "test.chord", ShortcutScope::Global).unwrap();
+
+        let mut state = state();
+        state.modifiers = ModifiersState::CONTROL;
+        assert!(state.dispatch_shortcut(&Key::Named(NamedKey::F9)));
+        // Modifikatorning o'zi chord'ni uzmaydi
+        assert!(!state.dispatch_shortcut(&Key::Named(NamedKey::Control)));
+        assert!(state.dispatch_shortcut(&Key::Named(NamedKey::F10)));
+        assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+        // Chord'dan keyin noto'g'ri tugma yutiladi, keyingisi esa vidjetga boradi
+        assert!(state.dispatch_shortcut(&Key::Named(NamedKey::F9)));
+        assert!(state.dispatch_shortcut(&Key::Character("q".into())));
+        assert!(!state.dispatch_shortcut(&Key::Character("q".into())));
+        assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+        // Remap: eski tugmalar endi ishlamaydi
+        let errors = Shortcuts::apply_user_bindings(r#"{"test.chord": ["Ctrl+Shift+F9"]}"#);
+        assert!(errors.is_empty());
+        assert!(!state.dispatch_shortcut(&Key::Named(NamedKey::F9)));
+        state.modifiers = ModifiersState::CONTROL | ModifiersState::SHIFT;
+        assert!(state.dispatch_shortcut(&Key::Named(NamedKey::F9)));
+        assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,11 +1,10 @@
-use crate::reactive::command::{CommandQueue, UICommand, COMMAND_RECEIVER};
-use crate::widgets::base::{SpatialHashGrid, Widget};
-use arboard::Clipboard;
-use glam::{Mat4, Vec2};
+use crate::reactive::command::{CommandQueue, UICommand};
+use crate::widgets::base::{EventResult, SpatialHashGrid, Widget, WidgetEvent};
+use glam::{Affine2, Mat4, Vec2};
 use rore_layout::Node as TaffyNode;
 use rore_types::RoreConfig;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, Mutex};
@@ -115,10 +114,22 @@ pub struct UiArena {
     pub widgets: Vec<Option<Box<dyn Widget>>>,
     pub overlays: Vec<TaffyNode>,
     pub anchors: HashMap<TaffyNode, String>,
+    pub overlay_positions: HashMap<TaffyNode, Vec2>,
+    // Ekranga nisbatan tekislash: (0,0) chap-tepa, (0.5,0.5) markaz, (1,1) o'ng-past
+    pub overlay_aligns: HashMap<TaffyNode, Vec2>,
+    // Backdrop'siz overlay'lar: ulardan tashqaridagi bosishlar pastdagi UI ga o'tadi
+    pub passthrough_overlays: HashSet<TaffyNode>,
+    // Modal oynalar: fokus eng oxirgisidan tashqariga chiqmaydi
+    pub focus_traps: Vec<TaffyNode>,
     pub dynamic_nodes: HashMap<String, NodeId>,
     pub node_to_id_str: HashMap<NodeId, String>,
     pub node_scopes: HashMap<NodeId, crate::reactive::signals::ScopeId>,
     pub logical_children: HashMap<TaffyNode, Vec<TaffyNode>>,
+    // Tooltip biriktirilgan tugunlar (hover uchun ular ham hit-test'ga tushadi)
+    pub tooltips: HashMap<TaffyNode, crate::tooltip::TooltipSpec>,
+    // `.opacity()` / `.cache_as_layer()` tugunlari: o'z qatlamiga chiziladi (true = keshlanadi).
+    // Shaffoflik `opacities` da turadi
+    pub group_layers: HashMap<NodeId, bool>,
 }
 
 impl UiArena {
@@ -137,10 +148,16 @@ impl UiArena {
             widgets: Vec::new(),
             overlays: Vec::new(),
             anchors: HashMap::new(),
+            overlay_positions: HashMap::new(),
+            overlay_aligns: HashMap::new(),
+            passthrough_overlays: HashSet::new(),
+            focus_traps: Vec::new(),
             dynamic_nodes: HashMap::new(),
             node_to_id_str: HashMap::new(),
             node_scopes: HashMap::new(),
             logical_children: HashMap::new(),
+            tooltips: HashMap::new(),
+            group_layers: HashMap::new(),
         }
     }
 
@@ -149,6 +166,7 @@ impl UiArena {
             let idx = reused_idx as usize;
             self.active[idx] = true;
             self.dirty_flags[idx] = DIRTY_ALL;
+            self.opacities[idx] = 1.0;
             NodeId(reused_idx, self.generations[idx])
         } else {
             let index = self.colors.len() as u32;
@@ -196,8 +214,14 @@ impl UiArena {
                 self.node_map.remove(&taffy_node);
                 self.overlays.retain(|&x| x != taffy_node);
                 self.anchors.remove(&taffy_node);
+                self.overlay_positions.remove(&taffy_node);
+                self.overlay_aligns.remove(&taffy_node);
+                self.passthrough_overlays.remove(&taffy_node);
+                self.focus_traps.retain(|&x| x != taffy_node);
                 self.logical_children.remove(&taffy_node);
+                self.tooltips.remove(&taffy_node);
             }
+            self.group_layers.remove(&id);
             if let Some(id_str) = self.node_to_id_str.remove(&id) {
                 self.dynamic_nodes.remove(&id_str);
             }
@@ -221,6 +245,12 @@ impl UiArena {
         self.widgets.clear();
         self.overlays.clear();
         self.anchors.clear();
+        self.overlay_positions.clear();
+        self.overlay_aligns.clear();
+        self.passthrough_overlays.clear();
+        self.focus_traps.clear();
+        self.tooltips.clear();
+        self.group_layers.clear();
         self.dynamic_nodes.clear();
         self.node_to_id_str.clear();
         self.node_scopes.clear();
@@ -229,6 +259,7 @@ impl UiArena {
 }
 
 pub struct FrameworkState {
+    pub window_id: crate::window::WindowId,
     pub wake_registry: Arc<Mutex<WakeRegistry>>,
     pub arena: UiArena,
     pub draw_order_set: std::collections::HashSet<u32>,
@@ -239,6 +270,10 @@ pub struct FrameworkState {
     pub pending_dirty_nodes: Vec<NodeId>,
     pub sparse_update_queue: Vec<NodeId>,
     pub node_transforms: HashMap<NodeId, Vec2>,
+    // Aylantirish/masshtab/qiyshaytirish: node markazi atrofida, butun daraxtiga ta'sir qiladi
+    pub local_transforms: HashMap<NodeId, rore_types::Transform>,
+    // Ajdodlar bilan yig'ilgan affine (faqat identity bo'lmaganlar), kalit - node indeksi
+    pub world_transforms: HashMap<u32, Affine2>,
     pub dirty_nodes: Vec<NodeId>,
     pub node_bounds: HashMap<TaffyNode, [f32; 4]>,
     pub aabb_list: Vec<AabbRect>,
@@ -251,7 +286,7 @@ pub struct FrameworkState {
     pub hovered_node: Option<TaffyNode>,
     pub focused_node: Option<TaffyNode>,
     pub active_node: Option<TaffyNode>,
-    pub clipboard: Option<std::sync::Mutex<Clipboard>>,
+    pub clipboard: Arc<crate::clipboard::ClipboardService>,
     pub full_redraw: bool,
     pub dirty_rect: Option<[u32; 4]>,
     pub current_cursor_icon: winit::window::CursorIcon,
@@ -262,13 +297,25 @@ pub struct FrameworkState {
     pub modifiers: ModifiersState,
     pub screen_size: Vec2,
     pub scroll_offsets: HashMap<NodeId, Vec2>,
+    pub drag: Option<crate::dnd::DragSession>,
+    // Har bir chap tugma bosilishida oshadi (vidjetlar "yangi bosish" ni ajratishi uchun)
+    pub press_serial: u64,
+    pub tooltip: crate::tooltip::TooltipState,
+    // "Ctrl+K ..." ning bosilgan qismi (keyingi chord kutilmoqda)
+    pub pending_chord: Vec<crate::shortcuts::KeyChord>,
+    // Filtr qatlamlari (render paytida vidjet yozadi; rect/a'zolar paket oldidan to'ldiriladi)
+    pub filter_layers: HashMap<NodeId, rore_render::FilterLayer>,
+    // Avlodi o'zgargan keshlangan qatlamlar: keyingi paketda qayta rasterlanadi
+    pub stale_layers: HashSet<NodeId>,
+    // `capture::capture_frame` so'rovlari: id va mantiqiy rect (None = butun oyna)
+    pub pending_captures: Vec<(u64, Option<[f32; 4]>)>,
 }
 
 impl FrameworkState {
     pub fn new(config: RoreConfig, wake_registry: Arc<Mutex<WakeRegistry>>) -> Self {
         CommandQueue::init();
-        let clipboard = Clipboard::new().ok().map(|c| std::sync::Mutex::new(c));
         Self {
+            window_id: crate::window::MAIN_WINDOW,
             wake_registry,
             arena: UiArena::new(),
             drop_queue: Rc::new(RefCell::new(Vec::new())),
@@ -278,6 +325,8 @@ impl FrameworkState {
             pending_dirty_nodes: Vec::new(),
             sparse_update_queue: Vec::new(),
             node_transforms: HashMap::new(),
+            local_transforms: HashMap::new(),
+            world_transforms: HashMap::new(),
             dirty_nodes: Vec::new(),
             node_bounds: HashMap::new(),
             aabb_list: Vec::new(),
@@ -290,7 +339,7 @@ impl FrameworkState {
             hovered_node: None,
             focused_node: None,
             active_node: None,
-            clipboard,
+            clipboard: crate::clipboard::clipboard(),
             full_redraw: true,
             dirty_rect: None,
             current_cursor_icon: winit::window::CursorIcon::Default,
@@ -302,6 +351,13 @@ impl FrameworkState {
             modifiers: ModifiersState::empty(),
             screen_size: Vec2::new(1024.0, 768.0),
             scroll_offsets: HashMap::new(),
+            drag: None,
+            press_serial: 0,
+            tooltip: Default::default(),
+            pending_chord: Vec::new(),
+            filter_layers: HashMap::new(),
+            stale_layers: HashSet::new(),
+            pending_captures: Vec::new(),
         }
     }
 
@@ -322,70 +378,101 @@ impl FrameworkState {
     }
 
     pub fn process_commands(&mut self, engine: &mut rore_layout::LayoutEngine) {
-        if let Some(rx_mutex) = COMMAND_RECEIVER.get() {
-            if let Ok(rx) = rx_mutex.try_lock() {
-                while let Ok(cmd) = rx.try_recv() {
-                    match cmd {
-                        UICommand::SetColor(id_str, color) => {
-                            if let Some(&node_id) = self.arena.dynamic_nodes.get(&id_str) {
-                                let idx = node_id.0 as usize;
-                                if idx < self.arena.colors.len()
-                                    && self.arena.generations[idx] == node_id.1
-                                {
-                                    self.arena.colors[idx] = color;
-                                    if !self.sparse_update_queue.contains(&node_id) {
-                                        self.sparse_update_queue.push(node_id);
-                                    }
-                                    self.mark_dirty_with_flag(node_id, DIRTY_COLOR);
+        loop {
+            let cmds = CommandQueue::drain_for(self.window_id);
+            if cmds.is_empty() {
+                break;
+            }
+            for cmd in cmds {
+                match cmd {
+                    UICommand::SetColor(id_str, color) => {
+                        if let Some(&node_id) = self.arena.dynamic_nodes.get(&id_str) {
+                            let idx = node_id.0 as usize;
+                            if idx < self.arena.colors.len()
+                                && self.arena.generations[idx] == node_id.1
+                            {
+                                self.arena.colors[idx] = color;
+                                if !self.sparse_update_queue.contains(&node_id) {
+                                    self.sparse_update_queue.push(node_id);
                                 }
+                                self.mark_dirty_with_flag(node_id, DIRTY_COLOR);
                             }
                         }
-                        UICommand::UpdateText(node_id, _new_text) => {
-                            if !self.sparse_update_queue.contains(&node_id) {
-                                self.sparse_update_queue.push(node_id);
-                            }
-                            self.mark_dirty_with_flag(node_id, DIRTY_TEXT);
+                    }
+                    UICommand::UpdateText(node_id, _new_text) => {
+                        if !self.sparse_update_queue.contains(&node_id) {
+                            self.sparse_update_queue.push(node_id);
+                        }
+                        self.mark_dirty_with_flag(node_id, DIRTY_TEXT);
+                        self.request_redraw();
+                    }
+                    UICommand::MarkDirty(node_id, flag) => {
+                        if !self.sparse_update_queue.contains(&node_id) {
+                            self.sparse_update_queue.push(node_id);
+                        }
+                        self.mark_dirty_with_flag(node_id, flag);
+                        if flag != DIRTY_COLOR {
                             self.request_redraw();
                         }
-                        UICommand::MarkDirty(node_id, flag) => {
+                    }
+                    UICommand::RebuildNode(node_id, action) => {
+                        self.pending_rebuilds.push((node_id, action));
+                        self.request_redraw();
+                    }
+                    UICommand::UpdateStyle(node_id, new_style) => {
+                        if let Some(&taffy_node) = self.arena.taffy_map.get(&node_id) {
+                            engine.update_style(taffy_node, new_style);
                             if !self.sparse_update_queue.contains(&node_id) {
                                 self.sparse_update_queue.push(node_id);
                             }
-                            self.mark_dirty_with_flag(node_id, flag);
-                            if flag != DIRTY_COLOR {
-                                self.request_redraw();
-                            }
-                        }
-                        UICommand::RebuildNode(node_id, action) => {
-                            self.pending_rebuilds.push((node_id, action));
+                            self.mark_dirty_with_flag(node_id, DIRTY_LAYOUT);
                             self.request_redraw();
                         }
-                        UICommand::UpdateStyle(node_id, new_style) => {
-                            if let Some(&taffy_node) = self.arena.taffy_map.get(&node_id) {
-                                engine.update_style(taffy_node, new_style);
-                                if !self.sparse_update_queue.contains(&node_id) {
-                                    self.sparse_update_queue.push(node_id);
-                                }
-                                self.mark_dirty_with_flag(node_id, DIRTY_LAYOUT);
-                                self.request_redraw();
-                            }
+                    }
+                    UICommand::UpdateResource(sig_id, boxed_val) => {
+                        crate::reactive::signals::set_signal_any(
+                            crate::reactive::signals::SignalId(sig_id),
+                            boxed_val,
+                        );
+                        crate::reactive::signals::process_pending_effects();
+                        self.request_redraw();
+                    }
+                    UICommand::UpdateTransform(node_id, dx, dy) => {
+                        self.node_transforms.insert(node_id, Vec2::new(dx, dy));
+                        self.needs_aabb_update = true;
+                        if !self.sparse_update_queue.contains(&node_id) {
+                            self.sparse_update_queue.push(node_id);
                         }
-                        UICommand::UpdateResource(sig_id, boxed_val) => {
-                            crate::reactive::signals::set_signal_any(
-                                crate::reactive::signals::SignalId(sig_id),
-                                boxed_val,
-                            );
-                            crate::reactive::signals::process_pending_effects();
-                            self.request_redraw();
+                        self.invalidate_cached_layers(node_id);
+                    }
+                    UICommand::SetTransform(node_id, transform) => {
+                        if transform.is_identity() {
+                            self.local_transforms.remove(&node_id);
+                        } else {
+                            self.local_transforms.insert(node_id, transform);
+                        }
+                        self.needs_aabb_update = true;
+                        if !self.sparse_update_queue.contains(&node_id) {
+                            self.sparse_update_queue.push(node_id);
                         }
-                        UICommand::UpdateTransform(node_id, dx, dy) => {
-                            self.node_transforms.insert(node_id, Vec2::new(dx, dy));
-                            self.needs_aabb_update = true;
+                        self.invalidate_cached_layers(node_id);
+                    }
+                    // Faqat kompozitsiya o'zgaradi: keshlangan tekstura qayta chizilmaydi
+                    UICommand::SetOpacity(node_id, opacity) => {
+                        let idx = node_id.0 as usize;
+                        if idx < self.arena.opacities.len()
+                            && self.arena.generations[idx] == node_id.1
+                        {
+                            self.arena.opacities[idx] = opacity;
                             if !self.sparse_update_queue.contains(&node_id) {
                                 self.sparse_update_queue.push(node_id);
                             }
                         }
                     }
+                    UICommand::Custom(type_id, payload) => {
+                        CommandQueue::run_custom(type_id, payload, self, engine);
+                        self.request_redraw();
+                    }
                 }
             }
         }
@@ -401,6 +488,23 @@ impl FrameworkState {
             if !self.pending_dirty_nodes.contains(&node) {
                 self.pending_dirty_nodes.push(node);
             }
+            self.invalidate_cached_layers(node);
+        }
+    }
+
+    /// Tugun (yoki uning ajdodi) `.cache_as_layer()` bo'lsa, qatlam teksturasi eskiradi.
+    pub fn invalidate_cached_layers(&mut self, node: NodeId) {
+        if !self.arena.group_layers.values().any(|&cached| cached) {
+            return;
+        }
+        let mut curr = self.arena.taffy_map.get(&node).copied();
+        while let Some(t_node) = curr {
+            if let Some(&id) = self.arena.node_map.get(&t_node) {
+                if self.arena.group_layers.get(&id) == Some(&true) {
+                    self.stale_layers.insert(id);
+                }
+            }
+            curr = self.parent_map.get(&t_node).copied();
         }
     }
     pub fn clear_dirty_flags(&mut self) {
@@ -450,10 +554,11 @@ impl FrameworkState {
         self.current_z_index = 0;
         self.node_bounds.clear();
         self.parent_map.clear();
+        self.world_transforms.clear();
         self.is_overlay_pass = false;
 
         let initial_clip = [-10000.0, -10000.0, 20000.0, 20000.0];
-        self.build_aabb_recursive(engine, root, Vec2::ZERO, initial_clip);
+        self.build_aabb_recursive(engine, root, Vec2::ZERO, initial_clip, Affine2::IDENTITY);
 
         // Ekran hajmini aniqlaymiz
         let root_bounds = self
@@ -469,7 +574,34 @@ impl FrameworkState {
         self.is_overlay_pass = true;
         for &overlay_node in &self.arena.overlays.clone() {
             let mut start_pos = Vec2::ZERO;
-            if let Some(target_id) = self.arena.anchors.get(&overlay_node) {
+            let point = self.arena.overlay_positions.get(&overlay_node).copied();
+            let align = self.arena.overlay_aligns.get(&overlay_node).copied();
+            if point.is_some() || align.is_some() {
+                let (mut ow, mut oh) = (0.0, 0.0);
+                if let Ok(children) = engine.taffy.children(overlay_node) {
+                    if let Some(&child) = children.first() {
+                        if let Ok(layout) = engine.taffy.layout(child) {
+                            ow = layout.size.width;
+                            oh = layout.size.height;
+                        }
+                    }
+                }
+                let free = Vec2::new(self.screen_size.x - ow, self.screen_size.y - oh);
+                let pos = match point {
+                    Some(pos) => pos,
+                    None => free * align.unwrap_or(Vec2::ZERO),
+                };
+                let px = pos.x.min(free.x).max(0.0);
+                let py = pos.y.min(free.y).max(0.0);
+
+                // Portal tugunining ota ichidagi joyini ayiramiz: natija aynan (px, py) bo'lsin
+                let local = engine
+                    .taffy
+                    .layout(overlay_node)
+                    .map(|l| Vec2::new(l.location.x, l.location.y))
+                    .unwrap_or(Vec2::ZERO);
+                start_pos = Vec2::new(px, py) - local;
+            } else if let Some(target_id) = self.arena.anchors.get(&overlay_node) {
                 if let Some(target_node_id) = self.arena.dynamic_nodes.get(target_id) {
                     if let Some(t_node) = self.arena.taffy_map.get(target_node_id) {
                         if let Some(bounds) = self.node_bounds.get(t_node).copied() {
@@ -502,7 +634,12 @@ impl FrameworkState {
             }
 
             // Click-Outside uchun ko'rinmas qutini (Backdrop) joylashtiramiz
-            if let Some(&overlay_id) = self.arena.node_map.get(&overlay_node) {
+            if let Some(&overlay_id) = self
+                .arena
+                .node_map
+                .get(&overlay_node)
+                .filter(|_| !self.arena.passthrough_overlays.contains(&overlay_node))
+            {
                 self.current_z_index += 1;
                 self.spatial_grid.insert(
                     overlay_id,
@@ -512,7 +649,13 @@ impl FrameworkState {
             }
 
             // Endi Overlay'ning o'zini va farzandlarini rekursiv joylashtiramiz (Ularning z-index'i yuqoriroq bo'ladi!)
-            self.build_aabb_recursive(engine, overlay_node, start_pos, initial_clip);
+            self.build_aabb_recursive(
+                engine,
+                overlay_node,
+                start_pos,
+                initial_clip,
+                Affine2::IDENTITY,
+            );
         }
         self.is_overlay_pass = false;
     }
@@ -573,6 +716,7 @@ impl FrameworkState {
         node: TaffyNode,
         parent_pos: Vec2,
         current_clip: [f32; 4],
+        parent_transform: Affine2,
     ) {
         if !self.is_overlay_pass && self.arena.overlays.contains(&node) {
             return;
@@ -599,14 +743,33 @@ impl FrameworkState {
         };
         self.aabb_list.push(rect);
 
+        let mut transform = parent_transform;
+        if let Some(&node_id) = self.arena.node_map.get(&node) {
+            if let Some(local) = self.local_transforms.get(&node_id) {
+                let center = rore_types::Point {
+                    x: pos.x + layout.width * 0.5,
+                    y: pos.y + layout.height * 0.5,
+                };
+                transform = parent_transform * local.to_affine(center);
+            }
+        }
+
         // Z-Index har qadamda oshib boradi
         self.current_z_index += 1;
         if let Some(&node_id) = self.arena.node_map.get(&node) {
-            self.spatial_grid.insert(
-                node_id,
-                [pos.x, pos.y, layout.width, layout.height],
-                self.current_z_index,
-            );
+            let rect = [pos.x, pos.y, layout.width, layout.height];
+            if transform == Affine2::IDENTITY {
+                self.spatial_grid
+                    .insert(node_id, rect, self.current_z_index);
+            } else {
+                self.world_transforms.insert(node_id.0, transform);
+                self.spatial_grid.insert_transformed(
+                    node_id,
+                    rect,
+                    self.current_z_index,
+                    &transform,
+                );
+            }
         }
 
         let next_clip = current_clip;
@@ -627,7 +790,7 @@ impl FrameworkState {
         if let Ok(children) = engine.taffy.children(node) {
             for child in children {
                 self.parent_map.insert(child, node);
-                self.build_aabb_recursive(engine, child, children_parent_pos, next_clip);
+                self.build_aabb_recursive(engine, child, children_parent_pos, next_clip, transform);
             }
         }
     }
@@ -649,7 +812,8 @@ impl FrameworkState {
                             .map(|w| w.is_interactive())
                             .unwrap_or(false);
                         let has_id = self.arena.node_to_id_str.contains_key(&item.node_id);
-                        if is_interactive || has_id {
+                        let has_tooltip = self.arena.tooltips.contains_key(&taffy_node);
+                        if is_interactive || has_id || has_tooltip {
                             return Some(taffy_node);
                         }
                     }
@@ -659,6 +823,14 @@ impl FrameworkState {
         None
     }
 
+    /// Layout rect'i ekranda egallaydigan soha (transform bo'lsa uning AABB si).
+    pub fn screen_rect(&self, id: u32, rect: [f32; 4]) -> [f32; 4] {
+        match self.world_transforms.get(&id) {
+            Some(m) => rore_types::transform_rect(m, rect),
+            None => rect,
+        }
+    }
+
     pub fn get_event_bubble_chain(&self, start_node: TaffyNode) -> Vec<TaffyNode> {
         let mut chain = Vec::new();
         let mut current = Some(start_node);
@@ -679,6 +851,39 @@ impl FrameworkState {
         chain
     }
 
+    /// Hodisani `start` dan boshlab ota-bobolar bo'ylab uzatadi.
+    /// Kim o'ziga olgan (Consumed) bo'lsa, o'sha tugunni qaytaradi.
+    pub fn dispatch_bubbling(
+        &mut self,
+        start: TaffyNode,
+        event: &WidgetEvent,
+    ) -> Option<TaffyNode> {
+        for node in self.get_event_bubble_chain(start) {
+            if let Some(&node_id) = self.arena.node_map.get(&node) {
+                if let Some(mut widget) = self.arena.widgets[node_id.0 as usize].take() {
+                    let res = widget.handle_event(self, event);
+                    self.arena.widgets[node_id.0 as usize] = Some(widget);
+                    if res == EventResult::Consumed {
+                        return Some(node);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Faqat bitta tugunga (bubbling'siz) hodisa yuboradi.
+    pub fn dispatch_to(&mut self, node: TaffyNode, event: &WidgetEvent) -> EventResult {
+        if let Some(&node_id) = self.arena.node_map.get(&node) {
+            if let Some(mut widget) = self.arena.widgets[node_id.0 as usize].take() {
+                let res = widget.handle_event(self, event);
+                self.arena.widgets[node_id.0 as usize] = Some(widget);
+                return res;
+            }
+        }
+        EventResult::Ignored
+    }
+
     pub fn process_drop_queue(&mut self, engine: &rore_layout::LayoutEngine) {
         let mut to_remove = Vec::new();
         {
@@ -689,6 +894,7 @@ impl FrameworkState {
         while i < to_remove.len() {
             let id = to_remove[i];
             self.node_transforms.remove(&id);
+            self.local_transforms.remove(&id);
             if let Some(&taffy_node) = self.arena.taffy_map.get(&id) {
                 if let Ok(children) = engine.taffy.children(taffy_node) {
                     for child_node in children {
@@ -733,11 +939,47 @@ impl FrameworkState {
         self.last_cursor_pos = Some(self.cursor_pos);
         self.cursor_pos = Vec2::new(x, y);
     }
+    /// Eng ustidagi modal (fokus tuzog'i), agar bo'lsa.
+    pub fn focus_trap(&self) -> Option<TaffyNode> {
+        self.arena.focus_traps.last().copied()
+    }
+
+    fn is_within_trap(&self, node: TaffyNode) -> bool {
+        match self.focus_trap() {
+            Some(trap) => self.get_event_bubble_chain(node).contains(&trap),
+            None => true,
+        }
+    }
+
+    /// Fokus modal'dan tashqarida qolgan bo'lsa, uni modal'ning o'ziga o'tkazadi.
+    pub fn contain_focus(&mut self) {
+        let Some(trap) = self.focus_trap() else {
+            return;
+        };
+        if self
+            .focused_node
+            .is_some_and(|node| self.is_within_trap(node))
+        {
+            return;
+        }
+        if let Some(old) = self.focused_node.replace(trap) {
+            if let Some(&old_id) = self.arena.node_map.get(&old) {
+                if !self.sparse_update_queue.contains(&old_id) {
+                    self.sparse_update_queue.push(old_id);
+                }
+            }
+            self.request_redraw();
+        }
+    }
+
     pub fn cycle_focus(&mut self, forward: bool) {
         let mut interactives = Vec::new();
         for (taffy_node, &node_id) in &self.arena.node_map {
             if let Some(widget) = self.arena.get(node_id) {
-                if widget.is_interactive() && widget.type_name() == "TextInput" {
+                if widget.is_interactive()
+                    && widget.type_name() == "TextInput"
+                    && self.is_within_trap(*taffy_node)
+                {
                     interactives.push(*taffy_node);
                 }
             }
@@ -1,4 +1,6 @@
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy)]
 pub struct TimeManager {
@@ -49,3 +51,219 @@ impl TimeManager {
         }
     }
 }
+
+// ==================== FRAME PACING ====================
+
+// Fokus yo'q (orqa fonda ko'rinib turgan) oyna uchun chegaralar
+const UNFOCUSED_FPS: u32 = 30;
+const UNFOCUSED_FPS_POWER_SAVING: u32 = 10;
+// Oyna butunlay yopilgan/minimize bo'lsa, tickerlar shu chastotada yuradi (rasm chizilmaydi)
+const OCCLUDED_TICK_HZ: u32 = 2;
+const STATS_WINDOW: usize = 120;
+
+/// Animatsiya paytida keyingi kadr qachon chizilishini hal qiladi.
+/// `max_fps`, monitor chastotasi, fokus/ko'rinish va `power_saving` ni hisobga oladi.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    pub max_fps: u32,
+    pub power_saving: bool,
+    pub monitor_hz: Option<u32>,
+    pub focused: bool,
+    pub occluded: bool,
+    last_frame: Option<Instant>,
+}
+
+impl FramePacer {
+    pub fn new(max_fps: u32, power_saving: bool) -> Self {
+        Self {
+            max_fps,
+            power_saving,
+            monitor_hz: None,
+            focused: true,
+            occluded: false,
+            last_frame: None,
+        }
+    }
+
+    /// Hozirgi holatda ruxsat etilgan kadr chastotasi. `None` — umuman chizmaslik.
+    pub fn target_fps(&self) -> Option<u32> {
+        if self.occluded {
+            return None;
+        }
+
+        let monitor = self.monitor_hz.unwrap_or(60).max(1);
+        let mut fps = if self.max_fps == 0 {
+            monitor
+        } else {
+            self.max_fps.min(monitor)
+        };
+
+        if !self.focused {
+            let cap = if self.power_saving {
+                UNFOCUSED_FPS_POWER_SAVING
+            } else {
+                UNFOCUSED_FPS
+            };
+            fps = fps.min(cap);
+        }
+        Some(fps.max(1))
+    }
+
+    pub fn frame_interval(&self) -> Duration {
+        match self.target_fps() {
+            Some(fps) => Duration::from_secs_f64(1.0 / fps as f64),
+            None => Duration::from_secs_f64(1.0 / OCCLUDED_TICK_HZ as f64),
+        }
+    }
+
+    /// Keyingi kadr vaqti kelganmi? Kelgan bo'lsa, uni hisobga oladi.
+    pub fn should_render(&mut self, now: Instant) -> bool {
+        if self.occluded {
+            return false;
+        }
+        let due = match self.last_frame {
+            Some(last) => now >= last + self.frame_interval(),
+            None => true,
+        };
+        if due {
+            // Kechikishni yig'ib qo'ymaslik uchun hozirgi vaqtdan hisoblaymiz
+            self.last_frame = Some(now);
+        }
+        due
+    }
+
+    /// Event loop qachon uyg'onishi kerak
+    pub fn next_wakeup(&self, now: Instant) -> Instant {
+        match (self.occluded, self.last_frame) {
+            (false, Some(last)) => (last + self.frame_interval()).max(now),
+            _ => now + self.frame_interval(),
+        }
+    }
+}
+
+/// Oxirgi kadrlar bo'yicha statistika (`frame_stats()` orqali olinadi).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    pub fps: f32,
+    pub avg_frame_ms: f32,
+    pub p95_frame_ms: f32,
+    pub max_frame_ms: f32,
+    pub avg_render_ms: f32,
+    pub total_frames: u64,
+    pub target_fps: Option<u32>,
+}
+
+#[derive(Default)]
+struct FrameHistory {
+    intervals: VecDeque<f32>,
+    render_times: VecDeque<f32>,
+    last_present: Option<Instant>,
+    total_frames: u64,
+    target_fps: Option<u32>,
+}
+
+static FRAME_HISTORY: Mutex<Option<FrameHistory>> = Mutex::new(None);
+
+fn push_sample(buf: &mut VecDeque<f32>, value: f32) {
+    if buf.len() == STATS_WINDOW {
+        buf.pop_front();
+    }
+    buf.push_back(value);
+}
+
+pub(crate) fn record_frame(now: Instant, render_time: Duration) {
+    let mut guard = FRAME_HISTORY.lock().unwrap();
+    let history = guard.get_or_insert_with(FrameHistory::default);
+
+    if let Some(last) = history.last_present {
+        push_sample(
+            &mut history.intervals,
+            now.duration_since(last).as_secs_f32() * 1000.0,
+        );
+    }
+    push_sample(
+        &mut history.render_times,
+        render_time.as_secs_f32() * 1000.0,
+    );
+    history.last_present = Some(now);
+    history.total_frames += 1;
+}
+
+pub(crate) fn record_target_fps(target: Option<u32>) {
+    let mut guard = FRAME_HISTORY.lock().unwrap();
+    guard.get_or_insert_with(FrameHistory::default).target_fps = target;
+}
+
+/// Istalgan oqimdan chaqirish mumkin (masalan, FPS hisoblagich vidjeti uchun).
+pub fn frame_stats() -> FrameStats {
+    let guard = FRAME_HISTORY.lock().unwrap();
+    let Some(history) = guard.as_ref() else {
+        return FrameStats::default();
+    };
+
+    let mut stats = FrameStats {
+        total_frames: history.total_frames,
+        target_fps: history.target_fps,
+        ..FrameStats::default()
+    };
+
+    if !history.intervals.is_empty() {
+        let n = history.intervals.len() as f32;
+        stats.avg_frame_ms = history.intervals.iter().sum::<f32>() / n;
+        stats.max_frame_ms = history.intervals.iter().copied().fold(0.0, f32::max);
+
+        let mut sorted: Vec<f32> = history.intervals.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let idx = ((sorted.len() as f32 * 0.95).ceil() as usize).saturating_sub(1);
+        stats.p95_frame_ms = sorted[idx.min(sorted.len() - 1)];
+
+        if stats.avg_frame_ms > 0.0 {
+            stats.fps = 1000.0 / stats.avg_frame_ms;
+        }
+    }
+    if !history.render_times.is_empty() {
+        stats.avg_render_ms =
+            history.render_times.iter().sum::<f32>() / history.render_times.len() as f32;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pacer_respects_max_fps_monitor_and_focus() {
+        let mut pacer = FramePacer::new(120, false);
+        pacer.monitor_hz = Some(60);
+        assert_eq!(pacer.target_fps(), Some(60));
+
+        pacer.monitor_hz = Some(144);
+        assert_eq!(pacer.target_fps(), Some(120));
+
+        pacer.focused = false;
+        assert_eq!(pacer.target_fps(), Some(UNFOCUSED_FPS));
+
+        pacer.power_saving = true;
+        assert_eq!(pacer.target_fps(), Some(UNFOCUSED_FPS_POWER_SAVING));
+
+        pacer.occluded = true;
+        assert_eq!(pacer.target_fps(), None);
+        assert!(!pacer.should_render(Instant::now()));
+    }
+
+    #[test]
+    fn pacer_skips_frames_until_interval_elapsed() {
+        let mut pacer = FramePacer::new(30, false);
+        pacer.monitor_hz = Some(60);
+        let start = Instant::now();
+
+        assert!(pacer.should_render(start));
+        assert!(!pacer.should_render(start + Duration::from_millis(10)));
+        assert_eq!(
+            pacer.next_wakeup(start + Duration::from_millis(10)),
+            start + pacer.frame_interval()
+        );
+        assert!(pacer.should_render(start + Duration::from_millis(34)));
+    }
+}
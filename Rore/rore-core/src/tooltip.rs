@@ -0,0 +1,146 @@
+use crate::reactive::signals::{create_scope, dispose_scope, ScopeId};
+use crate::state::{FrameworkState, NodeId};
+use crate::widgets::base::{BuildContext, Widget};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+
+pub const DEFAULT_TOOLTIP_DELAY: f32 = 0.5;
+// Tooltip va anchor orasidagi masofa
+const TOOLTIP_GAP: f32 = 6.0;
+
+/// Tugunga biriktirilgan tooltip: ko'rinish har safar `builder` dan yangidan quriladi.
+pub struct TooltipSpec {
+    pub builder: Box<dyn FnMut() -> Box<dyn Widget> + Send>,
+    // Soniya (global_time bilan solishtiriladi)
+    pub delay: f32,
+}
+
+#[derive(Default)]
+pub struct TooltipState {
+    owner: Option<TaffyNode>,
+    since: f32,
+    // Skroll yoki bosish bilan yopildi: kursor boshqa tugunga o'tmaguncha qayta chiqmaydi
+    dismissed: bool,
+    pending: bool,
+    shown: Option<(NodeId, ScopeId)>,
+}
+
+/// Anchor ostida, o'rtasiga tekislab joylashtiradi. Pastga sig'masa tepaga o'tadi,
+/// yon tomonlarga chiqib ketsa ekran ichiga suriladi.
+pub fn place_tooltip(anchor: [f32; 4], size: Vec2, screen: Vec2) -> Vec2 {
+    let [ax, ay, aw, ah] = anchor;
+    let x = (ax + aw / 2.0 - size.x / 2.0)
+        .min(screen.x - size.x)
+        .max(0.0);
+
+    let below = ay + ah + TOOLTIP_GAP;
+    let above = ay - TOOLTIP_GAP - size.y;
+    let y = if below + size.y <= screen.y || above < 0.0 {
+        below.min(screen.y - size.y).max(0.0)
+    } else {
+        above
+    };
+    Vec2::new(x, y)
+}
+
+impl FrameworkState {
+    /// Hover o'zgarganda chaqiriladi: tooltip egasi kursor ostidagi eng yaqin tooltip'li ota.
+    pub(crate) fn update_tooltip_owner(&mut self) {
+        let new_owner = self.hovered_node.and_then(|hover| {
+            self.get_event_bubble_chain(hover)
+                .into_iter()
+                .find(|node| self.arena.tooltips.contains_key(node))
+        });
+        if new_owner != self.tooltip.owner {
+            self.hide_tooltip();
+            self.tooltip.owner = new_owner;
+            self.tooltip.since = self.global_time;
+            self.tooltip.dismissed = false;
+        }
+    }
+
+    /// Skroll, bosish va h.k.: ochiq tooltip yopiladi va shu hover davomida qayta chiqmaydi.
+    pub fn dismiss_tooltip(&mut self) {
+        if self.tooltip.owner.is_some() {
+            self.tooltip.dismissed = true;
+            self.hide_tooltip();
+        }
+    }
+
+    fn hide_tooltip(&mut self) {
+        self.tooltip.pending = false;
+        if let Some((id, scope)) = self.tooltip.shown.take() {
+            self.drop_queue.borrow_mut().push(id);
+            dispose_scope(scope);
+            self.request_redraw();
+        }
+    }
+
+    /// Kechikish tugashi kutilyaptimi (event loop uxlab qolmasligi uchun)
+    pub(crate) fn tooltip_waiting(&self) -> bool {
+        self.tooltip.owner.is_some()
+            && self.tooltip.shown.is_none()
+            && !self.tooltip.pending
+            && !self.tooltip.dismissed
+    }
+
+    /// Har tickda: kechikish o'tgan bo'lsa, keyingi flush'da tooltip quriladi.
+    pub(crate) fn poll_tooltip(&mut self) {
+        let Some(owner) = self.tooltip.owner else {
+            return;
+        };
+        let Some(spec) = self.arena.tooltips.get(&owner) else {
+            // Egasi daraxtdan olib tashlandi
+            self.hide_tooltip();
+            self.tooltip.owner = None;
+            return;
+        };
+        if self.tooltip_waiting() && self.global_time - self.tooltip.since >= spec.delay {
+            self.tooltip.pending = true;
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn process_tooltip(&mut self, engine: &mut LayoutEngine) {
+        if !self.tooltip.pending {
+            return;
+        }
+        self.tooltip.pending = false;
+        let Some(owner) = self.tooltip.owner else {
+            return;
+        };
+        let Some(spec) = self.arena.tooltips.get_mut(&owner) else {
+            return;
+        };
+        let view = (spec.builder)();
+        let ctx = BuildContext {};
+        let (scope, id) = create_scope(|| view.build(&mut self.arena, engine, &ctx));
+        self.tooltip.shown = Some((id, scope));
+    }
+
+    /// Ochiq tooltip va uning anchor'i (egasining AABB si).
+    pub(crate) fn tooltip_view(&self) -> Option<(NodeId, [f32; 4])> {
+        let (id, _) = self.tooltip.shown?;
+        let anchor = self.node_bounds.get(&self.tooltip.owner?).copied()?;
+        Some((id, anchor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+    #[test]
+    fn tooltip_sits_below_anchor_centered() {
+        let pos = place_tooltip([100.0, 100.0, 80.0, 30.0], Vec2::new(60.0, 20.0), SCREEN);
+        assert_eq!(pos, Vec2::new(110.0, 136.0));
+    }
+
+    #[test]
+    fn tooltip_flips_above_and_shifts_inside_screen() {
+        let pos = place_tooltip([760.0, 570.0, 40.0, 30.0], Vec2::new(120.0, 24.0), SCREEN);
+        assert_eq!(pos, Vec2::new(680.0, 540.0));
+    }
+}
@@ -1,10 +1,11 @@
 use crate::state::{FrameworkState, NodeId, UiArena};
-use glam::Vec2;
+use glam::{Affine2, Vec2};
 use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_render::Instance;
 use rore_types::{Color, Style};
 use std::collections::{HashMap, HashSet};
-use winit::keyboard::Key;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState};
 
 // =====================================================================
 // INQILOB: CPU va Hodisalar uchun "AQL" (Phase 2)
@@ -22,7 +23,9 @@ const CELL_SIZE: f32 = 128.0;
 pub struct GridItem {
     pub node_id: NodeId,
     pub z_index: i32,
+    // Layout fazosida; transform bo'lsa nuqta avval `inverse` bilan qaytariladi
     pub rect: [f32; 4],
+    pub inverse: Option<Affine2>,
 }
 
 pub struct SpatialHashGrid {
@@ -41,16 +44,42 @@ impl SpatialHashGrid {
     }
 
     pub fn insert(&mut self, node_id: NodeId, rect: [f32; 4], z_index: i32) {
-        let min_x = (rect[0] / CELL_SIZE).floor() as i32;
-        let min_y = (rect[1] / CELL_SIZE).floor() as i32;
-        let max_x = ((rect[0] + rect[2]) / CELL_SIZE).floor() as i32;
-        let max_y = ((rect[1] + rect[3]) / CELL_SIZE).floor() as i32;
-
-        let item = GridItem {
-            node_id,
-            z_index,
+        self.insert_item(
             rect,
-        };
+            GridItem {
+                node_id,
+                z_index,
+                rect,
+                inverse: None,
+            },
+        );
+    }
+
+    // Aylantirilgan/masshtablangan node: kataklar ekrandagi chegarasi bo'yicha tanlanadi
+    pub fn insert_transformed(
+        &mut self,
+        node_id: NodeId,
+        rect: [f32; 4],
+        z_index: i32,
+        transform: &Affine2,
+    ) {
+        self.insert_item(
+            rore_types::transform_rect(transform, rect),
+            GridItem {
+                node_id,
+                z_index,
+                rect,
+                inverse: Some(transform.inverse()),
+            },
+        );
+    }
+
+    fn insert_item(&mut self, bounds: [f32; 4], item: GridItem) {
+        let min_x = (bounds[0] / CELL_SIZE).floor() as i32;
+        let min_y = (bounds[1] / CELL_SIZE).floor() as i32;
+        let max_x = ((bounds[0] + bounds[2]) / CELL_SIZE).floor() as i32;
+        let max_y = ((bounds[1] + bounds[3]) / CELL_SIZE).floor() as i32;
+
         for x in min_x..=max_x {
             for y in min_y..=max_y {
                 self.cells
@@ -68,10 +97,13 @@ impl SpatialHashGrid {
             let mut found = Vec::new();
             let mut seen = HashSet::new();
             for item in items {
-                if x >= item.rect[0]
-                    && x <= item.rect[0] + item.rect[2]
-                    && y >= item.rect[1]
-                    && y <= item.rect[1] + item.rect[3]
+                let p = item
+                    .inverse
+                    .map_or(Vec2::new(x, y), |inv| inv.transform_point2(Vec2::new(x, y)));
+                if p.x >= item.rect[0]
+                    && p.x <= item.rect[0] + item.rect[2]
+                    && p.y >= item.rect[1]
+                    && p.y <= item.rect[1] + item.rect[3]
                 {
                     if seen.insert(item.node_id) {
                         found.push(item.clone());
@@ -98,16 +130,73 @@ pub enum TextureSource {
 pub enum WidgetEvent {
     HoverEnter,
     HoverLeave,
-    MouseDown,
-    MouseUp,
-    Click,
+    // Qaysi tugma va o'sha paytdagi Ctrl/Shift/Alt holati
+    MouseDown {
+        button: MouseButton,
+        modifiers: ModifiersState,
+    },
+    MouseUp {
+        button: MouseButton,
+        modifiers: ModifiersState,
+    },
+    Click {
+        button: MouseButton,
+        modifiers: ModifiersState,
+    },
     TextInput(String),
     KeyPress(Key),
 
-    MouseMove { x: f32, y: f32 },
-    MouseDrag { dx: f32, dy: f32 },
-    MouseScroll { delta_x: f32, delta_y: f32 },
+    MouseMove {
+        x: f32,
+        y: f32,
+    },
+    MouseDrag {
+        dx: f32,
+        dy: f32,
+    },
+    MouseScroll {
+        delta_x: f32,
+        delta_y: f32,
+    },
     MouseCancel,
+
+    // Touch / Pen: har bir barmoqning o'z id si bor, pressure 0..1 (sensorda 1.0)
+    PointerDown {
+        id: u64,
+        x: f32,
+        y: f32,
+        pressure: f32,
+    },
+    PointerMove {
+        id: u64,
+        x: f32,
+        y: f32,
+        pressure: f32,
+    },
+    PointerUp {
+        id: u64,
+        x: f32,
+        y: f32,
+    },
+    Gesture(crate::gesture::Gesture),
+
+    // Drag & Drop: nishon DragOver ni Consumed qilsa, qabul qilishga tayyor hisoblanadi
+    DragEnter(crate::dnd::DragData),
+    DragOver {
+        data: crate::dnd::DragData,
+        x: f32,
+        y: f32,
+    },
+    DragLeave,
+    Drop {
+        data: crate::dnd::DragData,
+        x: f32,
+        y: f32,
+    },
+    // Manbaga: sudrash tugadi (accepted = biror nishon qabul qildi)
+    DragEnd {
+        accepted: bool,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -122,8 +211,9 @@ pub enum DisplayCommand {
         rect: [f32; 4],
     },
     PopClip,
+    // Keyingi buyruqlar shu 3x2 affine bilan (joriy transform'ga ko'paytiriladi)
     PushTransform {
-        offset: Vec2,
+        transform: Affine2,
     },
     PopTransform,
 
@@ -133,9 +223,13 @@ pub enum DisplayCommand {
         color_start: [f32; 4],
         color_end: [f32; 4],
         gradient_angle: f32,
-        border_radius: [f32; 4], // To'rt xil burchak uchun
-        border_width: [f32; 4],  // To'rt tomon qalinligi uchun
+        gradient: Option<Box<rore_render::GradientFill>>, // bo'lsa color_start/end o'rniga
+        image: Option<rore_render::ImageFill>,            // atlasdagi rasm, fon ustidan
+        border_radius: [f32; 4],                          // To'rt xil burchak uchun
+        border_width: [f32; 4],                           // To'rt tomon qalinligi uchun
         border_color: [f32; 4],
+        border_style: [rore_types::BorderStyle; 4], // top, right, bottom, left
+        border_dash: [f32; 2],                      // uzunlik, faza
         anim_start_time: f32,
         anim_duration: f32,
     },
@@ -153,6 +247,11 @@ pub enum DisplayCommand {
         wgsl_code: Option<String>,
         rect: [f32; 4],
         uniforms: Vec<u8>,
+        texture: Option<rore_render::ShaderTexture>,
+    },
+    // Tayyor uchburchaklar (`crate::path::Path::stroke` / `fill`). Arc: har kadrda nusxalanmaydi
+    DrawPath {
+        mesh: std::sync::Arc<rore_render::PathMesh>,
     },
 }
 
@@ -262,4 +361,33 @@ pub trait Widget: Send + 'static {
     fn visual_overflow(&self) -> [f32; 4] {
         [0.0, 0.0, 0.0, 0.0]
     }
+    // Copy/Cut/Paste buyruqlarini qabul qiladigan vidjetlar Some qaytaradi
+    fn as_copyable(&mut self) -> Option<&mut dyn crate::clipboard::Copyable> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rore_types::{Point, Transform};
+
+    #[test]
+    fn rotated_item_is_hit_in_its_local_space() {
+        let mut grid = SpatialHashGrid::new();
+        let rect = [0.0, 0.0, 100.0, 20.0];
+        let rotated = Transform::default()
+            .rotate(std::f32::consts::FRAC_PI_2)
+            .to_affine(Point { x: 50.0, y: 10.0 });
+        grid.insert_transformed(NodeId(1, 0), rect, 1, &rotated);
+
+        // Aylangandan keyin tik turadi: x 40..60, y -40..60
+        assert_eq!(grid.query_point(50.0, 50.0).len(), 1);
+        assert!(grid.query_point(90.0, 10.0).is_empty());
+
+        grid.insert(NodeId(2, 0), rect, 2);
+        let hits = grid.query_point(90.0, 10.0);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].node_id, NodeId(2, 0));
+    }
 }
@@ -0,0 +1,273 @@
+use crate::dnd::DragData;
+use crate::reactive::signals::Signal;
+use crate::state::{FrameworkState, NodeId, UiArena};
+use crate::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::Style;
+use std::marker::PhantomData;
+
+// Shuncha pikseldan kam siljish oddiy bosish hisoblanadi
+const DRAG_THRESHOLD: f32 = 4.0;
+
+fn build_wrapper(
+    widget: Box<dyn Widget>,
+    child: Option<Box<dyn Widget>>,
+    style: Style,
+    arena: &mut UiArena,
+    engine: &mut LayoutEngine,
+    ctx: &BuildContext,
+) -> NodeId {
+    let mut child_nodes = Vec::new();
+    if let Some(child) = child {
+        let child_id = child.build(arena, engine, ctx);
+        if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+            child_nodes.push(t_node);
+        }
+    }
+
+    let taffy_node = engine.new_node(style, &child_nodes);
+    let my_id = arena.allocate_node();
+    arena.taffy_map.insert(my_id, taffy_node);
+    arena.node_map.insert(taffy_node, my_id);
+
+    arena.widgets[my_id.0 as usize] = Some(widget);
+    my_id
+}
+
+fn render_children(
+    engine: &LayoutEngine,
+    state: &mut FrameworkState,
+    taffy_node: TaffyNode,
+    parent_pos: Vec2,
+    clip_rect: Option<[f32; 4]>,
+    path: String,
+) -> RenderOutput {
+    let mut output = RenderOutput::new();
+    let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+
+    if let Ok(children) = engine.taffy.children(taffy_node) {
+        for (i, &child_node) in children.iter().enumerate() {
+            if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                    let child_output = widget_ref.render(
+                        engine,
+                        state,
+                        child_node,
+                        Vec2::new(layout.x, layout.y),
+                        clip_rect,
+                        format!("{}_{}", path, i),
+                    );
+                    output.extend(child_output);
+                    state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Bolasini sudraladigan qiladi. Sudrash boshlanganda `payload` nusxasi `DragData` ga o'raladi.
+pub struct Draggable<T: Clone + Send + Sync + 'static> {
+    pub payload: T,
+    pub child: Option<Box<dyn Widget>>,
+    pub style: Style,
+    preview_builder: Option<Box<dyn FnMut() -> Box<dyn Widget> + Send>>,
+    on_drag_end: Option<Box<dyn FnMut(bool) + Send>>,
+    // (press_serial, shu bosishda yig'ilgan masofa)
+    press: Option<(u64, f32)>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Draggable<T> {
+    pub fn new(payload: T, child: impl Widget + 'static) -> Self {
+        Self {
+            payload,
+            child: Some(Box::new(child)),
+            style: Style::default(),
+            preview_builder: None,
+            on_drag_end: None,
+            press: None,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Kursor ortidan yuradigan ko'rinish (har bir sudrashda yangidan quriladi)
+    pub fn preview<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() -> Box<dyn Widget> + Send + 'static,
+    {
+        self.preview_builder = Some(Box::new(f));
+        self
+    }
+
+    // true: biror nishon qabul qildi
+    pub fn on_drag_end<F: FnMut(bool) + Send + 'static>(mut self, f: F) -> Self {
+        self.on_drag_end = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Widget for Draggable<T> {
+    fn type_name(&self) -> &'static str {
+        "Draggable"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let child = self.child.take();
+        let style = self.style.clone();
+        build_wrapper(self, child, style, arena, engine, ctx)
+    }
+
+    fn handle_event(&mut self, state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::MouseDrag { dx, dy } => {
+                let serial = state.press_serial;
+                let moved = match self.press {
+                    Some((s, dist)) if s == serial => dist + dx.hypot(*dy),
+                    _ => dx.hypot(*dy),
+                };
+                self.press = Some((serial, moved));
+
+                if moved >= DRAG_THRESHOLD && !state.is_dragging() {
+                    let preview = self.preview_builder.as_mut().map(|f| f());
+                    state.begin_drag(DragData::new(self.payload.clone()), preview);
+                }
+                // Ota ScrollView ishorani o'g'irlamasligi uchun
+                EventResult::Consumed
+            }
+            WidgetEvent::DragEnd { accepted } => {
+                self.press = None;
+                if let Some(cb) = &mut self.on_drag_end {
+                    cb(*accepted);
+                }
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        render_children(engine, state, taffy_node, parent_pos, clip_rect, path)
+    }
+}
+
+/// Faqat `T` turidagi ma'lumotni qabul qiladigan tashlash hududi.
+/// OS fayllari uchun: `DropTarget::<DroppedFiles>::new(...)`.
+pub struct DropTarget<T: Clone + Send + Sync + 'static> {
+    pub child: Option<Box<dyn Widget>>,
+    pub style: Style,
+    on_drop: Box<dyn FnMut(T, f32, f32) + Send>,
+    is_over: Option<Signal<bool>>,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T: Clone + Send + Sync + 'static> DropTarget<T> {
+    pub fn new<F>(child: impl Widget + 'static, on_drop: F) -> Self
+    where
+        F: FnMut(T, f32, f32) + Send + 'static,
+    {
+        Self {
+            child: Some(Box::new(child)),
+            style: Style::default(),
+            on_drop: Box::new(on_drop),
+            is_over: None,
+            _payload: PhantomData,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Ustida mos narsa sudralayotganda true (masalan, ramkani yoritish uchun)
+    pub fn hover_signal(mut self, sig: Signal<bool>) -> Self {
+        self.is_over = Some(sig);
+        self
+    }
+
+    fn set_over(&self, over: bool) {
+        if let Some(sig) = self.is_over {
+            sig.set(over);
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Widget for DropTarget<T> {
+    fn type_name(&self) -> &'static str {
+        "DropTarget"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let child = self.child.take();
+        let style = self.style.clone();
+        build_wrapper(self, child, style, arena, engine, ctx)
+    }
+
+    fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::DragOver { data, .. } if data.is::<T>() => EventResult::Consumed,
+            WidgetEvent::DragEnter(data) if data.is::<T>() => {
+                self.set_over(true);
+                EventResult::Consumed
+            }
+            WidgetEvent::DragLeave => {
+                self.set_over(false);
+                EventResult::Consumed
+            }
+            WidgetEvent::Drop { data, x, y } => {
+                self.set_over(false);
+                match data.get::<T>() {
+                    Some(value) => {
+                        (self.on_drop)(value.clone(), *x, *y);
+                        EventResult::Consumed
+                    }
+                    None => EventResult::Ignored,
+                }
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        render_children(engine, state, taffy_node, parent_pos, clip_rect, path)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::state::{FrameworkState, NodeId, UiArena};
+use crate::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use crate::window::{current_window, WindowId, MAIN_WINDOW};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::Style;
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Ramkasiz (decorations: false) oynalar uchun o'zimizning title bar hududi.
+/// Bosib sudralsa oyna ko'chadi, ikki marta bosilsa kattalashadi/kichrayadi.
+/// Ichidagi tugmalar (yopish, minimize) hodisani o'zlari yutadi, shuning uchun ular ishlayveradi.
+pub struct DragRegion {
+    pub child: Option<Box<dyn Widget>>,
+    pub style: Style,
+    pub double_click_maximize: bool,
+    window: WindowId,
+    last_press: Option<Instant>,
+}
+
+impl DragRegion {
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            child: Some(Box::new(child)),
+            style: Style::default(),
+            double_click_maximize: true,
+            window: MAIN_WINDOW,
+            last_press: None,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn double_click_maximize(mut self, enabled: bool) -> Self {
+        self.double_click_maximize = enabled;
+        self
+    }
+}
+
+impl Widget for DragRegion {
+    fn type_name(&self) -> &'static str {
+        "DragRegion"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        // Qaysi oynaning ichida qurilayotganimizni eslab qolamiz
+        self.window = current_window();
+
+        let mut child_nodes = Vec::new();
+        if let Some(child) = self.child.take() {
+            let child_id = child.build(arena, engine, ctx);
+            if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+                child_nodes.push(t_node);
+            }
+        }
+
+        let taffy_node = engine.new_node(self.style.clone(), &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::MouseDown {
+                button: MouseButton::Left,
+                ..
+            } => {
+                let now = Instant::now();
+                let is_double = self
+                    .last_press
+                    .is_some_and(|t| now.duration_since(t) < DOUBLE_CLICK);
+
+                if is_double && self.double_click_maximize {
+                    self.last_press = None;
+                    self.window.toggle_maximize();
+                } else {
+                    self.last_press = Some(now);
+                    self.window.drag();
+                }
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            Vec2::new(layout.x, layout.y),
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
@@ -0,0 +1,207 @@
+use crate::reactive::signals::{
+    catch_error, create_effect, create_scope, get_active_scope, panic_message, run_in_scope,
+    scope_is_within, ScopeId, Signal,
+};
+use crate::state::{FrameworkState, NodeId, UiArena};
+use crate::widgets::base::{BuildContext, RenderOutput, Widget};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::Style;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Xatoni tozalab, bolani qaytadan qurishni so'raydigan tutqich
+#[derive(Clone, Copy)]
+pub struct ErrorReset {
+    error: Signal<Option<String>>,
+}
+
+impl ErrorReset {
+    pub fn reset(&self) {
+        self.error.set(None);
+    }
+}
+
+/// Bolasidagi paniklarni (effect, ticker, build, rebuild) ushlab, o'rniga fallback chizadi.
+/// Bitta buzilgan plagin butun ilovani yiqitmaydi.
+pub struct ErrorBoundary {
+    pub child_builder: Box<dyn FnMut() -> Box<dyn Widget> + Send>,
+    pub fallback_builder: Box<dyn FnMut(String, ErrorReset) -> Box<dyn Widget> + Send>,
+    pub error: Signal<Option<String>>,
+
+    pub current_child_id: Option<NodeId>,
+    pub taffy_node: Option<TaffyNode>,
+    showing_fallback: bool,
+    my_id: Option<NodeId>,
+    owner_scope: Option<ScopeId>,
+}
+
+impl ErrorBoundary {
+    pub fn new<C, F>(child_fn: C, fallback_fn: F) -> Self
+    where
+        C: FnMut() -> Box<dyn Widget> + Send + 'static,
+        F: FnMut(String, ErrorReset) -> Box<dyn Widget> + Send + 'static,
+    {
+        Self {
+            child_builder: Box::new(child_fn),
+            fallback_builder: Box::new(fallback_fn),
+            error: Signal::new(None),
+            current_child_id: None,
+            taffy_node: None,
+            showing_fallback: false,
+            my_id: None,
+            owner_scope: None,
+        }
+    }
+
+    /// Tashqaridan (masalan, "Qayta urinish" tugmasidan) tiklash uchun
+    pub fn reset_handle(&self) -> ErrorReset {
+        ErrorReset { error: self.error }
+    }
+
+    // Bolani alohida Scope'da quradi. Build paytida panik bo'lsa, yarim qurilgan
+    // tugunlarni tozalab, fallback'ni quradi.
+    fn build_content(
+        &mut self,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        if self.error.get_untracked().is_none() {
+            let error = self.error;
+            let child_widget = (self.child_builder)();
+
+            let (child_scope, result) = create_scope(|| {
+                catch_error(move |payload| {
+                    if error.get_untracked().is_none() {
+                        error.set(Some(panic_message(payload)));
+                    }
+                });
+                catch_unwind(AssertUnwindSafe(|| child_widget.build(arena, engine, ctx)))
+            });
+
+            match result {
+                Ok(child_id) if error.get_untracked().is_none() => {
+                    self.showing_fallback = false;
+                    return child_id;
+                }
+                Ok(_) => {}
+                Err(payload) => error.set(Some(panic_message(&*payload))),
+            }
+
+            let broken: Vec<NodeId> = arena
+                .node_scopes
+                .iter()
+                .filter(|(_, s)| scope_is_within(**s, child_scope))
+                .map(|(id, _)| *id)
+                .collect();
+            for id in broken {
+                arena.remove_node(id);
+            }
+            crate::reactive::signals::dispose_scope(child_scope);
+        }
+
+        self.showing_fallback = true;
+        let message = self.error.get_untracked().unwrap_or_default();
+        let reset = self.reset_handle();
+        let fallback = (self.fallback_builder)(message, reset);
+        let (_, fallback_id) = create_scope(|| fallback.build(arena, engine, ctx));
+        fallback_id
+    }
+}
+
+impl Widget for ErrorBoundary {
+    fn type_name(&self) -> &'static str {
+        "ErrorBoundary"
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        self.owner_scope = get_active_scope();
+
+        let child_id = self.build_content(arena, engine, ctx);
+        self.current_child_id = Some(child_id);
+
+        let mut child_nodes = Vec::new();
+        if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+            child_nodes.push(t_node);
+        }
+
+        let taffy_node = engine.new_node(Style::default(), &child_nodes);
+        let my_id = arena.allocate_node();
+        self.my_id = Some(my_id);
+        self.taffy_node = Some(taffy_node);
+
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        let error = self.error;
+        create_effect(move || {
+            let action = if error.get().is_some() { 1 } else { 0 };
+            crate::reactive::command::CommandQueue::send(
+                crate::reactive::command::UICommand::RebuildNode(my_id, action),
+            );
+        });
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn rebuild(&mut self, state: &mut FrameworkState, engine: &mut LayoutEngine, action: u32) {
+        let has_error = action == 1;
+        if self.showing_fallback == has_error {
+            return;
+        }
+
+        if let Some(old_id) = self.current_child_id {
+            state.drop_queue.borrow_mut().push(old_id);
+        }
+
+        let ctx = BuildContext {};
+        let child_id = run_in_scope(self.owner_scope, || {
+            self.build_content(&mut state.arena, engine, &ctx)
+        });
+        self.current_child_id = Some(child_id);
+
+        if let Some(parent_taffy) = self.taffy_node {
+            if let Some(&child_taffy) = state.arena.taffy_map.get(&child_id) {
+                let _ = engine.taffy.set_children(parent_taffy, &[child_taffy]);
+                let _ = engine.taffy.dirty(parent_taffy);
+            }
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+
+        if let Some(child_id) = self.current_child_id {
+            if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                if let Some(&child_node) = state.arena.taffy_map.get(&child_id) {
+                    let child_output = widget_ref.render(
+                        engine,
+                        state,
+                        child_node,
+                        Vec2::new(layout.x, layout.y),
+                        clip_rect,
+                        format!("{}_boundary", path),
+                    );
+                    output.extend(child_output);
+                }
+                state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+            }
+        }
+        output
+    }
+}
@@ -1,5 +1,5 @@
 use crate::reactive::command::{CommandQueue, UICommand};
-use crate::reactive::signals::Signal;
+use crate::reactive::signals::{create_scope, get_active_scope, run_in_scope, ScopeId, Signal};
 use crate::state::{FrameworkState, NodeId, UiArena};
 use crate::widgets::base::{BuildContext, RenderOutput, Widget};
 use glam::Vec2;
@@ -19,6 +19,7 @@ pub struct ForList<T: Clone + PartialEq + 'static> {
     pub child_nodes: Vec<NodeId>,
     pub taffy_node: Option<TaffyNode>,
     my_id: Option<NodeId>,
+    owner_scope: Option<ScopeId>,
 }
 
 impl<T: Clone + PartialEq + 'static> ForList<T> {
@@ -38,6 +39,7 @@ impl<T: Clone + PartialEq + 'static> ForList<T> {
             child_nodes: Vec::new(),
             taffy_node: None,
             my_id: None,
+            owner_scope: None,
         }
     }
 
@@ -61,6 +63,7 @@ impl<T: Clone + PartialEq + Send + 'static> Widget for ForList<T> {
         let mut t_children = Vec::new();
         let initial_items = self.items.get_untracked();
         self.current_items = initial_items.clone();
+        self.owner_scope = get_active_scope();
 
         for item in initial_items {
             let child_widget = (self.builder)(item);
@@ -124,8 +127,8 @@ impl<T: Clone + PartialEq + Send + 'static> Widget for ForList<T> {
             let item = new_items[i].clone();
             let child_widget = (self.builder)(item);
             let ctx = BuildContext {};
-            let (_, child_id) = crate::reactive::signals::create_scope(|| {
-                child_widget.build(&mut state.arena, engine, &ctx)
+            let (_, child_id) = run_in_scope(self.owner_scope, || {
+                create_scope(|| child_widget.build(&mut state.arena, engine, &ctx))
             });
             new_middle_nodes.push(child_id);
         }
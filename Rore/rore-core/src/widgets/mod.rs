@@ -1,7 +1,11 @@
 pub mod base;
 pub use base::*;
 
+pub mod drag_drop;
+pub mod drag_region;
+pub mod error_boundary;
 pub mod list;
 pub mod portal;
+pub mod provider;
 pub mod show;
 pub mod suspense;
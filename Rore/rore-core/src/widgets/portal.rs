@@ -2,7 +2,7 @@ use crate::state::{FrameworkState, NodeId, UiArena};
 use crate::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
 use glam::Vec2;
 use rore_layout::{LayoutEngine, Node as TaffyNode};
-use rore_types::Style;
+use rore_types::{Position, Style, Thickness, Val};
 
 pub struct Portal {
     pub anchor_id: String,
@@ -10,6 +10,12 @@ pub struct Portal {
     pub current_child_id: Option<NodeId>,
     pub on_close: Option<Box<dyn FnMut() + Send + 'static>>,
     pub backdrop_color: Option<[f32; 4]>,
+    // Anchor o'rniga ekrandagi aniq nuqta (kontekst menyu, tooltip)
+    pub position: Option<Vec2>,
+    // Ekranga nisbatan tekislash (dialog markazda, toast'lar burchakda)
+    pub align: Option<Vec2>,
+    // true: backdrop yo'q, tashqaridagi bosishlar pastga o'tadi
+    pub passthrough: bool,
     node_id: Option<NodeId>,
 }
 
@@ -21,10 +27,27 @@ impl Portal {
             current_child_id: None,
             on_close: None,
             backdrop_color: Some([0.0, 0.0, 0.0, 0.2]), // Yengil shaffof qora fon (Backdrop)
+            position: None,
+            align: None,
+            passthrough: false,
             node_id: None,
         }
     }
 
+    /// Ekran koordinatasida ochiladi. Sig'masa, ekran ichiga suriladi.
+    pub fn at(x: f32, y: f32) -> Self {
+        let mut portal = Self::new("");
+        portal.position = Some(Vec2::new(x, y));
+        portal
+    }
+
+    /// Ekranning bo'sh joyiga nisbatan: `aligned(0.5, 0.5)` markaz, `aligned(1.0, 1.0)` o'ng-past burchak.
+    pub fn aligned(x: f32, y: f32) -> Self {
+        let mut portal = Self::new("");
+        portal.align = Some(Vec2::new(x, y));
+        portal
+    }
+
     pub fn child(mut self, w: impl Widget + 'static) -> Self {
         self.child = Some(Box::new(w));
         self
@@ -39,6 +62,13 @@ impl Portal {
         self.backdrop_color = None;
         self
     }
+
+    // Backdrop'siz: faqat bolaning o'zi bosishlarni ushlaydi
+    pub fn passthrough(mut self) -> Self {
+        self.backdrop_color = None;
+        self.passthrough = true;
+        self
+    }
 }
 
 impl Widget for Portal {
@@ -57,7 +87,20 @@ impl Widget for Portal {
         engine: &mut LayoutEngine,
         ctx: &BuildContext,
     ) -> NodeId {
-        let style = Style::default();
+        let floating = self.position.is_some() || self.align.is_some();
+        let style = match floating {
+            // Ota layout'ini surmasligi uchun oqimdan chiqaramiz
+            true => Style {
+                position: Position::Absolute,
+                inset: Thickness {
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            false => Style::default(),
+        };
         let taffy_node = engine.new_leaf(style);
         let my_id = arena.allocate_node();
         self.node_id = Some(my_id);
@@ -80,7 +123,20 @@ impl Widget for Portal {
         if !arena.overlays.contains(&taffy_node) {
             arena.overlays.push(taffy_node);
         }
-        arena.anchors.insert(taffy_node, self.anchor_id.clone());
+        match (self.position, self.align) {
+            (Some(pos), _) => {
+                arena.overlay_positions.insert(taffy_node, pos);
+            }
+            (None, Some(align)) => {
+                arena.overlay_aligns.insert(taffy_node, align);
+            }
+            (None, None) => {
+                arena.anchors.insert(taffy_node, self.anchor_id.clone());
+            }
+        }
+        if self.passthrough {
+            arena.passthrough_overlays.insert(taffy_node);
+        }
 
         engine.mark_interactive(taffy_node);
         arena.widgets[my_id.0 as usize] = Some(self);
@@ -89,7 +145,7 @@ impl Widget for Portal {
 
     fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
         match event {
-            WidgetEvent::MouseDown => {
+            WidgetEvent::MouseDown { .. } => {
                 // Foydalanuvchi Backdrop ga (Dropdown dan tashqariga) bosdi!
                 if let Some(cb) = &mut self.on_close {
                     cb();
@@ -123,10 +179,14 @@ impl Widget for Portal {
                 target_color_start: color,
                 target_color_end: color,
                 gradient_angle: 0.0,
+                gradient: None,
+                image: None,
                 border_radius: [0.0; 4],
                 border_width: [0.0; 4],
                 border_color: [0.0; 4],
                 target_border_color: [0.0; 4],
+                border_style: [rore_types::BorderStyle::Solid; 4],
+                border_dash: [0.0; 2],
                 shadow_color: [0.0; 4],
                 shadow_offset: Vec2::ZERO,
                 shadow_blur: 0.0,
@@ -134,6 +194,7 @@ impl Widget for Portal {
                 clip_rect: [-10000.0, -10000.0, 20000.0, 20000.0],
                 anim_start_time: 0.0,
                 anim_duration: 0.0,
+                transform: glam::Affine2::IDENTITY,
             };
             output.sparse_instances.push((my_id.0, backdrop));
         }
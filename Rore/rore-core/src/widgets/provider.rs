@@ -0,0 +1,183 @@
+use crate::reactive::signals::{create_scope, dispose_scope, on_cleanup, provide_context};
+use crate::state::{FrameworkState, NodeId, UiArena};
+use crate::widgets::base::{BuildContext, RenderOutput, Widget};
+use glam::Vec2;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+
+/// Kontekstni faqat o'z bolalari (subtree) uchun beradi.
+/// Masalan: har bir panelga alohida Theme, har bir tabga alohida Store.
+/// Qiymat build paytida Logic Thread'da yaratiladi, shuning uchun `T` Send bo'lishi shart emas
+/// (masalan, `Rc<RefCell<Store>>`).
+pub struct ContextProvider<T: Clone + 'static> {
+    pub init: Box<dyn FnOnce() -> T + Send>,
+    pub child: Box<dyn Widget>,
+}
+
+impl<T: Clone + 'static> ContextProvider<T> {
+    pub fn new(init: impl FnOnce() -> T + Send + 'static, child: impl Widget + 'static) -> Self {
+        Self {
+            init: Box::new(init),
+            child: Box::new(child),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Widget for ContextProvider<T> {
+    fn type_name(&self) -> &'static str {
+        "ContextProvider"
+    }
+
+    // Shaffof qobiq: o'zi Arena'da tugun egallamaydi, bolaning ID sini qaytaradi
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let this = *self;
+        let (scope, child_id) = create_scope(|| {
+            provide_context((this.init)());
+            this.child.build(arena, engine, ctx)
+        });
+        // dispose_scope bolalarga o'tmaydi: ota Scope yopilganda kontekst va
+        // bola effektlari ham yopilishi uchun o'zimizni unga bog'laymiz
+        on_cleanup(move || dispose_scope(scope));
+        arena.node_scopes.entry(child_id).or_insert(scope);
+        child_id
+    }
+
+    fn render(
+        &self,
+        _engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        _taffy_node: TaffyNode,
+        _parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        RenderOutput::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::signals::{create_effect, use_context, Signal};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{mpsc, Arc};
+
+    // Build paytida ko'rgan kontekstini yuboradi
+    struct Probe(mpsc::Sender<Option<String>>);
+
+    impl Widget for Probe {
+        fn type_name(&self) -> &'static str {
+            "Probe"
+        }
+
+        fn build(
+            self: Box<Self>,
+            arena: &mut UiArena,
+            _engine: &mut LayoutEngine,
+            _ctx: &BuildContext,
+        ) -> NodeId {
+            let seen = use_context::<Rc<String>>().map(|theme| theme.to_string());
+            self.0.send(seen).unwrap();
+            arena.allocate_node()
+        }
+
+        fn render(
+            &self,
+            _engine: &LayoutEngine,
+            _state: &mut FrameworkState,
+            _taffy_node: TaffyNode,
+            _parent_pos: Vec2,
+            _clip_rect: Option<[f32; 4]>,
+            _path: String,
+        ) -> RenderOutput {
+            RenderOutput::new()
+        }
+    }
+
+    #[test]
+    fn non_send_context_reaches_only_its_subtree() {
+        let (tx, rx) = mpsc::channel();
+        let mut arena = UiArena::new();
+        let mut engine = LayoutEngine::new();
+        let ctx = BuildContext {};
+
+        let panel = ContextProvider::new(|| Rc::new("dark".to_string()), Probe(tx.clone()));
+        Box::new(panel).build(&mut arena, &mut engine, &ctx);
+        Box::new(Probe(tx)).build(&mut arena, &mut engine, &ctx);
+
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("dark"));
+        assert_eq!(rx.recv().unwrap(), None);
+    }
+
+    // Build paytida effekt yaratadi, tugunini esa ichki Scope'da (shaffof o'ram kabi) ajratadi
+    struct Subscriber {
+        source: Signal<u32>,
+        runs: Arc<AtomicU32>,
+    }
+
+    impl Widget for Subscriber {
+        fn type_name(&self) -> &'static str {
+            "Subscriber"
+        }
+
+        fn build(
+            self: Box<Self>,
+            arena: &mut UiArena,
+            _engine: &mut LayoutEngine,
+            _ctx: &BuildContext,
+        ) -> NodeId {
+            let Subscriber { source, runs } = *self;
+            create_effect(move || {
+                source.get();
+                runs.fetch_add(1, Ordering::SeqCst);
+            });
+            create_scope(|| arena.allocate_node()).1
+        }
+
+        fn render(
+            &self,
+            _engine: &LayoutEngine,
+            _state: &mut FrameworkState,
+            _taffy_node: TaffyNode,
+            _parent_pos: Vec2,
+            _clip_rect: Option<[f32; 4]>,
+            _path: String,
+        ) -> RenderOutput {
+            RenderOutput::new()
+        }
+    }
+
+    #[test]
+    fn parent_rebuild_stops_effects_of_old_subtree() {
+        let source = Signal::new(0u32);
+        let runs = Arc::new(AtomicU32::new(0));
+        let mut arena = UiArena::new();
+        let mut engine = LayoutEngine::new();
+        let ctx = BuildContext {};
+
+        let mut build_parent = |runs: &Arc<AtomicU32>| {
+            let panel = ContextProvider::new(
+                || Rc::new(()),
+                Subscriber {
+                    source,
+                    runs: runs.clone(),
+                },
+            );
+            create_scope(|| Box::new(panel).build(&mut arena, &mut engine, &ctx)).0
+        };
+
+        // Ota qayta qurilganda eski Scope yopiladi, yangisi quriladi
+        let old = build_parent(&runs);
+        dispose_scope(old);
+        build_parent(&runs);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        source.set(1);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+}
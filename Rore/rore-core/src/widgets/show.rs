@@ -1,3 +1,4 @@
+use crate::reactive::signals::{create_scope, get_active_scope, run_in_scope, ScopeId};
 use crate::state::{FrameworkState, NodeId, UiArena};
 use crate::widgets::base::{BuildContext, IntoProp, Prop, RenderOutput, Widget};
 use glam::Vec2;
@@ -13,6 +14,7 @@ pub struct Show {
     pub taffy_node: Option<TaffyNode>,
     pub is_currently_true: bool,
     my_id: Option<NodeId>,
+    owner_scope: Option<ScopeId>,
 }
 
 impl Show {
@@ -29,6 +31,7 @@ impl Show {
             taffy_node: None,
             is_currently_true: false,
             my_id: None,
+            owner_scope: None,
         }
     }
 }
@@ -53,6 +56,7 @@ impl Widget for Show {
         };
 
         self.is_currently_true = condition_val;
+        self.owner_scope = get_active_scope();
 
         let child_widget = if condition_val {
             (self.true_builder)()
@@ -114,8 +118,8 @@ impl Widget for Show {
         let ctx = BuildContext {};
 
         // YANGI: Bolani xavfsiz Scope bilan quramiz
-        let (_, child_id) = crate::reactive::signals::create_scope(|| {
-            child_widget.build(&mut state.arena, engine, &ctx)
+        let (_, child_id) = run_in_scope(self.owner_scope, || {
+            create_scope(|| child_widget.build(&mut state.arena, engine, &ctx))
         });
         self.current_child_id = Some(child_id);
 
@@ -0,0 +1,430 @@
+use crate::app::RoreUserEvent;
+use crate::reactive::signals::create_effect;
+use crate::widgets::base::{IntoProp, Prop, Widget};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use winit::dpi::{LogicalPosition, LogicalSize};
+use winit::event_loop::EventLoopProxy;
+use winit::window::{Fullscreen, Icon, WindowBuilder, WindowLevel};
+
+/// Rore oynasining identifikatori. Asosiy oyna har doim `MAIN_WINDOW`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowId(pub u32);
+
+pub const MAIN_WINDOW: WindowId = WindowId(0);
+
+static NEXT_WINDOW_ID: AtomicU32 = AtomicU32::new(1);
+pub(crate) static WINDOW_PROXY: OnceLock<Mutex<EventLoopProxy<RoreUserEvent>>> = OnceLock::new();
+
+/// Oyna ikonkasi: RGBA8 piksellar (width * height * 4 bayt).
+#[derive(Debug, Clone)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Oyna yaratilishidagi barcha sozlamalar (Builder Pattern).
+/// O'lchamlar mantiqiy (logical) piksellarda.
+#[derive(Debug, Clone)]
+pub struct WindowConfig {
+    pub title: String,
+    pub size: (u32, u32),
+    pub position: Option<(i32, i32)>,
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+    pub resizable: bool,
+    pub maximized: bool,
+    pub decorations: bool,
+    pub transparent: bool,
+    pub always_on_top: bool,
+    pub icon: Option<WindowIcon>,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Rore UI App".to_string(),
+            size: (1024, 768),
+            position: None,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            maximized: true,
+            decorations: true,
+            transparent: false,
+            always_on_top: false,
+            icon: None,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            maximized: false,
+            ..Self::default()
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn maximized(mut self, maximized: bool) -> Self {
+        self.maximized = maximized;
+        self
+    }
+
+    // false: OS ramkasi va sarlavha paneli yo'q (o'zimizning title bar + DragRegion)
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn always_on_top(mut self, on_top: bool) -> Self {
+        self.always_on_top = on_top;
+        self
+    }
+
+    pub fn icon(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        self.icon = Some(WindowIcon {
+            rgba,
+            width,
+            height,
+        });
+        self
+    }
+
+    pub(crate) fn to_builder(&self) -> WindowBuilder {
+        let (w, h) = self.size;
+        let mut builder = WindowBuilder::new()
+            .with_title(self.title.clone())
+            .with_inner_size(LogicalSize::new(w, h))
+            .with_resizable(self.resizable)
+            .with_maximized(self.maximized)
+            .with_decorations(self.decorations)
+            .with_transparent(self.transparent)
+            .with_visible(true);
+
+        if let Some((x, y)) = self.position {
+            builder = builder.with_position(LogicalPosition::new(x, y));
+        }
+        if let Some((w, h)) = self.min_size {
+            builder = builder.with_min_inner_size(LogicalSize::new(w, h));
+        }
+        if let Some((w, h)) = self.max_size {
+            builder = builder.with_max_inner_size(LogicalSize::new(w, h));
+        }
+        if self.always_on_top {
+            builder = builder.with_window_level(WindowLevel::AlwaysOnTop);
+        }
+        if let Some(icon) = &self.icon {
+            match Icon::from_rgba(icon.rgba.clone(), icon.width, icon.height) {
+                Ok(icon) => builder = builder.with_window_icon(Some(icon)),
+                Err(e) => eprintln!("Rore: oyna ikonkasi yaroqsiz: {}", e),
+            }
+        }
+        builder
+    }
+}
+
+/// Ishlab turgan oynaga Main Thread'da bajariladigan buyruqlar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowControl {
+    SetTitle(String),
+    Minimize,
+    Maximize(bool),
+    ToggleMaximize,
+    Fullscreen(bool),
+    AlwaysOnTop(bool),
+    Focus,
+    DragWindow,
+}
+
+impl WindowControl {
+    pub(crate) fn apply(self, window: &winit::window::Window) {
+        match self {
+            WindowControl::SetTitle(title) => window.set_title(&title),
+            WindowControl::Minimize => window.set_minimized(true),
+            WindowControl::Maximize(on) => window.set_maximized(on),
+            WindowControl::ToggleMaximize => window.set_maximized(!window.is_maximized()),
+            WindowControl::Fullscreen(on) => {
+                window.set_fullscreen(on.then_some(Fullscreen::Borderless(None)))
+            }
+            WindowControl::AlwaysOnTop(on) => window.set_window_level(if on {
+                WindowLevel::AlwaysOnTop
+            } else {
+                WindowLevel::Normal
+            }),
+            WindowControl::Focus => window.focus_window(),
+            WindowControl::DragWindow => {
+                let _ = window.drag_window();
+            }
+        }
+    }
+}
+
+// Reaktiv oyna boshqaruvi: istalgan oqimdan chaqirish mumkin
+impl WindowId {
+    pub fn control(self, control: WindowControl) {
+        wake_event_loop(RoreUserEvent::Control(self, control));
+    }
+
+    pub fn set_title(self, title: &str) {
+        self.control(WindowControl::SetTitle(title.to_string()));
+    }
+
+    /// Sarlavhani signalga bog'laydi: signal o'zgarsa, oyna sarlavhasi ham o'zgaradi.
+    pub fn bind_title(self, title: impl IntoProp<String>) {
+        match title.into_prop() {
+            Prop::Static(t) => self.set_title(&t),
+            Prop::Dynamic(mut f) => {
+                create_effect(move || {
+                    let t = f();
+                    self.set_title(&t);
+                });
+            }
+        }
+    }
+
+    pub fn minimize(self) {
+        self.control(WindowControl::Minimize);
+    }
+
+    pub fn maximize(self, maximized: bool) {
+        self.control(WindowControl::Maximize(maximized));
+    }
+
+    pub fn toggle_maximize(self) {
+        self.control(WindowControl::ToggleMaximize);
+    }
+
+    pub fn set_fullscreen(self, fullscreen: bool) {
+        self.control(WindowControl::Fullscreen(fullscreen));
+    }
+
+    pub fn set_always_on_top(self, on_top: bool) {
+        self.control(WindowControl::AlwaysOnTop(on_top));
+    }
+
+    pub fn focus(self) {
+        self.control(WindowControl::Focus);
+    }
+
+    /// Oynani sichqoncha bilan sudrashni boshlaydi (chap tugma bosilgan paytda chaqiriladi).
+    pub fn drag(self) {
+        self.control(WindowControl::DragWindow);
+    }
+
+    pub fn close(self) {
+        close_window(self);
+    }
+}
+
+pub(crate) enum WindowOp {
+    Open {
+        id: WindowId,
+        config: WindowConfig,
+        view: Box<dyn FnOnce() -> Box<dyn Widget> + Send>,
+    },
+    Close(WindowId),
+}
+
+// Istalgan oqimdan kelgan ochish/yopish so'rovlari; Logic Thread tsiklida bo'shatiladi
+static PENDING_WINDOW_OPS: Mutex<Vec<WindowOp>> = Mutex::new(Vec::new());
+
+thread_local! {
+    // Logic Thread'da hozir qaysi oyna bilan ishlayapmiz (buyruqlarni to'g'ri oynaga yo'naltirish uchun)
+    static CURRENT_WINDOW: Cell<Option<WindowId>> = const { Cell::new(None) };
+}
+
+pub(crate) fn take_window_ops() -> Vec<WindowOp> {
+    std::mem::take(&mut *PENDING_WINDOW_OPS.lock().unwrap())
+}
+
+fn push_window_op(op: WindowOp) {
+    PENDING_WINDOW_OPS.lock().unwrap().push(op);
+    // Logic Thread uxlayotgan bo'lsa, event loop orqali uyg'otiladi
+    wake_event_loop(RoreUserEvent::WakeUp);
+}
+
+pub(crate) fn set_current_window(id: Option<WindowId>) {
+    CURRENT_WINDOW.with(|c| c.set(id));
+}
+
+/// Joriy kod qaysi oynaga tegishli. Effect va tickerlar o'z oynasining Scope kontekstidan oladi,
+/// Logic Thread'dan tashqaridagi oqimlar esa asosiy oynaga tushadi.
+pub fn current_window() -> WindowId {
+    match CURRENT_WINDOW.with(|c| c.get()) {
+        Some(fallback) => crate::reactive::signals::use_context::<WindowId>().unwrap_or(fallback),
+        None => MAIN_WINDOW,
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    // Testlarda event loop yo'q: yuborilgan oyna buyruqlari shu yerda yig'iladi
+    static SENT_CONTROLS: std::cell::RefCell<Vec<(WindowId, WindowControl)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn wake_event_loop(event: RoreUserEvent) {
+    #[cfg(test)]
+    if let RoreUserEvent::Control(id, control) = &event {
+        SENT_CONTROLS.with(|sent| sent.borrow_mut().push((*id, control.clone())));
+    }
+    if let Some(proxy) = WINDOW_PROXY.get() {
+        let _ = proxy.lock().unwrap().send_event(event);
+    }
+}
+
+/// Yangi oyna ochadi (istalgan oqimdan). `view` Logic Thread'da, umumiy ReactiveRuntime ichida
+/// quriladi, shuning uchun signallar oynalar orasida bemalol oqadi.
+pub fn open_window<F>(title: &str, width: u32, height: u32, view: F) -> WindowId
+where
+    F: FnOnce() -> Box<dyn Widget> + Send + 'static,
+{
+    open_window_with(WindowConfig::new(title).size(width, height), view)
+}
+
+/// `open_window` ning to'liq sozlanadigan varianti.
+pub fn open_window_with<F>(config: WindowConfig, view: F) -> WindowId
+where
+    F: FnOnce() -> Box<dyn Widget> + Send + 'static,
+{
+    let id = WindowId(NEXT_WINDOW_ID.fetch_add(1, Ordering::SeqCst));
+    push_window_op(WindowOp::Open {
+        id,
+        config,
+        view: Box::new(view),
+    });
+    id
+}
+
+/// Oynani yopadi. Asosiy oynani yopish ilovadan chiqishni bildiradi.
+pub fn close_window(id: WindowId) {
+    push_window_op(WindowOp::Close(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::signals::{create_scope, dispose_scope, Signal};
+    use winit::dpi::Size;
+
+    fn sent_controls() -> Vec<(WindowId, WindowControl)> {
+        SENT_CONTROLS.with(|sent| std::mem::take(&mut *sent.borrow_mut()))
+    }
+
+    #[test]
+    fn config_maps_onto_winit_builder() {
+        let builder = WindowConfig::new("Grafik")
+            .size(800, 600)
+            .min_size(320, 240)
+            .max_size(1600, 1200)
+            .decorations(false)
+            .always_on_top(true)
+            .icon(vec![255; 16 * 16 * 4], 16, 16)
+            .to_builder();
+        let attrs = builder.window_attributes();
+
+        assert_eq!(attrs.title, "Grafik");
+        assert_eq!(
+            attrs.inner_size,
+            Some(Size::Logical(LogicalSize::new(800.0, 600.0)))
+        );
+        assert_eq!(
+            attrs.min_inner_size,
+            Some(Size::Logical(LogicalSize::new(320.0, 240.0)))
+        );
+        assert_eq!(
+            attrs.max_inner_size,
+            Some(Size::Logical(LogicalSize::new(1600.0, 1200.0)))
+        );
+        assert!(!attrs.decorations && !attrs.maximized);
+        assert_eq!(attrs.window_level, WindowLevel::AlwaysOnTop);
+        assert!(attrs.window_icon.is_some());
+
+        let defaults = WindowConfig::default().to_builder();
+        let attrs = defaults.window_attributes();
+        assert!(attrs.decorations && attrs.maximized && attrs.resizable);
+        assert_eq!(attrs.min_inner_size, None);
+        assert_eq!(attrs.window_level, WindowLevel::Normal);
+    }
+
+    #[test]
+    fn invalid_icon_is_skipped() {
+        // Piksellar soni o'lchamga mos emas: oyna ikonkasiz ochiladi
+        let builder = WindowConfig::new("Ikonka")
+            .icon(vec![0; 10], 16, 16)
+            .to_builder();
+        assert!(builder.window_attributes().window_icon.is_none());
+        assert_eq!(builder.window_attributes().title, "Ikonka");
+    }
+
+    #[test]
+    fn bind_title_follows_signal() {
+        sent_controls();
+        let window = WindowId(7_001);
+        let (scope, title) = create_scope(|| {
+            let title = Signal::new("Birinchi".to_string());
+            window.bind_title(title);
+            title
+        });
+        assert_eq!(
+            sent_controls(),
+            [(window, WindowControl::SetTitle("Birinchi".into()))]
+        );
+
+        title.set("Ikkinchi".to_string());
+        assert_eq!(
+            sent_controls(),
+            [(window, WindowControl::SetTitle("Ikkinchi".into()))]
+        );
+
+        // Scope yopilgach sarlavha endi kuzatilmaydi
+        dispose_scope(scope);
+        title.set("Uchinchi".to_string());
+        assert!(sent_controls().is_empty());
+
+        window.bind_title("Statik");
+        assert_eq!(
+            sent_controls(),
+            [(window, WindowControl::SetTitle("Statik".into()))]
+        );
+    }
+}
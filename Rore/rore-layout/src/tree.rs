@@ -80,48 +80,67 @@ impl LayoutEngine {
 
     pub fn compute(&mut self, width: f32, height: f32) {
         if let Some(root) = self.root {
-            let available_space = taffy::geometry::Size {
-                width: taffy::style::AvailableSpace::Definite(width),
-                height: taffy::style::AvailableSpace::Definite(height),
-            };
-
-            let funcs = &self.measure_funcs;
-            let _ = self.taffy.compute_layout_with_measure(
+            self.compute_with(
                 root,
-                available_space,
-                |known_dims, avail_space, node_id, _ctx, _style| {
-                    if let Some(f) = funcs.get(&node_id) {
-                        let w = match known_dims.width {
-                            Some(w) => w,
-                            None => match avail_space.width {
-                                taffy::style::AvailableSpace::Definite(w) => w,
-                                taffy::style::AvailableSpace::MinContent => 0.0,
-                                taffy::style::AvailableSpace::MaxContent => f32::INFINITY,
-                            },
-                        };
-                        let h = match known_dims.height {
-                            Some(h) => h,
-                            None => match avail_space.height {
-                                taffy::style::AvailableSpace::Definite(h) => h,
-                                taffy::style::AvailableSpace::MinContent => 0.0,
-                                taffy::style::AvailableSpace::MaxContent => f32::INFINITY,
-                            },
-                        };
-
-                        let (mw, mh) = f(w, h);
-
-                        taffy::geometry::Size {
-                            width: (mw + 0.5).ceil(),
-                            height: (mh + 0.5).ceil(),
-                        }
-                    } else {
-                        taffy::geometry::Size::ZERO
-                    }
-                },
+                taffy::style::AvailableSpace::Definite(width),
+                taffy::style::AvailableSpace::Definite(height),
             );
         }
     }
 
+    // Daraxtga ulanmagan mustaqil tugun (masalan, drag preview) o'z kontenti bo'yicha o'lchanadi
+    pub fn compute_detached(&mut self, node: Node) {
+        self.compute_with(
+            node,
+            taffy::style::AvailableSpace::MaxContent,
+            taffy::style::AvailableSpace::MaxContent,
+        );
+    }
+
+    fn compute_with(
+        &mut self,
+        root: Node,
+        width: taffy::style::AvailableSpace,
+        height: taffy::style::AvailableSpace,
+    ) {
+        let available_space = taffy::geometry::Size { width, height };
+
+        let funcs = &self.measure_funcs;
+        let _ = self.taffy.compute_layout_with_measure(
+            root,
+            available_space,
+            |known_dims, avail_space, node_id, _ctx, _style| {
+                if let Some(f) = funcs.get(&node_id) {
+                    let w = match known_dims.width {
+                        Some(w) => w,
+                        None => match avail_space.width {
+                            taffy::style::AvailableSpace::Definite(w) => w,
+                            taffy::style::AvailableSpace::MinContent => 0.0,
+                            taffy::style::AvailableSpace::MaxContent => f32::INFINITY,
+                        },
+                    };
+                    let h = match known_dims.height {
+                        Some(h) => h,
+                        None => match avail_space.height {
+                            taffy::style::AvailableSpace::Definite(h) => h,
+                            taffy::style::AvailableSpace::MinContent => 0.0,
+                            taffy::style::AvailableSpace::MaxContent => f32::INFINITY,
+                        },
+                    };
+
+                    let (mw, mh) = f(w, h);
+
+                    taffy::geometry::Size {
+                        width: (mw + 0.5).ceil(),
+                        height: (mh + 0.5).ceil(),
+                    }
+                } else {
+                    taffy::geometry::Size::ZERO
+                }
+            },
+        );
+    }
+
     pub fn get_final_layout(&self, node: Node, parent_x: f32, parent_y: f32) -> ComputedLayout {
         let layout_res = self.taffy.layout(node);
         let layout = match layout_res {
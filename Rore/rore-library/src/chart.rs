@@ -2,7 +2,9 @@ use glam::Vec2;
 use std::cell::RefCell;
 use std::collections::HashSet;
 
+use rore_core::gesture::Gesture;
 use rore_core::reactive::command::{CommandQueue, UICommand};
+use rore_core::reactive::persist::create_persisted_signal;
 use rore_core::reactive::signals::{create_effect, Signal};
 use rore_core::state::{FrameworkState, NodeId, UiArena};
 use rore_core::widgets::base::{
@@ -59,6 +61,14 @@ impl CandlestickChart {
         }
     }
 
+    /// Pan va zoom holati ilova qayta ochilganda tiklanadi (`{key}.pan_x`, `{key}.zoom` ...)
+    pub fn persisted(key: &str, data: Signal<Vec<CandleData>>) -> Self {
+        let pan_x = create_persisted_signal(&format!("{}.pan_x", key), 0.0f32);
+        let pan_y = create_persisted_signal(&format!("{}.pan_y", key), 0.0f32);
+        let zoom = create_persisted_signal(&format!("{}.zoom", key), 1.0f32);
+        Self::new(data, pan_x, pan_y, zoom).id(key)
+    }
+
     pub fn id(mut self, id: &str) -> Self {
         self.id = Some(id.to_string());
         self
@@ -168,6 +178,29 @@ impl Widget for CandlestickChart {
                 }
                 EventResult::Consumed
             }
+            // Sensor va trackpad: ikki barmoq bilan kattalashtirish
+            WidgetEvent::Gesture(Gesture::Pinch { scale, .. }) => {
+                self.zoom.update(|z| *z = (*z * scale).clamp(0.1, 20.0));
+
+                if let Some(id) = self.node_id {
+                    if !state.sparse_update_queue.contains(&id) {
+                        state.sparse_update_queue.push(id);
+                    }
+                }
+                EventResult::Consumed
+            }
+            // Bitta barmoq MouseDrag orqali suriladi, ikki barmoqli pan shu yerda
+            WidgetEvent::Gesture(Gesture::Pan { dx, dy, pointers }) if *pointers >= 2 => {
+                self.pan_x.update(|pan| *pan += dx);
+                self.pan_y.update(|pan| *pan += dy);
+
+                if let Some(id) = self.node_id {
+                    if !state.sparse_update_queue.contains(&id) {
+                        state.sparse_update_queue.push(id);
+                    }
+                }
+                EventResult::Consumed
+            }
             _ => EventResult::Ignored,
         }
     }
@@ -262,10 +295,14 @@ impl Widget for CandlestickChart {
                     target_color_start: grid_color_arr,
                     target_color_end: grid_color_arr,
                     gradient_angle: 0.0,
+                    gradient: None,
+                    image: None,
                     border_radius: [0.0; 4],
                     border_width: [0.0; 4],
                     border_color: [0.0; 4],
                     target_border_color: [0.0; 4],
+                    border_style: [rore_types::BorderStyle::Solid; 4],
+                    border_dash: [0.0; 2],
                     shadow_color: [0.0; 4],
                     shadow_offset: Vec2::ZERO,
                     shadow_blur: 0.0,
@@ -273,6 +310,7 @@ impl Widget for CandlestickChart {
                     clip_rect: my_clip,
                     anim_start_time: 0.0,
                     anim_duration: 0.0,
+                    transform: glam::Affine2::IDENTITY,
                 };
                 output.sparse_instances.push((grid_id, grid_inst));
             }
@@ -320,10 +358,14 @@ impl Widget for CandlestickChart {
                         target_color_start: color,
                         target_color_end: color,
                         gradient_angle: 0.0,
+                        gradient: None,
+                        image: None,
                         border_radius: [0.0; 4],
                         border_width: [0.0; 4],
                         border_color: [0.0; 4],
                         target_border_color: [0.0; 4],
+                        border_style: [rore_types::BorderStyle::Solid; 4],
+                        border_dash: [0.0; 2],
                         shadow_color: [0.0; 4],
                         shadow_offset: Vec2::ZERO,
                         shadow_blur: 0.0,
@@ -331,6 +373,7 @@ impl Widget for CandlestickChart {
                         clip_rect: my_clip,
                         anim_start_time: 0.0,
                         anim_duration: 0.0,
+                        transform: glam::Affine2::IDENTITY,
                     };
                     output.sparse_instances.push((wick_id, wick_inst));
 
@@ -346,10 +389,14 @@ impl Widget for CandlestickChart {
                         target_color_start: color,
                         target_color_end: color,
                         gradient_angle: 0.0,
+                        gradient: None,
+                        image: None,
                         border_radius: [1.0; 4],
                         border_width: [0.0; 4],
                         border_color: [0.0; 4],
                         target_border_color: [0.0; 4],
+                        border_style: [rore_types::BorderStyle::Solid; 4],
+                        border_dash: [0.0; 2],
                         shadow_color: [0.0; 4],
                         shadow_offset: Vec2::ZERO,
                         shadow_blur: 0.0,
@@ -357,6 +404,7 @@ impl Widget for CandlestickChart {
                         clip_rect: my_clip,
                         anim_start_time: 0.0,
                         anim_duration: 0.0,
+                        transform: glam::Affine2::IDENTITY,
                     };
                     output.sparse_instances.push((body_id, body_inst));
                 }
@@ -371,10 +419,14 @@ impl Widget for CandlestickChart {
             target_color_start: [0.0; 4],
             target_color_end: [0.0; 4],
             gradient_angle: 0.0,
+            gradient: None,
+            image: None,
             border_radius: [0.0; 4],
             border_width: [0.0; 4],
             border_color: [0.0; 4],
             target_border_color: [0.0; 4],
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             shadow_color: [0.0; 4],
             shadow_offset: Vec2::ZERO,
             shadow_blur: 0.0,
@@ -382,6 +434,7 @@ impl Widget for CandlestickChart {
             clip_rect: [-10000.0, -10000.0, 0.0, 0.0],
             anim_start_time: 0.0,
             anim_duration: 0.0,
+            transform: glam::Affine2::IDENTITY,
         };
 
         for old_id in self.last_used_ids.borrow().iter() {
@@ -1,6 +1,8 @@
 // Barcha vidjetlarni boshqa crate'lar (masalan main.rs) ko'rishi uchun eksport qilamiz
 pub mod chart;
 pub mod order_book;
+pub mod plot;
 
 pub use chart::*;
 pub use order_book::*;
+pub use plot::{DepthChart, LineChart};
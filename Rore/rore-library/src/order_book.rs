@@ -1,7 +1,8 @@
 use glam::Vec2;
+use rore_core::clipboard::{to_tsv, ClipboardContent, Copyable};
 use rore_core::reactive::signals::Signal;
-use rore_core::state::{NodeId, UiArena};
-use rore_core::widgets::base::{BuildContext, RenderOutput, Widget};
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
 use rore_core::widgets::list::ForList;
 use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_text::widgets::{ScrollView, Text, UiBox};
@@ -127,60 +128,164 @@ impl Widget for OrderBook {
 }
 
 fn build_order_row(row: OrderRow, price_color: Color) -> Box<dyn Widget> {
-    Box::new(
-        UiBox::new()
-            .style(Style {
-                flex_direction: FlexDirection::Row,
-                justify_content: Align::SpaceBetween,
-                padding: Thickness {
-                    left: Val::Px(16.0),
-                    right: Val::Px(16.0),
-                    top: Val::Px(2.0),
-                    bottom: Val::Px(2.0),
-                }, // Zich qatorlar
-                ..Default::default()
-            })
-            // PRICE (Chapga)
-            .child(
-                UiBox::new()
-                    .style(Style {
-                        width: Val::Percent(33.3),
-                        align_items: Align::Start,
-                        ..Default::default()
-                    })
-                    .child(
-                        Text::new(format!("{:.2}", row.price))
-                            .color(price_color)
-                            .size(12.0),
-                    ),
-            )
-            // AMOUNT (O'ngga)
-            .child(
-                UiBox::new()
-                    .style(Style {
-                        width: Val::Percent(33.3),
-                        align_items: Align::End,
-                        ..Default::default()
-                    })
-                    .child(
-                        Text::new(format!("{:.5}", row.amount))
-                            .color(Color::hex("#EAECEF"))
-                            .size(12.0),
-                    ),
-            )
-            // TOTAL (O'ngga)
-            .child(
-                UiBox::new()
-                    .style(Style {
-                        width: Val::Percent(33.3),
-                        align_items: Align::End,
-                        ..Default::default()
-                    })
-                    .child(
-                        Text::new(format!("{:.2}", row.total))
-                            .color(Color::hex("#848E9C"))
-                            .size(12.0),
-                    ),
-            ),
-    )
+    let cells = row.cells();
+    let view = UiBox::new()
+        .style(Style {
+            flex_direction: FlexDirection::Row,
+            justify_content: Align::SpaceBetween,
+            padding: Thickness {
+                left: Val::Px(16.0),
+                right: Val::Px(16.0),
+                top: Val::Px(2.0),
+                bottom: Val::Px(2.0),
+            }, // Zich qatorlar
+            ..Default::default()
+        })
+        // PRICE (Chapga)
+        .child(
+            UiBox::new()
+                .style(Style {
+                    width: Val::Percent(33.3),
+                    align_items: Align::Start,
+                    ..Default::default()
+                })
+                .child(
+                    Text::new(format!("{:.2}", row.price))
+                        .color(price_color)
+                        .size(12.0),
+                ),
+        )
+        // AMOUNT (O'ngga)
+        .child(
+            UiBox::new()
+                .style(Style {
+                    width: Val::Percent(33.3),
+                    align_items: Align::End,
+                    ..Default::default()
+                })
+                .child(
+                    Text::new(format!("{:.5}", row.amount))
+                        .color(Color::hex("#EAECEF"))
+                        .size(12.0),
+                ),
+        )
+        // TOTAL (O'ngga)
+        .child(
+            UiBox::new()
+                .style(Style {
+                    width: Val::Percent(33.3),
+                    align_items: Align::End,
+                    ..Default::default()
+                })
+                .child(
+                    Text::new(format!("{:.2}", row.total))
+                        .color(Color::hex("#848E9C"))
+                        .size(12.0),
+                ),
+        );
+    Box::new(CopyableRow {
+        cells,
+        child: Some(Box::new(view)),
+    })
+}
+
+impl OrderRow {
+    // Ekranda ko'rinadigan formatda (nusxa ham shunday bo'lishi kerak)
+    fn cells(&self) -> [String; 3] {
+        [
+            format!("{:.2}", self.price),
+            format!("{:.5}", self.amount),
+            format!("{:.2}", self.total),
+        ]
+    }
+}
+
+// Bosilganda fokus oladi, Ctrl+C qatorni TSV qilib nusxalaydi (Excel/Sheets ga to'g'ridan-to'g'ri qo'yiladi)
+struct CopyableRow {
+    cells: [String; 3],
+    child: Option<Box<dyn Widget>>,
+}
+
+impl Widget for CopyableRow {
+    fn type_name(&self) -> &'static str {
+        "OrderRow"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn as_copyable(&mut self) -> Option<&mut dyn Copyable> {
+        Some(self)
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let mut child_nodes = Vec::new();
+        if let Some(child) = self.child.take() {
+            let child_id = child.build(arena, engine, ctx);
+            if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+                child_nodes.push(t_node);
+            }
+        }
+        let style = Style {
+            width: Val::Percent(100.0),
+            ..Default::default()
+        };
+        let taffy_node = engine.new_node(style, &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            // Fokus olish uchun (yadro bosilgan tugunni fokuslaydi)
+            WidgetEvent::MouseDown { .. } => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        output.extend(widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            Vec2::new(layout.x, layout.y),
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        ));
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+impl Copyable for CopyableRow {
+    fn copy(&mut self, _state: &mut FrameworkState) -> Option<ClipboardContent> {
+        Some(ClipboardContent::Text(to_tsv([&self.cells])))
+    }
 }
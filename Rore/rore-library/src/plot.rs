@@ -0,0 +1,348 @@
+use glam::Vec2;
+use std::sync::Arc;
+
+use rore_core::path::{FillRule, LineJoin, Path, StrokeStyle};
+use rore_core::reactive::command::{CommandQueue, UICommand};
+use rore_core::reactive::signals::{create_effect, Signal};
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{
+    BuildContext, DisplayCommand, IntoProp, Prop, RenderOutput, Widget,
+};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::{Color, Style};
+
+use rore_types::{impl_layout_modifiers, LayoutModifiers};
+
+use crate::order_book::OrderRow;
+
+// Grafik chiziqlari qirqilmasligi uchun yuqori/pastdan qoldiriladigan joy
+const PLOT_PADDING: f32 = 2.0;
+
+// Barg node yaratadi. Signal o'zgarsa faqat qayta chiziladi (layout emas)
+fn attach_plot(
+    style: &Prop<Style>,
+    id: Option<&str>,
+    arena: &mut UiArena,
+    engine: &mut LayoutEngine,
+    watch: impl Fn() + Send + 'static,
+) -> NodeId {
+    let base_style = match style {
+        Prop::Static(s) => s.clone(),
+        _ => Style::default(),
+    };
+    let taffy_node = engine.new_leaf(base_style);
+    let my_id = arena.allocate_node();
+    arena.taffy_map.insert(my_id, taffy_node);
+    arena.node_map.insert(taffy_node, my_id);
+
+    if let Some(id_str) = id {
+        arena.register_id(id_str, my_id);
+        engine.register_id(id_str, taffy_node);
+    }
+
+    create_effect(move || {
+        watch();
+        CommandQueue::send(UICommand::MarkDirty(my_id, rore_core::state::DIRTY_COLOR));
+    });
+    my_id
+}
+
+fn plot_commands(
+    my_id: NodeId,
+    rect: [f32; 4],
+    meshes: Vec<rore_render::PathMesh>,
+) -> RenderOutput {
+    let mut output = RenderOutput::new();
+    let mut commands = vec![DisplayCommand::PushClip { rect }];
+    commands.extend(meshes.into_iter().filter(|m| !m.is_empty()).map(|mesh| {
+        DisplayCommand::DrawPath {
+            mesh: Arc::new(mesh),
+        }
+    }));
+    commands.push(DisplayCommand::PopClip);
+    // Bo'sh bo'lsa ham yuboramiz: eski chiziqlar o'chishi kerak
+    output.node_commands.push((my_id.0, commands));
+    output
+}
+
+/// Qiymatlar qatorini tekis tarqatilgan nuqtalarga aylantiradi (min/max bo'yicha masshtab).
+pub fn line_points(values: &[f32], rect: [f32; 4]) -> Vec<Vec2> {
+    let [x, y, w, h] = rect;
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let (min, max) = values
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+    let range = (max - min).max(f32::EPSILON);
+    let step = if values.len() > 1 {
+        w / (values.len() - 1) as f32
+    } else {
+        0.0
+    };
+    let inner_h = (h - PLOT_PADDING * 2.0).max(0.0);
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let t = if max > min { (v - min) / range } else { 0.5 };
+            Vec2::new(x + step * i as f32, y + PLOT_PADDING + (1.0 - t) * inner_h)
+        })
+        .collect()
+}
+
+/// Chiziqli grafik. Ostini bo'yash uchun `area`, kichik jadval kataklari uchun `LineChart::sparkline`.
+pub struct LineChart {
+    pub id: Option<String>,
+    pub style: Prop<Style>,
+    pub data: Signal<Vec<f32>>,
+    color: Color,
+    line_width: f32,
+    area: Option<Color>,
+    // Sparkline: oxirgi qiymat birinchisidan past bo'lsa qizil
+    trend_colors: bool,
+    node_id: Option<NodeId>,
+}
+
+impl_layout_modifiers!(LineChart);
+
+impl LineChart {
+    pub fn new(data: Signal<Vec<f32>>) -> Self {
+        Self {
+            id: None,
+            style: Prop::Static(Style::default()),
+            data,
+            color: Color::hex("#3b82f6"),
+            line_width: 1.5,
+            area: None,
+            trend_colors: false,
+            node_id: None,
+        }
+    }
+
+    pub fn sparkline(data: Signal<Vec<f32>>) -> Self {
+        let mut chart = Self::new(data).line_width(1.0).width(80.0).height(24.0);
+        chart.trend_colors = true;
+        chart
+    }
+
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn style(mut self, style: impl IntoProp<Style>) -> Self {
+        self.style = style.into_prop();
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self.trend_colors = false;
+        self
+    }
+
+    pub fn line_width(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    pub fn area(mut self, color: Color) -> Self {
+        self.area = Some(color);
+        self
+    }
+}
+
+impl Widget for LineChart {
+    fn type_name(&self) -> &'static str {
+        "LineChart"
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        _ctx: &BuildContext,
+    ) -> NodeId {
+        let data = self.data;
+        let my_id = attach_plot(&self.style, self.id.as_deref(), arena, engine, move || {
+            let _ = data.get();
+        });
+        self.node_id = Some(my_id);
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let rect = [layout.x, layout.y, layout.width, layout.height];
+        let Some(my_id) = self.node_id else {
+            return RenderOutput::new();
+        };
+
+        let values = self.data.get_untracked();
+        let points = line_points(&values, rect);
+        let mut meshes = Vec::new();
+        if points.len() >= 2 {
+            let color = match (self.trend_colors, values.first(), values.last()) {
+                (true, Some(first), Some(last)) if last < first => Color::hex("#F6465D"),
+                (true, _, _) => Color::hex("#0ECB81"),
+                _ => self.color,
+            };
+
+            if let Some(area) = self.area {
+                let bottom = layout.y + layout.height;
+                let mut outline = points.clone();
+                outline.push(Vec2::new(points[points.len() - 1].x, bottom));
+                outline.push(Vec2::new(points[0].x, bottom));
+                meshes.push(Path::polygon(&outline).fill(FillRule::NonZero, area));
+            }
+            let stroke = StrokeStyle::new(self.line_width).join(LineJoin::Round);
+            meshes.push(Path::polyline(&points).stroke(&stroke, color));
+        }
+        plot_commands(my_id, rect, meshes)
+    }
+}
+
+// Narx bo'yicha tartiblab, jamlangan hajm zinapoyasini quradi (eng yaxshi narxdan chetga qarab)
+fn depth_steps(
+    levels: &[OrderRow],
+    descending: bool,
+    rect: [f32; 4],
+    price_range: (f32, f32),
+    max_total: f32,
+) -> Vec<Vec2> {
+    let [x, y, w, h] = rect;
+    let (lo, hi) = price_range;
+    let to_x = |price: f32| x + (price - lo) / (hi - lo).max(f32::EPSILON) * w;
+    let to_y = |total: f32| y + h - total / max_total.max(f32::EPSILON) * (h - PLOT_PADDING);
+
+    let mut sorted: Vec<&OrderRow> = levels.iter().collect();
+    sorted.sort_by(|a, b| a.price.total_cmp(&b.price));
+    if descending {
+        sorted.reverse();
+    }
+
+    let mut points = Vec::with_capacity(sorted.len() * 2 + 1);
+    let mut total = 0.0;
+    for level in sorted {
+        let px = to_x(level.price);
+        points.push(Vec2::new(px, to_y(total)));
+        total += level.amount;
+        points.push(Vec2::new(px, to_y(total)));
+    }
+    // Oxirgi daraja grafik chetigacha cho'ziladi
+    if !points.is_empty() {
+        let edge = if descending { x } else { x + w };
+        points.push(Vec2::new(edge, to_y(total)));
+    }
+    points
+}
+
+/// Buyurtmalar kitobi chuqurligi: chapda bid (yashil), o'ngda ask (qizil) jamlangan hajmlari.
+pub struct DepthChart {
+    pub id: Option<String>,
+    pub style: Prop<Style>,
+    pub bids: Signal<Vec<OrderRow>>,
+    pub asks: Signal<Vec<OrderRow>>,
+    node_id: Option<NodeId>,
+}
+
+impl_layout_modifiers!(DepthChart);
+
+impl DepthChart {
+    pub fn new(bids: Signal<Vec<OrderRow>>, asks: Signal<Vec<OrderRow>>) -> Self {
+        Self {
+            id: None,
+            style: Prop::Static(Style::default()),
+            bids,
+            asks,
+            node_id: None,
+        }
+    }
+
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn style(mut self, style: impl IntoProp<Style>) -> Self {
+        self.style = style.into_prop();
+        self
+    }
+}
+
+impl Widget for DepthChart {
+    fn type_name(&self) -> &'static str {
+        "DepthChart"
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        _ctx: &BuildContext,
+    ) -> NodeId {
+        let (bids, asks) = (self.bids, self.asks);
+        let my_id = attach_plot(&self.style, self.id.as_deref(), arena, engine, move || {
+            let _ = bids.get();
+            let _ = asks.get();
+        });
+        self.node_id = Some(my_id);
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let rect = [layout.x, layout.y, layout.width, layout.height];
+        let Some(my_id) = self.node_id else {
+            return RenderOutput::new();
+        };
+
+        let bids = self.bids.get_untracked();
+        let asks = self.asks.get_untracked();
+        let prices = bids.iter().chain(asks.iter()).map(|r| r.price);
+        let (lo, hi) = prices.fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p), hi.max(p)));
+        let total = |rows: &[OrderRow]| rows.iter().map(|r| r.amount).sum::<f32>();
+        let max_total = total(&bids).max(total(&asks));
+
+        let mut meshes = Vec::new();
+        if lo < hi && max_total > 0.0 {
+            let bottom = layout.y + layout.height;
+            let sides = [
+                (&bids, true, Color::hex("#0ECB81")),
+                (&asks, false, Color::hex("#F6465D")),
+            ];
+            for (rows, descending, color) in sides {
+                let steps = depth_steps(rows, descending, rect, (lo, hi), max_total);
+                if steps.len() < 2 {
+                    continue;
+                }
+                let mut outline = steps.clone();
+                outline.push(Vec2::new(steps[steps.len() - 1].x, bottom));
+                outline.push(Vec2::new(steps[0].x, bottom));
+                meshes
+                    .push(Path::polygon(&outline).fill(FillRule::NonZero, color.with_alpha(0.15)));
+                meshes.push(Path::polyline(&steps).stroke(&StrokeStyle::new(1.5), color));
+            }
+        }
+        plot_commands(my_id, rect, meshes)
+    }
+}
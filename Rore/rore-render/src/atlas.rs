@@ -0,0 +1,621 @@
+use rore_types::ImageFit;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+pub const ATLAS_PAGE_SIZE: u32 = 2048;
+// 0, 1/2, 1/4: kichraytirilgan ikonkalar uchun yetarli
+const MIP_LEVELS: u32 = 3;
+// Eng kichik mip'da ham 1 px chegara qoladi, qo'shni rasm qonab o'tmaydi
+const GUTTER: u32 = 1 << (MIP_LEVELS - 1);
+
+/// Instance'ga sig'adigan (Copy) rasm to'ldirishi. `key` rasm id'sining xeshi:
+/// bir xil id'li hamma instance atlasdagi bitta joyni bo'lishadi.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageFill {
+    pub key: u64,
+    pub fit: ImageFit,
+    // Tabiiy o'lchamida takrorlanadi (fit e'tiborga olinmaydi)
+    pub repeat: bool,
+}
+
+impl ImageFill {
+    pub fn new(id: &str, fit: ImageFit) -> Self {
+        Self {
+            key: image_key(id),
+            fit,
+            repeat: false,
+        }
+    }
+
+    pub fn repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    // StyleRaw.image.x (0 = rasm yo'q)
+    pub(crate) fn code(&self) -> f32 {
+        if self.repeat {
+            return 4.0;
+        }
+        match self.fit {
+            ImageFit::Fill => 1.0,
+            ImageFit::Contain => 2.0,
+            ImageFit::Cover => 3.0,
+        }
+    }
+}
+
+pub fn image_key(id: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub enum ImageSource {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+// Atlasdagi joy: shader uchun UV oralig'i va sahifa
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasRegion {
+    pub uv: [f32; 4],
+    pub page: u32,
+    // Rasmning asl o'lchami (contain/cover/repeat shu nisbatda)
+    pub size: [f32; 2],
+}
+
+struct Entry {
+    page: u32,
+    shelf: usize,
+    x: u32,
+    width: u32,
+    refs: u32,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    cursor: u32,
+    free: Vec<(u32, u32)>,
+    live: u32,
+}
+
+// Ajratilgan joyning chap yuqori burchagi
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    page: u32,
+    x: u32,
+    y: u32,
+}
+
+/// Sahifalardagi javonlar (shelf) va LRU hisobi, GPU'siz. Joy topilmasa avval yangi
+/// sahifa ochiladi (`max_pages` gacha), keyin hech kim ishlatmayotgan eng eski rasm chiqariladi.
+struct ShelfAllocator {
+    page_size: u32,
+    max_pages: u32,
+    shelves: Vec<Vec<Shelf>>,
+    entries: HashMap<u64, Entry>,
+    clock: u64,
+}
+
+impl ShelfAllocator {
+    fn new(page_size: u32) -> Self {
+        Self {
+            page_size,
+            max_pages: 1,
+            shelves: vec![Vec::new()],
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn pages(&self) -> u32 {
+        self.shelves.len() as u32
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        self.entries.contains_key(&key)
+    }
+
+    // Rasm hozir ishlatildi: LRU navbatining oxiriga o'tadi
+    fn touch(&mut self, key: u64) {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+        }
+    }
+
+    fn retain(&mut self, key: u64) {
+        self.touch(key);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.refs += 1;
+        }
+    }
+
+    fn release(&mut self, key: u64) {
+        self.touch(key);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.refs -= 1;
+        }
+    }
+
+    /// `w`x`h` joy ajratadi. Ikkinchi qiymat: chiqarilgan rasmlar kalitlari.
+    /// None: byudjet tugagan va bo'shatib bo'ladigan rasm yo'q.
+    fn insert(&mut self, key: u64, w: u32, h: u32) -> Option<(Placement, Vec<u64>)> {
+        let mut evicted = Vec::new();
+        let (page, shelf, x) = loop {
+            if let Some(found) = self.allocate(w, h) {
+                break found;
+            }
+            if self.pages() < self.max_pages {
+                self.shelves.push(Vec::new());
+            } else if let Some(old) = self.evict_oldest() {
+                evicted.push(old);
+            } else {
+                return None;
+            }
+        };
+
+        self.clock += 1;
+        self.entries.insert(
+            key,
+            Entry {
+                page,
+                shelf,
+                x,
+                width: w,
+                refs: 0,
+                last_used: self.clock,
+            },
+        );
+        let y = self.shelves[page as usize][shelf].y;
+        Some((Placement { page, x, y }, evicted))
+    }
+
+    fn allocate(&mut self, w: u32, h: u32) -> Option<(u32, usize, u32)> {
+        let page_size = self.page_size;
+        for (page, shelves) in self.shelves.iter_mut().enumerate() {
+            // Balandligi mos (juda baland bo'lmagan) javon yoki bo'shab qolgan javon
+            for (i, shelf) in shelves.iter_mut().enumerate() {
+                let fits = shelf.height >= h && (shelf.live == 0 || shelf.height <= h + h / 2);
+                if !fits {
+                    continue;
+                }
+                if shelf.live == 0 {
+                    shelf.cursor = 0;
+                    shelf.free.clear();
+                }
+                if let Some(pos) = shelf.free.iter().position(|&(_, fw)| fw >= w) {
+                    let (fx, fw) = shelf.free.swap_remove(pos);
+                    if fw > w {
+                        shelf.free.push((fx + w, fw - w));
+                    }
+                    shelf.live += 1;
+                    return Some((page as u32, i, fx));
+                }
+                if shelf.cursor + w <= page_size {
+                    let x = shelf.cursor;
+                    shelf.cursor += w;
+                    shelf.live += 1;
+                    return Some((page as u32, i, x));
+                }
+            }
+
+            let y = shelves.last().map_or(0, |s| s.y + s.height);
+            if y + h <= page_size {
+                shelves.push(Shelf {
+                    y,
+                    height: h,
+                    cursor: w,
+                    free: Vec::new(),
+                    live: 1,
+                });
+                return Some((page as u32, shelves.len() - 1, 0));
+            }
+        }
+        None
+    }
+
+    // LRU: hech bir instance ishlatmayotganlar ichidan eng uzoq ishlatilmagani
+    fn evict_oldest(&mut self) -> Option<u64> {
+        let key = self
+            .entries
+            .iter()
+            .filter(|(_, e)| e.refs == 0)
+            .min_by_key(|(_, e)| e.last_used)
+            .map(|(&key, _)| key)?;
+        let entry = self.entries.remove(&key).unwrap();
+        let shelves = &mut self.shelves[entry.page as usize];
+        let shelf = &mut shelves[entry.shelf];
+        shelf.free.push((entry.x, entry.width));
+        shelf.live -= 1;
+        // Oxirgi bo'sh javonlar sahifa balandligini qaytaradi
+        while shelves.last().is_some_and(|s| s.live == 0) {
+            shelves.pop();
+        }
+        Some(key)
+    }
+}
+
+/// Kichik rasm va ikonkalar uchun dinamik atlas (Rgba8 texture_2d_array, sahifa = qatlam).
+/// Har bir sahifa javonlarga (shelf) bo'linadi. Rasm birinchi ishlatilganda dekodlanadi;
+/// byudjet tugasa hech bir instance ishlatmayotgan eng eski rasm chiqariladi
+/// va kerak bo'lsa manbasidan qayta yuklanadi.
+pub struct ImageAtlas {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub sampler: wgpu::Sampler,
+    layout: ShelfAllocator,
+    sources: HashMap<u64, ImageSource>,
+    regions: HashMap<u64, AtlasRegion>,
+    slots: HashMap<u32, u64>,
+}
+
+impl ImageAtlas {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let texture = create_atlas_texture(device, 1);
+        Self {
+            view: array_view(&texture),
+            texture,
+            sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Image Atlas Sampler"),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                mipmap_filter: wgpu::FilterMode::Linear,
+                ..Default::default()
+            }),
+            layout: ShelfAllocator::new(ATLAS_PAGE_SIZE),
+            sources: HashMap::new(),
+            regions: HashMap::new(),
+            slots: HashMap::new(),
+        }
+    }
+
+    pub fn page_bytes() -> u64 {
+        (0..MIP_LEVELS)
+            .map(|level| (ATLAS_PAGE_SIZE as u64 >> level).pow(2) * 4)
+            .sum()
+    }
+
+    // Kamida bitta sahifa; kichraytirilsa allaqachon ajratilganlari qoladi
+    pub fn set_budget(&mut self, bytes: u64) {
+        self.layout.max_pages = (bytes / Self::page_bytes()).max(1) as u32;
+    }
+
+    // Bir xil id ikkinchi marta yuborilsa e'tiborsiz qoldiriladi
+    pub fn register(&mut self, id: &str, source: impl FnOnce() -> ImageSource) {
+        self.sources.entry(image_key(id)).or_insert_with(source);
+    }
+
+    /// Slot uchun atlasdagi joy. Ikkinchi qiymat true bo'lsa tekstura qayta yaratilgan:
+    /// uni ishlatadigan bind group'lar yangilanishi kerak.
+    pub fn assign(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        slot: u32,
+        fill: &ImageFill,
+    ) -> (Option<AtlasRegion>, bool) {
+        if self.slots.get(&slot) == Some(&fill.key) {
+            self.layout.touch(fill.key);
+            return (self.regions.get(&fill.key).copied(), false);
+        }
+        self.release(slot);
+
+        let mut grown = false;
+        if !self.layout.contains(fill.key) {
+            let Some(image) = self.decode(fill.key) else {
+                return (None, false);
+            };
+            match self.insert(device, queue, fill.key, &image) {
+                Some(g) => grown = g,
+                None => {
+                    eprintln!("Rore: rasm atlasi to'la, byudjetni oshiring");
+                    return (None, false);
+                }
+            }
+        }
+
+        self.layout.retain(fill.key);
+        self.slots.insert(slot, fill.key);
+        (self.regions.get(&fill.key).copied(), grown)
+    }
+
+    pub fn release(&mut self, slot: u32) {
+        let Some(key) = self.slots.remove(&slot) else {
+            return;
+        };
+        self.layout.release(key);
+    }
+
+    fn decode(&mut self, key: u64) -> Option<image::RgbaImage> {
+        let decoded = match self.sources.get(&key)? {
+            ImageSource::Bytes(bytes) => image::load_from_memory(bytes),
+            ImageSource::Path(path) => match std::fs::read(path) {
+                Ok(bytes) => image::load_from_memory(&bytes),
+                Err(e) => {
+                    eprintln!("Rore: {} o'qilmadi: {}", path.display(), e);
+                    self.sources.remove(&key);
+                    return None;
+                }
+            },
+        };
+        match decoded {
+            Ok(img) => Some(img.to_rgba8()),
+            Err(e) => {
+                eprintln!("Rore: rasm dekodlanmadi: {}", e);
+                self.sources.remove(&key);
+                None
+            }
+        }
+    }
+
+    // Joy topiladi (kerak bo'lsa sahifa qo'shiladi yoki eski rasm chiqariladi) va yoziladi
+    fn insert(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: u64,
+        image: &image::RgbaImage,
+    ) -> Option<bool> {
+        let size = [image.width() as f32, image.height() as f32];
+        // Sahifadan katta rasm nisbatini saqlab kichraytiriladi
+        let limit = ATLAS_PAGE_SIZE - 2 * GUTTER;
+        let scale = (limit as f32 / size[0].max(size[1])).min(1.0);
+        let w = ((size[0] * scale) as u32).max(1);
+        let h = ((size[1] * scale) as u32).max(1);
+        let (slot_w, slot_h) = (align(w + 2 * GUTTER), align(h + 2 * GUTTER));
+
+        let pages = self.layout.pages();
+        let (Placement { page, x, y }, evicted) = self.layout.insert(key, slot_w, slot_h)?;
+        for old in evicted {
+            self.regions.remove(&old);
+        }
+        let grown = self.layout.pages() > pages;
+        if grown {
+            self.grow(device, queue, pages);
+        }
+
+        let (ox, oy) = (x + GUTTER, y + GUTTER);
+        let page_size = ATLAS_PAGE_SIZE as f32;
+        self.regions.insert(
+            key,
+            AtlasRegion {
+                uv: [
+                    ox as f32 / page_size,
+                    oy as f32 / page_size,
+                    (ox + w) as f32 / page_size,
+                    (oy + h) as f32 / page_size,
+                ],
+                page,
+                size,
+            },
+        );
+
+        let resized;
+        let image = if (w, h) == (image.width(), image.height()) {
+            image
+        } else {
+            resized = image::imageops::resize(image, w, h, image::imageops::FilterType::Triangle);
+            &resized
+        };
+        self.upload(queue, page, [x, y], padded(image, slot_w, slot_h));
+        Some(grown)
+    }
+
+    fn upload(&self, queue: &wgpu::Queue, page: u32, origin: [u32; 2], image: image::RgbaImage) {
+        let mut level_image = image;
+        for level in 0..MIP_LEVELS {
+            if level > 0 {
+                level_image = image::imageops::resize(
+                    &level_image,
+                    level_image.width() / 2,
+                    level_image.height() / 2,
+                    image::imageops::FilterType::Triangle,
+                );
+            }
+            let (w, h) = level_image.dimensions();
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &self.texture,
+                    mip_level: level,
+                    origin: wgpu::Origin3d {
+                        x: origin[0] >> level,
+                        y: origin[1] >> level,
+                        z: page,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &level_image,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * w),
+                    rows_per_image: Some(h),
+                },
+                wgpu::Extent3d {
+                    width: w,
+                    height: h,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+    }
+
+    // Tekstura bitta sahifaga kattalashadi, mavjud `pages` ta qatlam ko'chiriladi
+    fn grow(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, pages: u32) {
+        let texture = create_atlas_texture(device, pages + 1);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Image Atlas Grow"),
+        });
+        for level in 0..MIP_LEVELS {
+            let size = ATLAS_PAGE_SIZE >> level;
+            encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    mip_level: level,
+                    ..self.texture.as_image_copy()
+                },
+                wgpu::ImageCopyTexture {
+                    mip_level: level,
+                    ..texture.as_image_copy()
+                },
+                wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: pages,
+                },
+            );
+        }
+        queue.submit(Some(encoder.finish()));
+
+        self.view = array_view(&texture);
+        self.texture = texture;
+    }
+}
+
+// Mip darajalari butun piksellarga tushishi uchun
+fn align(v: u32) -> u32 {
+    v.div_ceil(GUTTER) * GUTTER
+}
+
+// Chetlari GUTTER bo'ylab cho'ziladi: chiziqli filtr va mip'lar qo'shniga chiqmaydi
+fn padded(image: &image::RgbaImage, w: u32, h: u32) -> image::RgbaImage {
+    let (iw, ih) = image.dimensions();
+    image::RgbaImage::from_fn(w, h, |x, y| {
+        let sx = x.saturating_sub(GUTTER).min(iw - 1);
+        let sy = y.saturating_sub(GUTTER).min(ih - 1);
+        *image.get_pixel(sx, sy)
+    })
+}
+
+fn array_view(texture: &wgpu::Texture) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2Array),
+        ..Default::default()
+    })
+}
+
+fn create_atlas_texture(device: &wgpu::Device, pages: u32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Image Atlas"),
+        size: wgpu::Extent3d {
+            width: ATLAS_PAGE_SIZE,
+            height: ATLAS_PAGE_SIZE,
+            depth_or_array_layers: pages,
+        },
+        mip_level_count: MIP_LEVELS,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(max_pages: u32) -> ShelfAllocator {
+        let mut layout = ShelfAllocator::new(64);
+        layout.max_pages = max_pages;
+        layout
+    }
+
+    #[test]
+    fn shelf_fills_left_to_right_then_opens_new_shelf() {
+        let mut layout = layout(1);
+        let at = |layout: &mut ShelfAllocator, key| layout.insert(key, 32, 16).unwrap().0;
+        assert_eq!(
+            at(&mut layout, 1),
+            Placement {
+                page: 0,
+                x: 0,
+                y: 0
+            }
+        );
+        assert_eq!(
+            at(&mut layout, 2),
+            Placement {
+                page: 0,
+                x: 32,
+                y: 0
+            }
+        );
+        // Qator to'ldi: yangi javon oldingisining ostida
+        assert_eq!(
+            at(&mut layout, 3),
+            Placement {
+                page: 0,
+                x: 0,
+                y: 16
+            }
+        );
+        // Ancha past rasm baland javonga tushmaydi
+        let (low, _) = layout.insert(4, 16, 4).unwrap();
+        assert_eq!(
+            low,
+            Placement {
+                page: 0,
+                x: 0,
+                y: 32
+            }
+        );
+    }
+
+    #[test]
+    fn failed_fit_evicts_oldest_unused_image() {
+        let mut layout = layout(1);
+        for key in 1..=4 {
+            layout.insert(key, 64, 16).unwrap();
+        }
+        layout.retain(1);
+        let (at, evicted) = layout.insert(5, 64, 16).unwrap();
+        // 1 ishlatilmoqda, shuning uchun keyingi eng eskisi (2) chiqariladi
+        assert_eq!(evicted, [2]);
+        assert_eq!(
+            at,
+            Placement {
+                page: 0,
+                x: 0,
+                y: 16
+            }
+        );
+        assert!(!layout.contains(2) && layout.contains(5));
+    }
+
+    #[test]
+    fn touch_moves_image_to_back_of_lru() {
+        let mut layout = layout(1);
+        for key in 1..=4 {
+            layout.insert(key, 64, 16).unwrap();
+        }
+        layout.touch(1);
+        layout.touch(2);
+        assert_eq!(layout.insert(5, 64, 16).unwrap().1, [3]);
+        assert_eq!(layout.insert(6, 64, 16).unwrap().1, [4]);
+        assert_eq!(layout.insert(7, 64, 16).unwrap().1, [1]);
+    }
+
+    #[test]
+    fn budget_adds_pages_then_fails_when_everything_is_in_use() {
+        let mut layout = layout(2);
+        layout.insert(1, 64, 64).unwrap();
+        let (at, evicted) = layout.insert(2, 64, 64).unwrap();
+        assert_eq!((at.page, layout.pages()), (1, 2));
+        assert!(evicted.is_empty());
+
+        layout.retain(1);
+        layout.retain(2);
+        assert!(layout.insert(3, 64, 64).is_none());
+        assert_eq!(layout.pages(), 2);
+
+        layout.release(2);
+        assert_eq!(layout.insert(3, 64, 64).unwrap().1, [2]);
+    }
+}
@@ -20,6 +20,15 @@ impl CameraUniform {
         let projection = Mat4::orthographic_rh(0.0, width, height, 0.0, -1.0, 1.0);
         self.view_proj = projection.to_cols_array_2d();
     }
+
+    // Ekranning bir bo'lagi (offscreen qatlam) uchun: rect = [x, y, w, h]
+    pub fn update_view_region(&mut self, rect: [f32; 4]) {
+        use glam::Mat4;
+
+        let [x, y, w, h] = rect;
+        let projection = Mat4::orthographic_rh(x, x + w, y + h, y, -1.0, 1.0);
+        self.view_proj = projection.to_cols_array_2d();
+    }
 }
 
 pub struct CameraState {
@@ -11,7 +11,8 @@ struct InstanceRaw {
     clip_rect: vec4<f32>,
     style_index: u32,
     z_index: f32,
-    padding: vec2<u32>,
+    translate: vec2<f32>,
+    linear: vec4<f32>,
 };
 
 @group(0) @binding(0) var<storage, read> instances_in: array<InstanceRaw>;
@@ -40,6 +41,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let iw = instance.model_size.x;
     let ih = instance.model_size.y;
 
+    // Ikkalasi ham layout fazosida (transform'dan oldin)
     let out_of_bounds = (ix + iw < cx) || (ix > cx + cw) || (iy + ih < cy) || (iy > cy + ch);
 
     if (out_of_bounds) {
@@ -1,23 +1,61 @@
 use std::collections::HashMap;
 use wgpu::util::DeviceExt;
 
+// Builtins: rect, clip, linear, translate, texture_uv (har biri vec4)
+const BUILTINS_SIZE: u64 = 80;
+
+// Atlasdagi rasm slotlari: instance gpu indekslari bilan to'qnashmaydi
+pub(crate) const IMAGE_SLOT_BASE: u32 = 0xFFFF_0000;
+
+/// Shader'ga `@group(2)` orqali beriladigan tekstura:
+/// `@binding(0) var t_input: texture_2d<f32>; @binding(1) var s_input: sampler;`.
+/// `builtins.texture_uv` (u0, v0, u1, v1) node rect'iga mos UV oralig'ini beradi.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShaderTexture {
+    // `load_texture` yoki `Image` bilan yuklangan rasm id'si
+    Image(String),
+    // Shu paytgacha chizilgan kadr; texture_uv = (0, 0, 1, 1),
+    // UV ni `@builtin(position).xy / vec2<f32>(textureDimensions(t_input))` bilan olish qulay
+    Backdrop,
+}
+
+pub struct CustomDraw {
+    pub shader_id: String,
+    pub rect: [f32; 4],
+    pub clip: [f32; 4],
+    // Foydalanuvchi uniform'lari (`@group(1) @binding(1)`)
+    pub uniforms: Vec<u8>,
+    pub transform: glam::Affine2,
+    pub texture: Option<ShaderTexture>,
+}
+
 pub struct CustomShaderManager {
     pub pipelines: HashMap<String, wgpu::RenderPipeline>,
     pub uniform_layout: wgpu::BindGroupLayout,
+    pub texture_layout: wgpu::BindGroupLayout,
     pub builtin_buffer: Option<wgpu::Buffer>,
     pub custom_buffer: Option<wgpu::Buffer>,
     pub alignment: u32,
+    // Tekstura so'ramagan shader'lar uchun 1x1 oq tekstura (group 2 doim bog'lanadi)
+    pub(crate) dummy_bind_group: wgpu::BindGroup,
+    pub(crate) sampler: wgpu::Sampler,
+    pub(crate) backdrop: Option<(wgpu::Texture, wgpu::TextureView)>,
+    // Har bir draw uchun atlasdagi sahifa (ShaderTexture::Image)
+    pub(crate) image_pages: Vec<Option<u32>>,
+    // MSAA o'zgarganda pipeline'lar shu manbalardan qayta yig'iladi
+    sources: HashMap<String, String>,
+    sample_count: u32,
 }
 
 impl CustomShaderManager {
-    pub fn new(device: &wgpu::Device) -> Self {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
         let alignment = device.limits().min_uniform_buffer_offset_alignment as u32;
 
         let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Custom Shader Bind Group Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
-                    binding: 0, // Kordinatalar (Rect, Clip)
+                    binding: 0, // Kordinatalar (Rect, Clip, Transform)
                     visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
@@ -39,15 +77,88 @@ impl CustomShaderManager {
             ],
         });
 
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Custom Shader Texture Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Custom Shader Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let dummy = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Custom Shader Dummy Texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[255; 4],
+        );
+        let dummy_view = dummy.create_view(&wgpu::TextureViewDescriptor::default());
+        let dummy_bind_group = texture_bind_group(device, &texture_layout, &dummy_view, &sampler);
+
         Self {
             pipelines: HashMap::new(),
             uniform_layout,
+            texture_layout,
             builtin_buffer: None,
             custom_buffer: None,
             alignment,
+            dummy_bind_group,
+            sampler,
+            backdrop: None,
+            image_pages: Vec::new(),
+            sources: HashMap::new(),
+            sample_count: 1,
+        }
+    }
+
+    pub fn set_sample_count(
+        &mut self,
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        camera_layout: &wgpu::BindGroupLayout,
+        sample_count: u32,
+    ) {
+        self.sample_count = sample_count;
+        self.pipelines.clear();
+        for (id, wgsl) in std::mem::take(&mut self.sources) {
+            let _ = self.build(device, config, camera_layout, &id, &wgsl);
         }
     }
 
+    /// Shader birinchi marta kelganda yig'iladi. WGSL xatosi panic emas, `Err` bo'lib qaytadi
+    /// (matnda naga'ning qator/ustunli izohi bor).
     pub fn compile(
         &mut self,
         device: &wgpu::Device,
@@ -55,10 +166,34 @@ impl CustomShaderManager {
         camera_layout: &wgpu::BindGroupLayout,
         id: &str,
         wgsl: &str,
-    ) {
+    ) -> Result<(), String> {
         if self.pipelines.contains_key(id) {
-            return;
+            return Ok(());
         }
+        self.build(device, config, camera_layout, id, wgsl)
+    }
+
+    // Hot reload: yangi manba xato bo'lsa eski pipeline ishlashda davom etadi
+    pub fn reload(
+        &mut self,
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        camera_layout: &wgpu::BindGroupLayout,
+        id: &str,
+        wgsl: &str,
+    ) -> Result<(), String> {
+        self.build(device, config, camera_layout, id, wgsl)
+    }
+
+    fn build(
+        &mut self,
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        camera_layout: &wgpu::BindGroupLayout,
+        id: &str,
+        wgsl: &str,
+    ) -> Result<(), String> {
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
 
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some(&format!("Custom Shader {}", id)),
@@ -67,7 +202,7 @@ impl CustomShaderManager {
 
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some(&format!("Custom Pipeline Layout {}", id)),
-            bind_group_layouts: &[camera_layout, &self.uniform_layout],
+            bind_group_layouts: &[camera_layout, &self.uniform_layout, &self.texture_layout],
             push_constant_ranges: &[],
         });
 
@@ -98,17 +233,65 @@ impl CustomShaderManager {
                 conservative: false,
             },
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: self.sample_count,
+                ..Default::default()
+            },
             multiview: None,
         });
 
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            return Err(error.to_string());
+        }
         self.pipelines.insert(id.to_string(), pipeline);
+        self.sources.insert(id.to_string(), wgsl.to_string());
+        Ok(())
+    }
+
+    // Backdrop ishlatilsa kadr nusxasi uchun tekstura (o'lcham o'zgarsa qayta yaratiladi)
+    pub(crate) fn ensure_backdrop(
+        &mut self,
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+    ) {
+        if let Some((texture, _)) = &self.backdrop {
+            if texture.width() == config.width && texture.height() == config.height {
+                return;
+            }
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Custom Shader Backdrop"),
+            size: wgpu::Extent3d {
+                width: config.width.max(1),
+                height: config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.backdrop = Some((texture, view));
+    }
+
+    pub(crate) fn texture_bind_group(
+        &self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        texture_bind_group(device, &self.texture_layout, view, sampler)
     }
 
+    // `texture_uvs`: har bir draw uchun builtins.texture_uv
     pub fn prepare_uniforms(
         &mut self,
         device: &wgpu::Device,
-        draws: &[(String, [f32; 4], [f32; 4], Vec<u8>)],
+        draws: &[CustomDraw],
+        texture_uvs: &[[f32; 4]],
     ) -> (Option<wgpu::BindGroup>, Vec<(u32, u32)>) {
         if draws.is_empty() {
             return (None, vec![]);
@@ -118,32 +301,36 @@ impl CustomShaderManager {
         let mut customs = Vec::new();
         let mut offsets = Vec::new();
 
-        for (_, rect, clip, uniforms) in draws {
+        // Bloklar bir xil o'lchamda bog'lanadi: eng katta uniform sig'ishi kerak
+        let largest = draws
+            .iter()
+            .map(|d| d.uniforms.len().max(16).next_multiple_of(16))
+            .max()
+            .unwrap_or(16) as u32;
+        let custom_size = largest.next_multiple_of(self.alignment);
+
+        for (draw, texture_uv) in draws.iter().zip(texture_uvs) {
+            let (rect, clip, uniforms, transform) =
+                (&draw.rect, &draw.clip, &draw.uniforms, &draw.transform);
             let off0 = builtins.len() as u32;
             let off1 = customs.len() as u32;
             offsets.push((off0, off1));
 
-            // Builtins: (32 bytes = 8 floats)
+            // Builtins: (80 bytes = 20 floats). Eski shader'lar faqat rect va clip ni o'qiydi
+            let t = transform.translation;
             builtins.extend_from_slice(bytemuck::cast_slice(rect));
             builtins.extend_from_slice(bytemuck::cast_slice(clip));
+            builtins.extend_from_slice(bytemuck::cast_slice(&transform.matrix2.to_cols_array()));
+            builtins.extend_from_slice(bytemuck::cast_slice(&[t.x, t.y, 0.0, 0.0]));
+            builtins.extend_from_slice(bytemuck::cast_slice(texture_uv));
             let rem0 = builtins.len() as u32 % self.alignment;
             if rem0 != 0 {
                 builtins.resize(builtins.len() + (self.alignment - rem0) as usize, 0);
             }
 
-            // Customs
-            let start_len = customs.len() as u32;
-            if uniforms.is_empty() {
-                customs.extend_from_slice(&[0u8; 16]); // Dummy ma'lumot
-            } else {
-                customs.extend_from_slice(uniforms);
-            }
-            let block_size = customs.len() as u32 - start_len;
-            let mut padding = self.alignment - (block_size % self.alignment);
-            if padding == 0 {
-                padding = self.alignment;
-            }
-            customs.resize(customs.len() + padding as usize, 0);
+            // Customs (bo'sh bo'lsa nollar - dummy ma'lumot)
+            customs.extend_from_slice(uniforms);
+            customs.resize(off1 as usize + custom_size as usize, 0);
         }
 
         let builtin_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -167,7 +354,7 @@ impl CustomShaderManager {
                     resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
                         buffer: &builtin_buf,
                         offset: 0,
-                        size: wgpu::BufferSize::new(32), // 8 floats
+                        size: wgpu::BufferSize::new(BUILTINS_SIZE),
                     }),
                 },
                 wgpu::BindGroupEntry {
@@ -175,7 +362,7 @@ impl CustomShaderManager {
                     resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
                         buffer: &custom_buf,
                         offset: 0,
-                        size: wgpu::BufferSize::new(self.alignment as u64), // Dynamic block size
+                        size: wgpu::BufferSize::new(custom_size as u64), // Dynamic block size
                     }),
                 },
             ],
@@ -187,3 +374,25 @@ impl CustomShaderManager {
         (Some(bg), offsets)
     }
 }
+
+fn texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    view: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Custom Shader Texture Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
@@ -4,9 +4,238 @@ use crate::state::{CullConfigUniform, State, TimeUniform};
 use crate::vertex::Vertex;
 use rore_types::text::TextRenderer;
 use std::collections::HashMap;
+use std::sync::Arc;
 use wgpu::util::DeviceExt;
 use winit::window::Window;
 
+/// Barcha oynalar uchun bitta Instance, Adapter, Device va Queue. Har bir oyna faqat
+/// o'z Surface'ini yaratadi, shuning uchun GPU resurslarini oynalar bo'lishishi mumkin.
+#[derive(Clone)]
+pub struct GpuContext {
+    pub instance: Arc<wgpu::Instance>,
+    pub adapter: Arc<wgpu::Adapter>,
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+}
+
+impl GpuContext {
+    async fn new(instance: wgpu::Instance, surface: &wgpu::Surface<'_>) -> Self {
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: Some(surface),
+                force_fallback_adapter: false,
+            })
+            .await
+            .unwrap();
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("Rore Device"),
+                    // MSAA 8x kabi adapterga xos sample sonlari uchun
+                    required_features: adapter.features()
+                        & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+                    required_limits: wgpu::Limits::default(),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        Self {
+            instance: Arc::new(instance),
+            adapter: Arc::new(adapter),
+            device: Arc::new(device),
+            queue: Arc::new(queue),
+        }
+    }
+}
+
+// Partial redraw'da damage tile'larini fon rangi bilan tozalaydi
+const ERASER_WGSL: &str = r#"
+struct CameraUniform { view_proj: mat4x4<f32>, };
+@group(0) @binding(0) var<uniform> camera: CameraUniform;
+
+struct TimeUniform {
+    current_time: f32,
+    grid_width: f32,
+    grid_height: f32,
+    is_full_redraw: f32,
+    clear_color: vec4<f32>,
+};
+@group(1) @binding(0) var<uniform> time: TimeUniform;
+@group(1) @binding(1) var<storage, read> tile_mask: array<u32>;
+
+struct VertexInput { @location(0) pos: vec2<f32>, };
+struct InstanceInput {
+    @location(1) model_pos: vec2<f32>,
+    @location(2) model_size: vec2<f32>,
+};
+struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, };
+
+@vertex fn vs_main(model: VertexInput, inst: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+    let w_pos = inst.model_pos + (model.pos * inst.model_size);
+    out.clip_pos = camera.view_proj * vec4<f32>(w_pos, 0.0, 1.0);
+    return out;
+}
+
+@fragment fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let px = u32(in.clip_pos.x);
+    let py = u32(in.clip_pos.y);
+    let tx = px / 64u;
+    let ty = py / 64u;
+    let gw = u32(time.grid_width);
+    if (tx < gw && ty < u32(time.grid_height)) {
+        let tile_idx = ty * gw + tx;
+        let arr_idx = tile_idx / 32u;
+        let bit_idx = tile_idx % 32u;
+        let mask = tile_mask[arr_idx];
+        if (((mask >> bit_idx) & 1u) == 0u) {
+            discard;
+        }
+    }
+    return time.clear_color;
+}
+"#;
+
+// Asosiy kadrga chizadigan pipeline'lar: MSAA o'zgarsa hammasi qayta yaratiladi
+pub(crate) struct ScenePipelines {
+    pub render: wgpu::RenderPipeline,
+    pub eraser: wgpu::RenderPipeline,
+    pub image: wgpu::RenderPipeline,
+}
+
+// Vertex::QUAD + InstanceRaw bilan chiziladigan to'rtburchak pipeline'i
+pub(crate) fn quad_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    module: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    blend: wgpu::BlendState,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: "vs_main",
+            buffers: &[Vertex::desc(), InstanceRaw::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleStrip,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+    })
+}
+
+// layouts: camera, style, time, texture
+pub(crate) fn create_scene_pipelines(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    layouts: [&wgpu::BindGroupLayout; 4],
+    sample_count: u32,
+) -> ScenePipelines {
+    let [camera, style, time, texture] = layouts;
+    let pipeline_layout = |label: &str, groups: &[&wgpu::BindGroupLayout]| {
+        device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: groups,
+            push_constant_ranges: &[],
+        })
+    };
+
+    let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
+    let render = quad_pipeline(
+        device,
+        "Shape Pipeline",
+        &pipeline_layout("Render Pipeline Layout", &[camera, style, time]),
+        &shader,
+        format,
+        wgpu::BlendState::ALPHA_BLENDING,
+        sample_count,
+    );
+
+    let eraser_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Eraser Shader"),
+        source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(ERASER_WGSL)),
+    });
+    let eraser = quad_pipeline(
+        device,
+        "Eraser Pipeline",
+        &pipeline_layout("Eraser Pipeline Layout", &[camera, time]),
+        &eraser_shader,
+        format,
+        wgpu::BlendState::REPLACE,
+        sample_count,
+    );
+
+    let image_shader = device.create_shader_module(wgpu::include_wgsl!("shader_image.wgsl"));
+    let image = quad_pipeline(
+        device,
+        "Image Pipeline",
+        &pipeline_layout("Image Pipeline Layout", &[camera, texture, style, time]),
+        &image_shader,
+        format,
+        wgpu::BlendState::ALPHA_BLENDING,
+        sample_count,
+    );
+
+    ScenePipelines {
+        render,
+        eraser,
+        image,
+    }
+}
+
+pub(crate) fn create_style_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    styles: &wgpu::Buffer,
+    gradients: &crate::gradient::GradientRamps,
+    images: &crate::atlas::ImageAtlas,
+    i: usize,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(&format!("Style Bind Group {}", i)),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: styles.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&gradients.view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(&images.view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Sampler(&images.sampler),
+            },
+        ],
+    })
+}
+
 pub(crate) fn create_depth_texture(
     device: &wgpu::Device,
     config: &wgpu::SurfaceConfiguration,
@@ -39,31 +268,47 @@ impl<'a> State<'a> {
             &wgpu::SurfaceConfiguration,
         ) -> Box<dyn TextRenderer>,
     {
-        let size = window.inner_size();
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            ..Default::default()
-        });
-        let surface = instance.create_surface(window).unwrap();
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .unwrap();
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: Some("Rore Device"),
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                },
-                None,
-            )
-            .await
-            .unwrap();
+        Self::from_target(window.inner_size(), window, None, text_renderer_factory).await
+    }
+
+    /// Surface oynaga egalik qilishi mumkin (masalan `Arc<Window>`) — bir nechta oyna uchun
+    /// `gpu`: boshqa oynadan olingan umumiy GPU (`State::gpu`). `None` bo'lsa,
+    /// shu oyna Surface'iga mos adapter va device yaratiladi.
+    pub async fn from_target<W, F>(
+        size: winit::dpi::PhysicalSize<u32>,
+        window: W,
+        gpu: Option<GpuContext>,
+        text_renderer_factory: F,
+    ) -> Self
+    where
+        W: Into<wgpu::SurfaceTarget<'a>>,
+        F: FnOnce(
+            &wgpu::Device,
+            &wgpu::Queue,
+            &wgpu::SurfaceConfiguration,
+        ) -> Box<dyn TextRenderer>,
+    {
+        let (gpu, surface) = match gpu {
+            Some(gpu) => {
+                let surface = gpu.instance.create_surface(window).unwrap();
+                (gpu, surface)
+            }
+            None => {
+                let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+                    backends: wgpu::Backends::all(),
+                    ..Default::default()
+                });
+                let surface = instance.create_surface(window).unwrap();
+                let gpu = GpuContext::new(instance, &surface).await;
+                (gpu, surface)
+            }
+        };
+        let GpuContext {
+            instance,
+            adapter,
+            device,
+            queue,
+        } = gpu;
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps
@@ -85,6 +330,18 @@ impl<'a> State<'a> {
         };
         surface.configure(&device, &config);
 
+        let format_features = if device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            adapter.get_texture_format_features(config.format).flags
+        } else {
+            config
+                .format
+                .guaranteed_format_features(device.features())
+                .flags
+        };
+
         let camera = CameraState::new(&device, size.width as f32, size.height as f32);
         let depth_texture_view = create_depth_texture(&device, &config);
         let initial_capacity = 10_000;
@@ -157,16 +414,46 @@ impl<'a> State<'a> {
         let style_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Style SSBO Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                    // Gradient rampalari (textureLoad, sampler kerak emas)
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    // Rasm atlasi: sahifalar massiv qatlamlari, mip'lar bilan
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2Array,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
             });
 
         let style_buffers: [wgpu::Buffer; 3] = std::array::from_fn(|i| {
@@ -179,15 +466,17 @@ impl<'a> State<'a> {
             })
         });
 
+        let gradients = crate::gradient::GradientRamps::new(&device);
+        let images = crate::atlas::ImageAtlas::new(&device);
         let style_bind_groups: [wgpu::BindGroup; 3] = std::array::from_fn(|i| {
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some(&format!("Style Bind Group {}", i)),
-                layout: &style_bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: style_buffers[i].as_entire_binding(),
-                }],
-            })
+            create_style_bind_group(
+                &device,
+                &style_bind_group_layout,
+                &style_buffers[i],
+                &gradients,
+                &images,
+                i,
+            )
         });
 
         let cull_bind_group_layout =
@@ -401,122 +690,16 @@ impl<'a> State<'a> {
             bias: wgpu::DepthBiasState::default(),
         };
 
-        // 3. ASOSIY UI PIPELINE
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Shape Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
-
-        // 4. O'CHIRG'ICH (ERASER) SHADERI
-        let eraser_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                    label: Some("Eraser Shader"),
-                    source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(
-                        r#"
-                        struct CameraUniform { view_proj: mat4x4<f32>, };
-                        @group(0) @binding(0) var<uniform> camera: CameraUniform;
-
-                        struct TimeUniform {
-                            current_time: f32,
-                            grid_width: f32,
-                            grid_height: f32,
-                            is_full_redraw: f32,
-                            clear_color: vec4<f32>,
-                        };
-                        @group(1) @binding(0) var<uniform> time: TimeUniform;
-                        @group(1) @binding(1) var<storage, read> tile_mask: array<u32>;
-
-                        struct VertexInput { @location(0) pos: vec2<f32>, };
-                        struct InstanceInput {
-                            @location(1) model_pos: vec2<f32>,
-                            @location(2) model_size: vec2<f32>,
-                        };
-                        struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, };
-
-                        @vertex fn vs_main(model: VertexInput, inst: InstanceInput) -> VertexOutput {
-                            var out: VertexOutput;
-                            let w_pos = inst.model_pos + (model.pos * inst.model_size);
-                            out.clip_pos = camera.view_proj * vec4<f32>(w_pos, 0.0, 1.0);
-                            return out;
-                        }
-
-                        @fragment fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-                            let px = u32(in.clip_pos.x);
-                            let py = u32(in.clip_pos.y);
-                            let tx = px / 64u;
-                            let ty = py / 64u;
-                            let gw = u32(time.grid_width);
-                            if (tx < gw && ty < u32(time.grid_height)) {
-                                let tile_idx = ty * gw + tx;
-                                let arr_idx = tile_idx / 32u;
-                                let bit_idx = tile_idx % 32u;
-                                let mask = tile_mask[arr_idx];
-                                if (((mask >> bit_idx) & 1u) == 0u) {
-                                    discard;
-                                }
-                            }
-                            return time.clear_color;
-                        }
-                        "#
-                    )),
-                });
-
-        // 5. O'CHIRG'ICH LAYOUTI
-        let eraser_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Eraser Pipeline Layout"),
-                bind_group_layouts: &[
-                    &camera.bind_group_layout, // Group 0
-                    &time_bind_group_layout,   // Group 1
-                ],
-                push_constant_ranges: &[],
-            });
-
-        let eraser_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Eraser Pipeline"),
-            layout: Some(&eraser_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &eraser_shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &eraser_shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        // 3. FILTR QATLAMI UCHUN SHAKL PIPELINE'I (asosiysi: create_scene_pipelines)
+        let layer_pipeline = quad_pipeline(
+            &device,
+            "Shape Layer Pipeline",
+            &render_pipeline_layout,
+            &shader,
+            config.format,
+            crate::filter::LAYER_BLEND,
+            1,
+        );
 
         let cull_shader = device.create_shader_module(wgpu::include_wgsl!("cull.wgsl"));
         let cull_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -555,44 +738,17 @@ impl<'a> State<'a> {
                 label: Some("texture_bind_group_layout"),
             });
 
-        let image_shader = device.create_shader_module(wgpu::include_wgsl!("shader_image.wgsl"));
-        let image_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Image Pipeline Layout"),
-                bind_group_layouts: &[
-                    &camera.bind_group_layout,
-                    &texture_bind_group_layout,
-                    &style_bind_group_layout,
-                    &time_bind_group_layout,
-                ],
-                push_constant_ranges: &[],
-            });
-
-        let image_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Image Pipeline"),
-            layout: Some(&image_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &image_shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &image_shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        let scene = create_scene_pipelines(
+            &device,
+            config.format,
+            [
+                &camera.bind_group_layout,
+                &style_bind_group_layout,
+                &time_bind_group_layout,
+                &texture_bind_group_layout,
+            ],
+            1,
+        );
 
         let offscreen_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Offscreen Master Texture"),
@@ -605,7 +761,10 @@ impl<'a> State<'a> {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: config.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            // COPY_SRC: kadrni rasmga olish (capture_frame) uchun
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let offscreen_view = offscreen_texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -643,7 +802,8 @@ impl<'a> State<'a> {
                     clip_rect: [-10000.0, -10000.0, 20000.0, 20000.0],
                     style_index: 0,
                     z_index: -999.0,
-                    padding: [0, 0],
+                    translate: [0.0, 0.0],
+                    linear: InstanceRaw::IDENTITY_LINEAR,
                 }]),
                 usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             });
@@ -719,19 +879,32 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
         });
 
         let text_system = text_renderer_factory(&device, &queue, &config);
-        let custom_shaders = crate::custom_shader::CustomShaderManager::new(&device);
+        let custom_shaders = crate::custom_shader::CustomShaderManager::new(&device, &queue);
+        let paths =
+            crate::path::PathRenderer::new(&device, config.format, &camera.bind_group_layout);
+        let filters = crate::filter::FilterRenderer::new(
+            &device,
+            config.format,
+            &texture_bind_group_layout,
+            &camera.bind_group_layout,
+        );
         Self {
             surface,
             device,
             queue,
             config,
             size,
-            render_pipeline,
-            eraser_pipeline,
-            image_pipeline,
+            alpha_modes: surface_caps.alpha_modes,
+            render_pipeline: scene.render,
+            layer_pipeline,
+            eraser_pipeline: scene.eraser,
+            image_pipeline: scene.image,
             cull_pipeline,
             composite_pipeline,
             frame_index: 0,
+            sample_count: 1,
+            msaa_view: None,
+            format_features,
             current_capacity: initial_capacity,
             low_memory_timer: None,
             cull_bind_groups,
@@ -746,6 +919,10 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
             cull_bind_group_layout,
             style_bind_groups,
             style_buffers,
+            gradients,
+            images,
+            instance,
+            adapter,
             node_to_gpu_idx: HashMap::new(),
             gpu_free_list: Vec::new(),
             styles_cache: Vec::with_capacity(initial_capacity as usize),
@@ -764,6 +941,7 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
             camera,
             num_instances: 0,
             current_draw_count: 0,
+            main_draw_count: 0,
             text_system,
             offscreen_texture,
             offscreen_view,
@@ -774,6 +952,23 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
             current_custom_draws: Vec::new(),
             custom_bind_group: None,
             custom_offsets: Vec::new(),
+            paths,
+            filters,
+            readback: crate::readback::FrameReadback::new(),
+        }
+    }
+
+    // Gradient rampalari yoki rasm atlasi teksturasi qayta yaratilganda
+    pub(crate) fn refresh_style_bind_groups(&mut self) {
+        for i in 0..3 {
+            self.style_bind_groups[i] = create_style_bind_group(
+                &self.device,
+                &self.style_bind_group_layout,
+                &self.style_buffers[i],
+                &self.gradients,
+                &self.images,
+                i,
+            );
         }
     }
 
@@ -799,14 +994,14 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
                 mapped_at_creation: false,
             });
 
-            self.style_bind_groups[i] = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some(&format!("Style Bind Group {}", i)),
-                layout: &self.style_bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.style_buffers[i].as_entire_binding(),
-                }],
-            });
+            self.style_bind_groups[i] = create_style_bind_group(
+                &self.device,
+                &self.style_bind_group_layout,
+                &self.style_buffers[i],
+                &self.gradients,
+                &self.images,
+                i,
+            );
 
             self.instances_in_buffers[i] = self.device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some(&format!("Instances In Buffer {}", i)),
@@ -866,7 +1061,22 @@ struct VertexOutput { @builtin(position) clip_pos: vec4<f32>, @location(0) uv: v
         }
     }
 
-    pub fn load_texture(&mut self, _id: &str, _bytes: &[u8]) {}
+    // Rasm atlasga ro'yxatdan o'tadi; instance birinchi ishlatganda dekodlanadi
+    pub fn load_texture(&mut self, id: &str, bytes: &[u8]) {
+        self.images
+            .register(id, || crate::atlas::ImageSource::Bytes(bytes.to_vec()));
+    }
+
+    pub fn load_texture_file(&mut self, id: &str, path: &str) {
+        self.images
+            .register(id, || crate::atlas::ImageSource::Path(path.into()));
+    }
+
+    // Rasm atlasining GPU xotira chegarasi (baytda)
+    pub fn set_image_budget(&mut self, bytes: u64) {
+        self.images.set_budget(bytes);
+    }
+
     pub fn queue_image(&mut self, id: &str, instance: Instance) {
         self.image_batches
             .entry(id.to_string())
@@ -0,0 +1,904 @@
+use crate::state::State;
+use glam::Mat4;
+use rore_types::Filter;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use wgpu::util::DeviceExt;
+
+/// Shaffof fonga chizganda premultiplied rang va to'g'ri alfa beradi
+/// (oddiy ALPHA_BLENDING alfani ikki marta ko'paytiradi).
+pub(crate) const LAYER_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+const NO_CLIP: [f32; 4] = [-10000.0, -10000.0, 20000.0, 20000.0];
+// Har bir qatlam uchun ping-pong teksturalar (drop-shadow uchtasini ishlatadi)
+const TARGETS: usize = 3;
+
+/// Offscreen qatlam. Content rejimida node daraxti alohida teksturaga chiziladi,
+/// backdrop rejimida esa node ortida allaqachon chizilgan kadr olinadi.
+/// Filtrlar ketma-ket passlar bilan qo'llanib, natija asosiy kadrga qaytariladi.
+/// Matn va custom shader'lar qatlamga kirmaydi: ular hamisha eng ustida chiziladi.
+/// `cached` qatlam teksturasi `stale` kelmaguncha (yoki a'zolar, o'lcham o'zgarmaguncha)
+/// qayta chizilmaydi: faqat kompozitsiya qilinadi.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterLayer {
+    pub id: u32,
+    // Content: a'zolar egallagan soha, backdrop: node'ning o'zi (niqob)
+    pub rect: [f32; 4],
+    pub clip: [f32; 4],
+    pub radius: f32,
+    pub filters: Vec<Filter>,
+    pub backdrop: bool,
+    // Node va uning barcha avlodlari
+    pub members: Vec<u32>,
+    // Guruh shaffofligi: kompozitsiya paytida qo'llanadi (keshni buzmaydi)
+    pub opacity: f32,
+    pub cached: bool,
+    pub stale: bool,
+}
+
+impl FilterLayer {
+    pub fn new(id: u32, filters: Vec<Filter>, backdrop: bool) -> Self {
+        Self {
+            id,
+            rect: [0.0; 4],
+            clip: NO_CLIP,
+            radius: 0.0,
+            filters,
+            backdrop,
+            members: Vec::new(),
+            opacity: 1.0,
+            cached: false,
+            stale: false,
+        }
+    }
+
+    pub fn bleed(&self) -> f32 {
+        self.filters.iter().map(Filter::bleed).sum()
+    }
+
+    /// Teksturaga tushadigan soha: rect va filtrlar yoyilishi
+    pub fn bounds(&self) -> [f32; 4] {
+        let b = self.bleed();
+        let [x, y, w, h] = self.rect;
+        [x - b, y - b, w + b * 2.0, h + b * 2.0]
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct FilterParams {
+    matrix: [[f32; 4]; 4],
+    src_rect: [f32; 4],
+    blur: [f32; 4],
+    tint: [f32; 4],
+    offset: [f32; 4],
+    dest: [f32; 4],
+    clip: [f32; 4],
+    mask: [f32; 4],
+    extra: [f32; 4],
+}
+
+impl Default for FilterParams {
+    fn default() -> Self {
+        Self {
+            matrix: Mat4::IDENTITY.to_cols_array_2d(),
+            src_rect: [0.0, 0.0, 1.0, 1.0],
+            blur: [0.0; 4],
+            tint: [0.0; 4],
+            offset: [0.0; 4],
+            dest: [0.0; 4],
+            clip: NO_CLIP,
+            mask: [0.0; 4],
+            extra: [0.0; 4],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PassKind {
+    Blur,
+    Color,
+    Shadow,
+    Over,
+}
+
+#[derive(Clone, Copy)]
+enum Source {
+    // Asosiy kadr (backdrop)
+    Master,
+    Target(usize),
+}
+
+struct FilterPass {
+    kind: PassKind,
+    src: Source,
+    under: Option<usize>,
+    dst: usize,
+    params: FilterParams,
+}
+
+// Qatlam draw order'ning qayerida kompozitsiya qilinadi
+#[derive(Debug, Clone)]
+pub(crate) struct Placement {
+    pub layer: usize,
+    // Asosiy ketma-ketlikdagi shuncha instance'dan keyin
+    pub at: u32,
+    // Content qatlam instance'lari (buferning oxirida turadi)
+    pub instances: Range<u32>,
+}
+
+struct Job {
+    target: u32,
+    // false: keshdagi natija ishlatiladi, kontent va passlar o'tkazib yuboriladi
+    redraw: bool,
+    passes: Vec<(FilterPass, u32)>,
+    result: usize,
+    composite: u32,
+}
+
+struct LayerTarget {
+    size: (u32, u32),
+    _textures: Vec<wgpu::Texture>,
+    views: Vec<wgpu::TextureView>,
+    bind_groups: Vec<wgpu::BindGroup>,
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+}
+
+impl LayerTarget {
+    fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: (u32, u32),
+        texture_layout: &wgpu::BindGroupLayout,
+        camera_layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+    ) -> Self {
+        let textures: Vec<wgpu::Texture> = (0..TARGETS)
+            .map(|_| {
+                device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Filter Layer Texture"),
+                    size: wgpu::Extent3d {
+                        width: size.0,
+                        height: size.1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+            })
+            .collect();
+        let views: Vec<wgpu::TextureView> = textures
+            .iter()
+            .map(|t| t.create_view(&wgpu::TextureViewDescriptor::default()))
+            .collect();
+        let bind_groups = views
+            .iter()
+            .map(|view| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Filter Layer Bind Group"),
+                    layout: texture_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(sampler),
+                        },
+                    ],
+                })
+            })
+            .collect();
+
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Filter Layer Camera"),
+            size: std::mem::size_of::<crate::camera::CameraUniform>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Filter Layer Camera Bind Group"),
+            layout: camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        Self {
+            size,
+            _textures: textures,
+            views,
+            bind_groups,
+            camera_buffer,
+            camera_bind_group,
+        }
+    }
+}
+
+fn color_matrix(filter: &Filter) -> Option<Mat4> {
+    let m = match *filter {
+        Filter::Opacity(a) => Mat4::from_diagonal(glam::Vec4::splat(a.clamp(0.0, 1.0))),
+        Filter::Brightness(b) => {
+            let b = b.max(0.0);
+            Mat4::from_diagonal(glam::Vec4::new(b, b, b, 1.0))
+        }
+        Filter::Grayscale(g) => {
+            // CSS filter spetsifikatsiyasidagi matritsa
+            let k = 1.0 - g.clamp(0.0, 1.0);
+            let rows = [
+                [
+                    0.2126 + 0.7874 * k,
+                    0.7152 - 0.7152 * k,
+                    0.0722 - 0.0722 * k,
+                    0.0,
+                ],
+                [
+                    0.2126 - 0.2126 * k,
+                    0.7152 + 0.2848 * k,
+                    0.0722 - 0.0722 * k,
+                    0.0,
+                ],
+                [
+                    0.2126 - 0.2126 * k,
+                    0.7152 - 0.7152 * k,
+                    0.0722 + 0.9278 * k,
+                    0.0,
+                ],
+                [0.0, 0.0, 0.0, 1.0],
+            ];
+            Mat4::from_cols_array_2d(&rows).transpose()
+        }
+        _ => return None,
+    };
+    Some(m)
+}
+
+fn others(cur: usize) -> (usize, usize) {
+    ((cur + 1) % TARGETS, (cur + 2) % TARGETS)
+}
+
+fn intersects(a: [f32; 4], b: [f32; 4]) -> bool {
+    a[0] < b[0] + b[2] && b[0] < a[0] + a[2] && a[1] < b[1] + b[3] && b[1] < a[1] + a[3]
+}
+
+// Filtrlar zanjiri passlarga aylanadi. Natija qaysi teksturada ekanini qaytaradi
+fn build_passes(layer: &FilterLayer, size: [f32; 2], passes: &mut Vec<FilterPass>) -> usize {
+    let texel = [1.0 / size[0], 1.0 / size[1]];
+    let mut cur = 0;
+    let mut matrix: Option<Mat4> = None;
+
+    let flush = |matrix: &mut Option<Mat4>, cur: &mut usize, passes: &mut Vec<FilterPass>| {
+        if let Some(m) = matrix.take() {
+            let (dst, _) = others(*cur);
+            passes.push(FilterPass {
+                kind: PassKind::Color,
+                src: Source::Target(*cur),
+                under: None,
+                dst,
+                params: FilterParams {
+                    matrix: m.to_cols_array_2d(),
+                    ..Default::default()
+                },
+            });
+            *cur = dst;
+        }
+    };
+
+    for filter in &layer.filters {
+        // Ketma-ket rang filtrlari bitta matritsaga yig'iladi
+        if let Some(m) = color_matrix(filter) {
+            matrix = Some(m * matrix.unwrap_or(Mat4::IDENTITY));
+            continue;
+        }
+        flush(&mut matrix, &mut cur, passes);
+
+        let blur = |src: usize, tmp: usize, sigma: f32, passes: &mut Vec<FilterPass>| {
+            for (from, to, dir) in [(src, tmp, [texel[0], 0.0]), (tmp, src, [0.0, texel[1]])] {
+                passes.push(FilterPass {
+                    kind: PassKind::Blur,
+                    src: Source::Target(from),
+                    under: None,
+                    dst: to,
+                    params: FilterParams {
+                        blur: [dir[0], dir[1], sigma, 0.0],
+                        ..Default::default()
+                    },
+                });
+            }
+        };
+
+        match *filter {
+            Filter::Blur(sigma) if sigma > 0.0 => {
+                let (tmp, _) = others(cur);
+                blur(cur, tmp, sigma, passes);
+            }
+            Filter::DropShadow {
+                offset,
+                blur: sigma,
+                color,
+            } => {
+                let (shadow, tmp) = others(cur);
+                passes.push(FilterPass {
+                    kind: PassKind::Shadow,
+                    src: Source::Target(cur),
+                    under: None,
+                    dst: shadow,
+                    params: FilterParams {
+                        tint: [
+                            color.r * color.a,
+                            color.g * color.a,
+                            color.b * color.a,
+                            color.a,
+                        ],
+                        offset: [offset[0] * texel[0], offset[1] * texel[1], 0.0, 0.0],
+                        ..Default::default()
+                    },
+                });
+                if sigma > 0.0 {
+                    blur(shadow, tmp, sigma, passes);
+                }
+                passes.push(FilterPass {
+                    kind: PassKind::Over,
+                    src: Source::Target(cur),
+                    under: Some(shadow),
+                    dst: tmp,
+                    params: FilterParams::default(),
+                });
+                cur = tmp;
+            }
+            _ => {}
+        }
+    }
+    flush(&mut matrix, &mut cur, passes);
+    cur
+}
+
+/// Offscreen filtr qatlamlari: teksturalar node id bo'yicha keshlanadi,
+/// damage rect'ga tegmagan qatlam umuman qayta hisoblanmaydi.
+pub struct FilterRenderer {
+    blur_pipeline: wgpu::RenderPipeline,
+    color_pipeline: wgpu::RenderPipeline,
+    shadow_pipeline: wgpu::RenderPipeline,
+    over_pipeline: wgpu::RenderPipeline,
+    // Asosiy kadrga yozadi: MSAA bilan bir xil sample soni
+    composite_pipeline: wgpu::RenderPipeline,
+    layout: wgpu::PipelineLayout,
+    shader: wgpu::ShaderModule,
+    params_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+    alignment: u32,
+
+    layers: Vec<FilterLayer>,
+    pub(crate) placements: Vec<Placement>,
+    // Content qatlamga tushgan node -> qatlam indeksi
+    pub(crate) owner: HashMap<u32, usize>,
+    targets: HashMap<u32, LayerTarget>,
+    // Keshlangan qatlam -> natija turgan tekstura (hali yaroqli bo'lsa)
+    rasterized: HashMap<u32, usize>,
+    // placements bilan parallel; None = bu kadrda chizilmaydi
+    jobs: Vec<Option<Job>>,
+    params_bind_group: Option<wgpu::BindGroup>,
+}
+
+impl FilterRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        texture_layout: &wgpu::BindGroupLayout,
+        camera_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("filter.wgsl"));
+        let params_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Filter Params Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: wgpu::BufferSize::new(
+                        std::mem::size_of::<FilterParams>() as u64
+                    ),
+                },
+                count: None,
+            }],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Filter Pipeline Layout"),
+            bind_group_layouts: &[
+                &params_layout,
+                texture_layout,
+                texture_layout,
+                camera_layout,
+            ],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = |label: &str,
+                        vs: &str,
+                        fs: &str,
+                        blend: Option<wgpu::BlendState>,
+                        topology: wgpu::PrimitiveTopology| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: vs,
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: fs,
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let list = wgpu::PrimitiveTopology::TriangleList;
+
+        Self {
+            blur_pipeline: pipeline("Filter Blur", "vs_fill", "fs_blur", None, list),
+            color_pipeline: pipeline("Filter Color", "vs_fill", "fs_color", None, list),
+            shadow_pipeline: pipeline("Filter Shadow", "vs_fill", "fs_shadow", None, list),
+            over_pipeline: pipeline("Filter Over", "vs_fill", "fs_over", None, list),
+            composite_pipeline: composite_pipeline(device, &layout, &shader, format, 1),
+            layout,
+            shader,
+            params_layout,
+            sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Filter Sampler"),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                ..Default::default()
+            }),
+            format,
+            alignment: device.limits().min_uniform_buffer_offset_alignment,
+            layers: Vec::new(),
+            placements: Vec::new(),
+            owner: HashMap::new(),
+            targets: HashMap::new(),
+            rasterized: HashMap::new(),
+            jobs: Vec::new(),
+            params_bind_group: None,
+        }
+    }
+
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        self.composite_pipeline = composite_pipeline(
+            device,
+            &self.layout,
+            &self.shader,
+            self.format,
+            sample_count,
+        );
+    }
+
+    pub fn set_layers(&mut self, layers: Vec<FilterLayer>) {
+        self.targets
+            .retain(|id, _| layers.iter().any(|l| l.id == *id));
+        // Bir kadrda bir necha paket kelishi mumkin: stale belgisi keyingisida yo'qolmasligi kerak
+        for layer in &layers {
+            let same_members = self
+                .layers
+                .iter()
+                .find(|old| old.id == layer.id)
+                .is_some_and(|old| old.members == layer.members);
+            if layer.stale || !same_members {
+                self.rasterized.remove(&layer.id);
+            }
+        }
+        let targets = &self.targets;
+        self.rasterized.retain(|id, _| targets.contains_key(id));
+        self.layers = layers;
+    }
+
+    /// Draw order'ni asosiy va qatlam qismlariga ajratadi.
+    /// Qaytaradi: GPU indekslari (asosiylari boshida) va asosiylar soni.
+    pub(crate) fn arrange(
+        &mut self,
+        draw_order: &[u32],
+        gpu_index: impl Fn(u32) -> Option<u32>,
+    ) -> (Vec<u32>, u32) {
+        self.owner.clear();
+        self.placements.clear();
+
+        // Ichma-ich content qatlamlarda tashqisi (a'zosi ko'prog'i) ustun
+        let mut by_size: Vec<usize> = (0..self.layers.len()).collect();
+        by_size.sort_by_key(|&l| std::cmp::Reverse(self.layers[l].members.len()));
+        for &l in &by_size {
+            if !self.layers[l].backdrop {
+                for &m in &self.layers[l].members {
+                    self.owner.entry(m).or_insert(l);
+                }
+            }
+        }
+
+        let member_sets: Vec<HashSet<u32>> = self
+            .layers
+            .iter()
+            .map(|l| l.members.iter().copied().collect())
+            .collect();
+        let mut at: Vec<Option<u32>> = vec![None; self.layers.len()];
+        let mut main = Vec::with_capacity(draw_order.len());
+        let mut split: Vec<Vec<u32>> = vec![Vec::new(); self.layers.len()];
+
+        for &id in draw_order {
+            for (l, set) in member_sets.iter().enumerate() {
+                if at[l].is_none() && set.contains(&id) {
+                    at[l] = Some(main.len() as u32);
+                }
+            }
+            let Some(gpu) = gpu_index(id) else {
+                continue;
+            };
+            match self.owner.get(&id) {
+                Some(&l) => split[l].push(gpu),
+                None => main.push(gpu),
+            }
+        }
+
+        let main_count = main.len() as u32;
+        for (l, layer) in self.layers.iter().enumerate() {
+            // Boshqa content qatlam ichida qolgan qatlam alohida chizilmaydi
+            if self.owner.get(&layer.id).is_some_and(|&o| o != l) {
+                continue;
+            }
+            let Some(pos) = at[l] else {
+                continue;
+            };
+            let start = main.len() as u32;
+            main.extend_from_slice(&split[l]);
+            self.placements.push(Placement {
+                layer: l,
+                at: pos,
+                instances: start..main.len() as u32,
+            });
+        }
+        self.placements.sort_by_key(|p| p.at);
+        (main, main_count)
+    }
+
+    /// Kadr boshida: teksturalar, passlar va uniform'lar tayyorlanadi.
+    /// `damage` berilsa, unga tegmagan qatlam keshdagi holicha qoladi.
+    pub(crate) fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_layout: &wgpu::BindGroupLayout,
+        camera_layout: &wgpu::BindGroupLayout,
+        screen: [f32; 2],
+        damage: Option<&[[u32; 4]]>,
+    ) {
+        self.jobs.clear();
+        self.params_bind_group = None;
+        if self.placements.is_empty() {
+            return;
+        }
+
+        let mut params: Vec<FilterParams> = Vec::new();
+        let max_dim = device.limits().max_texture_dimension_2d;
+        for placement in &self.placements {
+            let layer = &self.layers[placement.layer];
+            let b = layer.bounds();
+            // Piksel chegarasiga yaxlitlanadi va ekran bilan kesiladi
+            let x0 = b[0].floor().max(0.0);
+            let y0 = b[1].floor().max(0.0);
+            let x1 = (b[0] + b[2]).ceil().min(screen[0]);
+            let y1 = (b[1] + b[3]).ceil().min(screen[1]);
+            let bounds = [x0, y0, x1 - x0, y1 - y0];
+            let touched = damage.is_none_or(|rects| {
+                rects.iter().any(|r| {
+                    intersects(bounds, [r[0] as f32, r[1] as f32, r[2] as f32, r[3] as f32])
+                })
+            });
+            if bounds[2] < 1.0 || bounds[3] < 1.0 || !touched {
+                self.jobs.push(None);
+                continue;
+            }
+
+            let size = (
+                (bounds[2] as u32).min(max_dim),
+                (bounds[3] as u32).min(max_dim),
+            );
+            let stale = self.targets.get(&layer.id).is_none_or(|t| t.size != size);
+            if stale {
+                self.rasterized.remove(&layer.id);
+                let target = LayerTarget::new(
+                    device,
+                    self.format,
+                    size,
+                    texture_layout,
+                    camera_layout,
+                    &self.sampler,
+                );
+                self.targets.insert(layer.id, target);
+            }
+            let target = &self.targets[&layer.id];
+            let mut camera = crate::camera::CameraUniform::new();
+            camera.update_view_region(bounds);
+            queue.write_buffer(&target.camera_buffer, 0, bytemuck::cast_slice(&[camera]));
+
+            let composite_params = FilterParams {
+                dest: bounds,
+                clip: layer.clip,
+                mask: layer.rect,
+                extra: [
+                    layer.radius,
+                    if layer.backdrop { 1.0 } else { 0.0 },
+                    layer.opacity.clamp(0.0, 1.0),
+                    0.0,
+                ],
+                ..Default::default()
+            };
+            let cached = layer.cached && !layer.backdrop;
+            if let Some(&result) = self.rasterized.get(&layer.id).filter(|_| cached) {
+                let composite = params.len() as u32;
+                params.push(composite_params);
+                self.jobs.push(Some(Job {
+                    target: layer.id,
+                    redraw: false,
+                    passes: Vec::new(),
+                    result,
+                    composite,
+                }));
+                continue;
+            }
+
+            let mut passes = Vec::new();
+            if layer.backdrop {
+                passes.push(FilterPass {
+                    kind: PassKind::Color,
+                    src: Source::Master,
+                    under: None,
+                    dst: 0,
+                    params: FilterParams {
+                        src_rect: [
+                            bounds[0] / screen[0],
+                            bounds[1] / screen[1],
+                            bounds[2] / screen[0],
+                            bounds[3] / screen[1],
+                        ],
+                        ..Default::default()
+                    },
+                });
+            }
+            let result = build_passes(layer, [size.0 as f32, size.1 as f32], &mut passes);
+            if cached {
+                self.rasterized.insert(layer.id, result);
+            }
+
+            let mut job_passes = Vec::with_capacity(passes.len());
+            for pass in passes {
+                let offset = params.len() as u32;
+                params.push(pass.params);
+                job_passes.push((pass, offset));
+            }
+            let composite = params.len() as u32;
+            params.push(composite_params);
+
+            self.jobs.push(Some(Job {
+                target: layer.id,
+                redraw: true,
+                passes: job_passes,
+                result,
+                composite,
+            }));
+        }
+
+        if params.is_empty() {
+            return;
+        }
+
+        let stride =
+            (std::mem::size_of::<FilterParams>() as u32).div_ceil(self.alignment) * self.alignment;
+        let mut bytes = vec![0u8; stride as usize * params.len()];
+        for (i, p) in params.iter().enumerate() {
+            let start = i * stride as usize;
+            bytes[start..start + std::mem::size_of::<FilterParams>()]
+                .copy_from_slice(bytemuck::bytes_of(p));
+        }
+        // Indeks -> bayt siljishi
+        for job in self.jobs.iter_mut().flatten() {
+            for (_, offset) in &mut job.passes {
+                *offset *= stride;
+            }
+            job.composite *= stride;
+        }
+
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Filter Params Buffer"),
+            contents: &bytes,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        self.params_bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Filter Params Bind Group"),
+            layout: &self.params_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: wgpu::BufferSize::new(std::mem::size_of::<FilterParams>() as u64),
+                }),
+            }],
+        }));
+    }
+
+    fn pipeline(&self, kind: PassKind) -> &wgpu::RenderPipeline {
+        match kind {
+            PassKind::Blur => &self.blur_pipeline,
+            PassKind::Color => &self.color_pipeline,
+            PassKind::Shadow => &self.shadow_pipeline,
+            PassKind::Over => &self.over_pipeline,
+        }
+    }
+}
+
+fn composite_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Filter Composite"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_composite",
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_composite",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleStrip,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+    })
+}
+
+fn clear_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    label: &str,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+impl State<'_> {
+    // Bitta qatlam: kontent (yoki orqa fon) -> filtr passlari -> asosiy kadrga kompozitsiya
+    pub(crate) fn draw_filter_layer(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        index: usize,
+        scissors: Option<&[[u32; 4]]>,
+    ) {
+        let filters = &self.filters;
+        let (Some(Some(job)), Some(params)) = (filters.jobs.get(index), &filters.params_bind_group)
+        else {
+            return;
+        };
+        let Some(target) = filters.targets.get(&job.target) else {
+            return;
+        };
+        let placement = &filters.placements[index];
+
+        if job.redraw && !filters.layers[placement.layer].backdrop {
+            let mut rpass = clear_pass(encoder, &target.views[0], "Filter Layer Content");
+            if !placement.instances.is_empty() {
+                rpass.set_pipeline(&self.layer_pipeline);
+                rpass.set_bind_group(0, &target.camera_bind_group, &[]);
+                rpass.set_bind_group(1, &self.style_bind_groups[self.frame_index], &[]);
+                rpass.set_bind_group(2, &self.time_bind_groups[self.frame_index], &[]);
+                rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                rpass.set_vertex_buffer(1, self.instance_out_buffers[self.frame_index].slice(..));
+                rpass.draw(0..4, placement.instances.clone());
+            }
+            let layer = placement.layer;
+            self.paths
+                .render_nodes(&mut rpass, &target.camera_bind_group, true, |id| {
+                    filters.owner.get(&id) == Some(&layer)
+                });
+        }
+
+        for (pass, offset) in &job.passes {
+            let mut rpass = clear_pass(encoder, &target.views[pass.dst], "Filter Pass");
+            let src = match pass.src {
+                Source::Master => &self.offscreen_bind_group,
+                Source::Target(i) => &target.bind_groups[i],
+            };
+            let under = pass.under.map_or(src, |i| &target.bind_groups[i]);
+            rpass.set_pipeline(filters.pipeline(pass.kind));
+            rpass.set_bind_group(0, params, &[*offset]);
+            rpass.set_bind_group(1, src, &[]);
+            rpass.set_bind_group(2, under, &[]);
+            rpass.set_bind_group(3, &self.camera.bind_group, &[]);
+            rpass.draw(0..3, 0..1);
+        }
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Filter Composite"),
+            color_attachments: &[Some(self.master_attachment(wgpu::LoadOp::Load))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        let result = &target.bind_groups[job.result];
+        rpass.set_pipeline(&filters.composite_pipeline);
+        rpass.set_bind_group(0, params, &[job.composite]);
+        rpass.set_bind_group(1, result, &[]);
+        rpass.set_bind_group(2, result, &[]);
+        rpass.set_bind_group(3, &self.camera.bind_group, &[]);
+        match scissors {
+            Some(rects) => {
+                for &rect in rects {
+                    if let Some([x, y, w, h]) = self.clamp_scissor(rect) {
+                        rpass.set_scissor_rect(x, y, w, h);
+                        rpass.draw(0..4, 0..1);
+                    }
+                }
+            }
+            None => rpass.draw(0..4, 0..1),
+        }
+    }
+}
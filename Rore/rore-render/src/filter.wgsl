@@ -0,0 +1,115 @@
+struct FilterParams {
+    matrix: mat4x4<f32>,
+    src_rect: vec4<f32>, // manba teksturadagi UV oralig'i
+    blur: vec4<f32>,     // xy: bir piksel qadami (UV), z: sigma (px)
+    tint: vec4<f32>,     // soya rangi (premultiplied)
+    offset: vec4<f32>,   // xy: soya siljishi (UV)
+    dest: vec4<f32>,     // kompozitsiya rect (dunyo koordinatasi)
+    clip: vec4<f32>,
+    mask: vec4<f32>,     // backdrop uchun yumaloq burchakli niqob
+    extra: vec4<f32>,    // x: radius, y: niqob yoqilgan, z: guruh shaffofligi
+};
+@group(0) @binding(0) var<uniform> params: FilterParams;
+@group(1) @binding(0) var t_src: texture_2d<f32>;
+@group(1) @binding(1) var s_src: sampler;
+@group(2) @binding(0) var t_under: texture_2d<f32>;
+@group(2) @binding(1) var s_under: sampler;
+
+struct CameraUniform { view_proj: mat4x4<f32>, };
+@group(3) @binding(0) var<uniform> camera: CameraUniform;
+
+struct VertexOutput {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) world_pos: vec2<f32>,
+};
+
+// Butun nishonni qoplaydigan bitta uchburchak
+@vertex
+fn vs_fill(@builtin(vertex_index) v_idx: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((v_idx << 1u) & 2u), f32(v_idx & 2u));
+    var out: VertexOutput;
+    out.clip_pos = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    out.world_pos = uv;
+    return out;
+}
+
+fn src_uv(uv: vec2<f32>) -> vec2<f32> {
+    return params.src_rect.xy + uv * params.src_rect.zw;
+}
+
+// Ajraladigan gauss: bir yo'nalish, ikkinchisi keyingi passda
+@fragment
+fn fs_blur(in: VertexOutput) -> @location(0) vec4<f32> {
+    let sigma = max(params.blur.z, 0.001);
+    let radius = ceil(sigma * 3.0);
+    // Katta sigma'da tap soni 64 atrofida qoladi
+    let step = max(1.0, ceil(radius / 32.0));
+    let uv = src_uv(in.uv);
+
+    var sum = vec4<f32>(0.0);
+    var weight = 0.0;
+    for (var x: f32 = -radius; x <= radius; x = x + step) {
+        let w = exp(-(x * x) / (2.0 * sigma * sigma));
+        sum = sum + textureSampleLevel(t_src, s_src, uv + params.blur.xy * x, 0.0) * w;
+        weight = weight + w;
+    }
+    return sum / weight;
+}
+
+// Opacity, grayscale, brightness: premultiplied rangga 4x4 matritsa
+@fragment
+fn fs_color(in: VertexOutput) -> @location(0) vec4<f32> {
+    let c = params.matrix * textureSampleLevel(t_src, s_src, src_uv(in.uv), 0.0);
+    let a = clamp(c.a, 0.0, 1.0);
+    return vec4<f32>(clamp(c.rgb, vec3<f32>(0.0), vec3<f32>(a)), a);
+}
+
+@fragment
+fn fs_shadow(in: VertexOutput) -> @location(0) vec4<f32> {
+    let a = textureSampleLevel(t_src, s_src, src_uv(in.uv) - params.offset.xy, 0.0).a;
+    return params.tint * a;
+}
+
+// Kontent soya ustiga
+@fragment
+fn fs_over(in: VertexOutput) -> @location(0) vec4<f32> {
+    let top = textureSampleLevel(t_src, s_src, in.uv, 0.0);
+    let under = textureSampleLevel(t_under, s_under, in.uv, 0.0);
+    return top + under * (1.0 - top.a);
+}
+
+@vertex
+fn vs_composite(@builtin(vertex_index) v_idx: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(v_idx & 1u), f32(v_idx >> 1u));
+    let pos = params.dest.xy + uv * params.dest.zw;
+    var out: VertexOutput;
+    out.clip_pos = camera.view_proj * vec4<f32>(pos, 0.0, 1.0);
+    out.uv = uv;
+    out.world_pos = pos;
+    return out;
+}
+
+fn sd_rounded_box(p: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
+    let q = abs(p) - b + vec2<f32>(r);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
+@fragment
+fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.world_pos;
+    let c = params.clip;
+    if (p.x < c.x || p.y < c.y || p.x > c.x + c.z || p.y > c.y + c.w) {
+        discard;
+    }
+
+    var color = textureSampleLevel(t_src, s_src, in.uv, 0.0);
+    if (params.extra.y > 0.5) {
+        let half_size = params.mask.zw * 0.5;
+        let radius = min(params.extra.x, min(half_size.x, half_size.y));
+        let d = sd_rounded_box(p - params.mask.xy - half_size, half_size, radius);
+        color = color * clamp(0.5 - d, 0.0, 1.0);
+    }
+    return color * params.extra.z;
+}
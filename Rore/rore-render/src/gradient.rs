@@ -0,0 +1,349 @@
+use rore_types::{Background, GradientStop};
+use std::collections::HashMap;
+
+pub const MAX_GRADIENT_STOPS: usize = 8;
+// Har bir rampa teksturaning bitta qatori: shuncha texel
+const RAMP_WIDTH: u32 = 256;
+const INITIAL_ROWS: u32 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GradientKind {
+    #[default]
+    Linear,
+    Radial,
+    Conic,
+}
+
+impl GradientKind {
+    // StyleRaw.gradient.x (0 = oddiy ikki rangli color_start/color_end)
+    pub(crate) fn code(self) -> f32 {
+        match self {
+            GradientKind::Linear => 1.0,
+            GradientKind::Radial => 2.0,
+            GradientKind::Conic => 3.0,
+        }
+    }
+}
+
+/// Instance'ga sig'adigan (Copy) ko'p to'xtashli gradient.
+/// To'xtashlar GPU'da `GradientRamps` teksturasining bir qatoriga pishiriladi;
+/// bir xil to'xtashli instance'lar bitta qatorni bo'lishadi.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientFill {
+    pub kind: GradientKind,
+    // Linear: yo'nalish, conic: boshlanish burchagi (radian)
+    pub angle: f32,
+    // Radial/conic markazi, 0..1
+    pub center: [f32; 2],
+    // Radial: 1.0 = markazdan eng uzoq burchakkacha
+    pub radius: f32,
+    pub stops: [([f32; 4], f32); MAX_GRADIENT_STOPS],
+    pub count: usize,
+}
+
+impl GradientFill {
+    pub fn new(kind: GradientKind, stops: &[GradientStop]) -> Self {
+        let mut sorted = stops.to_vec();
+        sorted.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+        let mut fill = Self {
+            kind,
+            angle: 0.0,
+            center: [0.5, 0.5],
+            radius: 1.0,
+            stops: [([0.0; 4], 0.0); MAX_GRADIENT_STOPS],
+            count: sorted.len().min(MAX_GRADIENT_STOPS),
+        };
+
+        if sorted.len() <= MAX_GRADIENT_STOPS {
+            for (slot, stop) in fill.stops.iter_mut().zip(&sorted) {
+                let c = stop.color;
+                *slot = ([c.r, c.g, c.b, c.a], stop.position);
+            }
+        } else {
+            // Ortiqchasini teng oraliqda qayta tanlaymiz: shakli saqlanadi
+            let (first, last) = (sorted[0].position, sorted[sorted.len() - 1].position);
+            let raw: Vec<([f32; 4], f32)> = sorted
+                .iter()
+                .map(|s| ([s.color.r, s.color.g, s.color.b, s.color.a], s.position))
+                .collect();
+            for (i, slot) in fill.stops.iter_mut().enumerate() {
+                let t = first + (last - first) * i as f32 / (MAX_GRADIENT_STOPS - 1) as f32;
+                *slot = (sample(&raw, t), t);
+            }
+        }
+        fill
+    }
+
+    pub fn from_background(background: &Background) -> Option<Self> {
+        match background {
+            Background::Gradient(g) => Some(Self {
+                angle: g.angle,
+                ..Self::new(GradientKind::Linear, &g.stops)
+            }),
+            Background::RadialGradient(g) => Some(Self {
+                center: g.center,
+                radius: g.radius,
+                ..Self::new(GradientKind::Radial, &g.stops)
+            }),
+            Background::ConicGradient(g) => Some(Self {
+                center: g.center,
+                angle: g.angle,
+                ..Self::new(GradientKind::Conic, &g.stops)
+            }),
+            Background::Solid(_) | Background::Image { .. } => None,
+        }
+    }
+
+    pub fn color_at(&self, t: f32) -> [f32; 4] {
+        sample(&self.stops[..self.count], t)
+    }
+
+    // Rampa faqat to'xtashlarga bog'liq (tur, burchak, markaz StyleRaw'da)
+    fn key(&self) -> Vec<u32> {
+        self.stops[..self.count]
+            .iter()
+            .flat_map(|(c, p)| c.iter().chain(std::iter::once(p)).map(|v| v.to_bits()))
+            .collect()
+    }
+
+    fn bake(&self) -> Vec<[f32; 4]> {
+        (0..RAMP_WIDTH)
+            .map(|x| self.color_at(x as f32 / (RAMP_WIDTH - 1) as f32))
+            .collect()
+    }
+}
+
+fn sample(stops: &[([f32; 4], f32)], t: f32) -> [f32; 4] {
+    let Some(first) = stops.first() else {
+        return [0.0; 4];
+    };
+    if t <= first.1 {
+        return first.0;
+    }
+    for pair in stops.windows(2) {
+        let ((c0, p0), (c1, p1)) = (pair[0], pair[1]);
+        if t <= p1 {
+            let k = if p1 > p0 { (t - p0) / (p1 - p0) } else { 1.0 };
+            return std::array::from_fn(|i| c0[i] + (c1[i] - c0[i]) * k);
+        }
+    }
+    stops[stops.len() - 1].0
+}
+
+/// Gradient rampalari teksturasi (Rgba32Float, qator = rampa).
+/// GPU slotlari qatorlarga havola qiladi; havolasi qolmagan qator qayta ishlatiladi,
+/// joy tugasa tekstura ikki baravar kattalashadi.
+pub struct GradientRamps {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    rows: u32,
+    next_row: u32,
+    free_rows: Vec<u32>,
+    refs: HashMap<u32, u32>,
+    by_stops: HashMap<Vec<u32>, u32>,
+    slots: HashMap<u32, (u32, Vec<u32>)>,
+}
+
+impl GradientRamps {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let texture = create_ramp_texture(device, INITIAL_ROWS);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            rows: INITIAL_ROWS,
+            next_row: 0,
+            free_rows: Vec::new(),
+            refs: HashMap::new(),
+            by_stops: HashMap::new(),
+            slots: HashMap::new(),
+        }
+    }
+
+    /// Slot uchun qator qaytaradi. Ikkinchi qiymat true bo'lsa tekstura qayta yaratilgan:
+    /// uni ishlatadigan bind group'lar yangilanishi kerak.
+    pub fn assign(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        slot: u32,
+        fill: &GradientFill,
+    ) -> (u32, bool) {
+        let key = fill.key();
+        if let Some((row, old_key)) = self.slots.get(&slot) {
+            if *old_key == key {
+                return (*row, false);
+            }
+        }
+        self.release(slot);
+
+        let mut grown = false;
+        let row = match self.by_stops.get(&key) {
+            Some(&row) => row,
+            None => {
+                let row = match self.free_rows.pop() {
+                    Some(row) => row,
+                    None => {
+                        if self.next_row == self.rows {
+                            self.grow(device, queue);
+                            grown = true;
+                        }
+                        self.next_row += 1;
+                        self.next_row - 1
+                    }
+                };
+                queue.write_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &self.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d { x: 0, y: row, z: 0 },
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    bytemuck::cast_slice(&fill.bake()),
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(RAMP_WIDTH * 16),
+                        rows_per_image: None,
+                    },
+                    wgpu::Extent3d {
+                        width: RAMP_WIDTH,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                );
+                self.by_stops.insert(key.clone(), row);
+                row
+            }
+        };
+
+        *self.refs.entry(row).or_insert(0) += 1;
+        self.slots.insert(slot, (row, key));
+        (row, grown)
+    }
+
+    pub fn release(&mut self, slot: u32) {
+        let Some((row, key)) = self.slots.remove(&slot) else {
+            return;
+        };
+        let refs = self.refs.entry(row).or_insert(1);
+        *refs -= 1;
+        if *refs == 0 {
+            self.refs.remove(&row);
+            self.by_stops.remove(&key);
+            self.free_rows.push(row);
+        }
+    }
+
+    fn grow(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let rows = self.rows * 2;
+        let texture = create_ramp_texture(device, rows);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Gradient Ramps Grow"),
+        });
+        encoder.copy_texture_to_texture(
+            self.texture.as_image_copy(),
+            texture.as_image_copy(),
+            wgpu::Extent3d {
+                width: RAMP_WIDTH,
+                height: self.rows,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        self.view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.texture = texture;
+        self.rows = rows;
+    }
+}
+
+fn create_ramp_texture(device: &wgpu::Device, rows: u32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Gradient Ramps"),
+        size: wgpu::Extent3d {
+            width: RAMP_WIDTH,
+            height: rows,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        // Filtrlanmaydi: shader ikki texel'ni o'zi aralashtiradi
+        format: wgpu::TextureFormat::Rgba32Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rore_types::Color;
+
+    const BLACK: Color = Color::new(0.0, 0.0, 0.0, 1.0);
+    const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    const RED: Color = Color::new(1.0, 0.0, 0.0, 1.0);
+    const BLUE: Color = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    fn close(a: [f32; 4], b: [f32; 4]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4)
+    }
+
+    #[test]
+    fn interpolates_between_stops_and_clamps_outside() {
+        let fill = GradientFill::new(
+            GradientKind::Linear,
+            &[GradientStop::new(0.2, BLACK), GradientStop::new(0.6, WHITE)],
+        );
+        assert!(close(fill.color_at(0.4), [0.5, 0.5, 0.5, 1.0]));
+        assert!(close(fill.color_at(0.3), [0.25, 0.25, 0.25, 1.0]));
+        // 0..1 dan tashqarida (va birinchi/oxirgi to'xtashdan narida) chetki rang
+        assert_eq!(fill.color_at(-1.0), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(fill.color_at(0.1), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(fill.color_at(2.0), [1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn unsorted_stops_are_ordered_and_duplicates_make_hard_edge() {
+        let fill = GradientFill::new(
+            GradientKind::Radial,
+            &[GradientStop::new(1.0, WHITE), GradientStop::new(0.0, BLACK)],
+        );
+        assert_eq!(fill.stops[0].1, 0.0);
+        assert!(close(fill.color_at(0.25), [0.25, 0.25, 0.25, 1.0]));
+
+        let edge = GradientFill::new(
+            GradientKind::Linear,
+            &[
+                GradientStop::new(0.0, RED),
+                GradientStop::new(0.5, RED),
+                GradientStop::new(0.5, BLUE),
+                GradientStop::new(1.0, BLUE),
+            ],
+        );
+        assert_eq!(edge.color_at(0.49), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(edge.color_at(0.5), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(edge.color_at(0.51), [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn more_than_eight_stops_are_resampled_keeping_endpoints() {
+        let stops: Vec<GradientStop> = (0..12)
+            .map(|i| {
+                let color = if i % 2 == 0 { BLACK } else { WHITE };
+                GradientStop::new(0.1 + 0.8 * i as f32 / 11.0, color)
+            })
+            .collect();
+        let fill = GradientFill::new(GradientKind::Conic, &stops);
+
+        assert_eq!(fill.count, MAX_GRADIENT_STOPS);
+        let (first, last) = (fill.stops[0], fill.stops[MAX_GRADIENT_STOPS - 1]);
+        assert!((first.1 - 0.1).abs() < 1e-5 && (last.1 - 0.9).abs() < 1e-5);
+        assert!(close(first.0, [0.0, 0.0, 0.0, 1.0]));
+        assert!(close(last.0, [1.0, 1.0, 1.0, 1.0]));
+        assert!(fill.stops.windows(2).all(|p| p[0].1 < p[1].1));
+    }
+}
@@ -1,4 +1,7 @@
+use crate::atlas::ImageFill;
+use crate::gradient::GradientFill;
 use bytemuck::{Pod, Zeroable};
+use rore_types::{Border, BorderStyle};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Instance {
@@ -9,10 +12,16 @@ pub struct Instance {
     pub target_color_start: [f32; 4],
     pub target_color_end: [f32; 4],
     pub gradient_angle: f32,
+    // Ko'p to'xtashli/radial/konik fon: bo'lsa color_start/color_end o'rniga ishlatiladi
+    pub gradient: Option<GradientFill>,
+    // Atlasdagi rasm fon ustidan chiziladi (burchak, chegara va clip bilan)
+    pub image: Option<ImageFill>,
     pub border_radius: [f32; 4], // INQILOB: 4 xil burchak
     pub border_width: [f32; 4],  // INQILOB: 4 xil qalinlik
     pub border_color: [f32; 4],
     pub target_border_color: [f32; 4],
+    pub border_style: [BorderStyle; 4], // top, right, bottom, left
+    pub border_dash: [f32; 2],          // shtrix uzunligi (0 = avto), faza
     pub shadow_color: [f32; 4],
     pub shadow_offset: glam::Vec2,
     pub shadow_blur: f32,
@@ -20,6 +29,8 @@ pub struct Instance {
     pub clip_rect: [f32; 4],
     pub anim_start_time: f32,
     pub anim_duration: f32,
+    // position/size/clip_rect layout fazosida, transform ularni ekranga olib chiqadi
+    pub transform: glam::Affine2,
 }
 
 #[repr(C)]
@@ -35,8 +46,44 @@ pub struct StyleRaw {
     pub shadow_data: [f32; 4],
     pub corner_radii: [f32; 4],  // INQILOB
     pub border_widths: [f32; 4], // INQILOB
-    pub extra_props: [f32; 4],   // gradient_angle, 0, 0, 0
+    pub extra_props: [f32; 4],   // gradient_angle, radial radius, 0, 0
     pub anim_data: [f32; 4],
+    pub border_styles: [f32; 4], // 0 none, 1 solid, 2 dashed, 3 dotted
+    pub border_dash: [f32; 4],   // uzunlik, faza, 0, 0
+    pub gradient: [f32; 4],      // tur (0 = ikki rang), rampa qatori, markaz x, y
+    pub image: [f32; 4],         // fit (0 = rasm yo'q), atlas sahifasi, asl kenglik, balandlik
+    pub image_uv: [f32; 4],      // atlasdagi u0, v0, u1, v1
+}
+
+impl StyleRaw {
+    pub fn border_style_code(style: BorderStyle) -> f32 {
+        match style {
+            BorderStyle::None => 0.0,
+            BorderStyle::Solid => 1.0,
+            BorderStyle::Dashed => 2.0,
+            BorderStyle::Dotted => 3.0,
+        }
+    }
+}
+
+impl Instance {
+    // rore_types::Border -> qalinlik, uslub va shtrix. Shader bitta rang chizadi:
+    // birinchi ko'rinadigan tomonning rangi olinadi
+    pub fn with_border(mut self, border: &Border) -> Self {
+        let sides = [border.top, border.right, border.bottom, border.left];
+        self.border_width = sides.map(|s| s.width);
+        self.border_style = sides.map(|s| s.style);
+        if let Some(side) = sides
+            .iter()
+            .find(|s| s.width > 0.0 && s.style != BorderStyle::None)
+        {
+            let c = side.color;
+            self.border_color = [c.r, c.g, c.b, c.a];
+            self.target_border_color = self.border_color;
+        }
+        self.border_dash = [border.dash_length, border.dash_phase];
+        self
+    }
 }
 
 #[repr(C)]
@@ -47,7 +94,18 @@ pub struct InstanceRaw {
     pub clip_rect: [f32; 4],
     pub style_index: u32,
     pub z_index: f32,
-    pub padding: [u32; 2],
+    pub translate: [f32; 2],
+    // 2x2 qism ustunlar bo'yicha: [x_axis.x, x_axis.y, y_axis.x, y_axis.y]
+    pub linear: [f32; 4],
+}
+
+impl InstanceRaw {
+    pub const IDENTITY_LINEAR: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+    pub fn set_transform(&mut self, m: &glam::Affine2) {
+        self.translate = m.translation.to_array();
+        self.linear = m.matrix2.to_cols_array();
+    }
 }
 
 impl InstanceRaw {
@@ -82,6 +140,16 @@ impl InstanceRaw {
                     shader_location: 5,
                     format: wgpu::VertexFormat::Float32,
                 },
+                wgpu::VertexAttribute {
+                    offset: 40,
+                    shader_location: 6,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: 48,
+                    shader_location: 7,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
             ],
         }
     }
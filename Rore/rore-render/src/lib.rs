@@ -1,11 +1,23 @@
+pub mod atlas;
 pub mod camera;
 pub mod custom_shader;
 pub mod dynamic;
+pub mod filter;
+pub mod gradient;
 pub mod instance;
+pub mod path;
+pub mod readback;
 pub mod state;
 pub mod texture;
 pub mod vertex;
+pub use atlas::{ImageAtlas, ImageFill};
+pub use custom_shader::ShaderTexture;
+pub use dynamic::GpuContext;
+pub use filter::FilterLayer;
+pub use gradient::{GradientFill, GradientKind};
+pub use image::RgbaImage;
 pub use instance::Instance;
+pub use path::{PathMesh, PathVertex};
 pub use state::State;
 
 // YANGI: Texture ni tashqariga eksport qilamiz
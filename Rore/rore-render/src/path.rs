@@ -0,0 +1,287 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use wgpu::util::DeviceExt;
+
+/// Tessellyatsiya qilingan yo'lning bitta uchi.
+/// `center` va `half_width` chetni silliqlash uchun: piksel qopqoqligi = half_width - |pos - center|.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PathVertex {
+    pub position: [f32; 2],
+    pub center: [f32; 2],
+    pub color: [f32; 4],
+    pub clip_rect: [f32; 4],
+    pub half_width: f32,
+}
+
+impl PathVertex {
+    const ATTRIBS: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+        0 => Float32x2,
+        1 => Float32x2,
+        2 => Float32x4,
+        3 => Float32x4,
+        4 => Float32,
+    ];
+
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<PathVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
+
+/// Uchburchaklar to'plami (lokal koordinatalarda, Logic Thread'da tayyorlanadi).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathMesh {
+    pub vertices: Vec<PathVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl PathMesh {
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn append(&mut self, other: &PathMesh) {
+        self.append_transformed(other, glam::Affine2::IDENTITY, None);
+    }
+
+    /// Affine bilan qo'shadi. clip berilsa, uchlarning clip_rect i almashtiriladi.
+    /// Clip ekran fazosida: aylantirilganda uning chegarasi (AABB) olinadi.
+    pub fn append_transformed(
+        &mut self,
+        other: &PathMesh,
+        transform: glam::Affine2,
+        clip: Option<[f32; 4]>,
+    ) {
+        let base = self.vertices.len() as u32;
+        // Chiziq qalinligi o'rtacha masshtab bilan
+        let scale = transform.matrix2.determinant().abs().sqrt();
+        let clip = clip.map(|c| rore_types::transform_rect(&transform, c));
+        let point = |p: [f32; 2]| transform.transform_point2(glam::Vec2::from(p)).to_array();
+        self.vertices
+            .extend(other.vertices.iter().map(|v| PathVertex {
+                position: point(v.position),
+                center: point(v.center),
+                clip_rect: clip.unwrap_or(v.clip_rect),
+                half_width: v.half_width * scale,
+                ..*v
+            }));
+        self.indices.extend(other.indices.iter().map(|i| i + base));
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    blend: wgpu::BlendState,
+    label: &str,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main",
+            buffers: &[PathVertex::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+    })
+}
+
+/// Vektor yo'llar uchun uchburchak pipeline. Har bir node o'z mesh'iga ega,
+/// faqat o'zgarganda GPU buferlari qayta yig'iladi.
+pub struct PathRenderer {
+    // Asosiy kadr uchun: MSAA o'zgarganda qayta yaratiladi
+    pipeline: wgpu::RenderPipeline,
+    // Offscreen filtr qatlami uchun (shaffof fonga premultiplied natija yozadi)
+    layer_pipeline: wgpu::RenderPipeline,
+    // Tartib barqaror bo'lishi uchun (node id bo'yicha chiziladi)
+    meshes: BTreeMap<u32, PathMesh>,
+    // Umumiy index buferidagi har bir node'ning oralig'i
+    ranges: Vec<(u32, Range<u32>)>,
+    vertex_buffer: Option<wgpu::Buffer>,
+    index_buffer: Option<wgpu::Buffer>,
+    dirty: bool,
+    layout: wgpu::PipelineLayout,
+    shader: wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+}
+
+impl PathRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        camera_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("path.wgsl"));
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Path Pipeline Layout"),
+            bind_group_layouts: &[camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = create_pipeline(
+            device,
+            &layout,
+            &shader,
+            format,
+            wgpu::BlendState::ALPHA_BLENDING,
+            "Path Pipeline",
+            1,
+        );
+        let layer_pipeline = create_pipeline(
+            device,
+            &layout,
+            &shader,
+            format,
+            crate::filter::LAYER_BLEND,
+            "Path Layer Pipeline",
+            1,
+        );
+
+        Self {
+            pipeline,
+            layer_pipeline,
+            meshes: BTreeMap::new(),
+            ranges: Vec::new(),
+            vertex_buffer: None,
+            index_buffer: None,
+            dirty: false,
+            layout,
+            shader,
+            format,
+        }
+    }
+
+    // Filtr qatlamlari 1x bo'lib qoladi
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        self.pipeline = create_pipeline(
+            device,
+            &self.layout,
+            &self.shader,
+            self.format,
+            wgpu::BlendState::ALPHA_BLENDING,
+            "Path Pipeline",
+            sample_count,
+        );
+    }
+
+    // Bo'sh mesh: node endi yo'l chizmaydi
+    pub fn set_mesh(&mut self, node_id: u32, mesh: PathMesh) {
+        if mesh.is_empty() {
+            if self.meshes.remove(&node_id).is_some() {
+                self.dirty = true;
+            }
+        } else {
+            self.meshes.insert(node_id, mesh);
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove(&mut self, node_id: u32) {
+        if self.meshes.remove(&node_id).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    pub fn prepare(&mut self, device: &wgpu::Device) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+
+        let mut all = PathMesh::default();
+        self.ranges.clear();
+        for (&id, mesh) in &self.meshes {
+            let start = all.indices.len() as u32;
+            all.append(mesh);
+            self.ranges.push((id, start..all.indices.len() as u32));
+        }
+        if all.is_empty() {
+            self.vertex_buffer = None;
+            self.index_buffer = None;
+            return;
+        }
+
+        self.vertex_buffer = Some(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Path Vertex Buffer"),
+                contents: bytemuck::cast_slice(&all.vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            }),
+        );
+        self.index_buffer = Some(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Path Index Buffer"),
+                contents: bytemuck::cast_slice(&all.indices),
+                usage: wgpu::BufferUsages::INDEX,
+            }),
+        );
+    }
+
+    pub fn render<'a>(&'a self, rpass: &mut wgpu::RenderPass<'a>, camera: &'a wgpu::BindGroup) {
+        self.render_nodes(rpass, camera, false, |_| true);
+    }
+
+    /// Faqat `include` tanlagan node'larni chizadi. `layer` = offscreen filtr qatlamiga.
+    pub fn render_nodes<'a>(
+        &'a self,
+        rpass: &mut wgpu::RenderPass<'a>,
+        camera: &'a wgpu::BindGroup,
+        layer: bool,
+        include: impl Fn(u32) -> bool,
+    ) {
+        let (Some(vb), Some(ib)) = (&self.vertex_buffer, &self.index_buffer) else {
+            return;
+        };
+        rpass.set_pipeline(if layer {
+            &self.layer_pipeline
+        } else {
+            &self.pipeline
+        });
+        rpass.set_bind_group(0, camera, &[]);
+        rpass.set_vertex_buffer(0, vb.slice(..));
+        rpass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+
+        // Ketma-ket tanlangan node'lar bitta draw call bo'ladi
+        let mut run: Option<Range<u32>> = None;
+        for (id, range) in &self.ranges {
+            if include(*id) {
+                run = Some(match run {
+                    Some(r) => r.start..range.end,
+                    None => range.clone(),
+                });
+            } else if let Some(r) = run.take() {
+                rpass.draw_indexed(r, 0, 0..1);
+            }
+        }
+        if let Some(r) = run {
+            rpass.draw_indexed(r, 0, 0..1);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+@group(0) @binding(0) var<uniform> camera: CameraUniform;
+
+struct VertexInput {
+    @location(0) pos: vec2<f32>,
+    @location(1) center: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) clip_rect: vec4<f32>,
+    @location(4) half_width: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) world_pos: vec2<f32>,
+    @location(1) center: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) clip_rect: vec4<f32>,
+    @location(4) half_width: f32,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_pos = camera.view_proj * vec4<f32>(in.pos, 0.0, 1.0);
+    out.world_pos = in.pos;
+    out.center = in.center;
+    out.color = in.color;
+    out.clip_rect = in.clip_rect;
+    out.half_width = in.half_width;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.world_pos;
+    let c = in.clip_rect;
+    if (p.x < c.x || p.y < c.y || p.x > c.x + c.z || p.y > c.y + c.w) {
+        discard;
+    }
+
+    // Chiziq markazidan masofa: chetdagi 1px yarim shaffof bo'ladi (MSAA siz silliqlash)
+    let coverage = clamp(in.half_width - distance(p, in.center), 0.0, 1.0);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,191 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+// Bir vaqtda GPU'dan o'qilayotgan kadrlar soni (yozuv 60 fps'da ham UI'ni to'xtatmaydi)
+pub const READBACK_RING: usize = 3;
+
+const PENDING: u8 = 0;
+const MAPPED: u8 = 1;
+const FAILED: u8 = 2;
+
+struct Staging {
+    buffer: wgpu::Buffer,
+    busy: bool,
+}
+
+struct InFlight {
+    ticket: u64,
+    slot: usize,
+    width: u32,
+    height: u32,
+    padded_row: u32,
+    state: Arc<AtomicU8>,
+}
+
+/// Master teksturadan CPU'ga asinxron o'qish. `request` nusxalashni navbatga qo'yadi
+/// (kutmaydi), `poll` esa tayyor bo'lganlarini keyingi kadrlarda qaytaradi.
+/// Staging buferlar kichik halqada qayta ishlatiladi.
+pub struct FrameReadback {
+    ring: Vec<Staging>,
+    in_flight: Vec<InFlight>,
+    next_ticket: u64,
+}
+
+impl Default for FrameReadback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameReadback {
+    pub fn new() -> Self {
+        Self {
+            ring: Vec::new(),
+            in_flight: Vec::new(),
+            next_ticket: 1,
+        }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    // Halqa to'la bo'lsa None: chaqiruvchi keyingi kadrda qayta urinadi
+    pub(crate) fn request(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+        [x, y, width, height]: [u32; 4],
+    ) -> Option<u64> {
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = (width * 4).div_ceil(align) * align;
+        let size = (padded_row * height) as wgpu::BufferAddress;
+        let slot = self.acquire(device, size)?;
+        let buffer = &self.ring[slot].buffer;
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Capture Encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let state = Arc::new(AtomicU8::new(PENDING));
+        let done = state.clone();
+        buffer
+            .slice(..size)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let code = if result.is_ok() { MAPPED } else { FAILED };
+                done.store(code, Ordering::Release);
+            });
+
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.in_flight.push(InFlight {
+            ticket,
+            slot,
+            width,
+            height,
+            padded_row,
+            state,
+        });
+        Some(ticket)
+    }
+
+    // Bo'sh staging bufer; kichigi qayta yaratiladi, halqa to'lgan bo'lsa None
+    fn acquire(&mut self, device: &wgpu::Device, size: u64) -> Option<usize> {
+        let create = || Staging {
+            buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Capture Staging Buffer"),
+                size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            busy: true,
+        };
+        if let Some(slot) = self.ring.iter().position(|s| !s.busy) {
+            if self.ring[slot].buffer.size() < size {
+                self.ring[slot] = create();
+            }
+            self.ring[slot].busy = true;
+            return Some(slot);
+        }
+        if self.ring.len() < READBACK_RING {
+            self.ring.push(create());
+            return Some(self.ring.len() - 1);
+        }
+        None
+    }
+
+    /// GPU'ni kutmasdan tekshiradi. `bgra`: surface formati BGRA bo'lsa kanallar almashtiriladi.
+    pub(crate) fn poll(
+        &mut self,
+        device: &wgpu::Device,
+        bgra: bool,
+    ) -> Vec<(u64, Option<image::RgbaImage>)> {
+        if self.in_flight.is_empty() {
+            return Vec::new();
+        }
+        device.poll(wgpu::Maintain::Poll);
+
+        let mut done = Vec::new();
+        let mut i = 0;
+        while i < self.in_flight.len() {
+            let state = self.in_flight[i].state.load(Ordering::Acquire);
+            if state == PENDING {
+                i += 1;
+                continue;
+            }
+            let job = self.in_flight.remove(i);
+            let staging = &mut self.ring[job.slot];
+            let image = (state == MAPPED).then(|| {
+                let image = read_rows(&staging.buffer, &job, bgra);
+                staging.buffer.unmap();
+                image
+            });
+            staging.busy = false;
+            done.push((job.ticket, image.flatten()));
+        }
+        done
+    }
+}
+
+fn read_rows(buffer: &wgpu::Buffer, job: &InFlight, bgra: bool) -> Option<image::RgbaImage> {
+    let size = (job.padded_row * job.height) as wgpu::BufferAddress;
+    let mut pixels = Vec::with_capacity((job.width * job.height * 4) as usize);
+    {
+        let data = buffer.slice(..size).get_mapped_range();
+        for row in data
+            .chunks(job.padded_row as usize)
+            .take(job.height as usize)
+        {
+            pixels.extend_from_slice(&row[..(job.width * 4) as usize]);
+        }
+    }
+    if bgra {
+        for px in pixels.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+    }
+    image::RgbaImage::from_raw(job.width, job.height, pixels)
+}
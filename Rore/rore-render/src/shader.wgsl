@@ -16,8 +16,16 @@ struct StyleRaw {
     border_widths: vec4<f32>,
     extra_props: vec4<f32>,
     anim_data: vec4<f32>,
+    border_styles: vec4<f32>, // top, right, bottom, left: 0 none, 1 solid, 2 dashed, 3 dotted
+    border_dash: vec4<f32>,   // x: shtrix uzunligi (0 = avto), y: faza
+    gradient: vec4<f32>,      // x: 0 ikki rang, 1 linear, 2 radial, 3 conic; y: rampa qatori; zw: markaz
+    image: vec4<f32>,         // x: 0 yo'q, 1 fill, 2 contain, 3 cover, 4 repeat; y: atlas sahifasi; zw: asl o'lcham
+    image_uv: vec4<f32>,      // atlasdagi u0, v0, u1, v1
 };
 @group(1) @binding(0) var<storage, read> styles: array<StyleRaw>;
+@group(1) @binding(1) var gradient_ramps: texture_2d<f32>;
+@group(1) @binding(2) var image_atlas: texture_2d_array<f32>;
+@group(1) @binding(3) var image_sampler: sampler;
 
 struct TimeUniform {
     current_time: f32,
@@ -39,6 +47,8 @@ struct InstanceInput {
     @location(3) clip_rect: vec4<f32>,
     @location(4) style_index: u32,
     @location(5) z_index: f32,
+    @location(6) translate: vec2<f32>,
+    @location(7) linear: vec4<f32>,
 };
 
 struct VertexOutput {
@@ -47,17 +57,21 @@ struct VertexOutput {
     @location(1) size: vec2<f32>,
     @location(2) clip_rect: vec4<f32>,
     @location(3) @interpolate(flat) style_index: u32,
+    @location(4) local_pos: vec2<f32>,
 };
 
 @vertex
 fn vs_main(model: VertexInput, inst: InstanceInput) -> VertexOutput {
     var out: VertexOutput;
-    let w_pos = inst.model_pos + (model.pos * inst.model_size);
+    let local = inst.model_pos + (model.pos * inst.model_size);
+    // 3x2 affine: layout fazosidan ekranga
+    let w_pos = mat2x2<f32>(inst.linear.xy, inst.linear.zw) * local + inst.translate;
     out.clip_pos = camera.view_proj * vec4<f32>(w_pos, 0.0, 1.0);
     out.uv = model.pos;
     out.size = inst.model_size;
     out.clip_rect = inst.clip_rect;
     out.style_index = inst.style_index;
+    out.local_pos = local;
     return out;
 }
 
@@ -66,10 +80,118 @@ fn sd_rounded_box(p: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
     return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
 }
 
+// Rampa qatoridan ikki texel olib o'zimiz aralashtiramiz (Rgba32Float filtrlanmaydi)
+fn sample_ramp(row: f32, t: f32) -> vec4<f32> {
+    let x = saturate(t) * 255.0;
+    let x0 = i32(floor(x));
+    let x1 = min(x0 + 1, 255);
+    let y = i32(row);
+    let c0 = textureLoad(gradient_ramps, vec2<i32>(x0, y), 0);
+    let c1 = textureLoad(gradient_ramps, vec2<i32>(x1, y), 0);
+    return mix(c0, c1, fract(x));
+}
+
+// Atlasdagi rasm, fit bo'yicha joylashtirilgan. Contain'da rasmdan tashqari shaffof.
+// textureSampleGrad: discard'dan keyin ham ishlaydi, mip hosilalardan tanlanadi
+fn sample_image(image: vec4<f32>, rect: vec4<f32>, uv: vec2<f32>, size: vec2<f32>, duv_dx: vec2<f32>, duv_dy: vec2<f32>) -> vec4<f32> {
+    let natural = max(image.zw, vec2<f32>(1.0));
+    var scale = size / natural;
+    if (image.x > 3.5) {
+        scale = vec2<f32>(1.0);
+    } else if (image.x > 2.5) {
+        scale = vec2<f32>(max(scale.x, scale.y));
+    } else if (image.x > 1.5) {
+        scale = vec2<f32>(min(scale.x, scale.y));
+    }
+    var drawn = size;
+    if (image.x > 1.5) {
+        drawn = natural * scale;
+    }
+
+    let k = size / drawn;
+    var t = (uv - 0.5) * k + 0.5;
+    if (image.x > 3.5) {
+        t = fract(uv * k);
+    } else if (any(t < vec2<f32>(0.0)) || any(t > vec2<f32>(1.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let span = rect.zw - rect.xy;
+    let coord = rect.xy + t * span;
+    return textureSampleGrad(image_atlas, image_sampler, coord, i32(image.y), duv_dx * k * span, duv_dy * k * span);
+}
+
+fn fmod(x: f32, y: f32) -> f32 {
+    return x - floor(x / y) * y;
+}
+
+// Yumaloq to'rtburchak perimetri bo'ylab masofa: chap-yuqori yoy tugagan joydan
+// soat yo'nalishida. r: x TL, y TR, z BR, w BL (corner_radii tartibi)
+fn perimeter_pos(p: vec2<f32>, b: vec2<f32>, radii: vec4<f32>) -> f32 {
+    let r = min(radii, vec4<f32>(min(b.x, b.y)));
+    let q = p + b;
+    let size = b * 2.0;
+    let arc = 1.5707964;
+
+    let s_tr = size.x - r.x - r.y;
+    let s_right = s_tr + arc * r.y;
+    let s_br = s_right + size.y - r.y - r.z;
+    let s_bottom = s_br + arc * r.z;
+    let s_bl = s_bottom + size.x - r.z - r.w;
+    let s_left = s_bl + arc * r.w;
+    let s_tl = s_left + size.y - r.w - r.x;
+
+    if (q.x < r.x && q.y < r.x) {
+        let v = q - vec2<f32>(r.x, r.x);
+        return s_tl + atan2(-v.y, -v.x) * r.x;
+    }
+    if (q.x > size.x - r.y && q.y < r.y) {
+        let v = q - vec2<f32>(size.x - r.y, r.y);
+        return s_tr + atan2(v.x, -v.y) * r.y;
+    }
+    if (q.x > size.x - r.z && q.y > size.y - r.z) {
+        let v = q - (size - vec2<f32>(r.z));
+        return s_br + atan2(v.y, v.x) * r.z;
+    }
+    if (q.x < r.w && q.y > size.y - r.w) {
+        let v = q - vec2<f32>(r.w, size.y - r.w);
+        return s_bl + atan2(-v.x, v.y) * r.w;
+    }
+
+    let d = vec4<f32>(q.y, size.x - q.x, size.y - q.y, q.x);
+    let m = min(min(d.x, d.y), min(d.z, d.w));
+    if (m == d.x) { return q.x - r.x; }
+    if (m == d.y) { return s_right + q.y - r.y; }
+    if (m == d.z) { return s_bottom + size.x - r.z - q.x; }
+    return s_left + size.y - r.w - q.y;
+}
+
+// Shtrix (2) yoki nuqta (3) niqobi. inset: tashqi chegaradan ichkariga masofa
+fn dash_mask(code: f32, s: f32, inset: f32, width: f32, dash: vec4<f32>, px: f32) -> f32 {
+    if (code > 2.5) {
+        let period = width * 2.0;
+        let u = fmod(s + dash.y, period) - period * 0.5;
+        let v = inset - width * 0.5;
+        return clamp((width * 0.5 - length(vec2<f32>(u, v))) / px + 0.5, 0.0, 1.0);
+    }
+    let len = select(dash.x, width * 3.0, dash.x <= 0.0);
+    let m = fmod(s + dash.y, len * 2.0);
+    let e = select(-min(m - len, len * 2.0 - m), min(m, len - m), m < len);
+    return clamp(e / px + 0.5, 0.0, 1.0);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let style = styles[in.style_index];
 
+    // Bitta ekran pikselining layout birligidagi o'lchami: scale factor va transform
+    // qanday bo'lsa ham qirra aynan bir piksel kengligida yumshatiladi.
+    // Hosilalar discard'dan oldin (uniform oqimda) olinadi
+    let p = (in.uv - 0.5) * in.size;
+    let px = max(0.5 * (length(dpdx(p)) + length(dpdy(p))), 0.0001);
+    let duv_dx = dpdx(in.uv);
+    let duv_dy = dpdy(in.uv);
+
     if (style.anim_data.y < 0.0) {
         let elapsed = time.current_time - style.anim_data.x;
         if (fract(elapsed) >= 0.5) {
@@ -77,7 +199,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
     }
 
-    let screen_pos = in.clip_pos.xy;
+    // Clip layout fazosida: aylantirilgan node ham, offscreen qatlam ham to'g'ri qirqiladi
+    let screen_pos = in.local_pos;
     if (screen_pos.x < in.clip_rect.x ||
         screen_pos.y < in.clip_rect.y ||
         screen_pos.x > in.clip_rect.x + in.clip_rect.z ||
@@ -106,9 +229,33 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let max_proj = abs(dir.x) * 0.5 + abs(dir.y) * 0.5;
     let proj = dot(centered_uv, dir);
     let grad_t = (proj / max(max_proj, 0.001)) * 0.5 + 0.5;
-    let current_color = mix(current_color_start, current_color_end, saturate(grad_t));
+    var current_color = mix(current_color_start, current_color_end, saturate(grad_t));
+
+    // Ko'p to'xtashli gradientlar: t ni topib rampadan o'qiymiz
+    let kind = style.gradient.x;
+    if (kind > 0.5) {
+        var t = grad_t;
+        let center = style.gradient.zw;
+        let offset = (in.uv - center) * in.size;
+        if (kind > 2.5) {
+            let a = atan2(offset.y, offset.x) - angle;
+            t = fract(a / 6.2831853);
+        } else if (kind > 1.5) {
+            // radius 1.0 = markazdan eng uzoq burchakkacha
+            let far = max(abs(center), abs(vec2<f32>(1.0) - center)) * in.size;
+            t = length(offset) / max(length(far) * style.extra_props.y, 0.001);
+        }
+        current_color = sample_ramp(style.gradient.y, t);
+    }
+
+    // Rasm fon ustidan (straight alpha)
+    if (style.image.x > 0.5) {
+        let img = sample_image(style.image, style.image_uv, in.uv, in.size, duv_dx, duv_dy);
+        let a = img.a + current_color.a * (1.0 - img.a);
+        let rgb = img.rgb * img.a + current_color.rgb * current_color.a * (1.0 - img.a);
+        current_color = vec4<f32>(rgb / max(a, 0.0001), a);
+    }
 
-    let p = (in.uv - 0.5) * in.size;
     let b = in.size * 0.5;
 
     // INQILOB: 4 xil radiusni 1 ta SDF orqali ifodalash (IQ math)
@@ -116,7 +263,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let border_radius = select(rs.x, rs.y, p.y > 0.0);
 
     let d = sd_rounded_box(p, b, border_radius);
-    let smoothed_d = smoothstep(-0.5, 0.5, -d);
+    let smoothed_d = smoothstep(-0.5 * px, 0.5 * px, -d);
 
     var out_color = current_color;
     out_color.a = out_color.a * smoothed_d;
@@ -128,11 +275,26 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let d_left = p.x - (-b.x);
 
     var border_alpha = 0.0;
-    let bw = style.border_widths;
-    if (bw.x > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.x - 1.0, bw.x, d_top)); }
-    if (bw.y > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.y - 1.0, bw.y, d_right)); }
-    if (bw.z > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.z - 1.0, bw.z, d_bottom)); }
-    if (bw.w > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.w - 1.0, bw.w, d_left)); }
+    // Uslubi None bo'lgan tomon chizilmaydi
+    let bw = style.border_widths * step(vec4<f32>(0.5), style.border_styles);
+    if (bw.x > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.x - px, bw.x, d_top)); }
+    if (bw.y > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.y - px, bw.y, d_right)); }
+    if (bw.z > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.z - px, bw.z, d_bottom)); }
+    if (bw.w > 0.0) { border_alpha = max(border_alpha, 1.0 - smoothstep(bw.w - px, bw.w, d_left)); }
+
+    // Dashed/Dotted: eng yaqin tomonning uslubi bilan perimetr bo'ylab niqob
+    if (border_alpha > 0.0 && max(style.border_styles.x, max(style.border_styles.y, max(style.border_styles.z, style.border_styles.w))) > 1.5) {
+        let sd = vec4<f32>(d_top, d_right, d_bottom, d_left);
+        let nearest = min(min(sd.x, sd.y), min(sd.z, sd.w));
+        var side = 3;
+        if (nearest == sd.x) { side = 0; } else if (nearest == sd.y) { side = 1; } else if (nearest == sd.z) { side = 2; }
+        let code = style.border_styles[side];
+        if (code > 1.5) {
+            let s = perimeter_pos(p, b, style.corner_radii);
+            let mask = dash_mask(code, s, -d, bw[side], style.border_dash, px);
+            border_alpha = select(border_alpha * mask, min(border_alpha, mask), code > 2.5);
+        }
+    }
 
     border_alpha = border_alpha * smoothed_d;
     out_color = mix(out_color, current_border_color, border_alpha);
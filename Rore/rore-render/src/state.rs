@@ -5,6 +5,7 @@ use crate::{
 };
 use rore_types::text::TextRenderer;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
 
 #[repr(C)]
@@ -28,17 +29,28 @@ pub(crate) struct CullConfigUniform {
 
 pub struct State<'a> {
     pub(crate) surface: wgpu::Surface<'a>,
-    pub device: wgpu::Device,
-    pub queue: wgpu::Queue,
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+    // Yangi oynalar shu GPU'ni bo'lishadi (`gpu()`)
+    pub(crate) instance: Arc<wgpu::Instance>,
+    pub(crate) adapter: Arc<wgpu::Adapter>,
     pub config: wgpu::SurfaceConfiguration,
     pub size: winit::dpi::PhysicalSize<u32>,
+    pub(crate) alpha_modes: Vec<wgpu::CompositeAlphaMode>,
 
     pub(crate) render_pipeline: wgpu::RenderPipeline,
+    // Filtr qatlamiga (shaffof offscreen tekstura) chizish uchun
+    pub(crate) layer_pipeline: wgpu::RenderPipeline,
     pub(crate) eraser_pipeline: wgpu::RenderPipeline,
     pub(crate) image_pipeline: wgpu::RenderPipeline,
     pub(crate) cull_pipeline: wgpu::ComputePipeline,
     pub(crate) composite_pipeline: wgpu::RenderPipeline,
 
+    // MSAA bo'lsa asosiy kadr msaa_view'ga chiziladi va offscreen_texture'ga resolve qilinadi
+    pub(crate) sample_count: u32,
+    pub(crate) msaa_view: Option<wgpu::TextureView>,
+    pub(crate) format_features: wgpu::TextureFormatFeatureFlags,
+
     pub(crate) frame_index: usize,
     pub(crate) current_capacity: u32,
     pub(crate) low_memory_timer: Option<Instant>,
@@ -58,6 +70,8 @@ pub struct State<'a> {
 
     pub style_bind_groups: [wgpu::BindGroup; 3],
     pub style_buffers: [wgpu::Buffer; 3],
+    pub(crate) gradients: crate::gradient::GradientRamps,
+    pub(crate) images: crate::atlas::ImageAtlas,
 
     pub(crate) node_to_gpu_idx: HashMap<u32, u32>,
     pub(crate) gpu_free_list: Vec<u32>,
@@ -81,10 +95,15 @@ pub struct State<'a> {
     pub(crate) num_instances: u32,
 
     pub(crate) current_draw_count: u32,
+    // Draw order'ning boshidagi asosiy qism; qolgani filtr qatlamlariniki
+    pub(crate) main_draw_count: u32,
     pub custom_shaders: crate::custom_shader::CustomShaderManager,
-    pub current_custom_draws: Vec<(String, [f32; 4], [f32; 4], Vec<u8>)>,
+    pub current_custom_draws: Vec<crate::custom_shader::CustomDraw>,
     pub custom_bind_group: Option<wgpu::BindGroup>,
     pub custom_offsets: Vec<(u32, u32)>,
+    pub paths: crate::path::PathRenderer,
+    pub(crate) readback: crate::readback::FrameReadback,
+    pub filters: crate::filter::FilterRenderer,
     pub text_system: Box<dyn TextRenderer>,
 
     pub(crate) offscreen_texture: wgpu::Texture,
@@ -101,7 +120,46 @@ impl<'a> State<'a> {
         for &node_id in deleted_nodes {
             if let Some(gpu_idx) = self.node_to_gpu_idx.remove(&node_id) {
                 self.gpu_free_list.push(gpu_idx);
+                self.gradients.release(gpu_idx);
+                self.images.release(gpu_idx);
             }
+            self.paths.remove(node_id);
+        }
+    }
+
+    // Shaffof oyna uchun Surface'ni alfa bilan kompozitsiya qilinadigan rejimga o'tkazadi.
+    // Adapter qo'llab-quvvatlamasa, false qaytadi va oyna shaffof bo'lmaydi.
+    pub fn gpu(&self) -> crate::dynamic::GpuContext {
+        crate::dynamic::GpuContext {
+            instance: self.instance.clone(),
+            adapter: self.adapter.clone(),
+            device: self.device.clone(),
+            queue: self.queue.clone(),
+        }
+    }
+
+    pub fn set_transparent(&mut self, transparent: bool) -> bool {
+        let wanted = if transparent {
+            [
+                wgpu::CompositeAlphaMode::PreMultiplied,
+                wgpu::CompositeAlphaMode::PostMultiplied,
+                wgpu::CompositeAlphaMode::Inherit,
+            ]
+            .into_iter()
+            .find(|m| self.alpha_modes.contains(m))
+        } else {
+            Some(wgpu::CompositeAlphaMode::Opaque)
+                .filter(|m| self.alpha_modes.contains(m))
+                .or(self.alpha_modes.first().copied())
+        };
+
+        match wanted {
+            Some(mode) => {
+                self.config.alpha_mode = mode;
+                self.surface.configure(&self.device, &self.config);
+                true
+            }
+            None => false,
         }
     }
 
@@ -129,12 +187,14 @@ impl<'a> State<'a> {
                 dimension: wgpu::TextureDimension::D2,
                 format: self.config.format,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             });
             self.offscreen_view = self
                 .offscreen_texture
                 .create_view(&wgpu::TextureViewDescriptor::default());
+            self.msaa_view = self.create_msaa_view();
 
             self.offscreen_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
                 layout: &self.texture_bind_group_layout,
@@ -160,12 +220,95 @@ impl<'a> State<'a> {
                     clip_rect: [-10000.0, -10000.0, 20000.0, 20000.0],
                     style_index: 0,
                     z_index: -999.0, // Eraser identifikatori
-                    padding: [0, 0],
+                    translate: [0.0, 0.0],
+                    linear: InstanceRaw::IDENTITY_LINEAR,
                 }]),
             );
         }
     }
 
+    /// Asosiy kadrning MSAA darajasi (1, 4 yoki 8). Adapter qo'llamasa pastrog'i tanlanadi
+    /// va o'rnatilgan qiymat qaytadi. Keyingi kadr to'liq qayta chizilishi kerak.
+    pub fn set_sample_count(&mut self, requested: u32) -> u32 {
+        let features = self.format_features;
+        let count = supported_sample_count(requested, |n| features.sample_count_supported(n));
+        if count == self.sample_count {
+            return count;
+        }
+        self.sample_count = count;
+
+        let scene = crate::dynamic::create_scene_pipelines(
+            &self.device,
+            self.config.format,
+            [
+                &self.camera.bind_group_layout,
+                &self.style_bind_group_layout,
+                &self.time_bind_group_layout,
+                &self.texture_bind_group_layout,
+            ],
+            count,
+        );
+        self.render_pipeline = scene.render;
+        self.eraser_pipeline = scene.eraser;
+        self.image_pipeline = scene.image;
+        self.paths.set_sample_count(&self.device, count);
+        self.filters.set_sample_count(&self.device, count);
+        self.custom_shaders.set_sample_count(
+            &self.device,
+            &self.config,
+            &self.camera.bind_group_layout,
+            count,
+        );
+        self.text_system.set_sample_count(&self.device, count);
+        self.msaa_view = self.create_msaa_view();
+        count
+    }
+
+    fn create_msaa_view(&self) -> Option<wgpu::TextureView> {
+        if self.sample_count <= 1 {
+            return None;
+        }
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("MSAA Master Texture"),
+            size: wgpu::Extent3d {
+                width: self.config.width.max(1),
+                height: self.config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+
+    // Asosiy kadrga yozadigan pass'lar uchun. Multisample tekstura saqlanadi (Store):
+    // partial redraw keyingi kadrda uni Load qiladi
+    pub(crate) fn master_attachment(
+        &self,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPassColorAttachment<'_> {
+        let ops = wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        };
+        match &self.msaa_view {
+            Some(msaa) => wgpu::RenderPassColorAttachment {
+                view: msaa,
+                resolve_target: Some(&self.offscreen_view),
+                ops,
+            },
+            None => wgpu::RenderPassColorAttachment {
+                view: &self.offscreen_view,
+                resolve_target: None,
+                ops,
+            },
+        }
+    }
+
     pub fn update_instances_sparse(
         &mut self,
         sparse_instances: &[(u32, Instance)],
@@ -183,14 +326,55 @@ impl<'a> State<'a> {
                 }
             });
 
-            let raw_inst = InstanceRaw {
+            let mut raw_inst = InstanceRaw {
                 model_pos: [inst.position.x, inst.position.y],
                 model_size: [inst.size.x, inst.size.y],
                 clip_rect: inst.clip_rect,
                 style_index: gpu_idx as u32,
                 z_index: 0.0,
-                padding: [0, 0],
+                translate: [0.0, 0.0],
+                linear: InstanceRaw::IDENTITY_LINEAR,
             };
+            raw_inst.set_transform(&inst.transform);
+
+            let mut gradient = [0.0; 4];
+            let mut gradient_props = [inst.gradient_angle, 0.0];
+            match &inst.gradient {
+                Some(fill) => {
+                    let (row, grown) =
+                        self.gradients
+                            .assign(&self.device, &self.queue, gpu_idx, fill);
+                    if grown {
+                        self.refresh_style_bind_groups();
+                    }
+                    gradient = [fill.kind.code(), row as f32, fill.center[0], fill.center[1]];
+                    gradient_props = [fill.angle, fill.radius];
+                }
+                None => self.gradients.release(gpu_idx),
+            }
+
+            let mut image = [0.0; 4];
+            let mut image_uv = [0.0; 4];
+            match &inst.image {
+                Some(fill) => {
+                    let (region, grown) =
+                        self.images.assign(&self.device, &self.queue, gpu_idx, fill);
+                    if grown {
+                        self.refresh_style_bind_groups();
+                    }
+                    // Hali yuklanmagan yoki topilmagan rasm: faqat fon chiziladi
+                    if let Some(region) = region {
+                        image = [
+                            fill.code(),
+                            region.page as f32,
+                            region.size[0],
+                            region.size[1],
+                        ];
+                        image_uv = region.uv;
+                    }
+                }
+                None => self.images.release(gpu_idx),
+            }
 
             let raw_style = StyleRaw {
                 color_start: inst.color_start,
@@ -208,8 +392,13 @@ impl<'a> State<'a> {
                 ],
                 corner_radii: inst.border_radius,
                 border_widths: inst.border_width,
-                extra_props: [inst.gradient_angle, 0.0, 0.0, 0.0],
+                extra_props: [gradient_props[0], gradient_props[1], 0.0, 0.0],
                 anim_data: [inst.anim_start_time, inst.anim_duration, 0.0, 0.0],
+                border_styles: inst.border_style.map(StyleRaw::border_style_code),
+                border_dash: [inst.border_dash[0], inst.border_dash[1], 0.0, 0.0],
+                gradient,
+                image,
+                image_uv,
             };
 
             let offset_inst =
@@ -238,14 +427,13 @@ impl<'a> State<'a> {
             }
         }
 
-        let mut order_data = Vec::with_capacity(draw_order.len());
-        for &id in draw_order {
-            if let Some(&gpu_idx) = self.node_to_gpu_idx.get(&id) {
-                order_data.push(gpu_idx);
-            }
-        }
+        let gpu_map = &self.node_to_gpu_idx;
+        let (order_data, main_count) = self
+            .filters
+            .arrange(draw_order, |id| gpu_map.get(&id).copied());
 
         self.current_draw_count = order_data.len() as u32;
+        self.main_draw_count = main_count;
 
         if !order_data.is_empty() {
             let order_bytes = bytemuck::cast_slice(&order_data);
@@ -256,15 +444,258 @@ impl<'a> State<'a> {
         }
     }
 
-    pub fn update_custom_draws(&mut self, draws: Vec<(String, [f32; 4], [f32; 4], Vec<u8>)>) {
+    pub fn update_custom_draws(&mut self, draws: Vec<crate::custom_shader::CustomDraw>) {
+        use crate::custom_shader::{ShaderTexture, IMAGE_SLOT_BASE};
+
+        // Rasmlar atlasda har bir draw uchun alohida slotni egallaydi
+        let mut pages = Vec::with_capacity(draws.len());
+        let mut texture_uvs = Vec::with_capacity(draws.len());
+        for (i, draw) in draws.iter().enumerate() {
+            let slot = IMAGE_SLOT_BASE + i as u32;
+            let region = match &draw.texture {
+                Some(ShaderTexture::Image(id)) => {
+                    let fill = crate::ImageFill::new(id, rore_types::ImageFit::Fill);
+                    let (region, grown) =
+                        self.images.assign(&self.device, &self.queue, slot, &fill);
+                    if grown {
+                        self.refresh_style_bind_groups();
+                    }
+                    region
+                }
+                _ => {
+                    self.images.release(slot);
+                    None
+                }
+            };
+            pages.push(region.map(|r| r.page));
+            texture_uvs.push(region.map_or([0.0, 0.0, 1.0, 1.0], |r| r.uv));
+        }
+        for i in draws.len()..self.custom_shaders.image_pages.len() {
+            self.images.release(IMAGE_SLOT_BASE + i as u32);
+        }
+        self.custom_shaders.image_pages = pages;
+
         self.current_custom_draws = draws;
-        let (bg, offsets) = self
-            .custom_shaders
-            .prepare_uniforms(&self.device, &self.current_custom_draws);
+        let (bg, offsets) = self.custom_shaders.prepare_uniforms(
+            &self.device,
+            &self.current_custom_draws,
+            &texture_uvs,
+        );
         self.custom_bind_group = bg;
         self.custom_offsets = offsets;
     }
 
+    /// Oxirgi kadrni (yoki uning fizik piksellardagi `region` qismini) o'qishni boshlaydi.
+    /// Surface'ga aynan shu master tekstura ko'chiriladi, shuning uchun natija ekrandagi bilan bir xil.
+    /// Kutmaydi: natija keyingi kadrlarda `poll_captures` dan shu chipta bilan keladi.
+    /// Staging halqasi band bo'lsa yoki region ekrandan tashqarida bo'lsa `None`.
+    pub fn request_capture(&mut self, region: Option<[u32; 4]>) -> Option<u64> {
+        let rect = match region {
+            Some(rect) => self.clamp_scissor(rect)?,
+            None => [0, 0, self.config.width, self.config.height],
+        };
+        self.readback
+            .request(&self.device, &self.queue, &self.offscreen_texture, rect)
+    }
+
+    // Tayyor bo'lgan o'qishlar (chipta, rasm); o'qib bo'lmaganlari None bilan
+    pub fn poll_captures(&mut self) -> Vec<(u64, Option<image::RgbaImage>)> {
+        // Ko'p platformalarda surface formati BGRA
+        let bgra = matches!(
+            self.config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+        self.readback.poll(&self.device, bgra)
+    }
+
+    pub fn pending_captures(&self) -> usize {
+        self.readback.pending()
+    }
+
+    pub(crate) fn clamp_scissor(&self, rect: [u32; 4]) -> Option<[u32; 4]> {
+        let sx = rect[0];
+        let sy = rect[1];
+
+        if sx >= self.config.width || sy >= self.config.height {
+            return None;
+        }
+
+        let sw = rect[2].min(self.config.width - sx);
+        let sh = rect[3].min(self.config.height - sy);
+
+        if sw == 0 || sh == 0 {
+            return None;
+        }
+        Some([sx, sy, sw, sh])
+    }
+
+    // Asosiy instance'larning `range` qismi. `first`: kadrning birinchi passi (tozalash),
+    // `overlays`: yo'llar, matn va custom shader'lar ham
+    fn draw_scene(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        range: std::ops::Range<u32>,
+        first: bool,
+        overlays: bool,
+        clear: Option<[f64; 4]>,
+        scissors: Option<&[[u32; 4]]>,
+    ) {
+        if !first && !overlays && range.is_empty() {
+            return;
+        }
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Offscreen Master Pass"),
+            color_attachments: &[Some(self.master_attachment(
+                match clear.filter(|_| first) {
+                    Some(c) => wgpu::LoadOp::Clear(wgpu::Color {
+                        r: c[0],
+                        g: c[1],
+                        b: c[2],
+                        a: c[3],
+                    }),
+                    None => wgpu::LoadOp::Load,
+                },
+            ))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        match scissors {
+            Some(rects) => {
+                for &rect in rects {
+                    let Some([sx, sy, sw, sh]) = self.clamp_scissor(rect) else {
+                        continue;
+                    };
+                    rpass.set_scissor_rect(sx, sy, sw, sh);
+
+                    if first {
+                        rpass.set_pipeline(&self.eraser_pipeline);
+                        rpass.set_bind_group(0, &self.camera.bind_group, &[]);
+                        rpass.set_bind_group(1, &self.time_bind_groups[self.frame_index], &[]);
+                        rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                        rpass.set_vertex_buffer(1, self.offscreen_instance_buffer.slice(..));
+                        rpass.draw(0..4, 0..1);
+                    }
+                    self.draw_scene_content(&mut rpass, range.clone(), overlays);
+                }
+            }
+            None => self.draw_scene_content(&mut rpass, range, overlays),
+        }
+    }
+
+    fn draw_scene_content<'p>(
+        &'p self,
+        rpass: &mut wgpu::RenderPass<'p>,
+        range: std::ops::Range<u32>,
+        overlays: bool,
+    ) {
+        if !range.is_empty() {
+            rpass.set_pipeline(&self.render_pipeline);
+            rpass.set_bind_group(0, &self.camera.bind_group, &[]);
+            rpass.set_bind_group(1, &self.style_bind_groups[self.frame_index], &[]);
+            rpass.set_bind_group(2, &self.time_bind_groups[self.frame_index], &[]);
+            rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            rpass.set_vertex_buffer(1, self.instance_out_buffers[self.frame_index].slice(..));
+            rpass.draw(0..4, range);
+        }
+        if !overlays {
+            return;
+        }
+
+        let owner = &self.filters.owner;
+        self.paths
+            .render_nodes(rpass, &self.camera.bind_group, false, |id| {
+                !owner.contains_key(&id)
+            });
+        self.text_system.render(rpass);
+    }
+
+    // Custom shader'lar alohida passda: backdrop so'ralsa, undan oldin kadr nusxalanadi
+    fn draw_custom_shaders(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        scissors: Option<&[[u32; 4]]>,
+    ) {
+        use crate::custom_shader::ShaderTexture;
+
+        let Some(bg) = &self.custom_bind_group else {
+            return;
+        };
+        let shaders = &self.custom_shaders;
+
+        let mut backdrop_view = None;
+        if let Some((texture, view)) = &shaders.backdrop {
+            if self
+                .current_custom_draws
+                .iter()
+                .any(|d| d.texture == Some(ShaderTexture::Backdrop))
+            {
+                encoder.copy_texture_to_texture(
+                    self.offscreen_texture.as_image_copy(),
+                    texture.as_image_copy(),
+                    texture.size(),
+                );
+                backdrop_view = Some(view);
+            }
+        }
+
+        let texture_groups: Vec<Option<wgpu::BindGroup>> = self
+            .current_custom_draws
+            .iter()
+            .enumerate()
+            .map(|(i, draw)| match &draw.texture {
+                Some(ShaderTexture::Backdrop) => backdrop_view
+                    .map(|view| shaders.texture_bind_group(&self.device, view, &shaders.sampler)),
+                Some(ShaderTexture::Image(_)) => {
+                    let page = shaders.image_pages.get(i).copied().flatten()?;
+                    let view = self
+                        .images
+                        .texture
+                        .create_view(&wgpu::TextureViewDescriptor {
+                            dimension: Some(wgpu::TextureViewDimension::D2),
+                            base_array_layer: page,
+                            array_layer_count: Some(1),
+                            ..Default::default()
+                        });
+                    Some(shaders.texture_bind_group(&self.device, &view, &self.images.sampler))
+                }
+                None => None,
+            })
+            .collect();
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Custom Shader Pass"),
+            color_attachments: &[Some(self.master_attachment(wgpu::LoadOp::Load))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let full = [[0, 0, self.config.width, self.config.height]];
+        for &rect in scissors.unwrap_or(&full) {
+            let Some([sx, sy, sw, sh]) = self.clamp_scissor(rect) else {
+                continue;
+            };
+            rpass.set_scissor_rect(sx, sy, sw, sh);
+
+            for (i, draw) in self.current_custom_draws.iter().enumerate() {
+                if let Some(pipeline) = shaders.pipelines.get(&draw.shader_id) {
+                    rpass.set_pipeline(pipeline);
+                    rpass.set_bind_group(0, &self.camera.bind_group, &[]);
+
+                    let offsets = self.custom_offsets[i];
+                    rpass.set_bind_group(1, bg, &[offsets.0, offsets.1]);
+                    let textures = texture_groups[i].as_ref();
+                    rpass.set_bind_group(2, textures.unwrap_or(&shaders.dummy_bind_group), &[]);
+
+                    rpass.draw(0..4, 0..1);
+                }
+            }
+        }
+    }
+
     pub fn render(
         &mut self,
         clear_color: [f64; 4],
@@ -308,6 +739,29 @@ impl<'a> State<'a> {
         );
 
         self.text_system.prepare(&self.device, &self.queue);
+        self.paths.prepare(&self.device);
+        if self
+            .current_custom_draws
+            .iter()
+            .any(|d| d.texture == Some(crate::custom_shader::ShaderTexture::Backdrop))
+        {
+            self.custom_shaders
+                .ensure_backdrop(&self.device, &self.config);
+        }
+
+        let scissors = if !is_full_redraw && !scissor_rects.is_empty() {
+            Some(scissor_rects)
+        } else {
+            None
+        };
+        self.filters.prepare(
+            &self.device,
+            &self.queue,
+            &self.texture_bind_group_layout,
+            &self.camera.bind_group_layout,
+            [self.config.width as f32, self.config.height as f32],
+            scissors,
+        );
 
         let mut encoder = self
             .device
@@ -328,118 +782,32 @@ impl<'a> State<'a> {
             }
         }
 
-        {
-            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Offscreen Master Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.offscreen_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: if is_full_redraw {
-                            wgpu::LoadOp::Clear(wgpu::Color {
-                                r: clear_color[0],
-                                g: clear_color[1],
-                                b: clear_color[2],
-                                a: clear_color[3],
-                            })
-                        } else {
-                            wgpu::LoadOp::Load
-                        },
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            if !is_full_redraw && !scissor_rects.is_empty() {
-                for &rect in scissor_rects {
-                    let sx = rect[0];
-                    let sy = rect[1];
-
-                    if sx >= self.config.width || sy >= self.config.height {
-                        continue;
-                    }
-
-                    let max_w = self.config.width - sx;
-                    let max_h = self.config.height - sy;
-
-                    let sw = rect[2].min(max_w);
-                    let sh = rect[3].min(max_h);
-
-                    if sw == 0 || sh == 0 {
-                        continue;
-                    }
-
-                    rpass.set_scissor_rect(sx, sy, sw, sh);
-
-                    rpass.set_pipeline(&self.eraser_pipeline);
-                    rpass.set_bind_group(0, &self.camera.bind_group, &[]);
-                    rpass.set_bind_group(1, &self.time_bind_groups[self.frame_index], &[]);
-                    rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                    rpass.set_vertex_buffer(1, self.offscreen_instance_buffer.slice(..));
-                    rpass.draw(0..4, 0..1);
-
-                    rpass.set_pipeline(&self.render_pipeline);
-                    rpass.set_bind_group(0, &self.camera.bind_group, &[]);
-                    rpass.set_bind_group(1, &self.style_bind_groups[self.frame_index], &[]);
-                    rpass.set_bind_group(2, &self.time_bind_groups[self.frame_index], &[]);
-                    rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                    rpass.set_vertex_buffer(
-                        1,
-                        self.instance_out_buffers[self.frame_index].slice(..),
-                    );
-
-                    if self.current_draw_count > 0 {
-                        rpass.draw(0..4, 0..self.current_draw_count);
-                    }
-
-                    self.text_system.render(&mut rpass);
-
-                    if let Some(bg) = &self.custom_bind_group {
-                        for (i, draw) in self.current_custom_draws.iter().enumerate() {
-                            if let Some(pipeline) = self.custom_shaders.pipelines.get(&draw.0) {
-                                rpass.set_pipeline(pipeline);
-                                rpass.set_bind_group(0, &self.camera.bind_group, &[]);
-
-                                let offsets = self.custom_offsets[i];
-                                rpass.set_bind_group(1, bg, &[offsets.0, offsets.1]);
-
-                                rpass.draw(0..4, 0..1);
-                            }
-                        }
-                    }
-                }
-            } else {
-                rpass.set_pipeline(&self.render_pipeline);
-                rpass.set_bind_group(0, &self.camera.bind_group, &[]);
-                rpass.set_bind_group(1, &self.style_bind_groups[self.frame_index], &[]);
-                rpass.set_bind_group(2, &self.time_bind_groups[self.frame_index], &[]);
-                rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                rpass.set_vertex_buffer(1, self.instance_out_buffers[self.frame_index].slice(..));
-
-                if self.current_draw_count > 0 {
-                    rpass.draw(0..4, 0..self.current_draw_count);
-                }
-
-                self.text_system.render(&mut rpass);
-
-                if let Some(bg) = &self.custom_bind_group {
-                    for (i, draw) in self.current_custom_draws.iter().enumerate() {
-                        if let Some(pipeline) = self.custom_shaders.pipelines.get(&draw.0) {
-                            rpass.set_pipeline(pipeline);
-                            rpass.set_bind_group(0, &self.camera.bind_group, &[]);
-
-                            let offsets = self.custom_offsets[i];
-                            rpass.set_bind_group(1, bg, &[offsets.0, offsets.1]);
-
-                            rpass.draw(0..4, 0..1);
-                        }
-                    }
-                }
-            }
+        // Filtr qatlamlari draw order'dagi o'rnida kompozitsiya qilinadi:
+        // sahna qatlamgacha chiziladi, so'ng qatlam, so'ng davomi
+        let clear = if is_full_redraw {
+            Some(clear_color)
+        } else {
+            None
+        };
+        let mut drawn = 0;
+        let mut first = true;
+        for i in 0..self.filters.placements.len() {
+            let at = self.filters.placements[i].at;
+            self.draw_scene(&mut encoder, drawn..at, first, false, clear, scissors);
+            self.draw_filter_layer(&mut encoder, i, scissors);
+            drawn = at;
+            first = false;
         }
+        let main_count = self.main_draw_count;
+        self.draw_scene(
+            &mut encoder,
+            drawn..main_count,
+            first,
+            true,
+            clear,
+            scissors,
+        );
+        self.draw_custom_shaders(&mut encoder, scissors);
 
         {
             let mut c_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -474,3 +842,32 @@ impl<'a> State<'a> {
         Ok(())
     }
 }
+
+// So'ralganidan oshmaydigan, adapter qo'llaydigan eng katta son (1 doim bor)
+fn supported_sample_count(requested: u32, supported: impl Fn(u32) -> bool) -> u32 {
+    [8, 4, 1]
+        .into_iter()
+        .filter(|&n| n <= requested.max(1))
+        .find(|&n| n == 1 || supported(n))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_count_falls_back_to_what_adapter_supports() {
+        let all = |_| true;
+        assert_eq!(supported_sample_count(8, all), 8);
+        assert_eq!(supported_sample_count(4, all), 4);
+        assert_eq!(supported_sample_count(1, all), 1);
+        // Qo'llanmaydigan oraliq qiymat pastroqqa tushadi
+        assert_eq!(supported_sample_count(2, all), 1);
+        assert_eq!(supported_sample_count(0, all), 1);
+
+        let only_four = |n| n == 4;
+        assert_eq!(supported_sample_count(8, only_four), 4);
+        assert_eq!(supported_sample_count(8, |_| false), 1);
+    }
+}
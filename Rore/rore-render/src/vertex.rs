@@ -11,22 +11,28 @@ impl Vertex {
         wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    offset: 0,
-                    shader_location: 0,
-                    format: wgpu::VertexFormat::Float32x2,
-                }
-            ],
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2,
+            }],
         }
     }
 
     // Standart Kvadrat (Quad)
     // (0,0) -> (1,1) oralig'idagi to'rtburchak
     pub const QUAD: &[Vertex] = &[
-        Vertex { position: [0.0, 0.0] }, // Chap-Yuqori
-        Vertex { position: [0.0, 1.0] }, // Chap-Past
-        Vertex { position: [1.0, 0.0] }, // O'ng-Yuqori
-        Vertex { position: [1.0, 1.0] }, // O'ng-Past
+        Vertex {
+            position: [0.0, 0.0],
+        }, // Chap-Yuqori
+        Vertex {
+            position: [0.0, 1.0],
+        }, // Chap-Past
+        Vertex {
+            position: [1.0, 0.0],
+        }, // O'ng-Yuqori
+        Vertex {
+            position: [1.0, 1.0],
+        }, // O'ng-Past
     ];
-}
\ No newline at end of file
+}
@@ -568,6 +568,14 @@ impl TextSystem {
     pub fn render<'a>(&'a self, pass: &mut RenderPass<'a>) {
         let _ = self.text_renderer.render(&self.atlas, pass);
     }
+
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, count: u32) {
+        let multisample = wgpu::MultisampleState {
+            count,
+            ..Default::default()
+        };
+        self.text_renderer = GlyphonRenderer::new(&mut self.atlas, device, multisample, None);
+    }
 }
 
 impl TextRenderer for TextSystem {
@@ -582,4 +590,7 @@ impl TextRenderer for TextSystem {
     fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
         self.render(pass);
     }
+    fn set_sample_count(&mut self, device: &wgpu::Device, count: u32) {
+        self.set_sample_count(device, count);
+    }
 }
@@ -117,10 +117,14 @@ impl Widget for AnimatedBox {
             target_color_start: color,
             target_color_end: color,
             gradient_angle: 0.0,
+            gradient: None,
+            image: None,
             border_radius: [20.0; 4], // TO'G'RILANDI
             border_width: [0.0; 4],   // TO'G'RILANDI
             border_color: [0.0; 4],
             target_border_color: [0.0; 4],
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             shadow_color: [0.0; 4],
             shadow_offset: Vec2::ZERO,
             shadow_blur: 0.0,
@@ -128,6 +132,7 @@ impl Widget for AnimatedBox {
             clip_rect: clip_rect.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
             anim_start_time: 0.0,
             anim_duration: 0.0,
+            transform: glam::Affine2::IDENTITY,
         };
         output.sparse_instances.push((my_id.0, inst));
 
@@ -1,11 +1,11 @@
 use glam::Vec2;
 use rore_core::state::{FrameworkState, NodeId, UiArena};
 use rore_core::widgets::base::{
-    BuildContext, EventResult, IntoProp, Prop, RenderOutput, Widget, WidgetEvent,
+    BuildContext, EventResult, IntoProp, Prop, RenderOutput, TextureSource, Widget, WidgetEvent,
 };
 use rore_layout::{LayoutEngine, Node as TaffyNode};
-use rore_render::Instance;
-use rore_types::{Color, Style};
+use rore_render::{GradientFill, ImageFill, Instance};
+use rore_types::{Background, Border, Color, Style};
 use std::sync::{Arc, Mutex};
 
 // API Makrolarini va Traitni chaqirib olamiz
@@ -55,6 +55,12 @@ pub struct UiBox {
     pub border_radius: f32,
     pub children: Vec<Box<dyn Widget>>,
     pub live_bg: Option<Arc<Mutex<Color>>>,
+    pub fill: Option<GradientFill>,
+    // Background::Image: url ham id, ham fayl yo'li
+    pub image: Option<(String, ImageFill)>,
+    pub border: Border,
+    pub dash_phase: Prop<f32>,
+    pub live_phase: Option<Arc<Mutex<f32>>>,
     // INQILOB: Kursor hodisalari orqadagi elementlarga o'tib ketishini to'suvchi fizik devor
     pub catch_clicks: bool,
 }
@@ -71,6 +77,11 @@ impl UiBox {
             border_radius: 0.0,
             children: vec![],
             live_bg: None,
+            fill: None,
+            image: None,
+            border: Border::default(),
+            dash_phase: Prop::Static(0.0),
+            live_phase: None,
             catch_clicks: false, // Standart holatda shaffof (pass-through) bo'ladi
         }
     }
@@ -122,6 +133,31 @@ impl UiBox {
         self
     }
 
+    // Background::RadialGradient(RadialGradient::new(stops)) va h.k.; Solid bg_color bilan bir xil
+    pub fn fill(mut self, background: Background) -> Self {
+        match background {
+            Background::Solid(color) => self.bg_color = Prop::Static(color),
+            Background::Image { url, fit, repeat } => {
+                let fill = ImageFill::new(&url, fit).repeat(repeat);
+                self.image = Some((url, fill));
+            }
+            other => self.fill = GradientFill::from_background(&other),
+        }
+        self
+    }
+
+    // Border::all(2.0, BorderStyle::Dashed, color).dash_length(8.0)
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    // Shtrixlarni perimetr bo'ylab suradi: signal bilan "marching ants"
+    pub fn dash_phase(mut self, phase: impl IntoProp<f32>) -> Self {
+        self.dash_phase = phase.into_prop();
+        self
+    }
+
     // INQILOB: Portallar va Modallarni "teshik" bo'lishdan qutqaruvchi, click yutuvchi zanjirli API
     pub fn catch_clicks(mut self) -> Self {
         self.catch_clicks = true;
@@ -143,9 +179,9 @@ impl Widget for UiBox {
     fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
         if self.catch_clicks {
             match event {
-                WidgetEvent::MouseDown
-                | WidgetEvent::MouseUp
-                | WidgetEvent::Click
+                WidgetEvent::MouseDown { .. }
+                | WidgetEvent::MouseUp { .. }
+                | WidgetEvent::Click { .. }
                 | WidgetEvent::MouseScroll { .. } => {
                     return EventResult::Consumed; // Hodisani shu yerda yo'q qilamiz
                 }
@@ -203,6 +239,25 @@ impl Widget for UiBox {
             }
         }
 
+        let phase_prop = std::mem::replace(&mut self.dash_phase, Prop::Static(0.0));
+        match phase_prop {
+            Prop::Static(phase) => self.border.dash_phase = phase,
+            Prop::Dynamic(mut f) => {
+                let live_p = Arc::new(Mutex::new(f()));
+                self.live_phase = Some(live_p.clone());
+
+                rore_core::reactive::signals::create_effect(move || {
+                    *live_p.lock().unwrap() = f();
+                    rore_core::reactive::command::CommandQueue::send(
+                        rore_core::reactive::command::UICommand::MarkDirty(
+                            my_id,
+                            rore_core::state::DIRTY_COLOR,
+                        ),
+                    );
+                });
+            }
+        }
+
         if let Some(id_str) = &self.id {
             arena.register_id(id_str, my_id);
             engine.register_id(id_str, taffy_node);
@@ -243,10 +298,14 @@ impl Widget for UiBox {
             target_color_start: current_color,
             target_color_end: current_color,
             gradient_angle: 0.0,
+            gradient: self.fill,
+            image: self.image.as_ref().map(|(_, fill)| *fill),
             border_radius: [self.border_radius; 4],
             border_width: [0.0; 4],
             border_color: [0.0; 4],
             target_border_color: [0.0; 4],
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             shadow_color: [0.0; 4],
             shadow_offset: Vec2::ZERO,
             shadow_blur: 0.0,
@@ -254,9 +313,21 @@ impl Widget for UiBox {
             clip_rect: clip_rect.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
             anim_start_time: 0.0,
             anim_duration: 0.0,
+            transform: glam::Affine2::IDENTITY,
         };
 
+        let mut border = self.border;
+        if let Some(live_phase) = &self.live_phase {
+            border.dash_phase = *live_phase.lock().unwrap();
+        }
+        let inst = inst.with_border(&border);
+
         output.sparse_instances.push((my_id.0, inst));
+        if let Some((url, _)) = &self.image {
+            output
+                .texture_loads
+                .push((url.clone(), TextureSource::Path(url.clone())));
+        }
 
         if let Ok(children) = engine.taffy.children(taffy_node) {
             let mut child_i = 0;
@@ -415,10 +486,14 @@ impl Widget for VirtualList {
             target_color_start: current_color,
             target_color_end: current_color,
             gradient_angle: 0.0,
+            gradient: None,
+            image: None,
             border_radius: [0.0; 4],
             border_width: [0.0; 4],
             border_color: [0.0; 4],
             target_border_color: [0.0; 4],
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             shadow_color: [0.0; 4],
             shadow_offset: Vec2::ZERO,
             shadow_blur: 0.0,
@@ -426,6 +501,7 @@ impl Widget for VirtualList {
             clip_rect: clip_rect.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
             anim_start_time: 0.0,
             anim_duration: 0.0,
+            transform: glam::Affine2::IDENTITY,
         };
 
         output.sparse_instances.push((my_id.0, inst));
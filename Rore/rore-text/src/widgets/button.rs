@@ -6,6 +6,7 @@ use rore_core::widgets::base::{
 use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_types::{Color, Style};
 use std::sync::{Arc, Mutex};
+use winit::event::MouseButton;
 
 // Yadroviy API'larni chaqiramiz
 use rore_types::{impl_layout_modifiers, LayoutModifiers};
@@ -229,17 +230,26 @@ impl Widget for Button {
                     let c = self.live_normal.lock().unwrap();
                     Some([c.r, c.g, c.b, c.a])
                 }
-                WidgetEvent::MouseDown => {
+                WidgetEvent::MouseDown {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     let c = self.live_click.lock().unwrap();
                     Some([c.r, c.g, c.b, c.a])
                 }
                 // INQILOB: MouseUp faqat rangni tiklaydi, hech qanday mantiq bajarmaydi!
-                WidgetEvent::MouseUp => {
+                WidgetEvent::MouseUp {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     let c = self.live_hover.lock().unwrap();
                     Some([c.r, c.g, c.b, c.a])
                 }
                 // Haqiqiy harakat faqat Click kelganda bajariladi
-                WidgetEvent::Click => {
+                WidgetEvent::Click {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     if let Some(cb) = &mut self.on_click_action {
                         cb();
                     }
@@ -285,9 +295,13 @@ impl Widget for Button {
             color_start: self.target_color,
             color_end: self.target_color,
             gradient_angle: 0.0,
+            gradient: None,
+            image: None,
             border_radius: [self.border_radius; 4],
             border_width: [0.0; 4],
             border_color: [0.0; 4],
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             anim_start_time: self.anim_start_time,
             anim_duration: 0.08,
         };
@@ -134,10 +134,9 @@ impl Widget for CustomPaint {
             let mut painter_fn = self.painter.lock().unwrap();
             let commands = painter_fn(pos, size);
 
-            if !commands.is_empty() {
-                // Minglab komandalar yig'indisini Yadroga WGPU ga jo'natish uchun uzatamiz
-                output.node_commands.push((my_id.0, commands));
-            }
+            // Minglab komandalar yig'indisini Yadroga WGPU ga jo'natish uchun uzatamiz.
+            // Bo'sh ro'yxat ham yuboriladi: oldingi kadrdagi DrawPath'lar o'chadi
+            output.node_commands.push((my_id.0, commands));
         }
 
         output
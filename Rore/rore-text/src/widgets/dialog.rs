@@ -0,0 +1,551 @@
+use glam::Vec2;
+use rore_core::reactive::context::{create_tween, Easing};
+use rore_core::reactive::signals::{create_effect, on_cleanup, Signal};
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use rore_core::widgets::list::ForList;
+use rore_core::widgets::portal::Portal;
+use rore_core::window::{current_window, WindowId};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_render::Instance;
+use rore_types::{Align, Color, LayoutModifiers, Position, Style};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use winit::keyboard::{Key, NamedKey};
+
+use crate::widgets::box_widget::{HBox, VBox};
+use crate::widgets::button::Button;
+use crate::widgets::text::Text;
+use crate::widgets::theme::current_theme;
+
+const DIALOG_ANIM: f32 = 0.18;
+// Ochilishda panel shuncha pikseldan yuqoriga suzib chiqadi
+const DIALOG_SLIDE: f32 = 16.0;
+const BACKDROP_ALPHA: f32 = 0.45;
+
+static NEXT_DIALOG_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    // Har bir oynaning DialogHost'i: ochiq dialoglar steki
+    static DIALOG_HOSTS: RefCell<HashMap<WindowId, Signal<Vec<DialogEntry>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Kirish/chiqish animatsiyasi: qurilganda 0 -> 1, `leave()` dan keyin 1 -> 0,
+/// nolga yetganda `on_gone` bir marta chaqiriladi.
+#[derive(Clone, Copy)]
+pub(crate) struct Presence {
+    target: Signal<f32>,
+    leaving: Signal<bool>,
+    progress: Signal<f32>,
+}
+
+impl Presence {
+    pub(crate) fn enter<F: FnMut() + 'static>(duration: f32, mut on_gone: F) -> Self {
+        let target = Signal::new(0.0);
+        let leaving = Signal::new(false);
+        let progress = create_tween(target, duration, Easing::EaseOut);
+        create_effect(move || {
+            if leaving.get() && progress.get() <= 0.0 {
+                on_gone();
+            }
+        });
+        target.set(1.0);
+        Self {
+            target,
+            leaving,
+            progress,
+        }
+    }
+
+    pub(crate) fn leave(&self) {
+        if !self.leaving.get_untracked() {
+            self.leaving.set(true);
+            self.target.set(0.0);
+        }
+    }
+
+    pub(crate) fn value(&self) -> f32 {
+        self.progress.get_untracked()
+    }
+}
+
+type DialogView = Box<dyn FnOnce() -> Box<dyn Widget> + Send>;
+type ResultCallback<R> = Box<dyn FnOnce(Option<R>) + Send>;
+
+struct ResultSlot<R> {
+    tx: Sender<Option<R>>,
+    rx: Option<Receiver<Option<R>>>,
+    callback: Option<ResultCallback<R>>,
+}
+
+// Dialogning tipga bog'liq bo'lmagan qismi (frame va host shu orqali boshqaradi)
+struct DialogControl {
+    id: u64,
+    window: WindowId,
+    closing: AtomicBool,
+    dismissible: AtomicBool,
+    presence: Mutex<Option<Presence>>,
+    cancel: Mutex<Option<Box<dyn FnOnce() + Send>>>,
+}
+
+impl DialogControl {
+    // true: yopilish shu chaqiruvda boshlandi
+    fn begin_close(&self) -> bool {
+        if self.closing.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        match *self.presence.lock().unwrap() {
+            Some(presence) => presence.leave(),
+            // Hali qurilmagan: animatsiyasiz olib tashlaymiz
+            None => remove_dialog(self.window, self.id),
+        }
+        true
+    }
+
+    fn cancel(&self) {
+        let cancel = self.cancel.lock().unwrap().take();
+        if let Some(cancel) = cancel {
+            if self.begin_close() {
+                cancel();
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DialogEntry {
+    control: Arc<DialogControl>,
+    view: Arc<Mutex<Option<DialogView>>>,
+}
+
+impl PartialEq for DialogEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.control.id == other.control.id
+    }
+}
+
+fn remove_dialog(window: WindowId, id: u64) {
+    if let Some(stack) = DIALOG_HOSTS.with(|hosts| hosts.borrow().get(&window).copied()) {
+        stack.update(|list| list.retain(|e| e.control.id != id));
+    }
+}
+
+/// Ochiq dialog. Natija `close(value)` bilan qaytadi; Escape, backdrop yoki `cancel()` — `None`.
+/// Logic Thread'da (vidjet callback'lari ichida) chaqiriladi.
+pub struct DialogHandle<R> {
+    control: Arc<DialogControl>,
+    slot: Arc<Mutex<ResultSlot<R>>>,
+}
+
+impl<R> Clone for DialogHandle<R> {
+    fn clone(&self) -> Self {
+        Self {
+            control: self.control.clone(),
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<R: Send + 'static> DialogHandle<R> {
+    fn new(window: WindowId) -> Self {
+        let (tx, rx) = channel();
+        let slot = Arc::new(Mutex::new(ResultSlot {
+            tx,
+            rx: Some(rx),
+            callback: None,
+        }));
+        let cancel_slot = slot.clone();
+        let control = Arc::new(DialogControl {
+            id: NEXT_DIALOG_ID.fetch_add(1, Ordering::Relaxed),
+            window,
+            closing: AtomicBool::new(false),
+            dismissible: AtomicBool::new(true),
+            presence: Mutex::new(None),
+            cancel: Mutex::new(Some(Box::new(move || finish(&cancel_slot, None)))),
+        });
+        Self { control, slot }
+    }
+
+    pub fn close(&self, value: R) {
+        if self.control.begin_close() {
+            finish(&self.slot, Some(value));
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.control.cancel();
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.control.closing.load(Ordering::SeqCst)
+    }
+
+    /// Backdrop'ga bosish dialogni yopmaydi (Escape ham).
+    pub fn persistent(self) -> Self {
+        self.control.dismissible.store(false, Ordering::SeqCst);
+        self
+    }
+
+    /// Natija kanali. Faqat bir marta beriladi.
+    pub fn receiver(&self) -> Option<Receiver<Option<R>>> {
+        self.slot.lock().unwrap().rx.take()
+    }
+
+    // Yopilganda Logic Thread'da chaqiriladi
+    pub fn on_result<F: FnOnce(Option<R>) + Send + 'static>(self, f: F) -> Self {
+        self.slot.lock().unwrap().callback = Some(Box::new(f));
+        self
+    }
+}
+
+fn finish<R>(slot: &Mutex<ResultSlot<R>>, value: Option<R>) {
+    let (tx, callback) = {
+        let mut slot = slot.lock().unwrap();
+        (slot.tx.clone(), slot.callback.take())
+    };
+    match callback {
+        Some(cb) => cb(value),
+        None => {
+            let _ = tx.send(value);
+        }
+    }
+}
+
+/// Joriy oynaning `DialogHost` iga yangi modal qo'shadi. Oldingi dialog ochiq bo'lsa, ustiga chiqadi.
+pub fn open_dialog<R, F>(builder: F) -> DialogHandle<R>
+where
+    R: Send + 'static,
+    F: FnOnce(DialogHandle<R>) -> Box<dyn Widget> + Send + 'static,
+{
+    let window = current_window();
+    let handle = DialogHandle::new(window);
+    let Some(stack) = DIALOG_HOSTS.with(|hosts| hosts.borrow().get(&window).copied()) else {
+        eprintln!("Rore: open_dialog chaqirildi, lekin oynada DialogHost yo'q");
+        handle.control.closing.store(true, Ordering::SeqCst);
+        finish(&handle.slot, None);
+        return handle;
+    };
+
+    let for_view = handle.clone();
+    let entry = DialogEntry {
+        control: handle.control.clone(),
+        view: Arc::new(Mutex::new(Some(Box::new(move || builder(for_view))))),
+    };
+    stack.update(|list| list.push(entry));
+    handle
+}
+
+/// Tayyor tasdiqlash oynasi: `Some(true)` — tasdiqlandi.
+pub fn confirm_dialog(
+    title: impl Into<String>,
+    message: impl Into<String>,
+    confirm_label: impl Into<String>,
+) -> DialogHandle<bool> {
+    let (title, message, confirm_label) = (title.into(), message.into(), confirm_label.into());
+    open_dialog(move |handle: DialogHandle<bool>| {
+        let theme = current_theme();
+        let id = handle.control.id;
+        let (ok, cancel) = (handle.clone(), handle);
+        Box::new(
+            VBox::new()
+                .gap(12.0)
+                .child(Text::new(title).color(theme.text).size(18.0))
+                .child(Text::new(message).color(theme.text_muted).size(14.0))
+                .child(
+                    HBox::new()
+                        .gap(8.0)
+                        .modify_style(|s| s.justify_content = Align::End)
+                        .child(
+                            Button::new(&format!("dialog_{}_cancel", id))
+                                .colors(
+                                    theme.surface,
+                                    theme.text_muted.with_alpha(0.2),
+                                    theme.text_muted.with_alpha(0.3),
+                                )
+                                .corner_radius(6.0)
+                                .padding(10.0)
+                                .on_click(move || cancel.cancel())
+                                .child(Text::new("Bekor qilish").color(theme.text).size(14.0)),
+                        )
+                        .child(
+                            Button::new(&format!("dialog_{}_ok", id))
+                                .colors(theme.primary, theme.primary_hover, theme.primary_click)
+                                .corner_radius(6.0)
+                                .padding(10.0)
+                                .on_click(move || ok.close(true))
+                                .child(Text::new(confirm_label).color(Color::WHITE).size(14.0)),
+                        ),
+                ),
+        )
+    })
+}
+
+/// Dialoglar chiziladigan joy. Ilova ildizida bir marta qo'yiladi (odatda oxirgi bola).
+pub struct DialogHost;
+
+impl DialogHost {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DialogHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for DialogHost {
+    fn type_name(&self) -> &'static str {
+        "DialogHost"
+    }
+
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let window = current_window();
+        let stack: Signal<Vec<DialogEntry>> = Signal::new(Vec::new());
+        DIALOG_HOSTS.with(|hosts| hosts.borrow_mut().insert(window, stack));
+        on_cleanup(move || {
+            DIALOG_HOSTS.with(|hosts| {
+                let mut hosts = hosts.borrow_mut();
+                if hosts.get(&window).is_some_and(|s| s.id == stack.id) {
+                    hosts.remove(&window);
+                }
+            });
+        });
+
+        // Portallar overlay bo'lib chiziladi, host esa layout'da joy egallamaydi
+        let list = ForList::new(stack, dialog_view).style(Style {
+            position: Position::Absolute,
+            ..Default::default()
+        });
+        Box::new(list).build(arena, engine, ctx)
+    }
+
+    // Daraxtda hech qachon turmaydi (build ForList id sini qaytaradi)
+    fn render(
+        &self,
+        _engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        _taffy_node: TaffyNode,
+        _parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        RenderOutput::new()
+    }
+}
+
+fn dialog_view(entry: DialogEntry) -> Box<dyn Widget> {
+    let control = entry.control.clone();
+    Box::new(
+        Portal::aligned(0.5, 0.5)
+            .transparent_backdrop()
+            .on_close(move || {
+                if control.dismissible.load(Ordering::SeqCst) {
+                    control.cancel();
+                }
+            })
+            .child(DialogFrame {
+                control: entry.control,
+                view: entry.view.lock().unwrap().take(),
+                presence: None,
+            }),
+    )
+}
+
+// Panel + animatsiyali backdrop. Modal fokus tuzog'i ham shu tugun.
+struct DialogFrame {
+    control: Arc<DialogControl>,
+    view: Option<DialogView>,
+    presence: Option<Presence>,
+}
+
+impl Widget for DialogFrame {
+    fn type_name(&self) -> &'static str {
+        "DialogFrame"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let (window, id) = (self.control.window, self.control.id);
+        let presence = Presence::enter(DIALOG_ANIM, move || remove_dialog(window, id));
+        *self.control.presence.lock().unwrap() = Some(presence);
+        self.presence = Some(presence);
+
+        let theme = current_theme();
+        let mut panel = VBox::new()
+            .bg_color(theme.surface)
+            .corner_radius(12.0)
+            .padding(20.0)
+            .min_width(320.0)
+            .max_width(560.0);
+        // Foydalanuvchi ko'rinishi shu dialog Scope'ida quriladi (signallari u bilan yo'qoladi)
+        if let Some(view) = self.view.take() {
+            panel.children.push(view());
+        }
+        let panel_id = Box::new(panel).build(arena, engine, ctx);
+        let child_nodes: Vec<TaffyNode> = arena
+            .taffy_map
+            .get(&panel_id)
+            .copied()
+            .into_iter()
+            .collect();
+
+        let taffy_node = engine.new_node(Style::default(), &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+        arena.focus_traps.push(taffy_node);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            // Panel ichidagi bosish backdrop'ga (Portal on_close) yetib bormasin
+            WidgetEvent::MouseDown { .. }
+            | WidgetEvent::MouseUp { .. }
+            | WidgetEvent::Click { .. } => EventResult::Consumed,
+            WidgetEvent::KeyPress(Key::Named(NamedKey::Escape)) => {
+                if self.control.dismissible.load(Ordering::SeqCst) {
+                    self.control.cancel();
+                }
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let t = self.presence.map(|p| p.value()).unwrap_or(1.0);
+
+        if let Some(&my_id) = state.arena.node_map.get(&taffy_node) {
+            let dim = [0.0, 0.0, 0.0, BACKDROP_ALPHA * t];
+            output.sparse_instances.push((
+                my_id.0,
+                Instance {
+                    position: Vec2::ZERO,
+                    size: state.screen_size,
+                    color_start: dim,
+                    color_end: dim,
+                    target_color_start: dim,
+                    target_color_end: dim,
+                    gradient_angle: 0.0,
+                    gradient: None,
+                    image: None,
+                    border_radius: [0.0; 4],
+                    border_width: [0.0; 4],
+                    border_color: [0.0; 4],
+                    target_border_color: [0.0; 4],
+                    border_style: [rore_types::BorderStyle::Solid; 4],
+                    border_dash: [0.0; 2],
+                    shadow_color: [0.0; 4],
+                    shadow_offset: Vec2::ZERO,
+                    shadow_blur: 0.0,
+                    shadow_spread: 0.0,
+                    clip_rect: [-10000.0, -10000.0, 20000.0, 20000.0],
+                    anim_start_time: 0.0,
+                    anim_duration: 0.0,
+                    transform: glam::Affine2::IDENTITY,
+                },
+            ));
+        }
+
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let offset = Vec2::new(layout.x, layout.y + (1.0 - t) * DIALOG_SLIDE);
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            offset,
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install_host() -> Signal<Vec<DialogEntry>> {
+        let stack = Signal::new(Vec::new());
+        DIALOG_HOSTS.with(|hosts| hosts.borrow_mut().insert(current_window(), stack));
+        stack
+    }
+
+    fn empty_view<R>(_: DialogHandle<R>) -> Box<dyn Widget> {
+        Box::new(VBox::new())
+    }
+
+    #[test]
+    fn stacked_dialogs_close_independently() {
+        let stack = install_host();
+        let first = open_dialog::<u32, _>(empty_view);
+        let second = open_dialog::<u32, _>(empty_view);
+        let rx = first.receiver().unwrap();
+        assert_eq!(stack.get_untracked().len(), 2);
+
+        first.close(7);
+        let left: Vec<u64> = stack.get_untracked().iter().map(|e| e.control.id).collect();
+        assert_eq!(left, vec![second.control.id]);
+        assert_eq!(rx.try_recv().unwrap(), Some(7));
+
+        // Ikkinchi marta yopish natija yubormaydi
+        first.close(8);
+        assert!(rx.try_recv().is_err());
+        assert!(!first.is_open() && second.is_open());
+    }
+
+    #[test]
+    fn cancel_reports_none_once() {
+        install_host();
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let sink = results.clone();
+        let handle =
+            open_dialog::<bool, _>(empty_view).on_result(move |r| sink.lock().unwrap().push(r));
+
+        handle.cancel();
+        handle.cancel();
+        handle.close(true);
+        assert_eq!(*results.lock().unwrap(), vec![None]);
+    }
+}
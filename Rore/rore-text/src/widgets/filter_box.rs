@@ -0,0 +1,131 @@
+use glam::Vec2;
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, RenderOutput, Widget};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_render::FilterLayer;
+use rore_types::{Filter, Style};
+
+/// Bolasini offscreen qatlamga chizib, filtrlarni (blur, opacity, grayscale,
+/// brightness, drop-shadow) ro'yxatdagi tartibda qo'llaydi.
+/// `FilterBox::backdrop` esa node ortida allaqachon chizilgan kadrni filtrlaydi.
+pub struct FilterBox {
+    pub style: Style,
+    pub filters: Vec<Filter>,
+    pub backdrop: bool,
+    pub radius: f32,
+    pub child: Option<Box<dyn Widget>>,
+}
+
+impl FilterBox {
+    pub fn new(filters: Vec<Filter>) -> Self {
+        Self {
+            style: Style::default(),
+            filters,
+            backdrop: false,
+            radius: 0.0,
+            child: None,
+        }
+    }
+
+    // Shisha effekti: FilterBox::backdrop(vec![Filter::Blur(20.0)])
+    pub fn backdrop(filters: Vec<Filter>) -> Self {
+        Self {
+            backdrop: true,
+            ..Self::new(filters)
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Backdrop niqobining burchak radiusi
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn child(mut self, w: impl Widget + 'static) -> Self {
+        self.child = Some(Box::new(w));
+        self
+    }
+}
+
+impl Widget for FilterBox {
+    fn type_name(&self) -> &'static str {
+        "FilterBox"
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let mut child_nodes = Vec::new();
+        if let Some(child) = self.child.take() {
+            let child_id = child.build(arena, engine, ctx);
+            if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+                child_nodes.push(t_node);
+            }
+        }
+
+        let taffy_node = engine.new_node(self.style.clone(), &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let Some(&my_id) = state.arena.node_map.get(&taffy_node) else {
+            return output;
+        };
+
+        let mut layer = FilterLayer::new(my_id.0, self.filters.clone(), self.backdrop);
+        layer.radius = self.radius;
+        state.filter_layers.insert(my_id, layer);
+
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            Vec2::new(layout.x, layout.y),
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    // Blur va soya node chegarasidan tashqariga yoyiladi (backdrop esa niqoblangan)
+    fn visual_overflow(&self) -> [f32; 4] {
+        if self.backdrop {
+            return [0.0; 4];
+        }
+        let bleed: f32 = self.filters.iter().map(Filter::bleed).sum();
+        [bleed; 4]
+    }
+}
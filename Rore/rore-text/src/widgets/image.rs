@@ -0,0 +1,126 @@
+use glam::Vec2;
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, Prop, RenderOutput, TextureSource, Widget};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_render::{ImageFill, Instance};
+use rore_types::{impl_layout_modifiers, ImageFit, Style};
+
+/// Rasm (ikonka, logotip). Hamma rasmlar bitta atlasda turadi va oddiy to'rtburchaklar
+/// bilan bitta draw call'da chiziladi. Bir xil id'li rasmlar GPU'da bir marta saqlanadi.
+pub struct Image {
+    pub style: Prop<Style>,
+    pub id: String,
+    pub source: TextureSource,
+    pub fit: ImageFit,
+    pub radius: f32,
+}
+
+impl_layout_modifiers!(Image);
+
+impl Image {
+    // Fayl yo'li id vazifasini ham bajaradi
+    pub fn new(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self::with_source(path.clone(), TextureSource::Path(path))
+    }
+
+    // Image::from_bytes("btc", include_bytes!("btc.png").to_vec())
+    pub fn from_bytes(id: impl Into<String>, bytes: Vec<u8>) -> Self {
+        Self::with_source(id.into(), TextureSource::Bytes(bytes))
+    }
+
+    fn with_source(id: String, source: TextureSource) -> Self {
+        Self {
+            style: Prop::Static(Style::default()),
+            id,
+            source,
+            fit: ImageFit::Contain,
+            radius: 0.0,
+        }
+    }
+
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+}
+
+impl Widget for Image {
+    fn type_name(&self) -> &'static str {
+        "Image"
+    }
+
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        _ctx: &BuildContext,
+    ) -> NodeId {
+        let style = match &self.style {
+            Prop::Static(s) => s.clone(),
+            _ => Style::default(),
+        };
+        let taffy_node = engine.new_node(style, &[]);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let Some(&my_id) = state.arena.node_map.get(&taffy_node) else {
+            return output;
+        };
+
+        // Render thread bir xil id'ni qayta yuklamaydi
+        output
+            .texture_loads
+            .push((self.id.clone(), self.source.clone()));
+        output.sparse_instances.push((
+            my_id.0,
+            Instance {
+                position: Vec2::new(layout.x, layout.y),
+                size: Vec2::new(layout.width, layout.height),
+                color_start: [0.0; 4],
+                color_end: [0.0; 4],
+                target_color_start: [0.0; 4],
+                target_color_end: [0.0; 4],
+                gradient_angle: 0.0,
+                gradient: None,
+                image: Some(ImageFill::new(&self.id, self.fit)),
+                border_radius: [self.radius; 4],
+                border_width: [0.0; 4],
+                border_color: [0.0; 4],
+                target_border_color: [0.0; 4],
+                border_style: [rore_types::BorderStyle::Solid; 4],
+                border_dash: [0.0; 2],
+                shadow_color: [0.0; 4],
+                shadow_offset: Vec2::ZERO,
+                shadow_blur: 0.0,
+                shadow_spread: 0.0,
+                clip_rect: clip_rect.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
+                anim_start_time: 0.0,
+                anim_duration: 0.0,
+                transform: glam::Affine2::IDENTITY,
+            },
+        ));
+        output
+    }
+}
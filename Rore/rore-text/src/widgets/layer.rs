@@ -0,0 +1,97 @@
+use glam::Vec2;
+use rore_core::reactive::command::{CommandQueue, UICommand};
+use rore_core::reactive::signals::create_effect;
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, IntoProp, Prop, RenderOutput, Widget};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+
+/// Bolasini butun holda bitta qatlamga chizadi. Tooltip kabi o'zi tugun yaratmaydi:
+/// shaffoflik bolaning tuguniga yoziladi va u alohida teksturada kompozitsiya qilinadi,
+/// shuning uchun ustma-ust turgan bolalar ikki marta aralashmaydi.
+pub struct Layer {
+    child: Box<dyn Widget>,
+    opacity: Prop<f32>,
+    cached: bool,
+}
+
+impl Layer {
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            child: Box::new(child),
+            opacity: Prop::Static(1.0),
+            cached: false,
+        }
+    }
+
+    // 0.0..1.0, signal bilan ham: o'zgarganda kesh buzilmaydi
+    pub fn opacity(mut self, value: impl IntoProp<f32>) -> Self {
+        self.opacity = value.into_prop();
+        self
+    }
+
+    // Kamdan-kam o'zgaradigan daraxt bir marta rasterlanadi va avlodlaridan biri
+    // `mark_dirty_with_flag` bilan belgilanmaguncha teksturadan olinadi
+    pub fn cache_as_layer(mut self) -> Self {
+        self.cached = true;
+        self
+    }
+}
+
+impl Widget for Layer {
+    fn type_name(&self) -> &'static str {
+        "Layer"
+    }
+
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let Layer {
+            child,
+            opacity,
+            cached,
+        } = *self;
+        let child_id = child.build(arena, engine, ctx);
+        let idx = child_id.0 as usize;
+
+        arena.group_layers.insert(child_id, cached);
+        match opacity {
+            Prop::Static(value) => arena.opacities[idx] = value,
+            Prop::Dynamic(mut f) => {
+                arena.opacities[idx] = f();
+                create_effect(move || {
+                    CommandQueue::send(UICommand::SetOpacity(child_id, f()));
+                });
+            }
+        }
+        child_id
+    }
+
+    // Daraxtda hech qachon turmaydi (build bolaning id sini qaytaradi)
+    fn render(
+        &self,
+        _engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        _taffy_node: TaffyNode,
+        _parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        RenderOutput::new()
+    }
+}
+
+/// `.opacity(...)` va `.cache_as_layer()` hamma vidjetlarda.
+pub trait LayerExt: Widget + Sized {
+    fn opacity(self, value: impl IntoProp<f32>) -> Layer {
+        Layer::new(self).opacity(value)
+    }
+
+    fn cache_as_layer(self) -> Layer {
+        Layer::new(self).cache_as_layer()
+    }
+}
+
+impl<W: Widget> LayerExt for W {}
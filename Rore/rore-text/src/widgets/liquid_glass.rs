@@ -1,10 +1,11 @@
+use crate::widgets::filter_box::FilterBox;
 use crate::widgets::shader_box::ShaderBox;
 use glam::Vec2;
 use rore_core::reactive::signals::Signal;
 use rore_core::state::{FrameworkState, NodeId, UiArena};
 use rore_core::widgets::base::{BuildContext, Prop, RenderOutput, Widget};
 use rore_layout::{LayoutEngine, Node as TaffyNode};
-use rore_types::Style;
+use rore_types::{Filter, Position, Style, Thickness, Val};
 
 // GPU Tushunadigan C-Xotira Strukturasi
 #[repr(C)]
@@ -19,7 +20,8 @@ const APPLE_GLASS_WGSL: &str = r#"
 struct CameraUniform { view_proj: mat4x4<f32>, };
 @group(0) @binding(0) var<uniform> camera: CameraUniform;
 
-struct Builtins { rect: vec4<f32>, clip: vec4<f32>, };
+// linear: 2x2 ustunlari, translate.xy: siljish (TransformBox ichida aylansa ham to'g'ri chiziladi)
+struct Builtins { rect: vec4<f32>, clip: vec4<f32>, linear: vec4<f32>, translate: vec4<f32>, };
 @group(1) @binding(0) var<uniform> builtins: Builtins;
 
 struct CustomData {
@@ -35,7 +37,8 @@ struct VertexOutput {
 
 @vertex fn vs_main(@builtin(vertex_index) v_idx: u32) -> VertexOutput {
     let uv = vec2<f32>(f32(v_idx & 1u), f32(v_idx >> 1u));
-    let pos = builtins.rect.xy + uv * builtins.rect.zw;
+    let local = builtins.rect.xy + uv * builtins.rect.zw;
+    let pos = mat2x2<f32>(builtins.linear.xy, builtins.linear.zw) * local + builtins.translate.xy;
 
     var out: VertexOutput;
     out.clip_pos = camera.view_proj * vec4<f32>(pos, 0.0, 1.0);
@@ -64,8 +67,8 @@ struct VertexOutput {
     let edge_y = smoothstep(0.0, 0.02, uv.y) * smoothstep(1.0, 0.98, uv.y);
     let edge_factor = edge_x * edge_y;
 
-    // 85% Shaffof suyuq oyna
-    return vec4<f32>(final_col, 0.85 * edge_factor);
+    // Orqa fon backdrop blur orqali ko'rinadi, shader faqat tus beradi
+    return vec4<f32>(final_col, 0.35 * edge_factor);
 }
 "#;
 
@@ -110,23 +113,33 @@ impl Widget for AppleGlassBox {
     ) -> NodeId {
         let t_sig = self.time_signal;
 
-        let uniform_prop = Prop::Dynamic(Box::new(move || {
-            let data = AppleUniforms {
-                time: t_sig.get(),
-                _padding: [0.0; 3],
-            };
-            bytemuck::bytes_of(&data).to_vec()
+        let uniform_prop = Prop::Dynamic(Box::new(move || AppleUniforms {
+            time: t_sig.get(),
+            _padding: [0.0; 3],
         }));
 
+        // Tashqi o'lcham va joylashuv FilterBox'da, ichki joylashuv (padding, flex) ShaderBox'da
+        let mut inner = self.style.clone();
+        inner.width = Val::Percent(100.0);
+        inner.height = Val::Percent(100.0);
+        inner.margin = Thickness::default();
+        inner.inset = Thickness::default();
+        inner.position = Position::default();
+        let mut outer = self.style.clone();
+        outer.padding = Thickness::default();
+
         let mut s_box = ShaderBox::new("apple_glass_v1", APPLE_GLASS_WGSL)
-            .style(self.style.clone())
-            .uniforms(uniform_prop);
+            .style(inner)
+            .uniform(uniform_prop);
 
         if let Some(child) = self.child.take() {
             s_box.children.push(child);
         }
 
-        Box::new(s_box).build(arena, engine, ctx)
+        let glass = FilterBox::backdrop(vec![Filter::Blur(18.0)])
+            .style(outer)
+            .child(s_box);
+        Box::new(glass).build(arena, engine, ctx)
     }
 
     fn render(
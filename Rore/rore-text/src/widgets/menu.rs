@@ -0,0 +1,833 @@
+use glam::Vec2;
+use rore_core::gesture::Gesture;
+use rore_core::reactive::signals::Signal;
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use rore_core::widgets::list::ForList;
+use rore_core::widgets::portal::Portal;
+use rore_core::widgets::show::Show;
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::{Align, Color, LayoutModifiers, Position, Style, Thickness, Val};
+use std::sync::{Arc, Mutex};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::widgets::box_widget::{HBox, Spacer, UiBox, VBox};
+use crate::widgets::text::Text;
+use crate::widgets::theme::{current_theme, Theme};
+
+// O'lchamlar qat'iy: menyu hajmini layout'siz oldindan bilib, ekranga sig'diramiz
+const MENU_WIDTH: f32 = 220.0;
+const ROW_HEIGHT: f32 = 28.0;
+const SEPARATOR_HEIGHT: f32 = 9.0;
+const MENU_PADDING: f32 = 4.0;
+const SUBMENU_OVERLAP: f32 = 2.0;
+
+type MenuCallback = Arc<Mutex<Box<dyn FnMut() + Send>>>;
+
+#[derive(Clone)]
+pub enum MenuItem {
+    Action {
+        label: String,
+        accel: Option<String>,
+        enabled: bool,
+        on_select: MenuCallback,
+    },
+    Check {
+        label: String,
+        accel: Option<String>,
+        enabled: bool,
+        checked: Signal<bool>,
+    },
+    Submenu {
+        label: String,
+        enabled: bool,
+        items: Arc<Vec<MenuItem>>,
+    },
+    Separator,
+}
+
+impl MenuItem {
+    pub fn action<F: FnMut() + Send + 'static>(label: impl Into<String>, f: F) -> Self {
+        MenuItem::Action {
+            label: label.into(),
+            accel: None,
+            enabled: true,
+            on_select: Arc::new(Mutex::new(Box::new(f))),
+        }
+    }
+
+    pub fn check(label: impl Into<String>, checked: Signal<bool>) -> Self {
+        MenuItem::Check {
+            label: label.into(),
+            accel: None,
+            enabled: true,
+            checked,
+        }
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        MenuItem::Submenu {
+            label: label.into(),
+            enabled: true,
+            items: Arc::new(items),
+        }
+    }
+
+    pub fn separator() -> Self {
+        MenuItem::Separator
+    }
+
+    // Faqat yorliq ("Ctrl+C"): menyu tugma bosilishini o'zi ushlamaydi
+    pub fn accel(mut self, text: impl Into<String>) -> Self {
+        if let MenuItem::Action { accel, .. } | MenuItem::Check { accel, .. } = &mut self {
+            *accel = Some(text.into());
+        }
+        self
+    }
+
+    pub fn enabled(mut self, value: bool) -> Self {
+        match &mut self {
+            MenuItem::Action { enabled, .. }
+            | MenuItem::Check { enabled, .. }
+            | MenuItem::Submenu { enabled, .. } => *enabled = value,
+            MenuItem::Separator => {}
+        }
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        match self {
+            MenuItem::Action { enabled, .. }
+            | MenuItem::Check { enabled, .. }
+            | MenuItem::Submenu { enabled, .. } => *enabled,
+            MenuItem::Separator => false,
+        }
+    }
+
+    fn height(&self) -> f32 {
+        match self {
+            MenuItem::Separator => SEPARATOR_HEIGHT,
+            _ => ROW_HEIGHT,
+        }
+    }
+}
+
+fn menu_height(items: &[MenuItem]) -> f32 {
+    items.iter().map(MenuItem::height).sum::<f32>() + MENU_PADDING * 2.0
+}
+
+// Ochiq panel: path = ildizdan boshlab submenu indekslari
+#[derive(Debug, Clone, PartialEq)]
+struct MenuLevel {
+    path: Vec<usize>,
+    x: f32,
+    y: f32,
+}
+
+/// Ochiq menyu holati. Panellar `levels` dan quriladi, yoritish alohida signalda:
+/// kursor yurganda panellar qayta qurilmaydi, faqat fon rangi o'zgaradi.
+#[derive(Clone)]
+struct MenuState {
+    root: Arc<Mutex<Arc<Vec<MenuItem>>>>,
+    levels: Signal<Vec<MenuLevel>>,
+    highlight: Signal<Vec<Option<usize>>>,
+}
+
+impl MenuState {
+    fn new() -> Self {
+        Self {
+            root: Arc::new(Mutex::new(Arc::new(Vec::new()))),
+            levels: Signal::new(Vec::new()),
+            highlight: Signal::new(Vec::new()),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        !self.levels.get_untracked().is_empty()
+    }
+
+    fn items_at(&self, path: &[usize]) -> Arc<Vec<MenuItem>> {
+        let mut items = self.root.lock().unwrap().clone();
+        for &i in path {
+            let next = match items.get(i) {
+                Some(MenuItem::Submenu { items, .. }) => items.clone(),
+                _ => return Arc::new(Vec::new()),
+            };
+            items = next;
+        }
+        items
+    }
+
+    fn open(&self, items: Vec<MenuItem>, x: f32, y: f32, screen: Vec2) {
+        let h = menu_height(&items);
+        *self.root.lock().unwrap() = Arc::new(items);
+
+        // Pastga sig'masa, kursordan yuqoriga ochiladi (native menyular kabi)
+        let x = x.min(screen.x - MENU_WIDTH).max(0.0);
+        let y = if y + h > screen.y {
+            (y - h).max(0.0)
+        } else {
+            y
+        };
+        self.levels.set(vec![MenuLevel {
+            path: Vec::new(),
+            x,
+            y,
+        }]);
+        self.highlight.set(vec![None]);
+    }
+
+    fn close(&self) {
+        if self.is_open() {
+            self.levels.set(Vec::new());
+            self.highlight.set(Vec::new());
+        }
+    }
+
+    fn set_highlight(&self, level: usize, index: Option<usize>) {
+        let mut hl = self.highlight.get_untracked();
+        hl.truncate(level + 1);
+        hl.resize(level + 1, None);
+        hl[level] = index;
+        if hl != self.highlight.get_untracked() {
+            self.highlight.set(hl);
+        }
+    }
+
+    fn truncate(&self, depth: usize) {
+        let mut levels = self.levels.get_untracked();
+        if levels.len() > depth {
+            levels.truncate(depth);
+            self.levels.set(levels);
+        }
+        let mut hl = self.highlight.get_untracked();
+        if hl.len() > depth {
+            hl.truncate(depth);
+            self.highlight.set(hl);
+        }
+    }
+
+    fn open_submenu(&self, level: usize, index: usize, screen: Vec2, select_first: bool) {
+        let levels = self.levels.get_untracked();
+        let Some(parent) = levels.get(level).cloned() else {
+            return;
+        };
+        let items = self.items_at(&parent.path);
+        let Some(MenuItem::Submenu { items: sub, .. }) = items.get(index) else {
+            return;
+        };
+
+        let row_y =
+            parent.y + MENU_PADDING + items[..index].iter().map(MenuItem::height).sum::<f32>();
+        let h = menu_height(sub);
+
+        // O'ngga sig'masa, chapga ochiladi
+        let mut x = parent.x + MENU_WIDTH - SUBMENU_OVERLAP;
+        if x + MENU_WIDTH > screen.x {
+            x = (parent.x - MENU_WIDTH + SUBMENU_OVERLAP).max(0.0);
+        }
+        let y = (row_y - MENU_PADDING).min(screen.y - h).max(0.0);
+
+        let mut path = parent.path.clone();
+        path.push(index);
+        let new_level = MenuLevel { path, x, y };
+
+        if levels.get(level + 1) != Some(&new_level) {
+            let mut next = levels;
+            next.truncate(level + 1);
+            next.push(new_level);
+            self.levels.set(next);
+        }
+        let first = if select_first {
+            sub.iter().position(MenuItem::is_selectable)
+        } else {
+            None
+        };
+        let mut hl = self.highlight.get_untracked();
+        hl.truncate(level + 1);
+        hl.push(first);
+        self.highlight.set(hl);
+    }
+
+    fn hover(&self, level: usize, index: usize, screen: Vec2) {
+        let levels = self.levels.get_untracked();
+        let Some(lvl) = levels.get(level) else {
+            return;
+        };
+        let items = self.items_at(&lvl.path);
+        let Some(item) = items.get(index) else {
+            return;
+        };
+
+        if !item.is_selectable() {
+            self.truncate(level + 1);
+            self.set_highlight(level, None);
+            return;
+        }
+        self.set_highlight(level, Some(index));
+        match item {
+            MenuItem::Submenu { .. } => {
+                // Allaqachon ochiq bo'lsa, ichidagi yoritishni buzmaymiz
+                let already_open = levels.get(level + 1).is_some_and(|l| {
+                    l.path.last() == Some(&index) && l.path.len() == lvl.path.len() + 1
+                });
+                if !already_open {
+                    self.open_submenu(level, index, screen, false);
+                }
+            }
+            _ => self.truncate(level + 1),
+        }
+    }
+
+    fn activate(&self, level: usize, index: usize, screen: Vec2) {
+        let Some(lvl) = self.levels.get_untracked().get(level).cloned() else {
+            return;
+        };
+        let items = self.items_at(&lvl.path);
+        let Some(item) = items.get(index) else {
+            return;
+        };
+        if !item.is_selectable() {
+            return;
+        }
+        match item {
+            MenuItem::Action { on_select, .. } => {
+                self.close();
+                (on_select.lock().unwrap())();
+            }
+            MenuItem::Check { checked, .. } => {
+                self.close();
+                checked.set(!checked.get_untracked());
+            }
+            MenuItem::Submenu { .. } => self.open_submenu(level, index, screen, true),
+            MenuItem::Separator => {}
+        }
+    }
+
+    fn step(&self, level: usize, forward: bool) {
+        let Some(lvl) = self.levels.get_untracked().get(level).cloned() else {
+            return;
+        };
+        let items = self.items_at(&lvl.path);
+        let len = items.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.highlight.get_untracked().get(level).copied().flatten();
+        let mut i = match current {
+            Some(i) => i,
+            None if forward => len - 1,
+            None => 0,
+        };
+        for _ in 0..len {
+            i = if forward {
+                (i + 1) % len
+            } else {
+                (i + len - 1) % len
+            };
+            if items[i].is_selectable() {
+                self.truncate(level + 1);
+                self.set_highlight(level, Some(i));
+                return;
+            }
+        }
+    }
+
+    /// Klaviatura: strelkalar, Enter/Space, Escape. Menyu yopiq bo'lsa false.
+    fn handle_key(&self, key: &Key, screen: Vec2) -> bool {
+        let depth = self.levels.get_untracked().len();
+        if depth == 0 {
+            return false;
+        }
+        let level = depth - 1;
+        let current = self.highlight.get_untracked().get(level).copied().flatten();
+
+        match key {
+            Key::Named(NamedKey::ArrowDown) => self.step(level, true),
+            Key::Named(NamedKey::ArrowUp) => self.step(level, false),
+            Key::Named(NamedKey::ArrowRight) => {
+                if let Some(i) = current {
+                    let path = self.levels.get_untracked()[level].path.clone();
+                    if matches!(
+                        self.items_at(&path).get(i),
+                        Some(MenuItem::Submenu { enabled: true, .. })
+                    ) {
+                        self.open_submenu(level, i, screen, true);
+                    }
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                if level > 0 {
+                    self.truncate(level);
+                }
+            }
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) => {
+                if let Some(i) = current {
+                    self.activate(level, i, screen);
+                }
+            }
+            Key::Named(NamedKey::Escape) => {
+                if level > 0 {
+                    self.truncate(level);
+                } else {
+                    self.close();
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    // ==================== KO'RINISH ====================
+
+    fn view(&self) -> Box<dyn Widget> {
+        let state = self.clone();
+        let on_close = self.clone();
+        Box::new(
+            Portal::at(0.0, 0.0)
+                .transparent_backdrop()
+                .on_close(move || on_close.close())
+                .child(ForList::new(self.levels, move |lvl: MenuLevel| {
+                    state.panel(lvl)
+                })),
+        )
+    }
+
+    fn panel(&self, lvl: MenuLevel) -> Box<dyn Widget> {
+        let theme = current_theme();
+        let level = lvl.path.len();
+        let items = self.items_at(&lvl.path);
+
+        let mut panel = VBox::new()
+            .catch_clicks()
+            .bg_color(theme.surface)
+            .corner_radius(6.0)
+            .modify_style(|s| {
+                s.position = Position::Absolute;
+                s.inset = Thickness {
+                    left: Val::Px(lvl.x),
+                    top: Val::Px(lvl.y),
+                    ..Default::default()
+                };
+                s.width = Val::Px(MENU_WIDTH);
+                s.padding = Thickness::all(Val::Px(MENU_PADDING));
+            });
+
+        for (i, item) in items.iter().enumerate() {
+            panel = match item {
+                MenuItem::Separator => panel.child(
+                    UiBox::new()
+                        .height(1.0)
+                        .bg_color(theme.text_muted.with_alpha(0.3))
+                        .modify_style(|s| {
+                            s.margin = Thickness {
+                                top: Val::Px(4.0),
+                                bottom: Val::Px(4.0),
+                                ..Default::default()
+                            };
+                        }),
+                ),
+                _ => panel.child(MenuRow {
+                    state: self.clone(),
+                    level,
+                    index: i,
+                    child: Some(self.row_view(item, level, i, &theme)),
+                }),
+            };
+        }
+        Box::new(panel)
+    }
+
+    fn row_view(
+        &self,
+        item: &MenuItem,
+        level: usize,
+        index: usize,
+        theme: &Theme,
+    ) -> Box<dyn Widget> {
+        let (label, accel, enabled, checked, is_submenu) = match item {
+            MenuItem::Action {
+                label,
+                accel,
+                enabled,
+                ..
+            } => (label, accel.clone(), *enabled, None, false),
+            MenuItem::Check {
+                label,
+                accel,
+                enabled,
+                checked,
+            } => (label, accel.clone(), *enabled, Some(*checked), false),
+            MenuItem::Submenu { label, enabled, .. } => (label, None, *enabled, None, true),
+            MenuItem::Separator => unreachable!(),
+        };
+
+        let highlight = self.highlight;
+        let hl_color = theme.primary;
+        let text_color = if enabled {
+            theme.text
+        } else {
+            theme.text_muted
+        };
+
+        let mut row = HBox::new()
+            .bg_color(move || {
+                if highlight.get().get(level).copied().flatten() == Some(index) {
+                    hl_color
+                } else {
+                    Color::TRANSPARENT
+                }
+            })
+            .corner_radius(4.0)
+            .width(Val::Percent(100.0))
+            .height(Val::Percent(100.0))
+            .gap(8.0)
+            .modify_style(|s| {
+                s.align_items = Align::Center;
+                s.padding.left = Val::Px(8.0);
+                s.padding.right = Val::Px(8.0);
+            });
+
+        // Belgi ustuni: checkable bo'lmasa ham joy qoldiriladi, yorliqlar bir tekis turadi
+        let mark = match checked {
+            Some(sig) => Text::new(move || {
+                if sig.get() {
+                    "✓".to_string()
+                } else {
+                    String::new()
+                }
+            }),
+            None => Text::new(""),
+        };
+        row = row
+            .child(mark.color(text_color).size(13.0).style(Style {
+                width: Val::Px(14.0),
+                ..Default::default()
+            }))
+            .child(Text::new(label.as_str()).color(text_color).size(13.0))
+            .child(Spacer::new());
+
+        if let Some(accel) = accel {
+            row = row.child(Text::new(accel).color(theme.text_muted).size(12.0));
+        }
+        if is_submenu {
+            row = row.child(Text::new("▶").color(theme.text_muted).size(10.0));
+        }
+        Box::new(row)
+    }
+}
+
+// Bitta qator: hover -> yoritish/submenu, bosish -> tanlash
+struct MenuRow {
+    state: MenuState,
+    level: usize,
+    index: usize,
+    child: Option<Box<dyn Widget>>,
+}
+
+impl Widget for MenuRow {
+    fn type_name(&self) -> &'static str {
+        "MenuRow"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let mut child_nodes = Vec::new();
+        if let Some(child) = self.child.take() {
+            let child_id = child.build(arena, engine, ctx);
+            if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+                child_nodes.push(t_node);
+            }
+        }
+
+        let style = Style {
+            width: Val::Percent(100.0),
+            height: Val::Px(ROW_HEIGHT),
+            flex_shrink: 0.0,
+            ..Default::default()
+        };
+        let taffy_node = engine.new_node(style, &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+        engine.mark_interactive(taffy_node);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::HoverEnter => {
+                self.state.hover(self.level, self.index, state.screen_size);
+                EventResult::Consumed
+            }
+            WidgetEvent::Click {
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.state
+                    .activate(self.level, self.index, state.screen_size);
+                EventResult::Consumed
+            }
+            // Backdrop'ga (yopish) yetib bormasin
+            WidgetEvent::MouseDown { .. } | WidgetEvent::Click { .. } => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            Vec2::new(layout.x, layout.y),
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+/// O'ng tugma (sensorda uzoq bosish) bilan kursor ostida menyu ochadi.
+/// `items` har ochilishda chaqiriladi, shuning uchun holatga qarab o'zgarishi mumkin.
+///
+/// ```ignore
+/// ContextMenu::new(row, move || vec![
+///     MenuItem::action("Copy", move || copy(id)).accel("Ctrl+C"),
+///     MenuItem::separator(),
+///     MenuItem::submenu("Export", vec![MenuItem::action("CSV", export_csv)]),
+/// ])
+/// ```
+pub struct ContextMenu {
+    pub child: Option<Box<dyn Widget>>,
+    pub style: Style,
+    items: Box<dyn FnMut() -> Vec<MenuItem> + Send>,
+    menu: MenuState,
+    taffy_node: Option<TaffyNode>,
+}
+
+impl ContextMenu {
+    pub fn new<F>(child: impl Widget + 'static, items: F) -> Self
+    where
+        F: FnMut() -> Vec<MenuItem> + Send + 'static,
+    {
+        Self {
+            child: Some(Box::new(child)),
+            style: Style::default(),
+            items: Box::new(items),
+            menu: MenuState::new(),
+            taffy_node: None,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn open(&mut self, state: &mut FrameworkState, x: f32, y: f32) {
+        let items = (self.items)();
+        if items.is_empty() {
+            return;
+        }
+        self.menu.open(items, x, y, state.screen_size);
+        // Klaviatura hodisalari bizga kelishi uchun
+        state.focused_node = self.taffy_node;
+        state.request_redraw();
+    }
+}
+
+impl Widget for ContextMenu {
+    fn type_name(&self) -> &'static str {
+        "ContextMenu"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let mut child_nodes = Vec::new();
+        if let Some(child) = self.child.take() {
+            let child_id = child.build(arena, engine, ctx);
+            if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+                child_nodes.push(t_node);
+            }
+        }
+
+        let levels = self.menu.levels;
+        let menu = self.menu.clone();
+        let popup = Show::new(
+            move || !levels.get().is_empty(),
+            move || menu.view(),
+            || Box::new(UiBox::new()),
+        );
+        let popup_id = Box::new(popup).build(arena, engine, ctx);
+        if let Some(&t_node) = arena.taffy_map.get(&popup_id) {
+            child_nodes.push(t_node);
+        }
+
+        let taffy_node = engine.new_node(self.style.clone(), &child_nodes);
+        let my_id = arena.allocate_node();
+        self.taffy_node = Some(taffy_node);
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::MouseDown {
+                button: MouseButton::Right,
+                ..
+            } => {
+                let pos = state.cursor_pos;
+                self.open(state, pos.x, pos.y);
+                EventResult::Consumed
+            }
+            WidgetEvent::Gesture(Gesture::LongPress { x, y }) if !self.menu.is_open() => {
+                self.open(state, *x, *y);
+                EventResult::Consumed
+            }
+            WidgetEvent::KeyPress(key) => {
+                if self.menu.handle_key(key, state.screen_size) {
+                    state.request_redraw();
+                    EventResult::Consumed
+                } else {
+                    EventResult::Ignored
+                }
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            Vec2::new(layout.x, layout.y),
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn sample() -> (MenuState, Signal<bool>, Arc<Mutex<u32>>) {
+        let hits = Arc::new(Mutex::new(0));
+        let counter = hits.clone();
+        let grid = Signal::new(false);
+        let state = MenuState::new();
+        state.open(
+            vec![
+                MenuItem::action("Copy", move || *counter.lock().unwrap() += 1).accel("Ctrl+C"),
+                MenuItem::separator(),
+                MenuItem::action("Disabled", || {}).enabled(false),
+                MenuItem::submenu("View", vec![MenuItem::check("Grid", grid)]),
+            ],
+            790.0,
+            590.0,
+            SCREEN,
+        );
+        (state, grid, hits)
+    }
+
+    #[test]
+    fn menu_is_kept_on_screen() {
+        let (state, _, _) = sample();
+        let root = &state.levels.get_untracked()[0];
+        assert!(root.x + MENU_WIDTH <= SCREEN.x);
+        assert!(root.y + menu_height(&state.items_at(&[])) <= SCREEN.y);
+
+        // O'ngda joy yo'q: submenu chapga ochiladi
+        state.hover(0, 3, SCREEN);
+        let sub = &state.levels.get_untracked()[1];
+        assert_eq!(sub.path, vec![3]);
+        assert!(sub.x < root.x);
+    }
+
+    #[test]
+    fn keyboard_skips_separators_and_disabled_items() {
+        let (state, grid, hits) = sample();
+        let down = Key::Named(NamedKey::ArrowDown);
+
+        assert!(state.handle_key(&down, SCREEN));
+        assert_eq!(state.highlight.get_untracked(), vec![Some(0)]);
+        state.handle_key(&down, SCREEN);
+        assert_eq!(state.highlight.get_untracked(), vec![Some(3)]);
+
+        state.handle_key(&Key::Named(NamedKey::ArrowRight), SCREEN);
+        assert_eq!(state.highlight.get_untracked(), vec![Some(3), Some(0)]);
+        state.handle_key(&Key::Named(NamedKey::Enter), SCREEN);
+        assert!(grid.get_untracked());
+        assert!(!state.is_open());
+        assert!(!state.handle_key(&down, SCREEN));
+        assert_eq!(*hits.lock().unwrap(), 0);
+    }
+}
@@ -2,12 +2,19 @@ pub mod animated;
 pub mod box_widget;
 pub mod button;
 pub mod custom_paint;
+pub mod dialog;
+pub mod filter_box;
+pub mod image;
+pub mod layer;
 pub mod liquid_glass;
+pub mod menu;
 pub mod router;
 pub mod shader_box;
 pub mod text;
 pub mod text_input;
 pub mod theme;
+pub mod toast;
+pub mod tooltip;
 pub use animated::AnimatedBox;
 pub use box_widget::*;
 pub use button::*;
@@ -17,6 +24,13 @@ pub use scroll_view::*;
 pub use text::*;
 pub use text_input::*;
 pub use theme::*;
+pub use tooltip::{Tooltip, TooltipExt};
 pub mod transform;
 pub use custom_paint::*;
+pub use dialog::{confirm_dialog, open_dialog, DialogHandle, DialogHost};
+pub use filter_box::FilterBox;
+pub use image::Image;
+pub use layer::{Layer, LayerExt};
+pub use menu::{ContextMenu, MenuItem};
+pub use toast::{dismiss_toast, toast, Toast, ToastHost, ToastId, ToastKind};
 pub use transform::*;
@@ -1,8 +1,8 @@
 use glam::Vec2;
 use rore_core::reactive::command::{CommandQueue, UICommand};
 use rore_core::reactive::signals::{
-    create_effect, create_scope, create_signal_untracked, get_signal_untyped, set_signal_untyped,
-    SignalId,
+    create_effect, create_scope, create_signal_untracked, get_active_scope, get_signal_untyped,
+    run_in_scope, set_signal_untyped, ScopeId, SignalId,
 };
 use rore_core::state::{FrameworkState, NodeId, UiArena};
 use rore_core::widgets::base::{BuildContext, RenderOutput, Widget};
@@ -29,6 +29,7 @@ pub struct Router {
     pub taffy_node: Option<TaffyNode>,
     pub current_path: String,
     my_id: Option<NodeId>,
+    owner_scope: Option<ScopeId>,
 }
 
 impl Router {
@@ -43,6 +44,7 @@ impl Router {
             taffy_node: None,
             current_path: initial_path.to_string(),
             my_id: None,
+            owner_scope: None,
         }
     }
 
@@ -67,6 +69,7 @@ impl Widget for Router {
         ctx: &BuildContext,
     ) -> NodeId {
         let sig_id = CURRENT_ROUTE.with(|cr| cr.borrow().unwrap());
+        self.owner_scope = get_active_scope();
 
         let child_widget = if let Some(builder) = self.routes.get_mut(&self.current_path) {
             builder()
@@ -127,7 +130,9 @@ impl Widget for Router {
         };
 
         let ctx = BuildContext {};
-        let (_, child_id) = create_scope(|| child_widget.build(&mut state.arena, engine, &ctx));
+        let (_, child_id) = run_in_scope(self.owner_scope, || {
+            create_scope(|| child_widget.build(&mut state.arena, engine, &ctx))
+        });
         self.current_child_id = Some(child_id);
 
         // 3. YANGI SAHIFANI TAFFY'GA ULASH
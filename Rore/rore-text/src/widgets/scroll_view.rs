@@ -7,6 +7,7 @@ use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_render::Instance;
 use rore_types::Style;
 use std::cell::Cell;
+use winit::event::MouseButton;
 
 pub struct ScrollView {
     pub id: Option<String>,
@@ -145,13 +146,20 @@ impl Widget for ScrollView {
         let mut changed = false;
 
         match event {
-            WidgetEvent::MouseDown => {
+            WidgetEvent::MouseDown {
+                button: MouseButton::Left,
+                ..
+            } => {
                 self.is_dragging.set(true);
                 self.velocity_x.set(0.0);
                 self.velocity_y.set(0.0);
                 changed = true;
             }
-            WidgetEvent::MouseUp | WidgetEvent::HoverLeave => {
+            WidgetEvent::MouseUp {
+                button: MouseButton::Left,
+                ..
+            }
+            | WidgetEvent::HoverLeave => {
                 if self.is_dragging.get() {
                     self.is_dragging.set(false);
                     self.last_time.set(state.global_time);
@@ -374,10 +382,14 @@ impl Widget for ScrollView {
                     target_color_start: scrollbar_color,
                     target_color_end: scrollbar_color,
                     gradient_angle: 0.0,
+                    gradient: None,
+                    image: None,
                     border_radius: [3.0; 4],
                     border_width: [0.0; 4],
                     border_color: [0.0; 4],
                     target_border_color: [0.0; 4],
+                    border_style: [rore_types::BorderStyle::Solid; 4],
+                    border_dash: [0.0; 2],
                     shadow_color: [0.0; 4],
                     shadow_offset: Vec2::ZERO,
                     shadow_blur: 0.0,
@@ -385,6 +397,7 @@ impl Widget for ScrollView {
                     clip_rect: strict_clip.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
                     anim_start_time: 0.0,
                     anim_duration: 0.0,
+                    transform: glam::Affine2::IDENTITY,
                 };
                 output.sparse_instances.push((my_id.0 + 200000, thumb_inst));
             }
@@ -405,10 +418,14 @@ impl Widget for ScrollView {
                     target_color_start: scrollbar_color,
                     target_color_end: scrollbar_color,
                     gradient_angle: 0.0,
+                    gradient: None,
+                    image: None,
                     border_radius: [3.0; 4],
                     border_width: [0.0; 4],
                     border_color: [0.0; 4],
                     target_border_color: [0.0; 4],
+                    border_style: [rore_types::BorderStyle::Solid; 4],
+                    border_dash: [0.0; 2],
                     shadow_color: [0.0; 4],
                     shadow_offset: Vec2::ZERO,
                     shadow_blur: 0.0,
@@ -416,6 +433,7 @@ impl Widget for ScrollView {
                     clip_rect: strict_clip.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
                     anim_start_time: 0.0,
                     anim_duration: 0.0,
+                    transform: glam::Affine2::IDENTITY,
                 };
                 output.sparse_instances.push((my_id.0 + 200001, thumb_inst));
             }
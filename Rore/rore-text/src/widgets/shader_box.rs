@@ -1,7 +1,10 @@
 use glam::Vec2;
 use rore_core::state::{FrameworkState, NodeId, UiArena};
-use rore_core::widgets::base::{BuildContext, DisplayCommand, Prop, RenderOutput, Widget};
+use rore_core::widgets::base::{
+    BuildContext, DisplayCommand, Prop, RenderOutput, TextureSource, Widget,
+};
 use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_render::ShaderTexture;
 use rore_types::Style;
 use std::sync::{Arc, Mutex};
 
@@ -11,6 +14,7 @@ pub struct ShaderBox {
     pub shader_id: String,
     pub wgsl_code: String,
     pub uniforms: Prop<Vec<u8>>,
+    pub texture: Option<ShaderTexture>,
     pub children: Vec<Box<dyn Widget>>,
 
     live_uniforms: Arc<Mutex<Vec<u8>>>,
@@ -25,12 +29,24 @@ impl ShaderBox {
             shader_id: shader_id.to_string(),
             wgsl_code: wgsl_code.to_string(),
             uniforms: Prop::Static(vec![]),
+            texture: None,
             children: vec![],
             live_uniforms: Arc::new(Mutex::new(vec![])),
             is_first_render: std::cell::Cell::new(true),
         }
     }
 
+    /// Shader fayldan o'qiladi. Debug build'da fayl kuzatiladi va saqlanganda qayta yig'iladi.
+    pub fn from_file(shader_id: &str, path: impl AsRef<std::path::Path>) -> Self {
+        let path = path.as_ref();
+        let wgsl = std::fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Rore: {} o'qilmadi: {}", path.display(), e);
+            String::new()
+        });
+        rore_core::shader::watch(shader_id, path);
+        Self::new(shader_id, &wgsl)
+    }
+
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
@@ -39,6 +55,25 @@ impl ShaderBox {
         self.uniforms = bytes;
         self
     }
+    // `#[repr(C)]` + `bytemuck::Pod` struct; maydonlar WGSL struct tartibi va 16 baytlik qoidasiga mos bo'lishi kerak
+    pub fn uniform<T: bytemuck::Pod + Send>(self, value: Prop<T>) -> Self {
+        self.uniforms(match value {
+            Prop::Static(v) => Prop::Static(bytemuck::bytes_of(&v).to_vec()),
+            Prop::Dynamic(mut f) => {
+                Prop::Dynamic(Box::new(move || bytemuck::bytes_of(&f()).to_vec()))
+            }
+        })
+    }
+    // Fayl yo'li yoki oldin yuklangan rasm id'si (`@group(2)`)
+    pub fn image(mut self, id: impl Into<String>) -> Self {
+        self.texture = Some(ShaderTexture::Image(id.into()));
+        self
+    }
+    // Shader ortidagi, allaqachon chizilgan kadr (`@group(2)`)
+    pub fn backdrop(mut self) -> Self {
+        self.texture = Some(ShaderTexture::Backdrop);
+        self
+    }
     pub fn child(mut self, w: impl Widget + 'static) -> Self {
         self.children.push(Box::new(w));
         self
@@ -116,11 +151,18 @@ impl Widget for ShaderBox {
 
         let current_uniforms = self.live_uniforms.lock().unwrap().clone();
 
+        if let Some(ShaderTexture::Image(id)) = &self.texture {
+            output
+                .texture_loads
+                .push((id.clone(), TextureSource::Path(id.clone())));
+        }
+
         let cmd = DisplayCommand::DrawCustomShader {
             shader_id: self.shader_id.clone(),
             wgsl_code: wgsl,
             rect: [layout.x, layout.y, layout.width, layout.height],
             uniforms: current_uniforms,
+            texture: self.texture.clone(),
         };
 
         output.node_commands.push((my_id.0, vec![cmd]));
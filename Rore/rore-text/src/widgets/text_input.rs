@@ -1,4 +1,5 @@
 use glam::Vec2;
+use rore_core::clipboard::{ClipboardContent, ClipboardService, Copyable};
 use rore_core::state::{FrameworkState, NodeId, UiArena};
 use rore_core::widgets::base::{
     BuildContext, EventResult, IntoProp, Prop, RenderOutput, Widget, WidgetEvent,
@@ -7,6 +8,7 @@ use rore_layout::{LayoutEngine, Node as TaffyNode};
 use rore_render::Instance;
 use rore_types::{Color, Style};
 use std::cell::Cell;
+use winit::event::MouseButton;
 use winit::keyboard::{Key, NamedKey};
 
 use crate::text::get_measurer;
@@ -161,6 +163,62 @@ impl TextInput {
         self.cursor_char = self.lines[self.cursor_row].chars().count();
     }
 
+    // Sichqoncha ostidagi matn bayti
+    fn byte_at_cursor(&self, state: &FrameworkState) -> usize {
+        let lx = state.cursor_pos.x - self.last_layout_x.get() - 12.0 + self.scroll_x.get();
+        let ly = state.cursor_pos.y - self.last_layout_y.get() - 12.0 + self.scroll_y.get();
+
+        let measurer_arc = get_measurer();
+        let mut fm = measurer_arc.lock().unwrap();
+        fm.get_byte_at_pos(
+            &self.get_full_text(),
+            self.font_size,
+            if self.multiline {
+                Some(self.last_width.get())
+            } else {
+                None
+            },
+            lx,
+            ly,
+        )
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        let anchor = self.selection_anchor.get()?;
+        let cursor = self.get_global_byte();
+        let (start, end) = (anchor.min(cursor), anchor.max(cursor));
+        let full = self.get_full_text();
+        (start < end && end <= full.len()).then(|| full[start..end].to_string())
+    }
+
+    // Tanlovni almashtirib, kursor o'rniga qo'yadi
+    fn insert_text(&mut self, text: &str) {
+        let text = if self.multiline {
+            text.to_string()
+        } else {
+            text.replace('\n', " ")
+        };
+        self.delete_selection();
+        let mut full = self.get_full_text();
+        let gb = self.get_global_byte();
+        full.insert_str(gb, &text);
+        self.set_full_text(&full);
+        self.set_cursor_global_byte(gb + text.len());
+    }
+
+    fn commit_change(&mut self, state: &mut FrameworkState, id: NodeId) {
+        self.is_dirty.set(true);
+
+        let current_text = self.get_full_text();
+        if let Some(cb) = &mut self.on_input {
+            cb(current_text);
+        }
+
+        if !state.sparse_update_queue.contains(&id) {
+            state.sparse_update_queue.push(id);
+        }
+    }
+
     pub fn delete_selection(&mut self) -> bool {
         if let Some(anchor) = self.selection_anchor.get() {
             let cursor = self.get_global_byte();
@@ -186,6 +244,10 @@ impl Widget for TextInput {
     fn type_name(&self) -> &'static str {
         "TextInput"
     }
+
+    fn as_copyable(&mut self) -> Option<&mut dyn Copyable> {
+        Some(self)
+    }
     fn is_interactive(&self) -> bool {
         true
     }
@@ -234,25 +296,11 @@ impl Widget for TextInput {
                     state.current_cursor_icon = cursor_icon::CursorIcon::Default;
                     return EventResult::Consumed;
                 }
-                WidgetEvent::MouseDown => {
-                    let lx =
-                        state.cursor_pos.x - self.last_layout_x.get() - 12.0 + self.scroll_x.get();
-                    let ly =
-                        state.cursor_pos.y - self.last_layout_y.get() - 12.0 + self.scroll_y.get();
-
-                    let measurer_arc = get_measurer();
-                    let mut fm = measurer_arc.lock().unwrap();
-                    let gb = fm.get_byte_at_pos(
-                        &self.get_full_text(),
-                        self.font_size,
-                        if self.multiline {
-                            Some(self.last_width.get())
-                        } else {
-                            None
-                        },
-                        lx,
-                        ly,
-                    );
+                WidgetEvent::MouseDown {
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    let gb = self.byte_at_cursor(state);
 
                     if is_shift {
                         if self.selection_anchor.get().is_none() {
@@ -267,35 +315,38 @@ impl Widget for TextInput {
                     self.last_input_time.set(state.global_time);
                 }
                 WidgetEvent::MouseDrag { .. } => {
-                    let lx =
-                        state.cursor_pos.x - self.last_layout_x.get() - 12.0 + self.scroll_x.get();
-                    let ly =
-                        state.cursor_pos.y - self.last_layout_y.get() - 12.0 + self.scroll_y.get();
-
-                    let measurer_arc = get_measurer();
-                    let mut fm = measurer_arc.lock().unwrap();
-                    let gb = fm.get_byte_at_pos(
-                        &self.get_full_text(),
-                        self.font_size,
-                        if self.multiline {
-                            Some(self.last_width.get())
-                        } else {
-                            None
-                        },
-                        lx,
-                        ly,
-                    );
+                    let gb = self.byte_at_cursor(state);
 
                     self.set_cursor_global_byte(gb);
                     changed = true;
                 }
-                WidgetEvent::MouseUp => {
+                WidgetEvent::MouseUp {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     let anchor = self.selection_anchor.get();
                     let gb = self.get_global_byte();
                     if anchor == Some(gb) {
                         self.selection_anchor.set(None); // Shunchaki bosilgan bo'lsa langarni olib tashlaymiz
+                    } else if let Some(text) = self.selected_text() {
+                        // Linux: belgilangan matn o'rta tugma bilan boshqa joyga qo'yiladi
+                        state.clipboard.set_primary_text(text);
                     }
                 }
+                WidgetEvent::MouseDown {
+                    button: MouseButton::Middle,
+                    ..
+                } => {
+                    let Some(text) = state.clipboard.primary_text() else {
+                        return EventResult::Ignored;
+                    };
+                    self.selection_anchor.set(None);
+                    let gb = self.byte_at_cursor(state);
+                    self.set_cursor_global_byte(gb);
+                    self.insert_text(&text);
+                    changed = true;
+                    self.last_input_time.set(state.global_time);
+                }
                 WidgetEvent::TextInput(input_str) => {
                     if !is_ctrl {
                         self.delete_selection();
@@ -311,50 +362,13 @@ impl Widget for TextInput {
                 WidgetEvent::KeyPress(key) => {
                     self.last_input_time.set(state.global_time);
 
-                    // OS Clipboard yorliqlari (Hotkeys)
+                    // Ctrl+A. Copy/Cut/Paste ni yadro Copyable orqali yuboradi
                     if is_ctrl {
                         if let Key::Character(c) = key {
                             if c.eq_ignore_ascii_case("a") {
                                 self.selection_anchor.set(Some(0));
                                 self.set_cursor_global_byte(self.get_full_text().len());
                                 changed = true;
-                            } else if c.eq_ignore_ascii_case("c") || c.eq_ignore_ascii_case("x") {
-                                if let Some(anchor) = self.selection_anchor.get() {
-                                    let cursor = self.get_global_byte();
-                                    let start = anchor.min(cursor);
-                                    let end = anchor.max(cursor);
-                                    if start < end {
-                                        let full = self.get_full_text();
-                                        if let Some(cb_mutex) = &state.clipboard {
-                                            if let Ok(mut cb) = cb_mutex.lock() {
-                                                let _ = cb.set_text(full[start..end].to_string());
-                                            }
-                                        }
-                                        if c.eq_ignore_ascii_case("x") {
-                                            self.delete_selection();
-                                            changed = true;
-                                        }
-                                    }
-                                }
-                            } else if c.eq_ignore_ascii_case("v") {
-                                if let Some(cb_mutex) = &state.clipboard {
-                                    if let Ok(mut cb) = cb_mutex.lock() {
-                                        if let Ok(pasted) = cb.get_text() {
-                                            let pasted = if self.multiline {
-                                                pasted
-                                            } else {
-                                                pasted.replace('\n', " ")
-                                            };
-                                            self.delete_selection();
-                                            let mut full = self.get_full_text();
-                                            let gb = self.get_global_byte();
-                                            full.insert_str(gb, &pasted);
-                                            self.set_full_text(&full);
-                                            self.set_cursor_global_byte(gb + pasted.len());
-                                            changed = true;
-                                        }
-                                    }
-                                }
                             }
                         }
                     }
@@ -480,16 +494,7 @@ impl Widget for TextInput {
             }
 
             if changed {
-                self.is_dirty.set(true);
-
-                let current_text = self.get_full_text();
-                if let Some(cb) = &mut self.on_input {
-                    cb(current_text);
-                }
-
-                if !state.sparse_update_queue.contains(&id) {
-                    state.sparse_update_queue.push(id);
-                }
+                self.commit_change(state, id);
                 return EventResult::Consumed;
             }
         }
@@ -549,10 +554,14 @@ impl Widget for TextInput {
             target_color_start: current_bg,
             target_color_end: current_bg,
             gradient_angle: 0.0,
+            gradient: None,
+            image: None,
             border_radius: [self.border_radius; 4],
             border_width: [1.0; 4],
             border_color: c_start,
             target_border_color: c_target,
+            border_style: [rore_types::BorderStyle::Solid; 4],
+            border_dash: [0.0; 2],
             shadow_color: [0.0; 4],
             shadow_offset: Vec2::ZERO,
             shadow_blur: 0.0,
@@ -560,6 +569,7 @@ impl Widget for TextInput {
             clip_rect: clip_rect.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
             anim_start_time: self.focus_anim_start.get(),
             anim_duration: 0.15,
+            transform: glam::Affine2::IDENTITY,
         };
         output.sparse_instances.push((my_id.0, box_inst));
 
@@ -660,10 +670,14 @@ impl Widget for TextInput {
                             target_color_start: [0.2, 0.4, 0.8, 0.5],
                             target_color_end: [0.2, 0.4, 0.8, 0.5],
                             gradient_angle: 0.0,
+                            gradient: None,
+                            image: None,
                             border_radius: [2.0; 4],
                             border_width: [0.0; 4],
                             border_color: [0.0; 4],
                             target_border_color: [0.0; 4],
+                            border_style: [rore_types::BorderStyle::Solid; 4],
+                            border_dash: [0.0; 2],
                             shadow_color: [0.0; 4],
                             shadow_offset: Vec2::ZERO,
                             shadow_blur: 0.0,
@@ -672,6 +686,7 @@ impl Widget for TextInput {
                                 .unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
                             anim_start_time: 0.0,
                             anim_duration: 0.0,
+                            transform: glam::Affine2::IDENTITY,
                         };
                         output
                             .sparse_instances
@@ -717,10 +732,14 @@ impl Widget for TextInput {
                     target_color_start: caret_color,
                     target_color_end: caret_color,
                     gradient_angle: 0.0,
+                    gradient: None,
+                    image: None,
                     border_radius: [1.0; 4],
                     border_width: [0.0; 4],
                     border_color: [0.0; 4],
                     target_border_color: [0.0; 4],
+                    border_style: [rore_types::BorderStyle::Solid; 4],
+                    border_dash: [0.0; 2],
                     shadow_color: [0.0; 4],
                     shadow_offset: Vec2::ZERO,
                     shadow_blur: 0.0,
@@ -728,6 +747,7 @@ impl Widget for TextInput {
                     clip_rect: combined_clip.unwrap_or([-10000.0, -10000.0, 20000.0, 20000.0]),
                     anim_start_time: 0.0,
                     anim_duration: 0.0,
+                    transform: glam::Affine2::IDENTITY,
                 };
                 output.sparse_instances.push((my_id.0 + 10000, caret_inst));
             }
@@ -740,3 +760,30 @@ impl Widget for TextInput {
         [0.0, 0.0, 0.0, 0.0]
     }
 }
+
+impl Copyable for TextInput {
+    fn copy(&mut self, _state: &mut FrameworkState) -> Option<ClipboardContent> {
+        self.selected_text().map(ClipboardContent::Text)
+    }
+
+    fn cut(&mut self, state: &mut FrameworkState) -> Option<ClipboardContent> {
+        let text = self.selected_text()?;
+        self.delete_selection();
+        if let Some(id) = self.node_id {
+            self.commit_change(state, id);
+        }
+        Some(ClipboardContent::Text(text))
+    }
+
+    fn paste(&mut self, state: &mut FrameworkState, clipboard: &ClipboardService) -> bool {
+        let Some(text) = clipboard.text() else {
+            return false;
+        };
+        self.insert_text(&text);
+        self.last_input_time.set(state.global_time);
+        if let Some(id) = self.node_id {
+            self.commit_change(state, id);
+        }
+        true
+    }
+}
@@ -1,3 +1,4 @@
+use rore_core::reactive::signals::{use_context, Signal};
 use rore_types::Color;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,3 +37,10 @@ impl Theme {
         }
     }
 }
+
+// Ilova `provide_context(Signal<Theme>)` qilmagan bo'lsa, qorong'i mavzu
+pub(crate) fn current_theme() -> Theme {
+    use_context::<Signal<Theme>>()
+        .map(|t| t.get_untracked())
+        .unwrap_or_else(Theme::dark)
+}
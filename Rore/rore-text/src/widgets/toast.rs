@@ -0,0 +1,465 @@
+use glam::Vec2;
+use rore_core::reactive::signals::{create_computed, create_ticker, on_cleanup, Signal};
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::widgets::base::{BuildContext, EventResult, RenderOutput, Widget, WidgetEvent};
+use rore_core::widgets::list::ForList;
+use rore_core::widgets::portal::Portal;
+use rore_core::window::{current_window, WindowId};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::{Align, Color, FlexDirection, LayoutModifiers, Style, Thickness, Val};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::widgets::box_widget::{HBox, UiBox, VBox};
+use crate::widgets::button::Button;
+use crate::widgets::dialog::Presence;
+use crate::widgets::text::Text;
+use crate::widgets::theme::current_theme;
+
+pub const DEFAULT_TOAST_DURATION: f32 = 4.0;
+// Bir vaqtda ko'rinadiganlar, qolganlari navbatda kutadi
+const MAX_VISIBLE_TOASTS: usize = 4;
+const TOAST_WIDTH: f32 = 340.0;
+const TOAST_ANIM: f32 = 0.2;
+// Kirishda o'ngdan shuncha piksel suriladi
+const TOAST_SLIDE: f32 = 40.0;
+
+static NEXT_TOAST_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static TOAST_HOSTS: RefCell<HashMap<WindowId, Signal<Vec<Toast>>>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn accent(self) -> Color {
+        match self {
+            ToastKind::Info => Color::hex("#3b82f6"),
+            ToastKind::Success => Color::hex("#0ECB81"),
+            ToastKind::Warning => Color::hex("#F0B90B"),
+            ToastKind::Error => Color::hex("#F6465D"),
+        }
+    }
+}
+
+type ToastAction = Arc<Mutex<Box<dyn FnMut() + Send>>>;
+
+/// Bildirishnoma. `show()` joriy oynaning `ToastHost` iga qo'shadi.
+#[derive(Clone)]
+pub struct Toast {
+    id: u64,
+    title: String,
+    message: Option<String>,
+    kind: ToastKind,
+    // None: foydalanuvchi yopmaguncha turadi
+    duration: Option<f32>,
+    action: Option<(String, ToastAction)>,
+}
+
+impl PartialEq for Toast {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Toast {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            id: NEXT_TOAST_ID.fetch_add(1, Ordering::Relaxed),
+            title: title.into(),
+            message: None,
+            kind: ToastKind::Info,
+            duration: Some(DEFAULT_TOAST_DURATION),
+            action: None,
+        }
+    }
+
+    pub fn message(mut self, text: impl Into<String>) -> Self {
+        self.message = Some(text.into());
+        self
+    }
+
+    pub fn kind(mut self, kind: ToastKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    // Soniyada
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    pub fn sticky(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    // Tugma bosilgach toast yopiladi
+    pub fn action<F: FnMut() + Send + 'static>(mut self, label: impl Into<String>, f: F) -> Self {
+        self.action = Some((label.into(), Arc::new(Mutex::new(Box::new(f)))));
+        self
+    }
+
+    pub fn show(self) -> ToastId {
+        let id = ToastId(self.id);
+        let window = current_window();
+        match TOAST_HOSTS.with(|hosts| hosts.borrow().get(&window).copied()) {
+            Some(queue) => queue.update(|list| list.push(self)),
+            None => eprintln!("Rore: toast ko'rsatilmadi, oynada ToastHost yo'q"),
+        }
+        id
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+/// Qisqa yo'l: `toast("Saqlandi")`.
+pub fn toast(title: impl Into<String>) -> ToastId {
+    Toast::new(title).show()
+}
+
+// Animatsiyasiz olib tashlaydi (navbatdagisi darhol chiqadi)
+pub fn dismiss_toast(id: ToastId) {
+    let window = current_window();
+    if let Some(queue) = TOAST_HOSTS.with(|hosts| hosts.borrow().get(&window).copied()) {
+        queue.update(|list| list.retain(|t| t.id != id.0));
+    }
+}
+
+/// Toast'lar o'ng pastki burchakda ustma-ust chiqadi. Ilova ildizida bir marta qo'yiladi.
+pub struct ToastHost;
+
+impl ToastHost {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ToastHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ToastHost {
+    fn type_name(&self) -> &'static str {
+        "ToastHost"
+    }
+
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let window = current_window();
+        let queue: Signal<Vec<Toast>> = Signal::new(Vec::new());
+        TOAST_HOSTS.with(|hosts| hosts.borrow_mut().insert(window, queue));
+        on_cleanup(move || {
+            TOAST_HOSTS.with(|hosts| {
+                let mut hosts = hosts.borrow_mut();
+                if hosts.get(&window).is_some_and(|q| q.id == queue.id) {
+                    hosts.remove(&window);
+                }
+            });
+        });
+
+        let visible = create_computed(move || visible_toasts(queue.get()));
+
+        let list = ForList::new(visible, move |toast: Toast| {
+            Box::new(ToastCard {
+                toast: Some(toast),
+                queue,
+                presence: None,
+            })
+        })
+        .style(Style {
+            flex_direction: FlexDirection::Column,
+            align_items: Align::End,
+            gap: rore_types::Size {
+                width: 8.0,
+                height: 8.0,
+            },
+            padding: Thickness::all(Val::Px(16.0)),
+            ..Default::default()
+        });
+
+        // Backdrop yo'q: toast'lar orasidan pastdagi UI bosiladi
+        let portal = Portal::aligned(1.0, 1.0).passthrough().child(list);
+        Box::new(portal).build(arena, engine, ctx)
+    }
+
+    // Daraxtda hech qachon turmaydi (build Portal id sini qaytaradi)
+    fn render(
+        &self,
+        _engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        _taffy_node: TaffyNode,
+        _parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        RenderOutput::new()
+    }
+}
+
+// Navbat boshidagilar ko'rinadi; biri yopilsa keyingisi o'rniga chiqadi
+fn visible_toasts(queue: Vec<Toast>) -> Vec<Toast> {
+    queue.into_iter().take(MAX_VISIBLE_TOASTS).collect()
+}
+
+// Avtomatik yopilish: muddat tugagan kadrda bir marta true
+struct DismissTimer {
+    elapsed: f32,
+    duration: Option<f32>,
+    fired: bool,
+}
+
+impl DismissTimer {
+    fn new(duration: Option<f32>) -> Self {
+        Self {
+            elapsed: 0.0,
+            duration,
+            fired: false,
+        }
+    }
+
+    fn tick(&mut self, dt: f32) -> bool {
+        let Some(duration) = self.duration else {
+            return false;
+        };
+        self.elapsed += dt;
+        if self.fired || self.elapsed < duration {
+            return false;
+        }
+        self.fired = true;
+        true
+    }
+}
+
+struct ToastCard {
+    toast: Option<Toast>,
+    queue: Signal<Vec<Toast>>,
+    presence: Option<Presence>,
+}
+
+impl ToastCard {
+    fn view(toast: &Toast, presence: Presence) -> Box<dyn Widget> {
+        let theme = current_theme();
+        let mut text = VBox::new()
+            .gap(4.0)
+            .expand()
+            .child(Text::new(toast.title.clone()).color(theme.text).size(14.0));
+        if let Some(message) = &toast.message {
+            text = text.child(
+                Text::new(message.clone())
+                    .color(theme.text_muted)
+                    .size(12.0),
+            );
+        }
+
+        let mut row = HBox::new()
+            .width(TOAST_WIDTH)
+            .bg_color(theme.surface)
+            .corner_radius(8.0)
+            .padding(12.0)
+            .gap(12.0)
+            .catch_clicks()
+            .modify_style(|s| s.align_items = Align::Center)
+            .child(
+                UiBox::new()
+                    .width(4.0)
+                    .height(Val::Percent(100.0))
+                    .corner_radius(2.0)
+                    .bg_color(toast.kind.accent()),
+            )
+            .child(text);
+
+        if let Some((label, action)) = &toast.action {
+            let action = action.clone();
+            row = row.child(
+                Button::new(&format!("toast_{}_action", toast.id))
+                    .colors(
+                        theme.surface,
+                        theme.primary.with_alpha(0.15),
+                        theme.primary.with_alpha(0.25),
+                    )
+                    .corner_radius(6.0)
+                    .padding(8.0)
+                    .on_click(move || {
+                        (action.lock().unwrap())();
+                        presence.leave();
+                    })
+                    .child(Text::new(label.clone()).color(theme.primary).size(13.0)),
+            );
+        }
+
+        Box::new(
+            row.child(
+                Button::new(&format!("toast_{}_close", toast.id))
+                    .colors(
+                        theme.surface,
+                        theme.text_muted.with_alpha(0.2),
+                        theme.text_muted.with_alpha(0.3),
+                    )
+                    .corner_radius(4.0)
+                    .padding(4.0)
+                    .on_click(move || presence.leave())
+                    .child(Text::new("✕").color(theme.text_muted).size(12.0)),
+            ),
+        )
+    }
+}
+
+impl Widget for ToastCard {
+    fn type_name(&self) -> &'static str {
+        "ToastCard"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn build(
+        mut self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let toast = self.toast.take().expect("ToastCard ikki marta qurildi");
+        let (queue, id) = (self.queue, toast.id);
+        let presence = Presence::enter(TOAST_ANIM, move || {
+            queue.update(|list| list.retain(|t| t.id != id));
+        });
+        self.presence = Some(presence);
+
+        // Taymer faqat karta tirik ekan yuradi (Scope bilan birga o'chadi)
+        if toast.duration.is_some() {
+            let mut timer = DismissTimer::new(toast.duration);
+            create_ticker(move |dt| {
+                if timer.tick(dt) {
+                    presence.leave();
+                }
+            });
+        }
+
+        let content_id = ToastCard::view(&toast, presence).build(arena, engine, ctx);
+        let child_nodes: Vec<TaffyNode> = arena
+            .taffy_map
+            .get(&content_id)
+            .copied()
+            .into_iter()
+            .collect();
+
+        let taffy_node = engine.new_node(Style::default(), &child_nodes);
+        let my_id = arena.allocate_node();
+        arena.taffy_map.insert(my_id, taffy_node);
+        arena.node_map.insert(taffy_node, my_id);
+
+        arena.widgets[my_id.0 as usize] = Some(self);
+        my_id
+    }
+
+    fn handle_event(&mut self, _state: &mut FrameworkState, event: &WidgetEvent) -> EventResult {
+        match event {
+            WidgetEvent::Click { .. } => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn render(
+        &self,
+        engine: &LayoutEngine,
+        state: &mut FrameworkState,
+        taffy_node: TaffyNode,
+        parent_pos: Vec2,
+        clip_rect: Option<[f32; 4]>,
+        path: String,
+    ) -> RenderOutput {
+        let mut output = RenderOutput::new();
+        let t = self.presence.map(|p| p.value()).unwrap_or(1.0);
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let offset = Vec2::new(layout.x + (1.0 - t) * TOAST_SLIDE, layout.y);
+
+        if let Ok(children) = engine.taffy.children(taffy_node) {
+            for (i, &child_node) in children.iter().enumerate() {
+                if let Some(&child_id) = state.arena.node_map.get(&child_node) {
+                    if let Some(widget_ref) = state.arena.widgets[child_id.0 as usize].take() {
+                        let child_output = widget_ref.render(
+                            engine,
+                            state,
+                            child_node,
+                            offset,
+                            clip_rect,
+                            format!("{}_{}", path, i),
+                        );
+                        output.extend(child_output);
+                        state.arena.widgets[child_id.0 as usize] = Some(widget_ref);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(list: &[Toast]) -> Vec<String> {
+        list.iter().map(|t| t.title.clone()).collect()
+    }
+
+    #[test]
+    fn queue_shows_at_most_four_and_keeps_overflow_in_order() {
+        let queue: Vec<Toast> = (1..=6).map(|i| Toast::new(format!("t{}", i))).collect();
+        assert_eq!(
+            titles(&visible_toasts(queue.clone())),
+            ["t1", "t2", "t3", "t4"]
+        );
+
+        // Ikkinchisi yopilsa, navbatdagi birinchi (t5) oxiriga qo'shiladi
+        let mut rest = queue;
+        rest.remove(1);
+        assert_eq!(titles(&visible_toasts(rest)), ["t1", "t3", "t4", "t5"]);
+    }
+
+    #[test]
+    fn auto_dismiss_fires_once_after_duration() {
+        let mut timer = DismissTimer::new(Some(DEFAULT_TOAST_DURATION));
+        assert!(!timer.tick(1.5));
+        assert!(!timer.tick(2.4));
+        assert!(timer.tick(0.2));
+        assert!(!timer.tick(1.0));
+
+        let mut sticky = DismissTimer::new(Toast::new("s").sticky().duration);
+        assert!(!sticky.tick(1000.0));
+    }
+
+    #[test]
+    fn dismiss_removes_only_that_toast() {
+        let queue: Signal<Vec<Toast>> = Signal::new(Vec::new());
+        let window = current_window();
+        TOAST_HOSTS.with(|hosts| hosts.borrow_mut().insert(window, queue));
+
+        let first = Toast::new("a").show();
+        Toast::new("b").show();
+        Toast::new("c").show();
+        dismiss_toast(first);
+        assert_eq!(titles(&queue.get()), ["b", "c"]);
+
+        TOAST_HOSTS.with(|hosts| hosts.borrow_mut().remove(&window));
+    }
+}
@@ -0,0 +1,114 @@
+use glam::Vec2;
+use rore_core::state::{FrameworkState, NodeId, UiArena};
+use rore_core::tooltip::{TooltipSpec, DEFAULT_TOOLTIP_DELAY};
+use rore_core::widgets::base::{BuildContext, RenderOutput, Widget};
+use rore_layout::{LayoutEngine, Node as TaffyNode};
+use rore_types::{Color, LayoutModifiers, Thickness, Val};
+
+use crate::widgets::box_widget::UiBox;
+use crate::widgets::text::Text;
+
+/// Bolasiga tooltip biriktiradi. O'zi alohida tugun yaratmaydi: bolaning layout'i o'zgarmaydi,
+/// yadro bolaning tuguni ustida hover kechikishini kuzatadi va tooltip'ni chizadi.
+pub struct Tooltip {
+    child: Box<dyn Widget>,
+    builder: Box<dyn FnMut() -> Box<dyn Widget> + Send>,
+    delay: f32,
+}
+
+impl Tooltip {
+    pub fn new(child: impl Widget + 'static, text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self::with(child, move || text_bubble(&text))
+    }
+
+    pub fn with<F>(child: impl Widget + 'static, builder: F) -> Self
+    where
+        F: FnMut() -> Box<dyn Widget> + Send + 'static,
+    {
+        Self {
+            child: Box::new(child),
+            builder: Box::new(builder),
+            delay: DEFAULT_TOOLTIP_DELAY,
+        }
+    }
+
+    // Soniyada
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds;
+        self
+    }
+}
+
+fn text_bubble(text: &str) -> Box<dyn Widget> {
+    Box::new(
+        UiBox::new()
+            .bg_color(Color::hex("#111827"))
+            .corner_radius(4.0)
+            .max_width(280.0)
+            .modify_style(|s| {
+                s.padding = Thickness {
+                    top: Val::Px(4.0),
+                    bottom: Val::Px(4.0),
+                    left: Val::Px(8.0),
+                    right: Val::Px(8.0),
+                };
+            })
+            .child(Text::new(text).color(Color::WHITE).size(12.0)),
+    )
+}
+
+impl Widget for Tooltip {
+    fn type_name(&self) -> &'static str {
+        "Tooltip"
+    }
+
+    fn build(
+        self: Box<Self>,
+        arena: &mut UiArena,
+        engine: &mut LayoutEngine,
+        ctx: &BuildContext,
+    ) -> NodeId {
+        let Tooltip {
+            child,
+            builder,
+            delay,
+        } = *self;
+        let child_id = child.build(arena, engine, ctx);
+        if let Some(&t_node) = arena.taffy_map.get(&child_id) {
+            arena
+                .tooltips
+                .insert(t_node, TooltipSpec { builder, delay });
+        }
+        child_id
+    }
+
+    // Daraxtda hech qachon turmaydi (build bolaning id sini qaytaradi)
+    fn render(
+        &self,
+        _engine: &LayoutEngine,
+        _state: &mut FrameworkState,
+        _taffy_node: TaffyNode,
+        _parent_pos: Vec2,
+        _clip_rect: Option<[f32; 4]>,
+        _path: String,
+    ) -> RenderOutput {
+        RenderOutput::new()
+    }
+}
+
+/// `.tooltip(...)` va `.tooltip_with(...)` hamma vidjetlarda.
+pub trait TooltipExt: Widget + Sized {
+    fn tooltip(self, text: impl Into<String>) -> Tooltip {
+        Tooltip::new(self, text)
+    }
+
+    fn tooltip_with<F>(self, builder: F) -> Tooltip
+    where
+        F: FnMut() -> Box<dyn Widget> + Send + 'static,
+    {
+        Tooltip::with(self, builder)
+    }
+}
+
+impl<W: Widget> TooltipExt for W {}
@@ -2,14 +2,20 @@ use glam::Vec2;
 use rore_core::reactive::command::{CommandQueue, UICommand};
 use rore_core::reactive::signals::{create_effect, Signal};
 use rore_core::state::{FrameworkState, NodeId, UiArena};
-use rore_core::widgets::base::{BuildContext, RenderOutput, Widget};
+use rore_core::widgets::base::{BuildContext, IntoProp, Prop, RenderOutput, Widget};
 use rore_layout::{LayoutEngine, Node as TaffyNode};
-use rore_types::Style;
+use rore_types::{Style, Transform};
 use std::cell::Cell;
 
+/// `new(x, y)`: bola signal bergan joyga ko'chadi (fizika, drag).
+/// `default()` esa layout joyida qoladi; `rotate`/`scale`/`skew` node markazi atrofida
+/// butun daraxtga qo'llanadi, hit-test ham shunga mos.
 pub struct TransformBox {
-    pub offset_x: Signal<f32>,
-    pub offset_y: Signal<f32>,
+    pub offset_x: Option<Signal<f32>>,
+    pub offset_y: Option<Signal<f32>>,
+    pub rotation: Prop<f32>,
+    pub scale: Prop<f32>,
+    pub skew: [f32; 2],
 
     pub on_size_signal: Option<Signal<Vec2>>,
     pub child: Option<Box<dyn Widget>>,
@@ -21,16 +27,29 @@ pub struct TransformBox {
 impl TransformBox {
     pub fn new(offset_x: Signal<f32>, offset_y: Signal<f32>) -> Self {
         Self {
-            offset_x,
-            offset_y,
-            on_size_signal: None,
-            child: None,
-            style: Style::default(),
-            my_id: None,
-            last_size: Cell::new(Vec2::ZERO),
+            offset_x: Some(offset_x),
+            offset_y: Some(offset_y),
+            ..Self::default()
         }
     }
 
+    // Radianlarda, signal bilan ham (aylanuvchi loader)
+    pub fn rotate(mut self, radians: impl IntoProp<f32>) -> Self {
+        self.rotation = radians.into_prop();
+        self
+    }
+
+    pub fn scale(mut self, factor: impl IntoProp<f32>) -> Self {
+        self.scale = factor.into_prop();
+        self
+    }
+
+    // Radianlarda
+    pub fn skew(mut self, x: f32, y: f32) -> Self {
+        self.skew = [x, y];
+        self
+    }
+
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
@@ -48,6 +67,30 @@ impl TransformBox {
     }
 }
 
+impl Default for TransformBox {
+    fn default() -> Self {
+        Self {
+            offset_x: None,
+            offset_y: None,
+            rotation: Prop::Static(0.0),
+            scale: Prop::Static(1.0),
+            skew: [0.0, 0.0],
+            on_size_signal: None,
+            child: None,
+            style: Style::default(),
+            my_id: None,
+            last_size: Cell::new(Vec2::ZERO),
+        }
+    }
+}
+
+fn read(prop: &mut Prop<f32>) -> f32 {
+    match prop {
+        Prop::Static(v) => *v,
+        Prop::Dynamic(f) => f(),
+    }
+}
+
 impl Widget for TransformBox {
     fn type_name(&self) -> &'static str {
         "TransformBox"
@@ -73,15 +116,35 @@ impl Widget for TransformBox {
         arena.taffy_map.insert(my_id, taffy_node);
         arena.node_map.insert(taffy_node, my_id);
 
-        let sig_x = self.offset_x;
-        let sig_y = self.offset_y;
-        let id_clone = my_id;
+        if let (Some(sig_x), Some(sig_y)) = (self.offset_x, self.offset_y) {
+            create_effect(move || {
+                let dx = sig_x.get();
+                let dy = sig_y.get();
+                CommandQueue::send(UICommand::UpdateTransform(my_id, dx, dy));
+            });
+        }
 
-        create_effect(move || {
-            let dx = sig_x.get();
-            let dy = sig_y.get();
-            CommandQueue::send(UICommand::UpdateTransform(id_clone, dx, dy));
-        });
+        let mut rotation = std::mem::replace(&mut self.rotation, Prop::Static(0.0));
+        let mut scale = std::mem::replace(&mut self.scale, Prop::Static(1.0));
+        let [skew_x, skew_y] = self.skew;
+        let is_static = matches!((&rotation, &scale), (Prop::Static(_), Prop::Static(_)));
+        let mut transform_of = move || {
+            let s = read(&mut scale);
+            Transform::default()
+                .rotate(read(&mut rotation))
+                .scale(s, s)
+                .skew(skew_x, skew_y)
+        };
+        if is_static {
+            let transform = transform_of();
+            if !transform.is_identity() {
+                CommandQueue::send(UICommand::SetTransform(my_id, transform));
+            }
+        } else {
+            create_effect(move || {
+                CommandQueue::send(UICommand::SetTransform(my_id, transform_of()));
+            });
+        }
 
         arena.widgets[my_id.0 as usize] = Some(self);
         my_id
@@ -92,17 +155,18 @@ impl Widget for TransformBox {
         engine: &LayoutEngine,
         state: &mut FrameworkState,
         taffy_node: TaffyNode,
-        _parent_pos: Vec2,
+        parent_pos: Vec2,
         clip_rect: Option<[f32; 4]>,
         path: String,
     ) -> RenderOutput {
         let mut output = RenderOutput::new();
 
-        let dx = self.offset_x.get_untracked();
-        let dy = self.offset_y.get_untracked();
-
         // Taffy o'lchab bo'lgan haqiqiy layout
-        let layout = engine.get_final_layout(taffy_node, 0.0, 0.0);
+        let layout = engine.get_final_layout(taffy_node, parent_pos.x, parent_pos.y);
+        let (dx, dy) = match (self.offset_x, self.offset_y) {
+            (Some(x), Some(y)) => (x.get_untracked(), y.get_untracked()),
+            _ => (layout.x, layout.y),
+        };
 
         let current_size = Vec2::new(layout.width, layout.height);
         let last = self.last_size.get();
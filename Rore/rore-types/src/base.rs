@@ -62,6 +62,68 @@ impl Default for Transform {
     }
 }
 
+impl Transform {
+    pub fn translate(mut self, x: f32, y: f32) -> Self {
+        self.translate = Point { x, y };
+        self
+    }
+
+    // Radianlarda, soat strelkasi bo'yicha (ekranda y pastga qaragan)
+    pub fn rotate(mut self, radians: f32) -> Self {
+        self.rotate = radians;
+        self
+    }
+
+    pub fn scale(mut self, x: f32, y: f32) -> Self {
+        self.scale = Point { x, y };
+        self
+    }
+
+    // Radianlarda: x - gorizontal qiyshayish, y - vertikal
+    pub fn skew(mut self, x: f32, y: f32) -> Self {
+        self.skew = Point { x, y };
+        self
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 3x2 affine matritsa. Aylantirish, qiyshaytirish va masshtab `origin` atrofida
+    /// (odatda node markazi), so'ng translate qo'shiladi.
+    pub fn to_affine(&self, origin: Point) -> glam::Affine2 {
+        let o = glam::Vec2::new(origin.x, origin.y);
+        let skew = glam::Affine2::from_cols(
+            glam::Vec2::new(1.0, self.skew.y.tan()),
+            glam::Vec2::new(self.skew.x.tan(), 1.0),
+            glam::Vec2::ZERO,
+        );
+        glam::Affine2::from_translation(glam::Vec2::new(self.translate.x, self.translate.y) + o)
+            * glam::Affine2::from_angle(self.rotate)
+            * skew
+            * glam::Affine2::from_scale(glam::Vec2::new(self.scale.x, self.scale.y))
+            * glam::Affine2::from_translation(-o)
+    }
+}
+
+/// Rect'ning affine o'zgartirilgandan keyingi chegarasi (AABB): [x, y, w, h]
+pub fn transform_rect(m: &glam::Affine2, rect: [f32; 4]) -> [f32; 4] {
+    let [x, y, w, h] = rect;
+    let corners = [
+        m.transform_point2(glam::Vec2::new(x, y)),
+        m.transform_point2(glam::Vec2::new(x + w, y)),
+        m.transform_point2(glam::Vec2::new(x, y + h)),
+        m.transform_point2(glam::Vec2::new(x + w, y + h)),
+    ];
+    let min = corners
+        .iter()
+        .fold(glam::Vec2::splat(f32::MAX), |a, c| a.min(*c));
+    let max = corners
+        .iter()
+        .fold(glam::Vec2::splat(f32::MIN), |a, c| a.max(*c));
+    [min.x, min.y, max.x - min.x, max.y - min.y]
+}
+
 // ==================== ANIMATION (LERP & EASING) ====================
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -229,8 +291,76 @@ pub struct GradientStop {
     pub position: f32,
 }
 
+impl GradientStop {
+    pub fn new(position: f32, color: Color) -> Self {
+        Self { color, position }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct LinearGradient {
     pub angle: f32,
     pub stops: Vec<GradientStop>,
 }
+
+impl LinearGradient {
+    pub fn new(angle: f32, stops: Vec<GradientStop>) -> Self {
+        Self { angle, stops }
+    }
+}
+
+// center 0..1 oralig'ida; radius = 1.0 markazdan eng uzoq burchakkacha (CSS farthest-corner)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    pub center: [f32; 2],
+    pub radius: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+impl RadialGradient {
+    pub fn new(stops: Vec<GradientStop>) -> Self {
+        Self {
+            center: [0.5, 0.5],
+            radius: 1.0,
+            stops,
+        }
+    }
+
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = [x, y];
+        self
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+}
+
+// Markaz atrofida aylanadi; angle = 0 o'ngdan boshlanadi, soat strelkasi bo'yicha
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicGradient {
+    pub center: [f32; 2],
+    pub angle: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+impl ConicGradient {
+    pub fn new(stops: Vec<GradientStop>) -> Self {
+        Self {
+            center: [0.5, 0.5],
+            angle: 0.0,
+            stops,
+        }
+    }
+
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = [x, y];
+        self
+    }
+
+    pub fn angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+}
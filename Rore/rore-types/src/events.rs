@@ -64,4 +64,4 @@ pub enum Role {
     Checkbox,
     List,
     ListItem,
-}
\ No newline at end of file
+}
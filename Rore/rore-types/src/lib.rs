@@ -20,6 +20,25 @@ pub enum PlatformMode {
     Web,     // Browser cheklovlari
 }
 
+// Asosiy kadrning multisampling darajasi. Adapter qo'llamasa pastrog'iga tushiladi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Msaa {
+    #[default]
+    Off,
+    X4,
+    X8,
+}
+
+impl Msaa {
+    pub fn samples(self) -> u32 {
+        match self {
+            Msaa::Off => 1,
+            Msaa::X4 => 4,
+            Msaa::X8 => 8,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct RoreConfig {
     pub mode: PlatformMode,
@@ -30,8 +49,11 @@ pub struct RoreConfig {
     pub virtual_keyboard: bool, // Input bosilganda ekran klaviaturasi
     pub text_selection: bool,   // Matnni belgilash (Selection)
     pub animations: bool,       // Global animatsiya o'chirgich
-    pub scaling: f32,
-    pub max_fps: u32, // Majburiy masshtab (Zoom)
+    pub scaling: f32,           // Majburiy masshtab (Zoom)
+    pub max_fps: u32,           // Animatsiya paytidagi kadr chegarasi (0 = monitor chastotasi)
+    pub power_saving: bool,     // Batareya rejimi: kam kadr, fonda deyarli to'xtash
+    pub msaa: Msaa,             // Yo'llar va custom shader qirralari uchun
+    pub image_cache_mb: u32,    // Rasm atlasining GPU xotira chegarasi
 }
 
 impl Default for RoreConfig {
@@ -52,6 +74,9 @@ impl RoreConfig {
             animations: true,
             scaling: 1.0,
             max_fps: 120,
+            power_saving: false,
+            msaa: Msaa::Off,
+            image_cache_mb: 128,
         }
     }
 
@@ -66,6 +91,9 @@ impl RoreConfig {
             animations: true,
             scaling: 1.0,
             max_fps: 120,
+            power_saving: false,
+            msaa: Msaa::Off,
+            image_cache_mb: 48,
         }
     }
 
@@ -73,6 +101,8 @@ impl RoreConfig {
     pub fn low_power() -> Self {
         let mut cfg = Self::mobile();
         cfg.animations = false;
+        cfg.max_fps = 30;
+        cfg.power_saving = true;
         cfg
     }
 
@@ -87,6 +117,27 @@ impl RoreConfig {
         self
     }
 
+    pub fn with_max_fps(mut self, fps: u32) -> Self {
+        self.max_fps = fps;
+        self
+    }
+
+    pub fn with_power_saving(mut self, enabled: bool) -> Self {
+        self.power_saving = enabled;
+        self
+    }
+
+    pub fn with_msaa(mut self, msaa: Msaa) -> Self {
+        self.msaa = msaa;
+        self
+    }
+
+    // Byudjet tugasa hech qayerda ko'rinmayotgan eng eski rasm chiqariladi
+    pub fn with_image_cache_mb(mut self, mb: u32) -> Self {
+        self.image_cache_mb = mb;
+        self
+    }
+
     pub fn disable_animations(mut self) -> Self {
         self.animations = false;
         self
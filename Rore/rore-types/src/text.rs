@@ -16,4 +16,8 @@ pub trait TextRenderer {
 
     fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue);
     fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>);
+
+    // Asosiy kadr MSAA bilan chizilsa, matn pipeline'i ham shu sample soniga mos bo'lishi kerak.
+    // Standart: hech narsa qilmaydi (faqat MSAA'siz kadrga chizadigan renderer uchun)
+    fn set_sample_count(&mut self, _device: &wgpu::Device, _count: u32) {}
 }
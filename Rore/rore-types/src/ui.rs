@@ -1,4 +1,4 @@
-use crate::base::{Color, LinearGradient, Size};
+use crate::base::{Color, ConicGradient, LinearGradient, RadialGradient, Size};
 use std::vec::Vec;
 
 // ==================== LAYOUT VALUES ====================
@@ -181,6 +181,10 @@ pub struct Border {
     pub right: BorderSide,
     pub top: BorderSide,
     pub bottom: BorderSide,
+    // Dashed/Dotted uchun: shtrix uzunligi (0 = qalinlikdan 3 baravar) va
+    // perimetr bo'ylab siljish. Fazani signal bilan oshirsangiz "marching ants"
+    pub dash_length: f32,
+    pub dash_phase: f32,
 }
 
 impl Border {
@@ -195,13 +199,24 @@ impl Border {
             right: side,
             top: side,
             bottom: side,
+            ..Default::default()
         }
     }
+
+    pub fn dash_length(mut self, length: f32) -> Self {
+        self.dash_length = length;
+        self
+    }
+
+    pub fn dash_phase(mut self, phase: f32) -> Self {
+        self.dash_phase = phase;
+        self
+    }
 }
 
 // ==================== BACKGROUND ====================
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ImageFit {
     Cover,
     Contain,
@@ -212,6 +227,8 @@ pub enum ImageFit {
 pub enum Background {
     Solid(Color),
     Gradient(LinearGradient),
+    RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
     Image {
         url: String,
         fit: ImageFit,
@@ -273,6 +290,12 @@ pub enum Filter {
     Blur(f32),
     Opacity(f32),
     Grayscale(f32),
+    Brightness(f32),
+    DropShadow {
+        offset: [f32; 2],
+        blur: f32,
+        color: Color,
+    },
 }
 
 impl Default for Filter {
@@ -281,6 +304,19 @@ impl Default for Filter {
     }
 }
 
+impl Filter {
+    // Effekt node chegarasidan qancha tashqariga chiqadi (blur ~3 sigma)
+    pub fn bleed(&self) -> f32 {
+        match self {
+            Filter::Blur(sigma) => sigma.max(0.0) * 3.0,
+            Filter::DropShadow { offset, blur, .. } => {
+                offset[0].abs().max(offset[1].abs()) + blur.max(0.0) * 3.0
+            }
+            _ => 0.0,
+        }
+    }
+}
+
 // ==================== TYPOGRAPHY ====================
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
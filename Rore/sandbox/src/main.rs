@@ -7,13 +7,18 @@ use rore_core::reactive::signals::{provide_context, Signal};
 use rore_text::text::TextSystem;
 use rore_text::widgets::theme::Theme;
 
-use rore_text::widgets::{Button, HBox, Spacer, Text, TextInput, UiBox, VBox};
+use rore_text::widgets::{
+    confirm_dialog, Button, ContextMenu, DialogHost, HBox, MenuItem, Spacer, Text, TextInput,
+    Toast, ToastHost, ToastKind, TooltipExt, UiBox, VBox,
+};
 
+use rore_core::clipboard::{clipboard, to_tsv};
+use rore_core::shortcuts::{ShortcutScope, Shortcuts};
 use rore_core::widgets::portal::Portal;
 use rore_core::widgets::show::Show;
 use rore_text::widgets::scroll_view::ScrollView;
 
-use rore_types::{Align, Color, LayoutModifiers, RoreConfig, Style, Thickness, Val};
+use rore_types::{Align, Color, FlexDirection, LayoutModifiers, RoreConfig, Style, Thickness, Val};
 
 thread_local! {
     static SCREEN_SIZE: RefCell<(f32, f32)> = RefCell::new((1024.0, 768.0));
@@ -31,52 +36,97 @@ impl App for RoreDashboardApp {
 
         let mut settings_list = VBox::new().gap(12.0).width(Val::Percent(100.0));
         for i in 1..=40 {
-            settings_list = settings_list.child(
-                HBox::new()
-                    .bg_color(Color::hex("#181A20"))
-                    .corner_radius(8.0)
-                    .padding(16.0)
-                    .gap(20.0)
-                    .modify_style(|s| s.align_items = Align::Center)
-                    .child(
-                        VBox::new()
-                            .width(200.0)
-                            .gap(4.0)
-                            .child(
-                                Text::new(format!("Sozlama bloki #{}", i))
-                                    .color(Color::WHITE)
-                                    .size(16.0),
-                            )
-                            .child(
-                                Text::new("Ushbu maydonni o'zgartiring")
-                                    .color(Color::hex("#848E9C"))
-                                    .size(12.0),
-                            ),
-                    )
-                    .child(
-                        TextInput::new(&format!("input_setting_{}", i))
-                            .placeholder("Qiymat kiriting...")
-                            .bg_color(Color::hex("#2B3139"))
-                            .text_color(Color::WHITE)
-                            .corner_radius(6.0)
-                            .width(Val::Percent(100.0))
-                            .height(40.0)
-                            .padding(10.0)
-                            .expand(),
-                    )
-                    .child(
-                        Button::new(&format!("btn_save_{}", i))
-                            .colors(
-                                Color::hex("#0ECB81"),
-                                Color::hex("#0b9961"),
-                                Color::hex("#087a4d"),
+            let pinned = Signal::new(false);
+            let row = HBox::new()
+                .bg_color(Color::hex("#181A20"))
+                .corner_radius(8.0)
+                .padding(16.0)
+                .gap(20.0)
+                .modify_style(|s| s.align_items = Align::Center)
+                .child(
+                    VBox::new()
+                        .width(200.0)
+                        .gap(4.0)
+                        .child(
+                            Text::new(format!("Sozlama bloki #{}", i))
+                                .color(Color::WHITE)
+                                .size(16.0),
+                        )
+                        .child(
+                            Text::new("Ushbu maydonni o'zgartiring")
+                                .color(Color::hex("#848E9C"))
+                                .size(12.0),
+                        ),
+                )
+                .child(
+                    TextInput::new(&format!("input_setting_{}", i))
+                        .placeholder("Qiymat kiriting...")
+                        .bg_color(Color::hex("#2B3139"))
+                        .text_color(Color::WHITE)
+                        .corner_radius(6.0)
+                        .width(Val::Percent(100.0))
+                        .height(40.0)
+                        .padding(10.0)
+                        .expand(),
+                )
+                .child(
+                    Button::new(&format!("btn_save_{}", i))
+                        .colors(
+                            Color::hex("#0ECB81"),
+                            Color::hex("#0b9961"),
+                            Color::hex("#087a4d"),
+                        )
+                        .corner_radius(6.0)
+                        .padding(10.0)
+                        .width(100.0)
+                        .center()
+                        .on_click(move || {
+                            confirm_dialog(
+                                format!("#{} sozlamani saqlash", i),
+                                "O'zgarishlar darhol kuchga kiradi.",
+                                "Saqlash",
                             )
-                            .corner_radius(6.0)
-                            .padding(10.0)
-                            .width(100.0)
-                            .center()
-                            .child(Text::new("Saqlash").color(Color::WHITE).size(14.0)),
-                    ),
+                            .on_result(move |ok| {
+                                if ok == Some(true) {
+                                    Toast::new("Saqlandi")
+                                        .message(format!("#{} sozlama yangilandi", i))
+                                        .kind(ToastKind::Success)
+                                        .show();
+                                }
+                            });
+                        })
+                        .child(Text::new("Saqlash").color(Color::WHITE).size(14.0))
+                        .tooltip(format!("#{} sozlamani saqlaydi", i)),
+                );
+
+            // Har bir qatorda o'ng tugma menyusi
+            settings_list = settings_list.child(
+                ContextMenu::new(row, move || {
+                    vec![
+                        MenuItem::action("Nusxa olish", move || {
+                            clipboard().set_text(format!("Sozlama bloki #{}", i));
+                        })
+                        .accel("Ctrl+C"),
+                        MenuItem::action("Tozalash", || {}).enabled(false),
+                        MenuItem::check("Qadab qo'yish", pinned),
+                        MenuItem::separator(),
+                        MenuItem::submenu(
+                            "Eksport",
+                            // Elektron jadvalga qo'yiladigan ko'rinishda
+                            vec![MenuItem::action("Jadval (TSV)", move || {
+                                clipboard().set_text(to_tsv([
+                                    vec!["Sozlama".to_string(), "Qadalgan".to_string()],
+                                    vec![format!("#{}", i), pinned.get().to_string()],
+                                ]));
+                            })],
+                        ),
+                    ]
+                })
+                .style(Style {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                }),
             );
         }
 
@@ -185,12 +235,34 @@ impl App for RoreDashboardApp {
                                 .child(settings_list)
                         )
                 )
+                .child(DialogHost::new())
+                .child(ToastHost::new())
         )
     }
 
     fn update(&mut self, event: AppEvent) {
-        if let AppEvent::Resize(w, h) = event {
-            SCREEN_SIZE.with(|s| *s.borrow_mut() = (w, h));
+        match event {
+            AppEvent::Init => {
+                Shortcuts::register_command("help.shortcuts", |_| {
+                    let label = Shortcuts::label("help.shortcuts").unwrap_or_default();
+                    Toast::new("Tugmalar")
+                        .message(format!("{} - shu oyna", label))
+                        .show();
+                });
+                if let Err(e) =
+                    Shortcuts::bind("Ctrl+K Ctrl+S", "help.shortcuts", ShortcutScope::Global)
+                {
+                    eprintln!("{}", e);
+                }
+                let path = Shortcuts::user_bindings_path("rore-sandbox");
+                for e in Shortcuts::load_user_bindings(path) {
+                    eprintln!("{}", e);
+                }
+            }
+            AppEvent::Resize(w, h) => {
+                SCREEN_SIZE.with(|s| *s.borrow_mut() = (w, h));
+            }
+            _ => {}
         }
     }
 }